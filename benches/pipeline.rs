@@ -0,0 +1,201 @@
+// Criterion benchmarks for the report pipeline (feature: bench).
+//
+// Builds against the library crate, which exposes the pipeline modules as #[doc(hidden)] internals.
+//
+// Synthetic repo size: GAR_BENCH_COMMITS (default 300) commits touching GAR_BENCH_FILES (default 4) files each.
+// Track regressions with criterion baselines:
+//   cargo bench --features bench -- --save-baseline main
+//   cargo bench --features bench -- --baseline main
+
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use git_activity_report::commit::{ProcessContext, process_commit};
+use git_activity_report::gitio;
+use git_activity_report::render::{ReportParams, run_simple};
+
+const SINCE: &str = "2025-08-01";
+const UNTIL: &str = "2025-09-01";
+/// 2025-08-01T00:00:00Z
+const BASE_EPOCH: i64 = 1_754_006_400;
+
+fn env_usize(key: &str, default: usize) -> usize {
+  std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+/// Build a synthetic repo with `commits` commits (spread over August 2025) via `git fast-import`.
+fn synthetic_repo(dir: &Path, commits: usize, files_per_commit: usize) {
+  let git = |args: &[&str]| {
+    let status = Command::new("git").args(args).current_dir(dir).status().expect("git");
+    assert!(status.success(), "git {:?} failed", args);
+  };
+  git(&["init", "-q", "-b", "main"]);
+
+  let step = (30 * 24 * 3600) / commits.max(1) as i64;
+  let mut stream = String::new();
+
+  for i in 0..commits {
+    let when = BASE_EPOCH + i as i64 * step;
+    let author = format!("Dev {} <dev{}@example.com>", i % 7, i % 7);
+    let msg = format!("change {}\n\nTouches {} files.\n", i, files_per_commit);
+
+    stream.push_str("commit refs/heads/main\n");
+    stream.push_str(&format!("mark :{}\n", i + 1));
+    stream.push_str(&format!("author {} {} +0000\n", author, when));
+    stream.push_str(&format!("committer {} {} +0000\n", author, when));
+    stream.push_str(&format!("data {}\n{}", msg.len(), msg));
+    if i > 0 {
+      stream.push_str(&format!("from :{}\n", i));
+    }
+    for f in 0..files_per_commit {
+      let path = format!("src/mod_{}/file_{}.rs", (i + f) % 11, (i * 3 + f) % 37);
+      let body: String = (0..(10 + (i + f) % 40))
+        .map(|l| format!("// {} {} {}\n", i, f, l))
+        .collect();
+      stream.push_str(&format!("M 100644 inline {}\ndata {}\n{}", path, body.len(), body));
+    }
+    stream.push('\n');
+  }
+
+  let mut child = Command::new("git")
+    .args(["fast-import", "--quiet"])
+    .current_dir(dir)
+    .stdin(Stdio::piped())
+    .spawn()
+    .expect("git fast-import");
+  child.stdin.take().unwrap().write_all(stream.as_bytes()).unwrap();
+  assert!(child.wait().unwrap().success(), "git fast-import failed");
+  git(&["reset", "-q", "--hard", "main"]);
+}
+
+fn params(repo: &str) -> ReportParams {
+  ReportParams {
+    repo: repo.to_string(),
+    label: Some("bench".into()),
+    since: SINCE.into(),
+    until: UNTIL.into(),
+    include_merges: false,
+    include_patch: false,
+    max_patch_bytes: 0,
+    max_files_per_commit: None,
+    tz: "utc".into(),
+    split_apart: false,
+    split_out: None,
+    include_unmerged: false,
+    save_patches_dir: None,
+    github_prs: false,
+    now_local: None,
+    estimate_effort: false,
+    provenance: None,
+    no_blob_stats: false,
+    stale_days: 30,
+    layout: Default::default(),
+    unmerged_base: None,
+    service_map: None,
+    identity_map: None,
+    include_pr_patch: false,
+    pr_files: false,
+    redactor: None,
+    env_trailers: vec![],
+    patch_budget_bytes: None,
+    side_indexes: false,
+    automation: None,
+    issue_keys: None,
+    repo_policy: false,
+    collaboration: false,
+    snapshot_paths: vec![],
+    snapshot_max_bytes: 0,
+    conflict_hotspots: false,
+    dependency_changes: false,
+    new_contributors: false,
+    no_bodies: false,
+    summary_only: false,
+    work_items: None,
+    calendar: false,
+    releases: false,
+    fields: None,
+    all_refs: false,
+    first_parent: false,
+    committers: false,
+    refs: vec![],
+    paths: vec![],
+    sample: None,
+    jobs: 0,
+    commit_cache: None,
+  }
+}
+
+fn context(repo: &str) -> ProcessContext<'_> {
+  ProcessContext {
+    repo,
+    tz: "utc",
+    github_prs: false,
+    include_patch: false,
+    max_patch_bytes: 0,
+    max_files_per_commit: None,
+    estimate_effort: false,
+    no_blob_stats: false,
+    service_map: None,
+    identity_map: None,
+    include_pr_patch: false,
+    pr_files: false,
+    redactor: None,
+    env_trailers: &[],
+    automation: None,
+    issue_keys: None,
+    snapshot_paths: &[],
+    snapshot_max_bytes: 0,
+    no_bodies: false,
+    paths: &[],
+  }
+}
+
+fn pipeline(c: &mut Criterion) {
+  let commits = env_usize("GAR_BENCH_COMMITS", 300);
+  let files = env_usize("GAR_BENCH_FILES", 4);
+  let td = tempfile::TempDir::new().unwrap();
+  synthetic_repo(td.path(), commits, files);
+  let repo = td.path().to_string_lossy().to_string();
+
+  let shas = gitio::rev_list(&repo, SINCE, UNTIL, false, &[]).unwrap();
+  assert_eq!(
+    shas.len(),
+    commits,
+    "synthetic repo should fall entirely inside the window"
+  );
+
+  let mut group = c.benchmark_group("pipeline");
+  group.sample_size(10);
+
+  group.throughput(Throughput::Elements(commits as u64));
+  group.bench_function("rev_list", |b| {
+    b.iter(|| gitio::rev_list(&repo, SINCE, UNTIL, false, &[]).unwrap())
+  });
+
+  let ctx = context(&repo);
+  let sample: Vec<&String> = shas.iter().step_by((shas.len() / 20).max(1)).collect();
+  group.throughput(Throughput::Elements(sample.len() as u64));
+  group.bench_function("process_commit", |b| {
+    b.iter(|| {
+      for sha in &sample {
+        process_commit(sha, &ctx).unwrap();
+      }
+    })
+  });
+
+  let p = params(&repo);
+  group.throughput(Throughput::Elements(commits as u64));
+  group.bench_function("run_simple", |b| b.iter(|| run_simple(&p).unwrap()));
+
+  let report = run_simple(&p).unwrap();
+  group.bench_function("serialize_report", |b| {
+    b.iter(|| serde_json::to_vec_pretty(&report).unwrap())
+  });
+
+  group.finish();
+}
+
+criterion_group!(benches, pipeline);
+criterion_main!(benches);
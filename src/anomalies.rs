@@ -0,0 +1,190 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Flag unusual ranges of a multi-range run (commit count/churn outliers, author drop-off) for manifest.json
+// role: summary/anomalies
+// inputs: Rollup (per-range trend and retention, in manifest order)
+// outputs: RangeAnomaly[] in range order, then commits → churn → authors within a range
+// side_effects: None
+// invariants:
+// - spike/dip: value more than 2σ above/below the run mean (population σ over every range, the range itself included)
+// - σ flags need σ > 0; with population σ a single range cannot exceed 2σ until the run has at least 6 ranges
+// - drop_off: active authors at most half of the previous range's, which had at least 2
+// - baseline and sigma are rounded to 2 decimals
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::rollup::Rollup;
+
+const SIGMA_THRESHOLD: f64 = 2.0;
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+pub struct RangeAnomaly {
+  pub label: String,
+  /// "commits", "churn", or "authors"
+  pub metric: String,
+  /// "spike" / "dip" (beyond 2σ of the run mean) or "drop_off" (active authors halved)
+  pub kind: String,
+  pub value: i64,
+  /// Run mean (spike/dip) or the previous range's active authors (drop_off)
+  pub baseline: f64,
+  /// Standard deviations from the run mean (spike/dip only)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sigma: Option<f64>,
+}
+
+fn round2(x: f64) -> f64 {
+  (x * 100.0).round() / 100.0
+}
+
+/// `(index, value, mean, z)` for every value beyond the threshold.
+fn outliers(values: &[i64]) -> Vec<(usize, i64, f64, f64)> {
+  let n = values.len() as f64;
+  let mean = values.iter().sum::<i64>() as f64 / n.max(1.0);
+  let sd = (values.iter().map(|v| (*v as f64 - mean).powi(2)).sum::<f64>() / n.max(1.0)).sqrt();
+
+  if sd == 0.0 {
+    return Vec::new();
+  }
+
+  values
+    .iter()
+    .enumerate()
+    .map(|(i, v)| (i, *v, mean, (*v as f64 - mean) / sd))
+    .filter(|(_, _, _, z)| z.abs() > SIGMA_THRESHOLD)
+    .collect()
+}
+
+/// Anomaly flags for the run summarized by `rollup`.
+pub fn detect_anomalies(rollup: &Rollup) -> Vec<RangeAnomaly> {
+  let mut found: Vec<(usize, usize, RangeAnomaly)> = Vec::new();
+  let metrics: [(&str, Vec<i64>); 2] = [
+    ("commits", rollup.trend.iter().map(|r| r.commits).collect()),
+    ("churn", rollup.trend.iter().map(|r| r.churn).collect()),
+  ];
+
+  for (order, (metric, values)) in metrics.iter().enumerate() {
+    for (i, value, mean, z) in outliers(values) {
+      found.push((
+        i,
+        order,
+        RangeAnomaly {
+          label: rollup.trend[i].label.clone(),
+          metric: metric.to_string(),
+          kind: if z > 0.0 { "spike" } else { "dip" }.into(),
+          value,
+          baseline: round2(mean),
+          sigma: Some(round2(z)),
+        },
+      ));
+    }
+  }
+
+  for (i, pair) in rollup.retention.windows(2).enumerate() {
+    let (prev, cur) = (&pair[0], &pair[1]);
+
+    if prev.active >= 2 && cur.active * 2 <= prev.active {
+      found.push((
+        i + 1,
+        metrics.len(),
+        RangeAnomaly {
+          label: cur.label.clone(),
+          metric: "authors".into(),
+          kind: "drop_off".into(),
+          value: cur.active as i64,
+          baseline: prev.active as f64,
+          sigma: None,
+        },
+      ));
+    }
+  }
+
+  found.sort_by_key(|(i, order, _)| (*i, *order));
+  found.into_iter().map(|(_, _, a)| a).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rollup::{RetentionPoint, RollupRange, RollupTotals};
+
+  fn rollup(ranges: &[(i64, i64, usize)]) -> Rollup {
+    Rollup {
+      repo: "<repo>".into(),
+      generated_at: String::new(),
+      ranges: ranges.len(),
+      totals: RollupTotals::default(),
+      trend: ranges
+        .iter()
+        .enumerate()
+        .map(|(i, (commits, churn, authors))| RollupRange {
+          label: format!("r{}", i),
+          start: String::new(),
+          end: String::new(),
+          commits: *commits,
+          additions: *churn,
+          deletions: 0,
+          churn: *churn,
+          churn_delta: None,
+          authors: *authors,
+          estimated_minutes: None,
+        })
+        .collect(),
+      retention: ranges
+        .iter()
+        .enumerate()
+        .map(|(i, (_, _, authors))| RetentionPoint {
+          label: format!("r{}", i),
+          active: *authors,
+          retained: 0,
+          new: 0,
+          departed: 0,
+        })
+        .collect(),
+      authors: Default::default(),
+    }
+  }
+
+  #[test]
+  fn spikes_dips_and_author_drop_off_are_flagged_in_range_order() {
+    let r = rollup(&[
+      (10, 100, 4),
+      (10, 100, 4),
+      (10, 100, 4),
+      (10, 100, 4),
+      (10, 100, 4),
+      (10, 100, 4),
+      (70, 100, 2),
+    ]);
+
+    let a = detect_anomalies(&r);
+
+    assert_eq!(a.len(), 2);
+    assert_eq!(
+      (a[0].label.as_str(), a[0].metric.as_str(), a[0].kind.as_str()),
+      ("r6", "commits", "spike")
+    );
+    assert_eq!((a[0].value, a[0].baseline, a[0].sigma), (70, 18.57, Some(2.45)));
+    assert_eq!(
+      a[1],
+      RangeAnomaly {
+        label: "r6".into(),
+        metric: "authors".into(),
+        kind: "drop_off".into(),
+        value: 2,
+        baseline: 4.0,
+        sigma: None,
+      }
+    );
+  }
+
+  #[test]
+  fn steady_or_short_runs_have_no_sigma_flags() {
+    assert!(detect_anomalies(&rollup(&[(5, 50, 1), (5, 50, 1), (5, 50, 1)])).is_empty());
+    // two ranges sit exactly 1σ from their mean, however far apart
+    assert!(detect_anomalies(&rollup(&[(1, 10, 3), (500, 9000, 3)])).is_empty());
+  }
+}
@@ -0,0 +1,270 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Package a split/multi output directory into a single .tar.gz or .zip (--archive)
+// role: persistence/archive
+// inputs: Output directory; destination archive path (format chosen by extension); effective now (entry mtime)
+// outputs: Archive file on disk; ArchiveSummary {path, format, entries} for the stdout pointer
+// side_effects: Streams every file under the output dir into <dest>.<pid>.tmp, then renames it over the archive path
+// invariants:
+// - member paths are relative to the output dir with '/' separators, so pointer `file`/`manifest` values are valid inner paths
+// - members are written in sorted path order with the same mtime, so identical inputs yield identical archives
+// - the archive itself is never included, even when written inside the output dir
+// - members are copied file by file, so memory stays flat however large the output dir is
+// - a failed write removes the temp file and leaves any earlier archive at the path untouched
+// errors: Unknown extension and IO failures bubble up with paths
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::fs::File;
+use std::io::{BufWriter, Seek, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Datelike, Local, Timelike};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use zip::write::SimpleFileOptions;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ArchiveFormat {
+  #[serde(rename = "tar.gz")]
+  TarGz,
+  #[serde(rename = "zip")]
+  Zip,
+}
+
+impl ArchiveFormat {
+  /// Format implied by the destination file name (`.tar.gz`/`.tgz` or `.zip`).
+  pub fn from_path(path: &str) -> Option<Self> {
+    let lower = path.to_ascii_lowercase();
+
+    if lower.ends_with(".tar.gz") || lower.ends_with(".tgz") {
+      return Some(Self::TarGz);
+    }
+
+    lower.ends_with(".zip").then_some(Self::Zip)
+  }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveSummary {
+  pub path: String,
+  pub format: ArchiveFormat,
+  pub entries: usize,
+}
+
+struct Member {
+  name: String,
+  path: PathBuf,
+}
+
+/// Write every file under `dir` into the archive at `dest`.
+pub fn write_archive(dir: &str, dest: &str, mtime: DateTime<Local>) -> Result<ArchiveSummary> {
+  let Some(format) = ArchiveFormat::from_path(dest) else {
+    bail!("--archive expects a .tar.gz, .tgz, or .zip path, got {:?}", dest);
+  };
+
+  let dest_path = Path::new(dest);
+  let members = collect_members(Path::new(dir), dest_path)?;
+
+  if let Some(parent) = dest_path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+
+  let tmp = PathBuf::from(format!("{}.{}.tmp", dest, std::process::id()));
+  let written = write_members(&tmp, format, &members, mtime).and_then(|()| Ok(std::fs::rename(&tmp, dest_path)?));
+
+  if let Err(e) = written {
+    let _ = std::fs::remove_file(&tmp);
+    return Err(e.context(format!("writing archive {}", dest)));
+  }
+
+  Ok(ArchiveSummary {
+    path: dest.to_string(),
+    format,
+    entries: members.len(),
+  })
+}
+
+fn collect_members(base: &Path, dest: &Path) -> Result<Vec<Member>> {
+  let mut files = Vec::new();
+  crate::util::collect_files(base, &mut files)?;
+
+  let dest_abs = std::fs::canonicalize(dest).ok();
+  let mut members = Vec::new();
+
+  for path in files {
+    if dest_abs.is_some() && std::fs::canonicalize(&path).ok() == dest_abs {
+      continue;
+    }
+
+    let Ok(rel) = path.strip_prefix(base) else { continue };
+    let name = rel
+      .components()
+      .map(|c| c.as_os_str().to_string_lossy())
+      .collect::<Vec<_>>()
+      .join("/");
+
+    members.push(Member { name, path });
+  }
+
+  members.sort_by(|a, b| a.name.cmp(&b.name));
+
+  Ok(members)
+}
+
+fn write_members(tmp: &Path, format: ArchiveFormat, members: &[Member], mtime: DateTime<Local>) -> Result<()> {
+  let out = BufWriter::new(File::create(tmp).with_context(|| format!("creating {}", tmp.display()))?);
+
+  let mut out = match format {
+    ArchiveFormat::TarGz => tar_gz(out, members, mtime.timestamp().max(0) as u64)?,
+    ArchiveFormat::Zip => zip(out, members, zip_time(mtime))?,
+  };
+  out.flush()?;
+
+  Ok(())
+}
+
+fn tar_gz<W: Write>(out: W, members: &[Member], mtime: u64) -> Result<W> {
+  let mut tar = tar::Builder::new(GzEncoder::new(out, Compression::default()));
+
+  for m in members {
+    let file = File::open(&m.path).with_context(|| format!("reading {}", m.path.display()))?;
+    let mut header = tar::Header::new_ustar();
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(file.metadata()?.len());
+    header.set_mode(0o644);
+    header.set_mtime(mtime);
+
+    tar
+      .append_data(&mut header, &m.name, file)
+      .with_context(|| format!("archiving {}", m.path.display()))?;
+  }
+
+  Ok(tar.into_inner()?.finish()?)
+}
+
+fn zip<W: Write + Seek>(out: W, members: &[Member], mtime: zip::DateTime) -> Result<W> {
+  let mut zip = zip::ZipWriter::new(out);
+  let options = SimpleFileOptions::default()
+    .compression_method(zip::CompressionMethod::Deflated)
+    .last_modified_time(mtime)
+    .unix_permissions(0o644);
+
+  for m in members {
+    let mut file = File::open(&m.path).with_context(|| format!("reading {}", m.path.display()))?;
+    let large = file.metadata()?.len() >= u32::MAX as u64;
+
+    zip.start_file(m.name.as_str(), options.large_file(large))?;
+    std::io::copy(&mut file, &mut zip).with_context(|| format!("archiving {}", m.path.display()))?;
+  }
+
+  Ok(zip.finish()?)
+}
+
+/// MS-DOS timestamp used by zip headers; times outside 1980..=2107 clamp to 1980-01-01.
+fn zip_time(t: DateTime<Local>) -> zip::DateTime {
+  zip::DateTime::from_date_and_time(
+    u16::try_from(t.year()).unwrap_or(0),
+    t.month() as u8,
+    t.day() as u8,
+    t.hour() as u8,
+    t.minute() as u8,
+    t.second() as u8,
+  )
+  .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::io::Read;
+
+  fn out_dir() -> tempfile::TempDir {
+    let td = tempfile::TempDir::new().unwrap();
+    std::fs::write(td.path().join("manifest.json"), "{}").unwrap();
+    std::fs::create_dir_all(td.path().join("2025-08")).unwrap();
+    std::fs::write(td.path().join("2025-08/shard.json"), "{\"sha\":\"x\"}").unwrap();
+    td
+  }
+
+  fn now() -> DateTime<Local> {
+    chrono::DateTime::parse_from_rfc3339("2025-08-15T12:00:00Z")
+      .unwrap()
+      .with_timezone(&Local)
+  }
+
+  #[test]
+  fn format_follows_extension() {
+    assert_eq!(ArchiveFormat::from_path("out.tar.gz"), Some(ArchiveFormat::TarGz));
+    assert_eq!(ArchiveFormat::from_path("OUT.TGZ"), Some(ArchiveFormat::TarGz));
+    assert_eq!(ArchiveFormat::from_path("out.zip"), Some(ArchiveFormat::Zip));
+    assert_eq!(ArchiveFormat::from_path("out.tar"), None);
+  }
+
+  #[test]
+  fn tar_gz_contains_sorted_members_with_valid_headers() {
+    let td = out_dir();
+    let dest = td.path().join("bundle.tar.gz");
+
+    let summary = write_archive(&td.path().to_string_lossy(), &dest.to_string_lossy(), now()).unwrap();
+
+    assert_eq!(summary.entries, 2);
+
+    let mut tar = Vec::new();
+    flate2::read::GzDecoder::new(File::open(&dest).unwrap())
+      .read_to_end(&mut tar)
+      .unwrap();
+    assert!(tar[..100].starts_with(b"2025-08/shard.json\0"));
+    assert_eq!(&tar[257..263], b"ustar\0");
+    assert!(tar[1024..1124].starts_with(b"manifest.json\0"));
+    assert_eq!(tar.len() % 512, 0);
+
+    let again = write_archive(&td.path().to_string_lossy(), &dest.to_string_lossy(), now()).unwrap();
+    assert_eq!(again.entries, 2, "archive must not include itself");
+
+    let leftovers: Vec<_> = std::fs::read_dir(td.path())
+      .unwrap()
+      .filter_map(|e| e.ok())
+      .filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+      .collect();
+    assert!(leftovers.is_empty(), "temp file must be renamed into place");
+  }
+
+  #[test]
+  fn zip_lists_every_member_with_its_contents() {
+    let td = out_dir();
+    let dest = td.path().join("bundle.zip");
+
+    write_archive(&td.path().to_string_lossy(), &dest.to_string_lossy(), now()).unwrap();
+
+    let mut zip = zip::ZipArchive::new(File::open(&dest).unwrap()).unwrap();
+    assert_eq!(zip.len(), 2);
+
+    let mut shard = String::new();
+    zip.by_index(0).unwrap().read_to_string(&mut shard).unwrap();
+    assert_eq!(zip.name_for_index(0), Some("2025-08/shard.json"));
+    assert_eq!(shard, "{\"sha\":\"x\"}");
+    assert_eq!(zip.name_for_index(1), Some("manifest.json"));
+  }
+
+  #[test]
+  fn long_member_names_survive_a_tar_round_trip() {
+    let td = out_dir();
+    let long_dir = td.path().join("d".repeat(120));
+    std::fs::create_dir_all(&long_dir).unwrap();
+    std::fs::write(long_dir.join("f".repeat(90)), "{}").unwrap();
+    let dest = td.path().join("bundle.tgz");
+
+    write_archive(&td.path().to_string_lossy(), &dest.to_string_lossy(), now()).unwrap();
+
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(File::open(&dest).unwrap()));
+    let names: Vec<String> = tar
+      .entries()
+      .unwrap()
+      .map(|e| e.unwrap().path().unwrap().to_string_lossy().to_string())
+      .collect();
+    assert!(names.contains(&format!("{}/{}", "d".repeat(120), "f".repeat(90))));
+  }
+}
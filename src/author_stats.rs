@@ -0,0 +1,176 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Accumulate per-author activity (commits, churn, files, active days, first/last commit) for a report's author_stats[]
+// role: summary/author-stats
+// inputs: the range's since/until; processed range commits, one at a time, in render's accumulation loops
+// outputs: Vec<AuthorStats> for SimpleReport.author_stats
+// side_effects: None
+// invariants:
+// - authors are keyed exactly like `authors` (Person::key: canonical identity after .mailmap/--identity-map)
+// - additions/deletions include entries dropped by --max-files-per-commit; files_touched counts only listed paths
+// - active days and first/last commit use author time in --tz (timestamps.author_local)
+// - activity_calendar has one '0'/'1' per day from the range's first to last day; days outside the window are not marked
+// - entries are ordered by commits descending, then author
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::model::{AuthorStats, Commit};
+
+#[derive(Default)]
+struct Totals {
+  commits: i64,
+  additions: i64,
+  deletions: i64,
+  files: HashSet<String>,
+  days: BTreeSet<String>,
+  first: Option<(i64, String)>,
+  last: Option<(i64, String)>,
+}
+
+/// Running per-author totals for one range.
+pub struct AuthorStatsAccumulator {
+  by_author: BTreeMap<String, Totals>,
+  /// First day of the window and its length in days; None when the bounds do not parse
+  window: Option<(NaiveDate, usize)>,
+}
+
+impl AuthorStatsAccumulator {
+  /// Accumulator for the window `since`..`until` (range start/end, `YYYY-MM-DD[THH:MM:SS]`).
+  pub fn new(since: &str, until: &str) -> Self {
+    Self {
+      by_author: BTreeMap::new(),
+      window: calendar_window(since, until),
+    }
+  }
+
+  pub fn add(&mut self, commit: &Commit) {
+    let t = self.by_author.entry(commit.author.key()).or_default();
+    let (additions, deletions) = crate::commit::commit_additions_deletions(commit);
+    let at = (commit.timestamps.author, commit.timestamps.author_local.clone());
+
+    t.commits += 1;
+    t.additions += additions;
+    t.deletions += deletions;
+    t.files.extend(commit.files.iter().map(|f| f.file.clone()));
+    t.days.insert(at.1.chars().take(10).collect());
+
+    if t.first.as_ref().is_none_or(|f| at.0 < f.0) {
+      t.first = Some(at.clone());
+    }
+    if t.last.as_ref().is_none_or(|l| at.0 > l.0) {
+      t.last = Some(at);
+    }
+  }
+
+  pub fn finish(self) -> Vec<AuthorStats> {
+    let mut stats: Vec<AuthorStats> = self
+      .by_author
+      .into_iter()
+      .map(|(author, t)| AuthorStats {
+        author,
+        commits: t.commits,
+        additions: t.additions,
+        deletions: t.deletions,
+        files_touched: t.files.len(),
+        active_days: t.days.len(),
+        first_commit_at: t.first.map(|f| f.1).unwrap_or_default(),
+        last_commit_at: t.last.map(|l| l.1).unwrap_or_default(),
+        activity_calendar: self.window.map(|w| calendar(w, &t.days)).unwrap_or_default(),
+      })
+      .collect();
+
+    // BTreeMap order already breaks ties by author
+    stats.sort_by(|a, b| b.commits.cmp(&a.commits));
+    stats
+  }
+}
+
+/// Window start day and day count; an `until` at midnight (or a bare date) excludes that day.
+fn calendar_window(since: &str, until: &str) -> Option<(NaiveDate, usize)> {
+  let start = NaiveDate::parse_from_str(since.get(..10)?, "%Y-%m-%d").ok()?;
+  let end_day = NaiveDate::parse_from_str(until.get(..10)?, "%Y-%m-%d").ok()?;
+  let ends_at_midnight = match until.get(..19) {
+    Some(ts) => NaiveDateTime::parse_from_str(ts, "%Y-%m-%dT%H:%M:%S").ok()?.time() == chrono::NaiveTime::MIN,
+    None => true,
+  };
+  let last = if ends_at_midnight { end_day.pred_opt()? } else { end_day };
+  let days = (last - start).num_days() + 1;
+
+  (days > 0).then_some((start, days as usize))
+}
+
+/// One '1' (active) or '0' per window day.
+fn calendar((start, days): (NaiveDate, usize), active: &BTreeSet<String>) -> String {
+  let mut bits = vec![b'0'; days];
+
+  for d in active
+    .iter()
+    .filter_map(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+  {
+    if let Some(bit) = usize::try_from((d - start).num_days())
+      .ok()
+      .and_then(|i| bits.get_mut(i))
+    {
+      *bit = b'1';
+    }
+  }
+
+  String::from_utf8(bits).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(email: &str, epoch: i64, local: &str, files: &[(&str, i64)]) -> Commit {
+    let files: Vec<(&str, i64, i64)> = files.iter().map(|&(f, additions)| (f, additions, 1)).collect();
+
+    Commit::fixture(&"0".repeat(40))
+      .with_author("A", email)
+      .at(epoch, local)
+      .with_files(&files)
+  }
+
+  #[test]
+  fn accumulates_churn_files_days_and_bounds_per_author() {
+    let mut acc = AuthorStatsAccumulator::new("2025-08-10T00:00:00", "2025-08-15T00:00:00");
+    acc.add(&commit("b@x", 300, "2025-08-13T09:00:00Z", &[("a.rs", 2), ("b.rs", 3)]));
+    acc.add(&commit("b@x", 100, "2025-08-12T09:00:00Z", &[("a.rs", 1)]));
+    acc.add(&commit("b@x", 200, "2025-08-12T18:00:00Z", &[]));
+    acc.add(&commit("a@x", 150, "2025-08-12T12:00:00Z", &[("c.rs", 4)]));
+
+    let stats = acc.finish();
+
+    assert_eq!(stats.len(), 2);
+    let b = &stats[0];
+    assert_eq!(b.author, "A <b@x>");
+    assert_eq!((b.commits, b.additions, b.deletions), (3, 6, 3));
+    assert_eq!((b.files_touched, b.active_days), (2, 2));
+    assert_eq!(b.first_commit_at, "2025-08-12T09:00:00Z");
+    assert_eq!(b.last_commit_at, "2025-08-13T09:00:00Z");
+    assert_eq!(b.activity_calendar, "00110");
+    assert_eq!(stats[1].author, "A <a@x>");
+  }
+
+  #[test]
+  fn calendar_window_covers_whole_days_and_skips_outside_activity() {
+    assert_eq!(calendar_window("2025-08-01", "2025-09-01").map(|w| w.1), Some(31));
+    assert_eq!(
+      calendar_window("2025-08-01T00:00:00", "2025-08-03T12:00:00").map(|w| w.1),
+      Some(3)
+    );
+    assert!(calendar_window("last week", "now").is_none());
+
+    let mut acc = AuthorStatsAccumulator::new("2025-08-12", "2025-08-14");
+    acc.add(&commit("a@x", 100, "2025-08-11T23:00:00Z", &[]));
+    acc.add(&commit("a@x", 200, "2025-08-13T09:00:00Z", &[]));
+    let stats = acc.finish();
+    assert_eq!(stats[0].activity_calendar, "01");
+    assert_eq!(stats[0].active_days, 2);
+  }
+}
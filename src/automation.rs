@@ -0,0 +1,110 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Detect CI/bot-authored commits (release bumps, changelog and dependency bots) so summaries can separate them
+// role: classification/automation
+// inputs: --bot-authors regex patterns (defaults below); commit author and committer identities
+// outputs: AutomationDetector; `automated` verdict per commit
+// side_effects: None (pure)
+// invariants:
+// - patterns are case-insensitive and matched against "Name <email>" of both author and committer
+// - a committer that is GitHub Actions marks the commit automated even when the author looks human
+// - GitHub web-UI commits (committer "GitHub <noreply@github.com>") are NOT treated as automated
+// errors: Invalid patterns are rejected during normalize with the offending pattern named
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use anyhow::{Context, Result};
+use regex::{Regex, RegexBuilder};
+
+use crate::model::Person;
+
+/// Default `--bot-authors` patterns.
+pub const DEFAULT_BOT_PATTERNS: [&str; 2] = [r"\[bot\]", r"^(dependabot|renovate|github-actions|semantic-release)\b"];
+
+#[derive(Debug, Clone)]
+pub struct AutomationDetector {
+  patterns: Vec<Regex>,
+}
+
+impl AutomationDetector {
+  pub fn new(patterns: &[String]) -> Result<Self> {
+    let patterns = patterns
+      .iter()
+      .map(|p| {
+        RegexBuilder::new(p)
+          .case_insensitive(true)
+          .build()
+          .with_context(|| format!("invalid --bot-authors pattern {:?}", p))
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    Ok(Self { patterns })
+  }
+
+  /// Whether a commit with this author/committer was produced by automation.
+  pub fn is_automated(&self, author: &Person, committer: &Person) -> bool {
+    if is_github_actions(committer) {
+      return true;
+    }
+
+    [author, committer].iter().any(|p| {
+      let ident = format!("{} <{}>", p.name, p.email);
+
+      self.patterns.iter().any(|re| re.is_match(&ident))
+    })
+  }
+}
+
+/// Committer identities used by GitHub Actions workflows.
+fn is_github_actions(p: &Person) -> bool {
+  let email = p.email.to_ascii_lowercase();
+
+  p.name.eq_ignore_ascii_case("GitHub Actions") || email == "actions@github.com" || email.contains("github-actions")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn person(name: &str, email: &str) -> Person {
+    Person {
+      name: name.into(),
+      email: email.into(),
+      date: String::new(),
+      identity: None,
+    }
+  }
+
+  fn detector() -> AutomationDetector {
+    let defaults: Vec<String> = DEFAULT_BOT_PATTERNS.iter().map(|s| s.to_string()).collect();
+
+    AutomationDetector::new(&defaults).unwrap()
+  }
+
+  #[test]
+  fn default_patterns_catch_common_bots() {
+    let d = detector();
+    let human = person("Jane Doe", "jane@example.com");
+
+    assert!(d.is_automated(
+      &person("dependabot[bot]", "49699333+dependabot[bot]@users.noreply.github.com"),
+      &human
+    ));
+    assert!(d.is_automated(&person("Renovate Bot", "bot@renovateapp.com"), &human));
+    assert!(!d.is_automated(&human, &human));
+  }
+
+  #[test]
+  fn github_actions_committer_marks_automated_but_web_ui_does_not() {
+    let d = detector();
+    let human = person("Jane Doe", "jane@example.com");
+
+    assert!(d.is_automated(&human, &person("GitHub Actions", "actions@github.com")));
+    assert!(!d.is_automated(&human, &person("GitHub", "noreply@github.com")));
+  }
+
+  #[test]
+  fn invalid_pattern_is_rejected() {
+    assert!(AutomationDetector::new(&["(".to_string()]).is_err());
+  }
+}
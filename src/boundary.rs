@@ -0,0 +1,147 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Flag commits within 24h of the window boundaries, whose inclusion depends on the timezone the window is read in
+// role: summary/boundary-commits
+// inputs: repo, since/until strings, include_merges, shas included by this run, report tz
+// outputs: Vec<BoundaryCommit> for summary.boundary_commits (included and excluded neighbours)
+// side_effects: One git log over the window widened by 24h on each side
+// invariants:
+// - only boundaries that parse as RFC3339, %Y-%m-%dT%H:%M:%S, or %Y-%m-%d (local midnight, as git reads them) are checked
+// - commit_utc/offset_seconds use the committer timestamp, which is what --since/--until compare against
+// - ordered by commit time, then sha; a commit near both boundaries is attributed to the closer one
+// errors: None; unparseable boundaries or git failures yield an empty list (best-effort)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::HashSet;
+
+use chrono::{Local, NaiveDate, TimeZone, Utc};
+
+use crate::gitio;
+use crate::model::BoundaryCommit;
+use crate::util::iso_in_tz;
+
+/// Every UTC offset in use lies within ±14h, so 24h covers any reading of a naive boundary.
+pub const BOUNDARY_SLACK_SECS: i64 = 24 * 3600;
+
+/// Epoch of a window boundary string, when it is an absolute date/time.
+fn boundary_epoch(s: &str) -> Option<i64> {
+  if let Some(dt) = crate::range_windows::parse_now(Some(s)) {
+    return Some(dt.timestamp());
+  }
+
+  let date = NaiveDate::parse_from_str(s, "%Y-%m-%d").ok()?;
+  let dt = date.and_hms_opt(0, 0, 0)?.and_local_timezone(Local).earliest()?;
+
+  Some(dt.timestamp())
+}
+
+fn git_date(epoch: i64) -> String {
+  Utc
+    .timestamp_opt(epoch, 0)
+    .single()
+    .map(|dt| dt.format("%Y-%m-%d %H:%M:%S +0000").to_string())
+    .unwrap_or_default()
+}
+
+/// Commits within `BOUNDARY_SLACK_SECS` of `since` or `until`, marked with whether this run included them.
+pub fn find_boundary_commits(
+  repo: &str,
+  since: &str,
+  until: &str,
+  include_merges: bool,
+  paths: &[String],
+  included: &HashSet<&str>,
+  tz: &str,
+) -> Vec<BoundaryCommit> {
+  let (Some(since_epoch), Some(until_epoch)) = (boundary_epoch(since), boundary_epoch(until)) else {
+    return Vec::new();
+  };
+  let wide = gitio::commit_epochs(
+    repo,
+    &git_date(since_epoch - BOUNDARY_SLACK_SECS),
+    &git_date(until_epoch + BOUNDARY_SLACK_SECS),
+    include_merges,
+    paths,
+  )
+  .unwrap_or_default();
+
+  classify(&wide, since_epoch, until_epoch, included, tz)
+}
+
+fn classify(
+  commits: &[(String, i64)],
+  since_epoch: i64,
+  until_epoch: i64,
+  included: &HashSet<&str>,
+  tz: &str,
+) -> Vec<BoundaryCommit> {
+  let mut out: Vec<BoundaryCommit> = commits
+    .iter()
+    .filter_map(|(sha, ct)| {
+      let to_since = ct - since_epoch;
+      let to_until = ct - until_epoch;
+      let (boundary, offset) = if to_since.abs() <= to_until.abs() {
+        ("since", to_since)
+      } else {
+        ("until", to_until)
+      };
+
+      if offset.abs() > BOUNDARY_SLACK_SECS {
+        return None;
+      }
+
+      Some(BoundaryCommit {
+        sha: sha.clone(),
+        boundary: boundary.into(),
+        included: included.contains(sha.as_str()),
+        commit_utc: iso_in_tz(*ct, "utc"),
+        commit_local: iso_in_tz(*ct, tz),
+        offset_seconds: offset,
+      })
+    })
+    .collect();
+  out.sort_by(|a, b| a.commit_utc.cmp(&b.commit_utc).then_with(|| a.sha.cmp(&b.sha)));
+
+  out
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 2025-08-01T00:00:00Z and 2025-09-01T00:00:00Z
+  const SINCE: i64 = 1_754_006_400;
+  const UNTIL: i64 = 1_756_684_800;
+
+  #[test]
+  fn flags_commits_near_either_boundary() {
+    let commits = vec![
+      ("in-early".to_string(), SINCE + 3600),
+      ("out-early".to_string(), SINCE - 7200),
+      ("middle".to_string(), SINCE + 10 * 86_400),
+      ("in-late".to_string(), UNTIL - 60),
+      ("far".to_string(), UNTIL + 2 * 86_400),
+    ];
+    let included: HashSet<&str> = ["in-early", "middle", "in-late"].into_iter().collect();
+
+    let b = classify(&commits, SINCE, UNTIL, &included, "America/Chicago");
+
+    let shas: Vec<&str> = b.iter().map(|c| c.sha.as_str()).collect();
+    assert_eq!(shas, ["out-early", "in-early", "in-late"]);
+    assert_eq!(b[0].boundary, "since");
+    assert!(!b[0].included);
+    assert_eq!(b[0].offset_seconds, -7200);
+    assert_eq!(b[0].commit_utc, "2025-07-31T22:00:00Z");
+    assert_eq!(b[0].commit_local, "2025-07-31T17:00:00-05:00");
+    assert_eq!(b[2].boundary, "until");
+    assert!(b[2].included);
+  }
+
+  #[test]
+  fn relative_boundaries_are_not_checked() {
+    assert!(boundary_epoch("last week").is_none());
+    assert_eq!(boundary_epoch("2025-08-01T00:00:00Z"), Some(SINCE));
+    assert!(boundary_epoch("2025-08-01").is_some());
+  }
+}
@@ -0,0 +1,639 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --browse mode: explore a split output directory (ranges, commits, diffstats, PR/MR details) in a terminal UI
+// role: entrypoint/browse
+// inputs: Output dir holding manifest.json (multi-range) or report-*.json (single range); commit shards listed in items[]
+// outputs: Interactive ratatui screen on the terminal; nothing written
+// side_effects: Reads the reports and, for the selected range, every listed commit shard; switches the terminal to raw/alternate mode until quit
+// invariants:
+// - ranges follow manifest order (report-*.json name order without a manifest); inline commits[] are used when a report has them
+// - an unreadable shard shows as a stub row (sha + subject) with a status-line warning instead of aborting the session
+// - the filter is a case-insensitive substring over sha, subject, author name/email, and file paths of the loaded range
+// - the terminal is restored on exit, including when drawing or input fails
+// errors: A missing/unparsable directory or report fails before the screen opens; later read failures surface on the status line
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span, Text};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use serde_json::Value;
+
+const MANIFEST: &str = "manifest.json";
+const HELP: &str = "q quit · Tab/←→ pane · ↑↓/jk move · / filter · Esc clear · PgUp/PgDn scroll";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Pane {
+  Ranges,
+  Commits,
+  Detail,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct RangeRef {
+  label: String,
+  /// Report path relative to the output dir
+  file: String,
+}
+
+struct Browser {
+  dir: PathBuf,
+  ranges: Vec<RangeRef>,
+  range_state: ListState,
+  commits: Vec<Value>,
+  /// Indexes into `commits` that match `filter`
+  visible: Vec<usize>,
+  commit_state: ListState,
+  filter: String,
+  editing: bool,
+  focus: Pane,
+  scroll: u16,
+  status: String,
+}
+
+/// Open the browser on `dir` and run until the user quits.
+pub fn run(dir: &str) -> Result<()> {
+  let mut browser = Browser::open(Path::new(dir))?;
+  let mut terminal = ratatui::init();
+  let result = browser.event_loop(&mut terminal);
+
+  ratatui::restore();
+
+  result
+}
+
+/// Ranges of an output dir: manifest.json order, else report-*.json files by name.
+fn discover_ranges(dir: &Path) -> Result<Vec<RangeRef>> {
+  let manifest = dir.join(MANIFEST);
+
+  if manifest.exists() {
+    let v = read_json(&manifest)?;
+    let ranges = v["ranges"]
+      .as_array()
+      .map(|ranges| {
+        ranges
+          .iter()
+          .filter_map(|r| {
+            let file = r["file"].as_str()?.to_string();
+            let label = r["label"].as_str().map(String::from).unwrap_or_else(|| file.clone());
+
+            Some(RangeRef { label, file })
+          })
+          .collect()
+      })
+      .unwrap_or_default();
+
+    return Ok(ranges);
+  }
+
+  let mut ranges: Vec<RangeRef> = std::fs::read_dir(dir)
+    .with_context(|| format!("reading {}", dir.display()))?
+    .filter_map(|entry| {
+      let name = entry.ok()?.file_name().to_string_lossy().to_string();
+      let label = name.strip_prefix("report-")?.strip_suffix(".json")?.to_string();
+
+      Some(RangeRef { label, file: name })
+    })
+    .collect();
+  ranges.sort_by(|a, b| a.file.cmp(&b.file));
+
+  Ok(ranges)
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+  let bytes = crate::util::read_output(path)?;
+
+  serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// Commits of one range report: inline `commits[]`, else each `items[]` shard. Unreadable shards become stubs and
+/// are reported in the returned warnings.
+fn load_commits(dir: &Path, report_file: &str) -> Result<(Vec<Value>, Vec<String>)> {
+  let report = read_json(&dir.join(report_file))?;
+
+  if let Some(commits) = report["commits"].as_array().filter(|c| !c.is_empty()) {
+    return Ok((commits.clone(), Vec::new()));
+  }
+
+  let mut warnings = Vec::new();
+  let commits = report["items"]
+    .as_array()
+    .map(|items| {
+      items
+        .iter()
+        .map(|item| {
+          let shard = item["file"].as_str().unwrap_or_default();
+
+          read_json(&dir.join(shard)).unwrap_or_else(|err| {
+            warnings.push(format!("{}: {:#}", shard, err));
+            serde_json::json!({ "sha": item["sha"], "subject": item["subject"] })
+          })
+        })
+        .collect()
+    })
+    .unwrap_or_default();
+
+  Ok((commits, warnings))
+}
+
+fn text(v: &Value, ptr: &str) -> String {
+  v.pointer(ptr).and_then(Value::as_str).unwrap_or_default().to_string()
+}
+
+fn short(sha: &str) -> &str {
+  &sha[..sha.len().min(12)]
+}
+
+fn matches(commit: &Value, needle: &str) -> bool {
+  if needle.is_empty() {
+    return true;
+  }
+
+  let fields = ["/sha", "/subject", "/author/name", "/author/email"];
+  let files = commit["files"]
+    .as_array()
+    .into_iter()
+    .flatten()
+    .map(|f| text(f, "/file"));
+
+  fields
+    .iter()
+    .map(|ptr| text(commit, ptr))
+    .chain(files)
+    .any(|s| s.to_lowercase().contains(needle))
+}
+
+/// Detail pane lines for one commit: header, message, diffstat, linked PRs/MRs.
+fn commit_detail(c: &Value) -> Vec<Line<'static>> {
+  let bold = Style::new().add_modifier(Modifier::BOLD);
+  let dim = Style::new().fg(Color::DarkGray);
+  let mut lines = vec![
+    Line::from(Span::styled(
+      format!("commit {}", text(c, "/sha")),
+      Style::new().fg(Color::Yellow),
+    )),
+    Line::from(format!(
+      "Author: {} <{}>",
+      text(c, "/author/name"),
+      text(c, "/author/email")
+    )),
+    Line::from(format!("Date:   {}", text(c, "/author/date"))),
+    Line::default(),
+    Line::from(Span::styled(text(c, "/subject"), bold)),
+  ];
+
+  let body = text(c, "/body");
+  if !body.trim().is_empty() {
+    lines.push(Line::default());
+    lines.extend(body.lines().map(|l| Line::from(l.to_string())));
+  }
+
+  if let Some(files) = c["files"].as_array().filter(|f| !f.is_empty()) {
+    let count = |f: &Value, key: &str| f[key].as_i64().unwrap_or(0);
+    let (add, del) = files.iter().fold((0, 0), |(a, d), f| {
+      (a + count(f, "additions"), d + count(f, "deletions"))
+    });
+
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled(
+      format!("Files ({})  +{} -{}", files.len(), add, del),
+      bold,
+    )));
+    for f in files {
+      lines.push(Line::from(vec![
+        Span::styled(format!("{:<3}", text(f, "/status")), dim),
+        Span::raw(text(f, "/file")),
+        Span::styled(format!("  +{}", count(f, "additions")), Style::new().fg(Color::Green)),
+        Span::styled(format!(" -{}", count(f, "deletions")), Style::new().fg(Color::Red)),
+      ]));
+    }
+  }
+
+  let prs = c.pointer("/github/pull_requests").and_then(Value::as_array);
+  if let Some(prs) = prs.filter(|p| !p.is_empty()) {
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("Pull requests", bold)));
+    for pr in prs {
+      lines.push(Line::from(format!(
+        "#{} {} [{}]",
+        pr["number"],
+        text(pr, "/title"),
+        text(pr, "/state")
+      )));
+      lines.push(Line::from(Span::styled(
+        format!(
+          "  by {} · reviews {} · {}",
+          text(pr, "/submitter/login"),
+          pr["review_count"].as_i64().unwrap_or(0),
+          text(pr, "/html_url")
+        ),
+        dim,
+      )));
+    }
+  }
+
+  let mrs = c.pointer("/gitlab/merge_requests").and_then(Value::as_array);
+  if let Some(mrs) = mrs.filter(|m| !m.is_empty()) {
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("Merge requests", bold)));
+    for mr in mrs {
+      lines.push(Line::from(format!(
+        "!{} {} [{}]",
+        mr["iid"],
+        text(mr, "/title"),
+        text(mr, "/state")
+      )));
+      lines.push(Line::from(Span::styled(
+        format!("  by {} · {}", text(mr, "/author/username"), text(mr, "/web_url")),
+        dim,
+      )));
+    }
+  }
+
+  lines
+}
+
+impl Browser {
+  fn open(dir: &Path) -> Result<Self> {
+    if !dir.is_dir() {
+      bail!(
+        "{} is not a directory; --browse expects a --split-apart output directory",
+        dir.display()
+      );
+    }
+
+    let ranges = discover_ranges(dir)?;
+    if ranges.is_empty() {
+      bail!("no {} or report-*.json in {}", MANIFEST, dir.display());
+    }
+
+    let mut browser = Self {
+      dir: dir.to_path_buf(),
+      ranges,
+      range_state: ListState::default().with_selected(Some(0)),
+      commits: Vec::new(),
+      visible: Vec::new(),
+      commit_state: ListState::default(),
+      filter: String::new(),
+      editing: false,
+      focus: Pane::Ranges,
+      scroll: 0,
+      status: HELP.to_string(),
+    };
+    browser.load_range()?;
+
+    Ok(browser)
+  }
+
+  fn load_range(&mut self) -> Result<()> {
+    let range = &self.ranges[self.range_state.selected().unwrap_or(0)];
+    let (commits, warnings) = load_commits(&self.dir, &range.file)?;
+
+    self.commits = commits;
+    self.status = match warnings.first() {
+      Some(first) => format!("{} unreadable shard(s); first: {}", warnings.len(), first),
+      None => HELP.to_string(),
+    };
+    self.apply_filter();
+
+    Ok(())
+  }
+
+  fn apply_filter(&mut self) {
+    let needle = self.filter.to_lowercase();
+
+    self.visible = (0..self.commits.len())
+      .filter(|i| matches(&self.commits[*i], &needle))
+      .collect();
+    self.commit_state.select((!self.visible.is_empty()).then_some(0));
+    self.scroll = 0;
+  }
+
+  fn selected_commit(&self) -> Option<&Value> {
+    self
+      .commit_state
+      .selected()
+      .and_then(|i| self.visible.get(i))
+      .map(|i| &self.commits[*i])
+  }
+
+  fn move_selection(&mut self, delta: isize) -> Result<()> {
+    match self.focus {
+      Pane::Ranges => {
+        let current = self.range_state.selected().unwrap_or(0);
+        let next = current.saturating_add_signed(delta).min(self.ranges.len() - 1);
+
+        if next != current {
+          self.range_state.select(Some(next));
+          self.load_range()?;
+        }
+      }
+      Pane::Commits if !self.visible.is_empty() => {
+        let current = self.commit_state.selected().unwrap_or(0);
+
+        self
+          .commit_state
+          .select(Some(current.saturating_add_signed(delta).min(self.visible.len() - 1)));
+        self.scroll = 0;
+      }
+      Pane::Commits => {}
+      Pane::Detail => self.scroll = self.scroll.saturating_add_signed(delta as i16),
+    }
+
+    Ok(())
+  }
+
+  /// Apply one key press; returns true when the user asked to quit.
+  fn handle_key(&mut self, key: KeyCode) -> Result<bool> {
+    if self.editing {
+      match key {
+        KeyCode::Enter => self.editing = false,
+        KeyCode::Esc => {
+          self.editing = false;
+          self.filter.clear();
+          self.apply_filter();
+        }
+        KeyCode::Backspace => {
+          self.filter.pop();
+          self.apply_filter();
+        }
+        KeyCode::Char(c) => {
+          self.filter.push(c);
+          self.apply_filter();
+        }
+        _ => {}
+      }
+
+      return Ok(false);
+    }
+
+    match key {
+      KeyCode::Char('q') => return Ok(true),
+      KeyCode::Char('/') => {
+        self.editing = true;
+        self.focus = Pane::Commits;
+      }
+      KeyCode::Esc if !self.filter.is_empty() => {
+        self.filter.clear();
+        self.apply_filter();
+      }
+      KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter => {
+        self.focus = match self.focus {
+          Pane::Ranges => Pane::Commits,
+          Pane::Commits | Pane::Detail => Pane::Detail,
+        };
+      }
+      KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => {
+        self.focus = match self.focus {
+          Pane::Ranges | Pane::Commits => Pane::Ranges,
+          Pane::Detail => Pane::Commits,
+        };
+      }
+      KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1)?,
+      KeyCode::Down | KeyCode::Char('j') => self.move_selection(1)?,
+      KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+      KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+      _ => {}
+    }
+
+    Ok(false)
+  }
+
+  fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+    loop {
+      terminal.draw(|frame| self.render(frame))?;
+
+      if let Event::Key(key) = event::read()? {
+        if key.kind == KeyEventKind::Press && self.handle_key(key.code)? {
+          return Ok(());
+        }
+      }
+    }
+  }
+
+  fn block(&self, title: String, pane: Pane) -> Block<'static> {
+    let style = if self.focus == pane {
+      Style::new().fg(Color::Cyan)
+    } else {
+      Style::new()
+    };
+
+    Block::bordered().title(title).border_style(style)
+  }
+
+  fn render(&mut self, frame: &mut Frame) {
+    let [main, status] = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).areas(frame.area());
+    let [ranges_area, commits_area, detail_area] = Layout::horizontal([
+      Constraint::Percentage(18),
+      Constraint::Percentage(37),
+      Constraint::Percentage(45),
+    ])
+    .areas(main);
+    let highlight = Style::new().add_modifier(Modifier::REVERSED);
+
+    let ranges = List::new(self.ranges.iter().map(|r| ListItem::new(r.label.clone())))
+      .block(self.block(" Ranges ".into(), Pane::Ranges))
+      .highlight_style(highlight);
+    frame.render_stateful_widget(ranges, ranges_area, &mut self.range_state);
+
+    let rows: Vec<ListItem> = self
+      .visible
+      .iter()
+      .map(|i| {
+        let c = &self.commits[*i];
+        let sha = text(c, "/sha");
+
+        ListItem::new(Line::from(vec![
+          Span::styled(short(&sha).to_string(), Style::new().fg(Color::Yellow)),
+          Span::raw(" "),
+          Span::raw(text(c, "/subject")),
+          Span::styled(
+            format!("  {}", text(c, "/author/name")),
+            Style::new().fg(Color::DarkGray),
+          ),
+        ]))
+      })
+      .collect();
+    let title = if self.filter.is_empty() && !self.editing {
+      format!(" Commits ({}) ", self.commits.len())
+    } else {
+      format!(
+        " Commits ({}/{}) /{} ",
+        self.visible.len(),
+        self.commits.len(),
+        self.filter
+      )
+    };
+    let commits = List::new(rows)
+      .block(self.block(title, Pane::Commits))
+      .highlight_style(highlight);
+    frame.render_stateful_widget(commits, commits_area, &mut self.commit_state);
+
+    let detail = self.selected_commit().map(commit_detail).unwrap_or_default();
+    let detail = Paragraph::new(Text::from(detail))
+      .block(self.block(" Detail ".into(), Pane::Detail))
+      .wrap(Wrap { trim: false })
+      .scroll((self.scroll, 0));
+    frame.render_widget(detail, detail_area);
+
+    let status_line = if self.editing {
+      format!("filter: {}▏ (Enter keep · Esc clear)", self.filter)
+    } else {
+      self.status.clone()
+    };
+    frame.render_widget(
+      Paragraph::new(status_line).style(Style::new().fg(Color::DarkGray)),
+      status,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ratatui::Terminal;
+  use ratatui::backend::TestBackend;
+
+  fn commit(sha: &str, subject: &str, author: &str, file: &str) -> Value {
+    serde_json::json!({
+      "sha": sha,
+      "subject": subject,
+      "body": "",
+      "author": { "name": author, "email": format!("{}@x", author.to_lowercase()), "date": "2025-08-12T14:03:00Z" },
+      "files": [{ "file": file, "status": "A", "additions": 1, "deletions": 0 }],
+      "github": { "pull_requests": [{ "number": 7, "title": "Payments", "state": "merged", "html_url": "https://example.com/pr/7", "submitter": { "login": "bo" } }] },
+    })
+  }
+
+  fn write(dir: &Path, rel: &str, v: &Value) {
+    let path = dir.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, serde_json::to_vec(v).unwrap()).unwrap();
+  }
+
+  /// Two ranges: the first sharded (one shard missing), the second with inline commits.
+  fn out_dir() -> tempfile::TempDir {
+    let td = tempfile::TempDir::new().unwrap();
+    let d = td.path();
+    write(
+      d,
+      "2025-07/a.json",
+      &commit("aaaaaaaaaaaaaaaa", "add user model", "Ann", "app/models/user.rb"),
+    );
+    write(
+      d,
+      "report-2025-07.json",
+      &serde_json::json!({ "items": [
+        { "sha": "aaaaaaaaaaaaaaaa", "file": "2025-07/a.json", "subject": "add user model" },
+        { "sha": "bbbbbbbbbbbbbbbb", "file": "2025-07/missing.json", "subject": "lost shard" },
+      ] }),
+    );
+    write(
+      d,
+      "report-2025-08.json",
+      &serde_json::json!({ "commits": [
+        commit("cccccccccccccccc", "extract payment service", "Bo", "app/services/payment_service.rb"),
+        commit("dddddddddddddddd", "docs", "Ann", "README.md"),
+      ] }),
+    );
+    write(
+      d,
+      MANIFEST,
+      &serde_json::json!({ "ranges": [
+        { "label": "2025-07", "file": "report-2025-07.json" },
+        { "label": "2025-08", "file": "report-2025-08.json" },
+      ] }),
+    );
+
+    td
+  }
+
+  fn screen(browser: &mut Browser) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(160, 30)).unwrap();
+    terminal.draw(|frame| browser.render(frame)).unwrap();
+
+    terminal
+      .backend()
+      .buffer()
+      .content()
+      .iter()
+      .map(|c| c.symbol())
+      .collect()
+  }
+
+  #[test]
+  fn opens_ranges_in_manifest_order_and_stubs_unreadable_shards() {
+    let td = out_dir();
+    let b = Browser::open(td.path()).unwrap();
+
+    let labels: Vec<&str> = b.ranges.iter().map(|r| r.label.as_str()).collect();
+    assert_eq!(labels, vec!["2025-07", "2025-08"]);
+    assert_eq!(b.commits.len(), 2);
+    assert_eq!(b.commits[0]["author"]["name"], "Ann");
+    assert_eq!(b.commits[1]["subject"], "lost shard");
+    assert!(b.status.starts_with("1 unreadable shard(s)"), "{}", b.status);
+  }
+
+  #[test]
+  fn without_manifest_report_files_are_ranges() {
+    let td = out_dir();
+    std::fs::remove_file(td.path().join(MANIFEST)).unwrap();
+
+    let ranges = discover_ranges(td.path()).unwrap();
+    assert_eq!(
+      ranges[1],
+      RangeRef {
+        label: "2025-08".into(),
+        file: "report-2025-08.json".into()
+      }
+    );
+    assert!(Browser::open(&td.path().join("nope")).is_err());
+  }
+
+  #[test]
+  fn keys_switch_ranges_and_filter_commits() {
+    let td = out_dir();
+    let mut b = Browser::open(td.path()).unwrap();
+
+    b.handle_key(KeyCode::Down).unwrap();
+    assert_eq!(b.commits.len(), 2);
+    assert_eq!(b.commits[0]["subject"], "extract payment service");
+
+    for key in [
+      KeyCode::Char('/'),
+      KeyCode::Char('R'),
+      KeyCode::Char('e'),
+      KeyCode::Char('a'),
+    ] {
+      b.handle_key(key).unwrap();
+    }
+    assert_eq!(b.focus, Pane::Commits);
+    assert_eq!(b.visible, vec![1]);
+    // 'q' while editing is filter text, not quit
+    assert!(!b.handle_key(KeyCode::Char('q')).unwrap());
+    assert!(b.visible.is_empty());
+
+    b.handle_key(KeyCode::Esc).unwrap();
+    assert_eq!(b.visible, vec![0, 1]);
+    assert!(b.handle_key(KeyCode::Char('q')).unwrap());
+  }
+
+  #[test]
+  fn renders_commit_list_diffstat_and_pull_requests() {
+    let td = out_dir();
+    let mut b = Browser::open(td.path()).unwrap();
+
+    let text = screen(&mut b);
+
+    assert!(text.contains("2025-08"));
+    assert!(text.contains("aaaaaaaaaaaa add user model"));
+    assert!(text.contains("Files (1)  +1 -0"));
+    assert!(text.contains("#7 Payments [merged]"));
+  }
+}
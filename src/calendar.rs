@@ -0,0 +1,97 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Bucket a range's commits by local day and by weekday × hour for the report's `calendar` section (--calendar)
+// role: summary/calendar
+// inputs: Processed range commits, one at a time (timestamps.author_local, already in --tz); the --tz name
+// outputs: ActivityCalendar { timezone, days, punch_card } for SimpleReport.calendar
+// side_effects: None
+// invariants:
+// - buckets use author time in --tz, read from the offset-carrying author_local, so they agree with author_stats days
+// - days lists only days with commits; punch_card is always 7 rows (Monday first) of 24 hourly counts
+// - every commit lands in exactly one day and one punch-card cell, so both sum to the commits counted
+// - commits whose author_local does not parse are skipped
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Timelike};
+
+use crate::model::{ActivityCalendar, Commit};
+
+/// Running calendar buckets for one range.
+pub struct CalendarAccumulator {
+  timezone: String,
+  days: BTreeMap<String, i64>,
+  punch_card: [[i64; 24]; 7],
+}
+
+impl CalendarAccumulator {
+  pub fn new(tz: &str) -> Self {
+    Self {
+      timezone: tz.to_string(),
+      days: BTreeMap::new(),
+      punch_card: [[0; 24]; 7],
+    }
+  }
+
+  pub fn add(&mut self, commit: &Commit) {
+    let Ok(at) = DateTime::parse_from_rfc3339(&commit.timestamps.author_local) else {
+      return;
+    };
+
+    *self.days.entry(at.format("%Y-%m-%d").to_string()).or_insert(0) += 1;
+    self.punch_card[at.weekday().num_days_from_monday() as usize][at.hour() as usize] += 1;
+  }
+
+  pub fn finish(self) -> ActivityCalendar {
+    ActivityCalendar {
+      timezone: self.timezone,
+      days: self.days,
+      punch_card: self.punch_card.iter().map(|hours| hours.to_vec()).collect(),
+    }
+  }
+}
+
+/// Calendar buckets for all of `commits`.
+pub fn build_calendar(commits: &[Commit], tz: &str) -> ActivityCalendar {
+  let mut acc = CalendarAccumulator::new(tz);
+  commits.iter().for_each(|c| acc.add(c));
+
+  acc.finish()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(author_local: &str) -> Commit {
+    Commit::fixture("a1").at(0, author_local)
+  }
+
+  #[test]
+  fn commits_are_bucketed_by_local_day_and_weekday_hour() {
+    let commits = vec![
+      // Tuesday 14:03 and 14:59 in the report's zone
+      commit("2025-08-12T14:03:00-05:00"),
+      commit("2025-08-12T14:59:00-05:00"),
+      // Sunday 23:30 locally, already Monday in UTC
+      commit("2025-08-17T23:30:00-05:00"),
+      commit("not a timestamp"),
+    ];
+
+    let cal = build_calendar(&commits, "America/Chicago");
+
+    assert_eq!(cal.timezone, "America/Chicago");
+    assert_eq!(
+      cal.days,
+      BTreeMap::from([("2025-08-12".to_string(), 2), ("2025-08-17".to_string(), 1)])
+    );
+    assert_eq!(cal.punch_card.len(), 7);
+    assert!(cal.punch_card.iter().all(|hours| hours.len() == 24));
+    assert_eq!(cal.punch_card[1][14], 2);
+    assert_eq!(cal.punch_card[6][23], 1);
+    assert_eq!(cal.punch_card.iter().flatten().sum::<i64>(), 3);
+  }
+}
@@ -0,0 +1,294 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Checkpoint multi-range runs in <out>/backfill-state.json so --resume can skip completed ranges
+// role: persistence/checkpoint
+// inputs: EffectiveConfig (options fingerprint), base_dir, LabeledRange, RangeEntry per completed range
+// outputs: backfill-state.json {options, completed[]}; reusable RangeEntry values for completed ranges
+// side_effects: Reads/writes backfill-state.json (written via temp file + rename after every range, under the caller's crate::out_lock::DirLock)
+// invariants:
+// - options are the OPTION_KEYS subset of the serialized EffectiveConfig (what shapes report contents, not how the run goes: --jobs, --out, --lock-timeout, HTTP timeouts are left out); a state recorded with other options is never reused
+// - a range counts as completed only when label/start/end match and its report file still exists under base_dir
+// - without --resume the state starts empty, so a fresh run never inherits old progress
+// - record merges in ranges another run with the same options recorded since, so runs sharing a dir keep each other's progress
+// errors: IO/JSON errors with path context; --resume against a state with different options bails
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+
+use crate::cli::EffectiveConfig;
+use crate::manifest::RangeEntry;
+use crate::range_windows::LabeledRange;
+
+pub const STATE_FILE: &str = "backfill-state.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompletedRange {
+  pub label: String,
+  pub start: String,
+  pub end: String,
+  pub file: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackfillState {
+  pub options: serde_json::Value,
+  pub completed: Vec<CompletedRange>,
+}
+
+/// EffectiveConfig keys that change which ranges a run reports or what their reports contain.
+const OPTION_KEYS: [&str; 54] = [
+  "repo",
+  "window",
+  "multi_windows",
+  "split_apart",
+  "include_merges",
+  "include_patch",
+  "max_patch_bytes",
+  "max_files_per_commit",
+  "save_patches",
+  "no_blob_stats",
+  "layout",
+  "format",
+  "csv_columns",
+  "github_prs",
+  "include_unmerged",
+  "unmerged_base",
+  "stale_days",
+  "tz",
+  "now_override",
+  "estimate_effort",
+  "service_map",
+  "identity_map",
+  "include_pr_patch",
+  "pr_files",
+  "github_repo",
+  "redact_paths",
+  "env_trailers",
+  "patch_budget_bytes",
+  "side_indexes",
+  "git_args",
+  "bot_authors",
+  "issue_patterns",
+  "issue_url_templates",
+  "repo_policy",
+  "http_max_pages",
+  "github_max_requests",
+  "collaboration",
+  "work_items",
+  "calendar",
+  "releases",
+  "snapshot_paths",
+  "snapshot_max_bytes",
+  "conflict_hotspots",
+  "dependency_changes",
+  "new_contributors",
+  "no_bodies",
+  "summary_only",
+  "fields",
+  "all_refs",
+  "first_parent",
+  "committers",
+  "refs",
+  "paths",
+  "sample",
+];
+
+/// The options a state is valid for: the effective config restricted to `OPTION_KEYS`.
+fn options_fingerprint(cfg: &EffectiveConfig) -> Result<serde_json::Value> {
+  let all = serde_json::to_value(cfg)?;
+  let options: serde_json::Map<String, serde_json::Value> = OPTION_KEYS
+    .iter()
+    .map(|k| (k.to_string(), all.get(*k).cloned().unwrap_or_default()))
+    .collect();
+
+  Ok(serde_json::Value::Object(options))
+}
+
+impl BackfillState {
+  /// Fresh state, or (with `--resume`) the one already recorded in `base_dir` for the same options.
+  pub fn start(cfg: &EffectiveConfig, base_dir: &str) -> Result<Self> {
+    let options = options_fingerprint(cfg)?;
+    let path = Path::new(base_dir).join(STATE_FILE);
+
+    if !cfg.resume || !path.exists() {
+      return Ok(Self {
+        options,
+        completed: Vec::new(),
+      });
+    }
+
+    let bytes = std::fs::read(&path).with_context(|| format!("reading {}", path.display()))?;
+    let state: Self = serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))?;
+
+    if state.options != options {
+      bail!(
+        "{} was recorded with different options; rerun without --resume to start over",
+        path.display()
+      );
+    }
+
+    Ok(state)
+  }
+
+  /// Manifest entry for `range` when it already completed and its report is still on disk.
+  pub fn completed_entry(&self, base_dir: &str, range: &LabeledRange) -> Option<RangeEntry> {
+    let done = self
+      .completed
+      .iter()
+      .find(|c| c.label == range.label && c.start == range.since && c.end == range.until)?;
+
+    Path::new(base_dir).join(&done.file).exists().then(|| RangeEntry {
+      label: done.label.clone(),
+      start: done.start.clone(),
+      end: done.end.clone(),
+      file: done.file.clone(),
+    })
+  }
+
+  /// Record a finished range and persist the state, keeping ranges another run with the same options recorded in
+  /// the meantime. Callers hold the output dir lock.
+  pub fn record(&mut self, base_dir: &str, entry: &RangeEntry) -> Result<()> {
+    let path = Path::new(base_dir).join(STATE_FILE);
+    let on_disk = std::fs::read(&path)
+      .ok()
+      .and_then(|bytes| serde_json::from_slice::<Self>(&bytes).ok())
+      .filter(|s| s.options == self.options);
+
+    for other in on_disk.into_iter().flat_map(|s| s.completed) {
+      if !self.completed.iter().any(|c| c.label == other.label) {
+        self.completed.push(other);
+      }
+    }
+
+    self.completed.retain(|c| c.label != entry.label);
+    self.completed.push(CompletedRange {
+      label: entry.label.clone(),
+      start: entry.start.clone(),
+      end: entry.end.clone(),
+      file: entry.file.clone(),
+    });
+
+    let tmp = path.with_extension(format!("json.{}.tmp", std::process::id()));
+
+    std::fs::write(&tmp, serde_json::to_vec_pretty(self)?).with_context(|| format!("writing {}", tmp.display()))?;
+    std::fs::rename(&tmp, &path).with_context(|| format!("writing {}", path.display()))?;
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::range_windows::WindowSpec;
+  use clap::Parser;
+
+  fn cfg(resume: bool) -> EffectiveConfig {
+    let cli = crate::cli::Cli::parse_from([
+      "git-activity-report",
+      "--for",
+      "every month for the last 3 months",
+      "--out",
+      "/tmp/x",
+    ]);
+    let mut cfg = crate::cli::normalize(cli).unwrap();
+    cfg.resume = resume;
+    cfg
+  }
+
+  fn range(label: &str) -> LabeledRange {
+    LabeledRange {
+      label: label.into(),
+      since: format!("{}-01", label),
+      until: "2100-01-01".into(),
+    }
+  }
+
+  fn entry(label: &str) -> RangeEntry {
+    RangeEntry {
+      label: label.into(),
+      start: format!("{}-01", label),
+      end: "2100-01-01".into(),
+      file: format!("report-{}.json", label),
+    }
+  }
+
+  #[test]
+  fn resume_reuses_completed_ranges_whose_reports_exist() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path().to_str().unwrap();
+
+    let mut state = BackfillState::start(&cfg(false), dir).unwrap();
+    state.record(dir, &entry("2025-06")).unwrap();
+    state.record(dir, &entry("2025-07")).unwrap();
+    std::fs::write(td.path().join("report-2025-06.json"), "{}").unwrap();
+
+    let resumed = BackfillState::start(&cfg(true), dir).unwrap();
+
+    assert_eq!(resumed.completed.len(), 2);
+    assert_eq!(
+      resumed.completed_entry(dir, &range("2025-06")).unwrap().file,
+      "report-2025-06.json"
+    );
+    // recorded but the report is gone → redo
+    assert!(resumed.completed_entry(dir, &range("2025-07")).is_none());
+    assert!(resumed.completed_entry(dir, &range("2025-08")).is_none());
+  }
+
+  #[test]
+  fn fresh_runs_ignore_state_and_resume_rejects_other_options() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path().to_str().unwrap();
+
+    let mut state = BackfillState::start(&cfg(false), dir).unwrap();
+    state.record(dir, &entry("2025-06")).unwrap();
+
+    assert!(BackfillState::start(&cfg(false), dir).unwrap().completed.is_empty());
+
+    let mut other = cfg(true);
+    other.window = WindowSpec::ForPhrase {
+      phrase: "every month for the last 6 months".into(),
+    };
+    let err = BackfillState::start(&other, dir).unwrap_err();
+
+    assert!(err.to_string().contains("different options"));
+  }
+
+  #[test]
+  fn resume_ignores_options_that_do_not_shape_reports() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path().to_str().unwrap();
+
+    let mut state = BackfillState::start(&cfg(false), dir).unwrap();
+    state.record(dir, &entry("2025-06")).unwrap();
+
+    let mut rerun = cfg(true);
+    rerun.jobs = 8;
+    rerun.lock_timeout = 0;
+    rerun.http_timeout += 30;
+    rerun.out = "/tmp/elsewhere".into();
+
+    assert_eq!(BackfillState::start(&rerun, dir).unwrap().completed.len(), 1);
+  }
+
+  #[test]
+  fn record_keeps_ranges_another_run_recorded() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path().to_str().unwrap();
+
+    let mut first = BackfillState::start(&cfg(false), dir).unwrap();
+    let mut second = BackfillState::start(&cfg(false), dir).unwrap();
+    first.record(dir, &entry("2025-06")).unwrap();
+    second.record(dir, &entry("2025-07")).unwrap();
+
+    let bytes = std::fs::read(td.path().join(STATE_FILE)).unwrap();
+    let state: BackfillState = serde_json::from_slice(&bytes).unwrap();
+    let labels: Vec<&str> = state.completed.iter().map(|c| c.label.as_str()).collect();
+
+    assert_eq!(labels, ["2025-06", "2025-07"]);
+  }
+}
@@ -6,7 +6,7 @@
 // outputs: EffectiveConfig with normalized paths and flags; multi_windows is initialized false (set later)
 // side_effects: none (pure with respect to filesystem); may canonicalize paths
 // invariants:
-// - exactly one window selection is provided: --month | --for | (--since & --until)
+// - exactly one window selection is provided: --month (repeatable) | --for | --since and/or --until | --ranges-json; --since-merge-base is resolved to --since/--until before normalize
 // - --detailed implies include_unmerged/include_patch/github_prs
 // - out semantics: file path when single non-split; directory when split or multi
 // errors: Invalid window selection or conflicting flags result in early bail!
@@ -19,6 +19,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 use crate::range_windows::WindowSpec;
+use crate::services::ServiceMap;
 use crate::util;
 
 #[derive(Parser, Debug)]
@@ -33,22 +34,83 @@ pub struct Cli {
   #[arg(long, default_value = ".")]
   pub repo: PathBuf,
 
-  /// Calendar month, e.g. 2025-08
+  /// Config file with flag defaults (default: <repo>/.gar.toml, else $XDG_CONFIG_HOME/git-activity-report/config.toml); flags on the command line win
+  #[arg(long, value_name = "FILE")]
+  pub config: Option<PathBuf>,
+
+  /// Extra global git option inserted before every git subcommand (repeatable), e.g. --git-arg=-c --git-arg=core.abbrev=12
+  #[arg(long = "git-arg", value_name = "ARG", allow_hyphen_values = true)]
+  pub git_args: Vec<String>,
+
+  /// How per-commit metadata, diffstats, and patches are read: cli (one git subprocess each) or libgit2 (in-process; needs the `libgit2` build feature; not with --git-arg)
+  #[arg(long, value_name = "BACKEND", default_value = "cli", value_parser = clap::builder::PossibleValuesParser::new(crate::gitio::GIT_BACKENDS))]
+  pub git_backend: String,
+
+  /// Fail immediately when another git process holds repository locks (index.lock, gc.pid) instead of waiting up to 30s
+  #[arg(long)]
+  pub no_wait: bool,
+
+  /// Validate every report, shard, manifest, and rollup against the bundled JSON schemas before writing it; fail on the first mismatch
+  #[arg(long)]
+  pub validate_output: bool,
+
+  /// Check links in rendered Markdown (PR URLs well-formed, local report paths present) and warn about broken ones
   #[arg(long)]
-  pub month: Option<String>,
+  pub check_links: bool,
+
+  /// Suppress progress and warning messages on stderr (errors still print); stdout is unaffected
+  #[arg(short, long, conflicts_with = "verbose")]
+  pub quiet: bool,
 
-  /// Natural language window, e.g. "last week" or "every month for the last 6 months"
+  /// More progress on stderr: -v adds debug events (ranges started/finished, commits processed, lookups skipped), -vv adds per-commit trace
+  #[arg(short, long, action = clap::ArgAction::Count)]
+  pub verbose: u8,
+
+  /// Also append diagnostics to FILE as JSON lines {ts, level, target, message, fields?}; written even with --quiet
+  #[arg(long, value_name = "FILE")]
+  pub json_logs_to: Option<String>,
+
+  /// At exit, print one JSON line to stderr summarizing the run (status, ranges, commits, warnings, outputs, duration)
+  #[arg(long)]
+  pub exit_summary: bool,
+
+  /// Calendar month, e.g. 2025-08, 2025-8, or "aug 2025"; repeat for a multi-range run (one range per month)
+  #[arg(long)]
+  pub month: Vec<String>,
+
+  /// Natural language window, e.g. "last week", "every month for the last 6 months", or "every quarter for the last 4 quarters"
   #[arg(long = "for")]
   pub for_str: Option<String>,
 
-  /// Custom since (Git approxidate ok); must be paired with --until
+  /// Custom since (Git approxidate ok); without --until the window runs until now
   #[arg(long, alias = "start")]
   pub since: Option<String>,
 
-  /// Custom until (exclusive); must be paired with --since
+  /// Custom until (exclusive); without --since the window starts at the repository's first commit
   #[arg(long, alias = "end")]
   pub until: Option<String>,
 
+  /// JSON array of {label, since, until} windows, used verbatim as a multi-range run (manifest + rollup); replaces the window flags
+  #[arg(long, value_name = "FILE")]
+  pub ranges_json: Option<String>,
+
+  /// Start the window just after the merge-base of HEAD and BRANCH and end it now (pre-PR self-review)
+  #[arg(long, value_name = "BRANCH")]
+  pub since_merge_base: Option<String>,
+
+  /// Compact digest of the previous calendar day (commits, linked PRs, unmerged branches, Markdown); replaces the window flags
+  #[arg(long, value_name = "KIND", value_parser = clap::builder::PossibleValuesParser::new(crate::digest::DIGEST_KINDS))]
+  pub digest: Option<String>,
+
+  /// Process only a stratified sample of N commits (by ISO week and author) and add extrapolated
+  /// totals with 95% intervals as summary.sample; commit-level output covers only the sample
+  #[arg(long, value_name = "N")]
+  pub sample: Option<usize>,
+
+  /// Commits processed concurrently (0 = one per CPU); output order is unchanged
+  #[arg(long, value_name = "N", default_value_t = 0)]
+  pub jobs: usize,
+
   /// Split output into multiple files (per-commit shards) and include an items index in the report.
   #[arg(long)]
   pub split_apart: bool,
@@ -69,40 +131,290 @@ pub struct Cli {
   #[arg(long)]
   pub include_patch: bool,
 
+  /// Download and embed the combined diff of merged PRs (implies --github-prs; clipped by --max-patch-bytes)
+  #[arg(long)]
+  pub include_pr_patch: bool,
+
+  /// Attach each PR's file list with per-file additions/deletions from the GitHub API (implies --github-prs)
+  #[arg(long)]
+  pub pr_files: bool,
+
   /// Per-commit patch cap (0 = no limit)
   #[arg(long, default_value_t = 0)]
   pub max_patch_bytes: usize,
 
+  /// Byte budget for embedded patches across the whole range (implies --include-patch): smallest commits
+  /// embed in full, then one is clipped and the largest become reference-only
+  #[arg(long, value_name = "N")]
+  pub patch_budget_bytes: Option<usize>,
+
+  /// Keep only the N file entries with the most churn per commit; the rest are counted in files_truncated
+  /// (omitted, total_additions, total_deletions) so totals stay exact
+  #[arg(long, value_name = "N")]
+  pub max_files_per_commit: Option<usize>,
+
   /// Directory to write .patch files (referenced in JSON)
   #[arg(long)]
   pub save_patches: Option<PathBuf>,
 
+  /// Degraded mode for blobless partial clones: status-only file entries (no additions/deletions)
+  #[arg(long)]
+  pub no_blob_stats: bool,
+
+  /// Omit commit bodies (body is emptied, body_lines dropped) and PR body_lines; subjects and titles are kept
+  #[arg(long)]
+  pub no_bodies: bool,
+
+  /// Walk every ref (`rev-list --all`, minus stash and notes) instead of HEAD, annotating each commit with the
+  /// refs that contain it
+  #[arg(long)]
+  pub all_refs: bool,
+
+  /// Follow first parents only (`rev-list --first-parent`): each merge is one unit of work, with the commits it
+  /// brought in nested under merge_group
+  #[arg(long)]
+  pub first_parent: bool,
+
+  /// Count committers alongside authors: a committers map, per-commit authored_by_committer, and
+  /// summary.maintainer_activity (commits landed for others, merges performed)
+  #[arg(long)]
+  pub committers: bool,
+
+  /// Walk this branch, remote branch, or tag instead of HEAD (repeatable), e.g. `--branch origin/release/1.4`;
+  /// no checkout needed, recorded as range.refs
+  #[arg(long = "branch", alias = "ref", value_name = "REF")]
+  pub refs: Vec<String>,
+
+  /// Limit the report to commits and files matching this pathspec (repeatable), e.g. `--path src/ --path 'docs/**.md'`
+  #[arg(long = "path", value_name = "PATHSPEC")]
+  pub paths: Vec<String>,
+
+  /// Leave out files matching this pathspec (repeatable), e.g. `--exclude-path vendor/ --exclude-path '*.lock'`;
+  /// commits touching only excluded files drop out of the window
+  #[arg(long = "exclude-path", value_name = "PATHSPEC")]
+  pub exclude_paths: Vec<String>,
+
+  /// Emit only summary and authors: the commits array is omitted and no commit shards are written
+  #[arg(long)]
+  pub summary_only: bool,
+
+  /// Keep only these commit fields in reports and shards (repeatable or comma-separated), e.g. sha,subject,author.name,files.stats
+  #[arg(long, value_name = "FIELD", value_delimiter = ',')]
+  pub fields: Vec<String>,
+
+  /// Mask file paths matching these globs (repeatable or comma-separated) with stable hashed placeholders
+  #[arg(long, value_delimiter = ',')]
+  pub redact_paths: Vec<String>,
+
+  /// Embed the post-image content of changed files matching these globs (repeatable or comma-separated)
+  #[arg(long = "snapshot-path", value_name = "GLOB", value_delimiter = ',')]
+  pub snapshot_paths: Vec<String>,
+
+  /// Cap in bytes for each embedded --snapshot-path content (0 = no cap); split-apart writes full files instead
+  #[arg(long, value_name = "N", default_value_t = crate::snapshots::DEFAULT_SNAPSHOT_MAX_BYTES)]
+  pub snapshot_max_bytes: usize,
+
+  /// JSON file mapping path globs to services ({"services":[{"glob":"services/auth/**","service":"auth"}]})
+  #[arg(long)]
+  pub service_map: Option<PathBuf>,
+
+  /// TOML file merging author aliases into canonical identities, applied after .mailmap
+  /// ([[identities]] name = "Jane Doe", email = "jane@example.com", aliases = ["jane@old.com", "J <j@users.noreply.github.com>"])
+  #[arg(long, value_name = "PATH")]
+  pub identity_map: Option<PathBuf>,
+
   /// Output location:
   /// - without `--split-apart` (single report): file path (default stdout "-")
   /// - with `--split-apart` or multi-range runs: base directory (default: auto-named temp dir)
   #[arg(long, default_value = "-")]
   pub out: String,
 
+  /// On-disk layout of split/multi output: nested (<label>/<shard>), flat, dated (<label>/YYYY/MM/DD/), or hashed (<label>/<sha[..2]>/)
+  #[arg(long, value_name = "LAYOUT", default_value = "nested", value_parser = clap::builder::PossibleValuesParser::new(crate::layout::LAYOUTS))]
+  pub layout: String,
+
+  /// Report rendering: json, markdown, csv, or ndjson (single runs print/write Markdown or CSV; split/multi runs add
+  /// report-<label>.md or report-<label>.csv; ndjson streams one commit per line plus a summary line, single window only)
+  #[arg(long, value_name = "FORMAT", default_value = "json", value_parser = clap::builder::PossibleValuesParser::new(crate::render_markdown::FORMATS))]
+  pub format: String,
+
+  /// Columns for --format csv (repeatable or comma-separated), e.g. sha,author,subject; default
+  /// sha,author,date,subject,additions,deletions,files,prs
+  #[arg(long, value_name = "COLUMN", value_delimiter = ',')]
+  pub csv_columns: Vec<String>,
+
+  /// Encoding of report, shard, manifest, rollup, and index files (stdout is always UTF-8)
+  #[arg(long, value_name = "ENC", default_value = "utf-8", value_parser = clap::builder::PossibleValuesParser::new(crate::util::OUTPUT_ENCODINGS))]
+  pub output_encoding: String,
+
+  /// With --split-apart, also write per-range authors/<slug>.json and days/<date>.json index files
+  #[arg(long)]
+  pub side_indexes: bool,
+
+  /// After a split/multi run, also package the output dir into this .tar.gz/.tgz or .zip file
+  #[arg(long, value_name = "PATH")]
+  pub archive: Option<String>,
+
+  /// Resume an interrupted multi-range run in --out: skip ranges recorded as complete in backfill-state.json
+  #[arg(long)]
+  pub resume: bool,
+
+  /// Seconds to wait for another run's lock on the --out dir (manifest/rollup updates) before giving up; --no-wait means 0
+  #[arg(long, value_name = "SECS", default_value_t = crate::out_lock::DEFAULT_LOCK_TIMEOUT_SECS)]
+  pub lock_timeout: u64,
+
+  /// Reuse processed commits from an on-disk cache (~/.cache/git-activity-report/<repo-id>/commits/) across runs
+  #[arg(long, overrides_with = "no_cache")]
+  pub cache: bool,
+
+  /// Turn --cache off again (e.g. when a wrapper script passes --cache)
+  #[arg(long, overrides_with = "cache")]
+  pub no_cache: bool,
+
+  /// Add summary.collaboration: authors as nodes, edges weighted by co-authored commits and co-edited files
+  #[arg(long)]
+  pub collaboration: bool,
+
+  /// Also write the collaboration graph as collaboration-<label>.dot in the output dir (implies --collaboration)
+  #[arg(long)]
+  pub collaboration_dot: bool,
+
+  /// Add work_items: runs of one author's commits that touch overlapping files, with combined churn and duration
+  #[arg(long)]
+  pub work_items: bool,
+
+  /// Longest pause in minutes between two commits of one work item (with --work-items)
+  #[arg(long, value_name = "MINUTES", default_value_t = crate::work_items::DEFAULT_GAP_MINUTES)]
+  pub work_item_gap: i64,
+
+  /// Add calendar: commits per local day and a weekday × hour punch card, in --tz
+  #[arg(long)]
+  pub calendar: bool,
+
+  /// Tag each commit with the nearest tag containing it (release) and list tags created in the window (summary.releases)
+  #[arg(long)]
+  pub releases: bool,
+
   /// Try to enrich with GitHub PRs (quietly ignored if not available)
   #[arg(long)]
   pub github_prs: bool,
 
+  /// GitHub repository as owner/name, overriding the one parsed from origin (e.g. when origin is a mirror)
+  #[arg(long)]
+  pub github_repo: Option<String>,
+
+  /// Add summary.repo_policy: default-branch protection settings and the commit status at window end (GitHub token)
+  #[arg(long)]
+  pub repo_policy: bool,
+
+  /// Add summary.conflict_hotspots: files that needed merge resolution in the window (scans merge commits)
+  #[arg(long)]
+  pub conflict_hotspots: bool,
+
+  /// Add summary.dependency_changes: dependencies added/removed/updated in Cargo.toml/Cargo.lock/package.json/go.mod/requirements*.txt
+  #[arg(long)]
+  pub dependency_changes: bool,
+
+  /// Add summary.new_contributors: authors whose first commit in the repo falls in the window
+  #[arg(long)]
+  pub new_contributors: bool,
+
+  /// Timeout in seconds for each GitHub HTTP request (0 = none)
+  #[arg(long, value_name = "SECS", default_value_t = crate::enrichment::github_api::DEFAULT_HTTP_TIMEOUT_SECS)]
+  pub http_timeout: u64,
+
+  /// Retries (with jittered exponential backoff) after network errors, 429, or 5xx responses
+  #[arg(long, value_name = "N", default_value_t = crate::enrichment::github_api::DEFAULT_HTTP_RETRIES)]
+  pub http_retries: u32,
+
+  /// Pages (100 items each) followed per GitHub/GitLab list endpoint: PRs for a commit, reviews, PR commits
+  #[arg(long, value_name = "N", default_value_t = crate::enrichment::github_api::DEFAULT_HTTP_MAX_PAGES)]
+  pub http_max_pages: usize,
+
+  /// Stop issuing GitHub enrichment requests after N per run; skipped lookups are reported in summary.warnings
+  #[arg(long, value_name = "N")]
+  pub github_max_requests: Option<u64>,
+
+  /// Persist GitHub API responses in DIR across runs, so repeated windows reuse PR data instead of re-fetching it
+  #[arg(long, value_name = "DIR")]
+  pub github_cache_dir: Option<String>,
+
+  /// Seconds a --github-cache-dir entry, or a --cache commit enriched with --github-prs, stays fresh before it is fetched again
+  #[arg(long, value_name = "SECS", default_value_t = crate::enrichment::github_disk_cache::DEFAULT_GITHUB_CACHE_TTL_SECS)]
+  pub github_cache_ttl: u64,
+
   /// Scan local branches for commits in the window not reachable from HEAD; include separately.
   #[arg(long)]
   pub include_unmerged: bool,
 
-  /// Timezone for local ISO timestamps in output (label only)
-  /// Timezone for local ISO timestamps in output: "local", "utc", or IANA zone like "America/Chicago"
+  /// Compare unmerged branches against this ref instead of HEAD, e.g. origin/main (with --include-unmerged or --digest)
+  #[arg(long, value_name = "REF")]
+  pub unmerged_base: Option<String>,
+
+  /// Regex (case-insensitive, repeatable) matched against "Name <email>" of author/committer to flag automated commits
+  #[arg(long = "bot-authors", value_name = "REGEX", default_values_t = crate::automation::DEFAULT_BOT_PATTERNS.map(String::from))]
+  pub bot_authors: Vec<String>,
+
+  /// Regex (repeatable) for issue keys to collect from subjects, bodies, and PR/MR titles into commit.issues, e.g. '\b[A-Z]+-\d+\b' or '#\d+'
+  #[arg(long = "issue-pattern", value_name = "REGEX")]
+  pub issue_patterns: Vec<String>,
+
+  /// URL for matched issue keys ({key} = match, {num} = its digits); one for all --issue-pattern values, or one per pattern in order
+  #[arg(long = "issue-url-template", value_name = "TEMPLATE")]
+  pub issue_url_templates: Vec<String>,
+
+  /// Commit trailer names that record deployment targets (comma-separated)
+  #[arg(long, value_delimiter = ',', default_values_t = ["Deployed-To".to_string(), "Environment".to_string()])]
+  pub env_trailers: Vec<String>,
+
+  /// Age in days after which an unmerged branch tip is reported as stale (with --include-unmerged)
+  #[arg(long, default_value_t = 30)]
+  pub stale_days: i64,
+
+  /// Timezone for local ISO timestamps, day buckets, and shard names: "local", "utc", or an IANA zone like "Europe/London"
   #[arg(long, default_value = "local")]
   pub tz: String,
 
+  /// Freeze "now" for the whole run: phrase windows, stale-branch ages, generated_at, signoff time, and temp
+  /// directory names (RFC3339, or YYYY-MM-DDTHH:MM:SS in local time); for reproducible reruns
+  #[arg(long = "freeze-now", alias = "now-override", value_name = "TS", value_parser = parse_freeze_now)]
+  pub now_override: Option<String>,
+
+  /// Diagnose the environment (git, repo, GitHub token/scopes, temp dir, timezone) and print fixes
+  #[arg(long)]
+  pub doctor: bool,
+
+  /// Sign off on an existing output dir (--out) as "Name <email>": stamps reviewer, time, and artifact hashes
+  #[arg(long, value_name = "NAME <EMAIL>")]
+  pub signoff: Option<String>,
+
+  /// Verify that artifacts in --out still match the hashes recorded at sign-off
+  #[arg(long)]
+  pub verify_signoff: bool,
+
+  /// Print one field of a report or manifest, e.g. --query report.json /summary/count, or an aggregate like 'sum(/summary/changeset/additions)'
+  #[arg(long, num_args = 2, value_names = ["REPORT", "EXPR"])]
+  pub query: Option<Vec<String>>,
+
+  /// Explore a --split-apart output directory (ranges, commits, diffstats, PR details) in an interactive terminal UI
+  #[arg(long, value_name = "OUT_DIR")]
+  pub browse: Option<String>,
+
+  /// Upgrade archived output in place (a report/manifest .json file, or every .json under a directory)
+  #[arg(long, value_name = "PATH")]
+  pub migrate: Option<String>,
+
+  /// Schema version for --migrate (default: the version this build writes)
+  #[arg(long, value_name = "VERSION", requires = "migrate", default_value = crate::migrate::CURRENT_SCHEMA_VERSION, value_parser = clap::builder::PossibleValuesParser::new(crate::migrate::SCHEMA_VERSIONS))]
+  pub to: String,
+
+  /// Print the JSON Schema of an output (simple report, commit shard, or overall manifest), generated from this binary's model types
+  #[arg(long, value_name = "KIND", value_parser = clap::builder::PossibleValuesParser::new(crate::schema::SCHEMA_KINDS))]
+  pub emit_schema: Option<String>,
+
   /// Emit a troff man page to stdout (internal; for packaging)
   #[arg(long, hide = true)]
   pub gen_man: bool,
-
-  /// Override the "now" instant for natural-language parsing (hidden; tests only)
-  #[arg(long = "now-override", hide = true)]
-  pub now_override: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -114,28 +426,122 @@ pub struct EffectiveConfig {
   pub include_merges: bool,
   pub include_patch: bool,
   pub max_patch_bytes: usize,
+  pub max_files_per_commit: Option<usize>,
   pub save_patches: Option<String>,
+  pub no_blob_stats: bool,
   pub out: String,
+  pub layout: crate::layout::OutputLayout,
+  pub format: crate::render_markdown::ReportFormat,
+  /// --csv-columns, validated (defaults filled in)
+  pub csv_columns: Vec<String>,
   pub github_prs: bool,
   pub include_unmerged: bool,
+  pub unmerged_base: Option<String>,
+  pub stale_days: i64,
   pub tz: String,
   pub now_override: Option<String>,
   pub estimate_effort: bool,
+  pub service_map: Option<ServiceMap>,
+  pub identity_map: Option<crate::identities::IdentityMap>,
+  pub include_pr_patch: bool,
+  pub pr_files: bool,
+  pub github_repo: Option<String>,
+  pub redact_paths: Vec<String>,
+  pub env_trailers: Vec<String>,
+  pub archive: Option<String>,
+  pub patch_budget_bytes: Option<usize>,
+  pub side_indexes: bool,
+  pub git_args: Vec<String>,
+  pub bot_authors: Vec<String>,
+  pub issue_patterns: Vec<String>,
+  pub issue_url_templates: Vec<String>,
+  pub repo_policy: bool,
+  pub http_timeout: u64,
+  pub http_retries: u32,
+  pub http_max_pages: usize,
+  pub github_max_requests: Option<u64>,
+  /// --github-cache-ttl; also bounds how long --cache reuses commits enriched with --github-prs
+  pub github_cache_ttl: u64,
+  pub no_wait: bool,
+  pub collaboration: bool,
+  pub collaboration_dot: bool,
+  /// --work-item-gap minutes when --work-items is on
+  pub work_items: Option<i64>,
+  pub calendar: bool,
+  pub releases: bool,
+  pub snapshot_paths: Vec<String>,
+  pub snapshot_max_bytes: usize,
+  pub conflict_hotspots: bool,
+  pub dependency_changes: bool,
+  pub new_contributors: bool,
+  pub no_bodies: bool,
+  pub summary_only: bool,
+  pub fields: Option<crate::fields::FieldSelection>,
+  pub all_refs: bool,
+  pub first_parent: bool,
+  pub committers: bool,
+  pub refs: Vec<String>,
+  pub paths: Vec<String>,
+  pub resume: bool,
+  pub lock_timeout: u64,
+  pub cache: bool,
+  pub sample: Option<usize>,
+  pub jobs: usize,
+  pub digest: Option<String>,
+}
+
+/// `--freeze-now` value parser: accept only timestamps `parse_now` understands, so a typo cannot silently mean "now".
+fn parse_freeze_now(raw: &str) -> std::result::Result<String, String> {
+  match crate::range_windows::parse_now(Some(raw)) {
+    Some(_) => Ok(raw.to_string()),
+    None => Err(format!(
+      "expected RFC3339 (2025-08-15T12:00:00Z) or local YYYY-MM-DDTHH:MM:SS, got {:?}",
+      raw
+    )),
+  }
+}
+
+/// One `--month` → Month; several → Months in chronological order (duplicates collapse).
+fn month_window(months: &[String]) -> Result<WindowSpec> {
+  let mut yms = months
+    .iter()
+    .map(|m| crate::range_windows::normalize_month(m))
+    .collect::<Result<Vec<_>>>()?;
+  yms.sort();
+  yms.dedup();
+
+  if yms.len() == 1 {
+    return Ok(WindowSpec::Month { ym: yms.remove(0) });
+  }
+
+  Ok(WindowSpec::Months { yms })
 }
 
 pub fn normalize(cli: Cli) -> Result<EffectiveConfig> {
   // Validate window selection
-  let window = match (&cli.month, &cli.for_str, &cli.since, &cli.until) {
-    (Some(ym), None, None, None) => WindowSpec::Month { ym: ym.clone() },
-    (None, Some(p), None, None) => WindowSpec::ForPhrase { phrase: p.clone() },
-    (None, None, Some(s), Some(u)) => WindowSpec::SinceUntil {
-      since: s.clone(),
-      until: u.clone(),
-    },
-    (None, None, None, None) => {
-      bail!("Provide one of --month, --for, or (--since AND --until)")
+  let window = if let Some(path) = &cli.ranges_json {
+    if !cli.month.is_empty() || cli.for_str.is_some() || cli.since.is_some() || cli.until.is_some() {
+      bail!("--ranges-json replaces --month | --for | --since/--until; choose only one");
+    }
+
+    WindowSpec::Explicit {
+      ranges: crate::range_windows::load_ranges_json(path)?,
+    }
+  } else {
+    match (cli.month.as_slice(), &cli.for_str, &cli.since, &cli.until) {
+      (months @ [_, ..], None, None, None) => month_window(months)?,
+      ([], Some(p), None, None) => WindowSpec::ForPhrase { phrase: p.clone() },
+      ([], None, Some(s), Some(u)) => WindowSpec::SinceUntil {
+        since: s.clone(),
+        until: u.clone(),
+      },
+      ([], None, Some(s), None) => WindowSpec::Since { since: s.clone() },
+      ([], None, None, Some(u)) => WindowSpec::Until { until: u.clone() },
+      ([], None, None, None) => {
+        bail!("Provide one of --month, --for, --ranges-json, --since, or --until")
+      }
+      _ => bail!("Ambiguous time selection: choose only one of --month | --for | --since/--until"),
     }
-    _ => bail!("Ambiguous time selection: choose only one of --month | --for | --since/--until"),
   };
 
   // Determine split_apart behavior (no back-compat flags kept)
@@ -143,12 +549,126 @@ pub fn normalize(cli: Cli) -> Result<EffectiveConfig> {
 
   // Determine effective detail flags
   let include_unmerged = cli.include_unmerged || cli.detailed;
-  let include_patch = cli.include_patch || cli.detailed;
-  let github_prs = cli.github_prs || cli.detailed;
+  let include_patch = cli.include_patch || cli.detailed || cli.patch_budget_bytes.is_some();
+  let github_prs = cli.github_prs || cli.detailed || cli.include_pr_patch || cli.pr_files;
   let estimate_effort = cli.estimate_effort || cli.detailed;
 
   let repo = util::canonicalize_lossy(&cli.repo);
 
+  if let Some(spec) = &cli.github_repo {
+    if crate::enrichment::github_api::parse_repo_spec(spec).is_none() {
+      bail!("--github-repo expects owner/name, got {:?}", spec);
+    }
+  }
+  if let Some(path) = &cli.archive {
+    if crate::archive::ArchiveFormat::from_path(path).is_none() {
+      bail!("--archive expects a .tar.gz, .tgz, or .zip path, got {:?}", path);
+    }
+  }
+  if !util::is_known_tz(&cli.tz) {
+    bail!(
+      "--tz expects local, utc, or an IANA zone such as Europe/London, got {:?} (run --doctor for details)",
+      cli.tz
+    );
+  }
+  if cli.sample == Some(0) {
+    bail!("--sample expects a positive commit count");
+  }
+  let fields = crate::fields::FieldSelection::parse(&cli.fields)?;
+  let format = crate::render_markdown::ReportFormat::parse(&cli.format)?;
+  let ndjson = format == crate::render_markdown::ReportFormat::Ndjson;
+  if fields.is_some() && format != crate::render_markdown::ReportFormat::Json && !ndjson {
+    bail!(
+      "--fields trims JSON commits; --format {} renders full commits, so use one or the other",
+      cli.format
+    );
+  }
+  if !cli.csv_columns.is_empty() && format != crate::render_markdown::ReportFormat::Csv {
+    bail!("--csv-columns selects CSV columns; add --format csv");
+  }
+  if cli.digest.is_some() && format == crate::render_markdown::ReportFormat::Csv {
+    bail!("--digest prints JSON or Markdown; --format csv is not supported");
+  }
+  if ndjson {
+    let whole_report = [
+      (split_apart, "--split-apart"),
+      (include_unmerged, "--include-unmerged/--detailed"),
+      (cli.patch_budget_bytes.is_some(), "--patch-budget-bytes"),
+      (cli.summary_only, "--summary-only"),
+      (cli.sample.is_some(), "--sample"),
+      (cli.collaboration, "--collaboration"),
+      (cli.work_items, "--work-items"),
+      (cli.dependency_changes, "--dependency-changes"),
+      (cli.new_contributors, "--new-contributors"),
+      (cli.committers, "--committers"),
+      (cli.validate_output, "--validate-output"),
+      (cli.digest.is_some(), "--digest"),
+    ];
+
+    if let Some((_, flag)) = whole_report.iter().find(|(on, _)| *on) {
+      bail!(
+        "--format ndjson streams commits without building a report, so it cannot be combined with {}",
+        flag
+      );
+    }
+    if cli.output_encoding != "utf-8" {
+      bail!("--format ndjson is always UTF-8; drop --output-encoding");
+    }
+  }
+  let csv_columns = crate::render_csv::parse_columns(&cli.csv_columns)?;
+  if fields.is_some() && cli.validate_output {
+    bail!("--fields drops commit fields the output schemas require; it cannot be combined with --validate-output");
+  }
+  if cli.http_max_pages == 0 {
+    bail!("--http-max-pages expects at least 1 page");
+  }
+  if cli.work_item_gap <= 0 {
+    bail!("--work-item-gap expects a positive number of minutes");
+  }
+  if cli.stale_days < 0 {
+    bail!("--stale-days expects 0 or more days");
+  }
+  if cli.max_files_per_commit == Some(0) {
+    bail!("--max-files-per-commit expects a positive file count");
+  }
+  if cli.all_refs && !cli.refs.is_empty() {
+    bail!("--all-refs already walks every ref; drop --branch/--ref or --all-refs");
+  }
+  if cli.first_parent && cli.all_refs {
+    bail!("--first-parent follows one line of history; use --branch/--ref instead of --all-refs");
+  }
+  if cli.first_parent && cli.sample.is_some() {
+    bail!("--sample draws from every commit in the window; it does not combine with --first-parent");
+  }
+  if cli.git_backend == "libgit2" && !cli.git_args.is_empty() {
+    bail!("--git-arg settings only reach git subprocesses; drop --git-arg or use --git-backend cli");
+  }
+  if let Some(p) = cli
+    .exclude_paths
+    .iter()
+    .find(|p| p.trim().is_empty() || p.starts_with(':'))
+  {
+    bail!(
+      "--exclude-path expects a plain pathspec such as vendor/ or '*.lock', got {:?}; use --path for pathspec magic",
+      p
+    );
+  }
+  if let Some(r) = cli.refs.iter().find(|r| r.trim().is_empty() || r.starts_with('-')) {
+    bail!("--branch/--ref expects a branch, tag, or commit name, got {:?}", r);
+  }
+  crate::automation::AutomationDetector::new(&cli.bot_authors)?;
+  crate::enrichment::issue_keys::IssueKeyMatcher::new(&cli.issue_patterns, &cli.issue_url_templates)?;
+  let service_map = cli
+    .service_map
+    .as_deref()
+    .map(|p| ServiceMap::load(&p.to_string_lossy()))
+    .transpose()?;
+  let identity_map = cli
+    .identity_map
+    .as_deref()
+    .map(|p| crate::identities::IdentityMap::load(&p.to_string_lossy()))
+    .transpose()?;
+
   Ok(EffectiveConfig {
     repo,
     window,
@@ -157,13 +677,70 @@ pub fn normalize(cli: Cli) -> Result<EffectiveConfig> {
     include_merges: cli.include_merges,
     include_patch,
     max_patch_bytes: cli.max_patch_bytes,
+    max_files_per_commit: cli.max_files_per_commit,
     save_patches: cli.save_patches.as_deref().map(util::canonicalize_lossy),
+    no_blob_stats: cli.no_blob_stats,
     out: cli.out,
+    layout: crate::layout::OutputLayout::parse(&cli.layout)?,
+    format,
+    csv_columns,
     github_prs,
     include_unmerged,
+    unmerged_base: cli.unmerged_base.clone(),
+    stale_days: cli.stale_days,
     tz: cli.tz.clone(),
     now_override: cli.now_override.clone(),
     estimate_effort,
+    service_map,
+    identity_map,
+    include_pr_patch: cli.include_pr_patch,
+    pr_files: cli.pr_files,
+    github_repo: cli.github_repo.clone(),
+    redact_paths: cli.redact_paths.clone(),
+    env_trailers: cli.env_trailers.clone(),
+    archive: cli.archive.as_deref().map(util::canonicalize_lossy),
+    patch_budget_bytes: cli.patch_budget_bytes,
+    side_indexes: cli.side_indexes,
+    git_args: cli.git_args.clone(),
+    bot_authors: cli.bot_authors.clone(),
+    issue_patterns: cli.issue_patterns.clone(),
+    issue_url_templates: cli.issue_url_templates.clone(),
+    repo_policy: cli.repo_policy,
+    http_timeout: cli.http_timeout,
+    http_retries: cli.http_retries,
+    http_max_pages: cli.http_max_pages,
+    github_max_requests: cli.github_max_requests,
+    github_cache_ttl: cli.github_cache_ttl,
+    no_wait: cli.no_wait,
+    collaboration: cli.collaboration || cli.collaboration_dot,
+    collaboration_dot: cli.collaboration_dot,
+    work_items: cli.work_items.then_some(cli.work_item_gap),
+    calendar: cli.calendar,
+    releases: cli.releases,
+    snapshot_paths: cli.snapshot_paths.clone(),
+    snapshot_max_bytes: cli.snapshot_max_bytes,
+    conflict_hotspots: cli.conflict_hotspots,
+    dependency_changes: cli.dependency_changes,
+    new_contributors: cli.new_contributors,
+    no_bodies: cli.no_bodies,
+    summary_only: cli.summary_only,
+    fields,
+    all_refs: cli.all_refs,
+    first_parent: cli.first_parent,
+    committers: cli.committers,
+    refs: cli.refs,
+    paths: cli
+      .paths
+      .iter()
+      .cloned()
+      .chain(cli.exclude_paths.iter().map(|p| crate::gitio::exclude_pathspec(p)))
+      .collect(),
+    resume: cli.resume,
+    lock_timeout: if cli.no_wait { 0 } else { cli.lock_timeout },
+    cache: cli.cache && !cli.no_cache,
+    sample: cli.sample,
+    jobs: cli.jobs,
+    digest: cli.digest.clone(),
   })
 }
 
@@ -175,21 +752,96 @@ mod tests {
   fn base_cli() -> Cli {
     Cli {
       repo: PathBuf::from("."),
-      month: None,
+      config: None,
+      git_args: vec![],
+      git_backend: "cli".into(),
+      no_wait: false,
+      validate_output: false,
+      check_links: false,
+      quiet: false,
+      verbose: 0,
+      json_logs_to: None,
+      exit_summary: false,
+      month: vec![],
       for_str: None,
       since: None,
       until: None,
+      ranges_json: None,
+      since_merge_base: None,
+      sample: None,
+      jobs: 0,
+      digest: None,
       split_apart: false,
       detailed: false,
       estimate_effort: false,
       include_merges: false,
       include_patch: false,
+      include_pr_patch: false,
+      pr_files: false,
       max_patch_bytes: 0,
+      max_files_per_commit: None,
+      patch_budget_bytes: None,
       save_patches: None,
+      no_blob_stats: false,
+      no_bodies: false,
+      summary_only: false,
+      fields: vec![],
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      exclude_paths: vec![],
+      redact_paths: vec![],
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 65536,
+      service_map: None,
+      identity_map: None,
       out: "-".into(),
+      layout: "nested".into(),
+      format: "json".into(),
+      csv_columns: vec![],
+      output_encoding: "utf-8".into(),
+      side_indexes: false,
+      archive: None,
+      resume: false,
+      lock_timeout: crate::out_lock::DEFAULT_LOCK_TIMEOUT_SECS,
+      cache: false,
+      no_cache: false,
+      collaboration: false,
+      collaboration_dot: false,
+      work_items: false,
+      work_item_gap: crate::work_items::DEFAULT_GAP_MINUTES,
+      calendar: false,
+      releases: false,
       github_prs: false,
+      github_repo: None,
+      repo_policy: false,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      http_timeout: 30,
+      http_retries: 2,
+      http_max_pages: 10,
+      github_max_requests: None,
+      github_cache_dir: None,
+      github_cache_ttl: 86_400,
       include_unmerged: false,
+      unmerged_base: None,
+      stale_days: 30,
+      bot_authors: vec![],
+      issue_patterns: vec![],
+      issue_url_templates: vec![],
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
       tz: "utc".into(),
+      doctor: false,
+      signoff: None,
+      verify_signoff: false,
+      query: None,
+      browse: None,
+      migrate: None,
+      to: "2".into(),
+      emit_schema: None,
       gen_man: false,
       now_override: None,
     }
@@ -198,7 +850,7 @@ mod tests {
   #[test]
   fn normalize_month_defaults_to_simple() {
     let mut cli = base_cli();
-    cli.month = Some("2025-08".into());
+    cli.month = vec!["2025-08".into()];
     let cfg = normalize(cli).unwrap();
     assert_eq!(cfg.split_apart, false);
     match cfg.window {
@@ -207,10 +859,134 @@ mod tests {
     }
   }
 
+  #[test]
+  fn repeated_months_become_sorted_multi_window() {
+    let mut cli = base_cli();
+    cli.month = vec!["aug 2025".into(), "2025-7".into(), "2025-08".into()];
+    let cfg = normalize(cli).unwrap();
+    assert_eq!(
+      cfg.window,
+      WindowSpec::Months {
+        yms: vec!["2025-07".into(), "2025-08".into()]
+      }
+    );
+  }
+
+  #[test]
+  fn month_shorthand_is_canonicalized() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-8".into()];
+    let cfg = normalize(cli).unwrap();
+    assert_eq!(cfg.window, WindowSpec::Month { ym: "2025-08".into() });
+  }
+
+  #[test]
+  fn since_or_until_alone_is_an_open_ended_window() {
+    let mut cli = base_cli();
+    cli.since = Some("2025-06-01".into());
+    let cfg = normalize(cli).unwrap();
+    assert_eq!(
+      cfg.window,
+      WindowSpec::Since {
+        since: "2025-06-01".into()
+      }
+    );
+
+    let mut cli = base_cli();
+    cli.until = Some("2025-07-01".into());
+    assert_eq!(
+      normalize(cli).unwrap().window,
+      WindowSpec::Until {
+        until: "2025-07-01".into()
+      }
+    );
+
+    let mut cli = base_cli();
+    cli.until = Some("2025-07-01".into());
+    cli.for_str = Some("last week".into());
+    assert!(normalize(cli).is_err());
+  }
+
+  #[test]
+  fn branch_refs_are_kept_and_exclude_all_refs() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.refs = vec!["origin/release/1.4".into(), "v1.4.0".into()];
+    let cfg = normalize(cli).unwrap();
+    assert_eq!(cfg.refs, vec!["origin/release/1.4", "v1.4.0"]);
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.refs = vec!["main".into()];
+    cli.all_refs = true;
+    assert!(normalize(cli).is_err());
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.refs = vec!["--all".into()];
+    assert!(normalize(cli).is_err());
+  }
+
+  #[test]
+  fn first_parent_walks_one_line_without_all_refs_or_sampling() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.first_parent = true;
+    cli.refs = vec!["main".into()];
+    assert!(normalize(cli).unwrap().first_parent);
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.first_parent = true;
+    cli.all_refs = true;
+    assert!(normalize(cli).is_err());
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.first_parent = true;
+    cli.sample = Some(10);
+    assert!(normalize(cli).is_err());
+  }
+
+  #[test]
+  fn git_args_are_refused_with_the_libgit2_backend() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.git_args = vec!["-c".into(), "diff.renames=false".into()];
+    assert!(normalize(cli).is_ok());
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.git_args = vec!["-c".into(), "diff.renames=false".into()];
+    cli.git_backend = "libgit2".into();
+    let err = normalize(cli).unwrap_err();
+    assert!(err.to_string().contains("--git-backend cli"));
+  }
+
+  #[test]
+  fn ranges_json_excludes_other_window_flags() {
+    let td = tempfile::TempDir::new().unwrap();
+    let path = td.path().join("ranges.json");
+    std::fs::write(&path, r#"[{"label":"q3","since":"2025-07-01","until":"2025-10-01"}]"#).unwrap();
+
+    let mut cli = base_cli();
+    cli.ranges_json = Some(path.to_string_lossy().into_owned());
+    let cfg = normalize(cli).unwrap();
+    match cfg.window {
+      WindowSpec::Explicit { ref ranges } => assert_eq!(ranges[0].label, "q3"),
+      _ => panic!("expected Explicit window"),
+    }
+
+    let mut cli = base_cli();
+    cli.ranges_json = Some(path.to_string_lossy().into_owned());
+    cli.month = vec!["2025-08".into()];
+    assert!(normalize(cli).is_err());
+  }
+
   #[test]
   fn detailed_implies_other_flags() {
     let mut cli = base_cli();
-    cli.month = Some("2025-08".into());
+    cli.month = vec!["2025-08".into()];
     cli.detailed = true;
     let cfg = normalize(cli).unwrap();
     assert!(cfg.include_unmerged);
@@ -218,4 +994,118 @@ mod tests {
     assert!(cfg.github_prs);
     assert!(cfg.estimate_effort);
   }
+
+  #[test]
+  fn patch_budget_implies_include_patch() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.patch_budget_bytes = Some(4096);
+    let cfg = normalize(cli).unwrap();
+    assert!(cfg.include_patch);
+    assert_eq!(cfg.patch_budget_bytes, Some(4096));
+  }
+
+  #[test]
+  fn sample_must_be_positive() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.sample = Some(0);
+    assert!(normalize(cli).unwrap_err().to_string().contains("--sample"));
+  }
+
+  #[test]
+  fn stale_days_must_not_be_negative() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.stale_days = 0;
+    assert_eq!(normalize(cli).unwrap().stale_days, 0);
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.stale_days = -1;
+    assert!(normalize(cli).unwrap_err().to_string().contains("--stale-days"));
+  }
+
+  #[test]
+  fn ndjson_rejects_whole_report_flags() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.format = "ndjson".into();
+    cli.fields = vec!["sha".into()];
+    assert!(normalize(cli).is_ok());
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.format = "ndjson".into();
+    cli.split_apart = true;
+    assert!(normalize(cli).unwrap_err().to_string().contains("--split-apart"));
+  }
+
+  #[test]
+  fn freeze_now_keeps_the_old_spelling_and_rejects_unparsable_values() {
+    let parse = |args: &[&str]| Cli::try_parse_from(std::iter::once("git-activity-report").chain(args.iter().copied()));
+
+    let cli = parse(&["--freeze-now", "2025-08-15T12:00:00Z"]).unwrap();
+    assert_eq!(cli.now_override.as_deref(), Some("2025-08-15T12:00:00Z"));
+    let cli = parse(&["--now-override", "2025-08-15T12:00:00"]).unwrap();
+    assert_eq!(cli.now_override.as_deref(), Some("2025-08-15T12:00:00"));
+    assert!(parse(&["--freeze-now", "yesterday"]).is_err());
+  }
+
+  #[test]
+  fn exclude_paths_become_exclude_pathspecs_after_the_includes() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.paths = vec!["src/".into()];
+    cli.exclude_paths = vec!["src/vendor/".into(), "*.lock".into()];
+    assert_eq!(
+      normalize(cli).unwrap().paths,
+      vec!["src/", ":(exclude)src/vendor/", ":(exclude)*.lock"]
+    );
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.exclude_paths = vec![":(glob)**/gen".into()];
+    assert!(normalize(cli).unwrap_err().to_string().contains("--exclude-path"));
+  }
+
+  #[test]
+  fn tz_accepts_local_utc_and_iana_zones_only() {
+    for tz in ["local", "UTC", "Europe/London", "America/Chicago"] {
+      let mut cli = base_cli();
+      cli.month = vec!["2025-08".into()];
+      cli.tz = tz.into();
+      assert_eq!(normalize(cli).unwrap().tz, tz);
+    }
+
+    for tz in ["Mars/Olympus", "+02:00", ""] {
+      let mut cli = base_cli();
+      cli.month = vec!["2025-08".into()];
+      cli.tz = tz.into();
+      assert!(
+        normalize(cli).unwrap_err().to_string().contains("--tz"),
+        "{:?} accepted",
+        tz
+      );
+    }
+  }
+
+  #[test]
+  fn work_items_carry_the_gap_and_reject_non_positive_gaps() {
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.work_items = true;
+    cli.work_item_gap = 45;
+    assert_eq!(normalize(cli).unwrap().work_items, Some(45));
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    assert_eq!(normalize(cli).unwrap().work_items, None);
+
+    let mut cli = base_cli();
+    cli.month = vec!["2025-08".into()];
+    cli.work_items = true;
+    cli.work_item_gap = 0;
+    assert!(normalize(cli).unwrap_err().to_string().contains("--work-item-gap"));
+  }
 }
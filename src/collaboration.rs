@@ -0,0 +1,223 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Build the optional collaboration graph (--collaboration) and its Graphviz DOT rendering (--collaboration-dot)
+// role: summary/collaboration
+// inputs: Processed commits of one range (author, Co-authored-by trailers, touched files)
+// outputs: Collaboration { nodes, edges }; DOT text / collaboration-<label>.dot under the output base dir
+// side_effects: None for building; write_dot writes one file
+// invariants:
+// - node ids are "Name <email>" exactly as in the report's `authors` map; co-author ids are the trailer value as written
+// - edges are undirected without self loops: source < target lexicographically
+// - shared_files counts files touched by commits of both authors; co-authors do not contribute file overlap
+// - edges are ordered by weight desc, then source, then target
+// errors: write_dot surfaces IO errors with the file path
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::model::{Collaboration, CollaborationEdge, CollaborationNode, Commit};
+
+const CO_AUTHOR_TRAILER: &str = "co-authored-by";
+
+/// Build the collaboration graph for one range.
+pub fn build_collaboration(commits: &[Commit]) -> Collaboration {
+  let mut nodes: BTreeMap<String, CollaborationNode> = BTreeMap::new();
+  let mut edges: BTreeMap<(String, String), CollaborationEdge> = BTreeMap::new();
+  let mut file_authors: BTreeMap<&str, BTreeSet<String>> = BTreeMap::new();
+
+  for c in commits {
+    let author = c.author.key();
+    node(&mut nodes, &author).commits += 1;
+
+    let co_authors: BTreeSet<String> = co_authors(&c.body).into_iter().filter(|id| *id != author).collect();
+
+    for co in &co_authors {
+      node(&mut nodes, co).co_authored += 1;
+    }
+
+    let participants: Vec<&String> = std::iter::once(&author).chain(&co_authors).collect();
+
+    for (i, a) in participants.iter().enumerate() {
+      for b in &participants[i + 1..] {
+        edge(&mut edges, a, b).co_authored_commits += 1;
+      }
+    }
+
+    for f in &c.files {
+      file_authors.entry(f.file.as_str()).or_default().insert(author.clone());
+    }
+  }
+
+  for authors in file_authors.values() {
+    let authors: Vec<&String> = authors.iter().collect();
+
+    for (i, a) in authors.iter().enumerate() {
+      for b in &authors[i + 1..] {
+        edge(&mut edges, a, b).shared_files += 1;
+      }
+    }
+  }
+
+  let mut edges: Vec<CollaborationEdge> = edges
+    .into_values()
+    .map(|mut e| {
+      e.weight = e.co_authored_commits + e.shared_files;
+      e
+    })
+    .collect();
+  edges.sort_by(|a, b| {
+    b.weight
+      .cmp(&a.weight)
+      .then_with(|| a.source.cmp(&b.source))
+      .then_with(|| a.target.cmp(&b.target))
+  });
+
+  Collaboration {
+    nodes: nodes.into_values().collect(),
+    edges,
+  }
+}
+
+/// `Co-authored-by` trailer values (case-insensitive key), trimmed.
+fn co_authors(body: &str) -> Vec<String> {
+  crate::util::parse_trailers(body)
+    .into_iter()
+    .filter(|(k, _)| k.eq_ignore_ascii_case(CO_AUTHOR_TRAILER))
+    .map(|(_, v)| v.trim().to_string())
+    .filter(|v| !v.is_empty())
+    .collect()
+}
+
+fn node<'a>(nodes: &'a mut BTreeMap<String, CollaborationNode>, id: &str) -> &'a mut CollaborationNode {
+  nodes.entry(id.to_string()).or_insert_with(|| CollaborationNode {
+    id: id.to_string(),
+    ..Default::default()
+  })
+}
+
+fn edge<'a>(
+  edges: &'a mut BTreeMap<(String, String), CollaborationEdge>,
+  a: &str,
+  b: &str,
+) -> &'a mut CollaborationEdge {
+  let (source, target) = if a <= b { (a, b) } else { (b, a) };
+
+  edges
+    .entry((source.to_string(), target.to_string()))
+    .or_insert_with(|| CollaborationEdge {
+      source: source.to_string(),
+      target: target.to_string(),
+      ..Default::default()
+    })
+}
+
+/// Render the graph as an undirected Graphviz DOT document.
+pub fn to_dot(graph: &Collaboration) -> String {
+  let mut out = String::from("graph collaboration {\n");
+
+  for n in &graph.nodes {
+    out.push_str(&format!(
+      "  \"{}\" [commits={}, co_authored={}];\n",
+      dot_escape(&n.id),
+      n.commits,
+      n.co_authored
+    ));
+  }
+
+  for e in &graph.edges {
+    out.push_str(&format!(
+      "  \"{}\" -- \"{}\" [weight={}, co_authored_commits={}, shared_files={}];\n",
+      dot_escape(&e.source),
+      dot_escape(&e.target),
+      e.weight,
+      e.co_authored_commits,
+      e.shared_files
+    ));
+  }
+
+  out.push_str("}\n");
+  out
+}
+
+fn dot_escape(s: &str) -> String {
+  s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Write the DOT rendering to `file_name` (see `OutputLayout::collaboration_dot_file`) under `base_dir`.
+pub fn write_dot(base_dir: &str, file_name: &str, graph: &Collaboration) -> Result<PathBuf> {
+  let path = Path::new(base_dir).join(file_name);
+
+  std::fs::write(&path, to_dot(graph)).with_context(|| format!("writing {}", path.display()))?;
+
+  Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(name: &str, body: &str, files: &[&str]) -> Commit {
+    let files: Vec<(&str, i64, i64)> = files.iter().map(|f| (*f, 1, 0)).collect();
+    let mut c = Commit::fixture("abc")
+      .with_author(name, &format!("{}@x", name.to_lowercase()))
+      .with_files(&files);
+    c.body = body.into();
+    c
+  }
+
+  #[test]
+  fn co_authors_and_shared_files_become_weighted_edges() {
+    let commits = vec![
+      commit("Ann", "pairing\n\nCo-authored-by: Bob <bob@x>", &["a.rs"]),
+      commit("Bob", "", &["a.rs", "b.rs"]),
+      commit("Cy", "", &["b.rs"]),
+      commit("Ann", "", &["c.rs"]),
+    ];
+
+    let g = build_collaboration(&commits);
+
+    assert_eq!(g.nodes.len(), 3);
+    assert_eq!(g.nodes[0].id, "Ann <ann@x>");
+    assert_eq!((g.nodes[0].commits, g.nodes[0].co_authored), (2, 0));
+    assert_eq!((g.nodes[1].commits, g.nodes[1].co_authored), (1, 1));
+    assert_eq!(
+      g.edges[0],
+      CollaborationEdge {
+        source: "Ann <ann@x>".into(),
+        target: "Bob <bob@x>".into(),
+        weight: 2,
+        co_authored_commits: 1,
+        shared_files: 1,
+      }
+    );
+    assert_eq!(g.edges[1].source, "Bob <bob@x>");
+    assert_eq!(g.edges[1].target, "Cy <cy@x>");
+    assert_eq!(g.edges.len(), 2);
+  }
+
+  #[test]
+  fn self_co_author_and_solo_files_add_no_edges() {
+    let commits = vec![commit("Ann", "x\n\nCo-authored-by: Ann <ann@x>", &["a.rs"])];
+
+    let g = build_collaboration(&commits);
+
+    assert_eq!(g.nodes.len(), 1);
+    assert_eq!(g.nodes[0].co_authored, 0);
+    assert!(g.edges.is_empty());
+  }
+
+  #[test]
+  fn dot_output_is_undirected_and_escaped() {
+    let commits = vec![commit("Ann", "x\n\nCo-authored-by: \"Q\" <q@x>", &[])];
+
+    let dot = to_dot(&build_collaboration(&commits));
+
+    assert!(dot.starts_with("graph collaboration {\n"));
+    assert!(dot.contains("\"\\\"Q\\\" <q@x>\" -- \"Ann <ann@x>\" [weight=1, co_authored_commits=1, shared_files=0];"));
+    assert!(dot.ends_with("}\n"));
+  }
+}
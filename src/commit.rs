@@ -7,8 +7,11 @@
 // side_effects: Reads git; may write .patch files to disk (save_patch_to_disk)
 // invariants:
 // - clip_patch preserves UTF-8 boundaries; patch_clipped is accurate
-// - body_lines derived when body is non-empty
+// - body_lines derived when body is non-empty; --no-bodies empties body and drops commit/PR body_lines after trailers are read
 // - enrichment is best-effort; absence of PRs leaves fields None
+// - issue keys are extracted after PR/MR enrichment (titles are a source) and before --no-bodies strips the body
+// - author.identity is set only when .mailmap or --identity-map changes the recorded author
+// - --max-files-per-commit runs last; kept files + files_truncated totals equal the untruncated additions/deletions
 // errors: Propagates git IO errors; enrichment failures are swallowed (best-effort)
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
@@ -16,10 +19,17 @@
 use anyhow::Result;
 use chrono::TimeZone;
 
-use crate::enrichment::github_pull_requests::enrich_with_github_prs;
+use crate::automation::AutomationDetector;
+use crate::enrichment::github_api::resolve_github_repo;
+use crate::enrichment::github_pull_requests::{attach_pr_diffs, attach_pr_files, enrich_with_github_prs};
+use crate::enrichment::gitlab_merge_requests::{enrich_with_gitlab_mrs, is_gitlab_origin};
+use crate::enrichment::issue_keys::IssueKeyMatcher;
 use crate::gitio;
-use crate::model::{Commit, FileEntry, PatchReferences, Person, Timestamps};
-use crate::util::{clip_patch, iso_in_tz, short_sha};
+use crate::model::{Commit, FileEntry, FilesTruncated, Identity, PatchReferences, Person, Timestamps};
+use crate::redact::PathRedactor;
+use crate::services::ServiceMap;
+use crate::telemetry;
+use crate::util::{self, clip_patch, iso_in_tz, short_sha};
 use std::path::Path;
 
 pub struct ProcessContext<'a> {
@@ -28,7 +38,24 @@ pub struct ProcessContext<'a> {
   pub github_prs: bool,
   pub include_patch: bool,
   pub max_patch_bytes: usize,
+  /// --max-files-per-commit: file entries beyond the top N by churn are summarized in files_truncated
+  pub max_files_per_commit: Option<usize>,
   pub estimate_effort: bool,
+  pub no_blob_stats: bool,
+  pub service_map: Option<&'a ServiceMap>,
+  pub identity_map: Option<&'a crate::identities::IdentityMap>,
+  pub include_pr_patch: bool,
+  pub pr_files: bool,
+  pub redactor: Option<&'a PathRedactor>,
+  pub env_trailers: &'a [String],
+  pub automation: Option<&'a AutomationDetector>,
+  /// --issue-pattern: issue keys from subjects, bodies, and PR/MR titles
+  pub issue_keys: Option<&'a IssueKeyMatcher>,
+  pub snapshot_paths: &'a [String],
+  pub snapshot_max_bytes: usize,
+  pub no_bodies: bool,
+  /// --path pathspecs; file entries and patches cover only matching paths
+  pub paths: &'a [String],
 }
 
 /// Sums additions and deletions across a slice of `FileEntry`s.
@@ -39,14 +66,104 @@ pub fn sum_additions_deletions(files: &[FileEntry]) -> (i64, i64) {
   (additions, deletions)
 }
 
-/// Builds a vector of `FileEntry` structs for a given commit.
-pub fn build_file_entries(repo: &str, sha: &str) -> Result<Vec<FileEntry>> {
-  let (num_list, num_map) = gitio::commit_numstat(repo, sha)?;
-  let name_status_list = gitio::commit_name_status(repo, sha)?;
+/// Additions and deletions across all of `commit`'s files, including entries dropped by --max-files-per-commit.
+pub fn commit_additions_deletions(commit: &Commit) -> (i64, i64) {
+  let (additions, deletions) = sum_additions_deletions(&commit.files);
+
+  match &commit.files_truncated {
+    Some(t) => (additions + t.total_additions, deletions + t.total_deletions),
+    None => (additions, deletions),
+  }
+}
+
+/// Keep the `max` file entries with the most churn (ties keep git's order; kept entries stay in git's order) and
+/// record what was dropped in `files_truncated`.
+pub fn cap_file_entries(commit: &mut Commit, max: usize) {
+  if commit.files.len() <= max {
+    return;
+  }
+
+  let churn = |f: &FileEntry| f.additions.unwrap_or(0) + f.deletions.unwrap_or(0);
+  let mut ranked: Vec<usize> = (0..commit.files.len()).collect();
+  ranked.sort_by_key(|&i| std::cmp::Reverse(churn(&commit.files[i])));
+  let keep: std::collections::HashSet<usize> = ranked.into_iter().take(max).collect();
+
+  let (kept, omitted): (Vec<_>, Vec<_>) = std::mem::take(&mut commit.files)
+    .into_iter()
+    .enumerate()
+    .partition(|(i, _)| keep.contains(i));
+  let omitted: Vec<FileEntry> = omitted.into_iter().map(|(_, f)| f).collect();
+  let (total_additions, total_deletions) = sum_additions_deletions(&omitted);
+
+  commit.files = kept.into_iter().map(|(_, f)| f).collect();
+  commit.files_truncated = Some(FilesTruncated {
+    omitted: omitted.len(),
+    total_additions,
+    total_deletions,
+  });
+}
+
+/// Builds a vector of `FileEntry` structs for a given commit, limited to `paths` when given.
+pub fn build_file_entries(repo: &str, sha: &str, paths: &[String]) -> Result<Vec<FileEntry>> {
+  let (num_list, num_map) = gitio::commit_numstat(repo, sha, paths)?;
+  let name_status_list = gitio::commit_name_status(repo, sha, paths)?;
 
   Ok(build_file_entries_from(num_list, num_map, name_status_list))
 }
 
+/// Builds status-only `FileEntry`s (no additions/deletions) from tree data alone; never fetches blobs.
+pub fn build_status_only_file_entries(repo: &str, sha: &str, paths: &[String]) -> Result<Vec<FileEntry>> {
+  let name_status_list = gitio::commit_name_status_no_renames(repo, sha, paths)?;
+
+  let entries = name_status_list
+    .into_iter()
+    .map(|entry| FileEntry {
+      file: entry.get("file").cloned().unwrap_or_default(),
+      status: entry.get("status").cloned().unwrap_or_else(|| "M".to_string()),
+      old_path: None,
+      additions: None,
+      deletions: None,
+      service: None,
+      language: None,
+      lfs: None,
+      lfs_size_delta: None,
+    })
+    .collect();
+
+  Ok(entries)
+}
+
+/// Build file entries honoring `--no-blob-stats`, degrading to status-only when numstat fails on a partial clone.
+///
+/// Returns the entries and whether blob-derived stats were unavailable for this commit.
+fn build_file_entries_resilient(sha: &str, context: &ProcessContext) -> Result<(Vec<FileEntry>, bool)> {
+  if context.no_blob_stats {
+    let entries = build_status_only_file_entries(context.repo, sha, context.paths)?;
+
+    return Ok((entries, true));
+  }
+
+  let full = build_file_entries(context.repo, sha, context.paths);
+
+  match full {
+    Ok(entries) => Ok((entries, false)),
+    Err(err) if gitio::is_partial_clone(context.repo) => {
+      util::warn_commit(
+        "gar",
+        sha,
+        &format!(
+          "numstat unavailable (partial clone); using status-only entries: {}",
+          err
+        ),
+      );
+      let entries = build_status_only_file_entries(context.repo, sha, context.paths)?;
+
+      Ok((entries, true))
+    }
+    Err(err) => Err(err),
+  }
+}
+
 pub fn build_file_entries_from(
   num_list: Vec<(String, Option<i64>, Option<i64>)>,
   num_map: std::collections::HashMap<String, (Option<i64>, Option<i64>)>,
@@ -64,6 +181,10 @@ pub fn build_file_entries_from(
           old_path: entry.get("old_path").cloned(),
           additions,
           deletions,
+          service: None,
+          language: None,
+          lfs: None,
+          lfs_size_delta: None,
         }
       })
       .collect()
@@ -76,6 +197,10 @@ pub fn build_file_entries_from(
         old_path: None,
         additions,
         deletions,
+        service: None,
+        language: None,
+        lfs: None,
+        lfs_size_delta: None,
       })
       .collect()
   }
@@ -83,7 +208,14 @@ pub fn build_file_entries_from(
 
 pub fn build_commit_object(sha: &str, context: &ProcessContext) -> Result<Commit> {
   let meta = gitio::commit_meta(context.repo, sha)?;
-  let files = build_file_entries(context.repo, sha)?;
+  let (mut files, blob_stats_unavailable) = build_file_entries_resilient(sha, context)?;
+
+  crate::lfs::mark_lfs_files(context.repo, sha, meta.parents.first().map(String::as_str), &mut files);
+  crate::languages::tag_files(&mut files);
+
+  if let Some(map) = context.service_map {
+    map.tag_files(&mut files);
+  }
 
   // Synthesize a shortstat-like summary from numstat-derived entries to avoid an extra git call.
   let files_changed = files.len();
@@ -126,16 +258,28 @@ pub fn build_commit_object(sha: &str, context: &ProcessContext) -> Result<Commit
     timezone,
   };
 
+  let (identity_name, identity_email) = crate::identities::resolve(
+    context.identity_map,
+    &meta.author_mailmap_name,
+    &meta.author_mailmap_email,
+  );
+  let identity = (identity_name != meta.author_name || identity_email != meta.author_email).then(|| Identity {
+    name: identity_name.to_string(),
+    email: identity_email.to_string(),
+  });
+
   let author = Person {
     name: meta.author_name,
     email: meta.author_email,
     date: meta.author_date,
+    identity,
   };
 
   let committer = Person {
     name: meta.committer_name,
     email: meta.committer_email,
     date: meta.committer_date,
+    identity: None,
   };
 
   let patch_references = PatchReferences {
@@ -149,12 +293,14 @@ pub fn build_commit_object(sha: &str, context: &ProcessContext) -> Result<Commit
     sha: meta.sha.clone(),
     short_sha: short_sha(&meta.sha),
     parents: meta.parents,
+    refs: None,
     author,
     committer,
     timestamps,
     subject: meta.subject,
     body: meta.body,
     files,
+    files_truncated: None,
     diffstat_text,
     patch_references,
     patch_clipped: None,
@@ -166,28 +312,85 @@ pub fn build_commit_object(sha: &str, context: &ProcessContext) -> Result<Commit
     estimate_confidence: None,
     estimate_basis: None,
     github: None,
+    gitlab: None,
+    blob_stats_unavailable: blob_stats_unavailable.then_some(true),
+    environments: None,
+    patch_allocation: None,
+    automated: None,
+    file_snapshots: None,
+    authored_by_committer: None,
+    issues: None,
+    merge_group: None,
+    release: None,
   };
 
   Ok(commit)
 }
 
+/// Environments named by the configured trailers (case-insensitive keys; comma-separated values split).
+pub fn environments_from_trailers(body: &str, trailer_names: &[String]) -> Option<Vec<String>> {
+  let mut envs: Vec<String> = Vec::new();
+
+  for (key, value) in crate::util::parse_trailers(body) {
+    if !trailer_names.iter().any(|n| n.eq_ignore_ascii_case(&key)) {
+      continue;
+    }
+
+    for env in value.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+      if !envs.iter().any(|e| e == env) {
+        envs.push(env.to_string());
+      }
+    }
+  }
+
+  (!envs.is_empty()).then_some(envs)
+}
+
 /// Processes a single git commit SHA and returns a fully populated `Commit` struct.
 pub fn process_commit(sha: &str, context: &ProcessContext) -> Result<Commit> {
+  let _span = telemetry::span("process_commit").attr("commit.sha", sha);
   let mut commit = build_commit_object(sha, context)?;
 
   if context.include_patch {
-    let patch_text = gitio::commit_patch(context.repo, sha)?;
+    let patch_text = gitio::commit_patch(context.repo, sha, context.paths)?;
     let (maybe_patch, clipped) = clip_patch(patch_text, context.max_patch_bytes);
     commit.patch_lines = maybe_patch.map(|p| p.lines().map(String::from).collect());
     commit.patch_clipped = clipped;
   }
 
   if context.github_prs {
-    enrich_with_github_prs(&mut commit, context.repo);
+    // The backend follows origin; an explicit --github-repo always means GitHub
+    if resolve_github_repo(context.repo).is_none() && is_gitlab_origin(context.repo) {
+      let _span = telemetry::span("enrich.gitlab_merge_requests");
+      enrich_with_gitlab_mrs(&mut commit, context.repo);
+    } else {
+      let _span = telemetry::span("enrich.github_pull_requests");
+      enrich_with_github_prs(&mut commit, context.repo);
+
+      if context.include_pr_patch {
+        let _span = telemetry::span("enrich.pr_diffs");
+        attach_pr_diffs(&mut commit, context.repo, context.max_patch_bytes);
+      }
+
+      if context.pr_files {
+        let _span = telemetry::span("enrich.pr_files");
+        attach_pr_files(&mut commit, context.repo);
+      }
+    }
   }
 
   if !commit.body.is_empty() {
     commit.body_lines = Some(commit.body.lines().map(String::from).collect());
+    commit.environments = environments_from_trailers(&commit.body, context.env_trailers);
+  }
+
+  // Before --no-bodies strips the body and PR/MR titles are final
+  if let Some(matcher) = context.issue_keys {
+    commit.issues = matcher.find(&commit);
+  }
+
+  if let Some(detector) = context.automation {
+    commit.automated = detector.is_automated(&commit.author, &commit.committer).then_some(true);
   }
 
   if context.estimate_effort {
@@ -200,16 +403,59 @@ pub fn process_commit(sha: &str, context: &ProcessContext) -> Result<Commit> {
     commit.estimate_basis = Some(e.basis);
   }
 
+  if context.no_bodies {
+    strip_bodies(&mut commit);
+  }
+
+  // Before redaction: snapshots are looked up by real path, and redacted paths are skipped.
+  commit.file_snapshots = crate::snapshots::collect_snapshots(
+    &commit,
+    context.repo,
+    context.snapshot_paths,
+    context.snapshot_max_bytes,
+    context.redactor,
+  );
+
+  if let Some(r) = context.redactor {
+    r.redact_commit(&mut commit);
+  }
+
+  // Last, so effort estimates, snapshots, and the diffstat text still see every file.
+  if let Some(max) = context.max_files_per_commit {
+    cap_file_entries(&mut commit, max);
+  }
+
   Ok(commit)
 }
 
+/// `--no-bodies`: drop commit and PR body text once trailer-derived fields are set; subjects/titles stay.
+fn strip_bodies(commit: &mut Commit) {
+  commit.body.clear();
+  commit.body_lines = None;
+
+  if let Some(gh) = commit.github.as_mut() {
+    for pr in &mut gh.pull_requests {
+      pr.body_lines = None;
+    }
+  }
+}
+
 /// Save the full patch to disk and update `commit.patch_references.local_patch_file`.
 ///
 /// Optimization: When this run already fetched the patch and it was not clipped
 /// (i.e., `commit.patch_clipped == Some(false)`), write that in‑memory content
 /// instead of spawning another `git show`. Fallback to `git show` when the patch
 /// is clipped or not embedded.
-pub fn save_patch_to_disk(commit: &mut Commit, repo: &str, directory_path: &Path) -> Result<()> {
+///
+/// When a `redactor` is given, matching paths are masked in the written file as well; non-empty `paths` limit the
+/// patch to those pathspecs.
+pub fn save_patch_to_disk(
+  commit: &mut Commit,
+  repo: &str,
+  directory_path: &Path,
+  redactor: Option<&PathRedactor>,
+  paths: &[String],
+) -> Result<()> {
   std::fs::create_dir_all(directory_path)?;
   let path = directory_path.join(format!("{}.patch", commit.short_sha));
 
@@ -229,7 +475,11 @@ pub fn save_patch_to_disk(commit: &mut Commit, repo: &str, directory_path: &Path
   let patch_content = if let Some(s) = content_from_memory {
     s
   } else {
-    gitio::commit_patch(repo, &commit.sha)?
+    gitio::commit_patch(repo, &commit.sha, paths)?
+  };
+  let patch_content = match redactor {
+    Some(r) => r.redact_patch_text(&patch_content),
+    None => patch_content,
   };
 
   std::fs::write(&path, patch_content)?;
@@ -0,0 +1,246 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --cache: reuse processed commits across runs over overlapping windows via an on-disk cache of serialized Commit objects
+// role: persistence/commit-cache
+// inputs: EffectiveConfig (processing settings fingerprint), repo path, commit SHA; GAR_CACHE_DIR / XDG_CACHE_HOME / HOME for the cache root
+// outputs: Cached Commit on hit; <root>/<repo-id>/commits/<sha>.json written after each miss
+// side_effects: Reads/writes cache files (temp file + rename); write failures warn once and the run continues uncached
+// invariants:
+// - an entry is reused only when its schema version, tool version, and settings fingerprint all match the current run; otherwise it is reprocessed and overwritten
+// - the fingerprint covers every option that shapes process_commit output (enrichment, patches, redaction, identities, .mailmap, git args)
+// - cached commits are the output of process_commit only; range-level steps (--all-refs, --patch-budget-bytes, --fields) run on every read
+// - entries recorded with --github-prs expire after --github-cache-ttl, since PR/MR data (and its absence) goes stale
+// - unreadable or corrupt entries count as misses
+// errors: None surfaced; the cache never fails a run
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::cli::EffectiveConfig;
+use crate::model::Commit;
+use crate::util;
+
+/// Bump whenever the serialized `Commit` shape changes.
+pub const CACHE_SCHEMA_VERSION: u32 = 3;
+
+/// EffectiveConfig keys that change what `process_commit` produces.
+const SETTINGS_KEYS: [&str; 22] = [
+  "tz",
+  "include_patch",
+  "max_patch_bytes",
+  "max_files_per_commit",
+  "no_blob_stats",
+  "github_prs",
+  "include_pr_patch",
+  "pr_files",
+  "github_repo",
+  "estimate_effort",
+  "service_map",
+  "identity_map",
+  "redact_paths",
+  "env_trailers",
+  "bot_authors",
+  "issue_patterns",
+  "issue_url_templates",
+  "snapshot_paths",
+  "snapshot_max_bytes",
+  "no_bodies",
+  "paths",
+  "git_args",
+];
+
+static WRITE_WARNED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+  schema_version: u32,
+  tool_version: String,
+  settings: serde_json::Value,
+  /// Epoch seconds when the entry was written
+  stored_at: i64,
+  commit: Commit,
+}
+
+/// Per-repo commit cache for one run's settings.
+#[derive(Debug, Clone)]
+pub struct CommitCache {
+  dir: PathBuf,
+  settings: serde_json::Value,
+  /// Max entry age when commits carry PR/MR enrichment; None keeps entries until settings change
+  enriched_ttl_secs: Option<u64>,
+}
+
+impl CommitCache {
+  /// The cache for `cfg.repo` when `--cache` is on and a cache root can be found.
+  pub fn for_config(cfg: &EffectiveConfig) -> Option<Self> {
+    if !cfg.cache {
+      return None;
+    }
+
+    let dir = cache_root()?.join(repo_id(&cfg.repo)).join("commits");
+
+    let enriched_ttl_secs = cfg.github_prs.then_some(cfg.github_cache_ttl);
+
+    Some(Self::new(dir, settings_fingerprint(cfg), enriched_ttl_secs))
+  }
+
+  fn new(dir: PathBuf, settings: serde_json::Value, enriched_ttl_secs: Option<u64>) -> Self {
+    Self {
+      dir,
+      settings,
+      enriched_ttl_secs,
+    }
+  }
+
+  fn entry_path(&self, sha: &str) -> PathBuf {
+    self.dir.join(format!("{}.json", sha))
+  }
+
+  /// The cached commit for `sha`, if it was stored by this tool version with the same settings
+  /// and, when PR/MR enrichment is on, within the enrichment TTL.
+  pub fn get(&self, sha: &str) -> Option<Commit> {
+    let bytes = std::fs::read(self.entry_path(sha)).ok()?;
+    let entry: CacheEntry = serde_json::from_slice(&bytes).ok()?;
+    let now = chrono::Utc::now().timestamp();
+    let fresh = self
+      .enriched_ttl_secs
+      .is_none_or(|ttl| now - entry.stored_at <= ttl as i64);
+    let current = entry.schema_version == CACHE_SCHEMA_VERSION
+      && entry.tool_version == env!("CARGO_PKG_VERSION")
+      && entry.settings == self.settings
+      && fresh;
+
+    current.then_some(entry.commit)
+  }
+
+  /// Store `commit`; failures only warn (once per run).
+  pub fn put(&self, commit: &Commit) {
+    if let Err(e) = self.write(commit) {
+      if !WRITE_WARNED.swap(true, Ordering::Relaxed) {
+        util::log(
+          util::LogLevel::Warn,
+          "gar",
+          &format!("commit cache {} is not writable: {:#}", self.dir.display(), e),
+        );
+      }
+    }
+  }
+
+  fn write(&self, commit: &Commit) -> anyhow::Result<()> {
+    let entry = CacheEntry {
+      schema_version: CACHE_SCHEMA_VERSION,
+      tool_version: env!("CARGO_PKG_VERSION").to_string(),
+      settings: self.settings.clone(),
+      stored_at: chrono::Utc::now().timestamp(),
+      commit: commit.clone(),
+    };
+    let path = self.entry_path(&commit.sha);
+    let tmp = self.dir.join(format!(".{}.{}.tmp", commit.sha, std::process::id()));
+
+    std::fs::create_dir_all(&self.dir)?;
+    std::fs::write(&tmp, serde_json::to_vec(&entry)?)?;
+    std::fs::rename(&tmp, &path)?;
+
+    Ok(())
+  }
+}
+
+/// `$GAR_CACHE_DIR`, else `$XDG_CACHE_HOME/git-activity-report`, else `~/.cache/git-activity-report`.
+fn cache_root() -> Option<PathBuf> {
+  let from_env = |k: &str| std::env::var_os(k).filter(|v| !v.is_empty()).map(PathBuf::from);
+
+  from_env("GAR_CACHE_DIR")
+    .or_else(|| from_env("XDG_CACHE_HOME").map(|d| d.join("git-activity-report")))
+    .or_else(|| from_env("HOME").map(|d| d.join(".cache").join("git-activity-report")))
+}
+
+/// `<repo dir name>-<fnv1a-64 of the absolute repo path>`: readable and unique per checkout.
+fn repo_id(repo: &str) -> String {
+  let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+  for b in repo.as_bytes() {
+    hash ^= *b as u64;
+    hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+  }
+
+  let name = Path::new(repo)
+    .file_name()
+    .map(|n| n.to_string_lossy().to_string())
+    .unwrap_or_else(|| "repo".into());
+
+  format!("{}-{:016x}", name, hash)
+}
+
+/// The processing options plus the repo's `.mailmap`, which git applies to author names.
+fn settings_fingerprint(cfg: &EffectiveConfig) -> serde_json::Value {
+  let all = serde_json::to_value(cfg).unwrap_or_default();
+  let mut settings: serde_json::Map<String, serde_json::Value> = SETTINGS_KEYS
+    .iter()
+    .map(|k| (k.to_string(), all.get(*k).cloned().unwrap_or_default()))
+    .collect();
+
+  settings.insert(
+    "mailmap".into(),
+    std::fs::read_to_string(Path::new(&cfg.repo).join(".mailmap"))
+      .map(serde_json::Value::String)
+      .unwrap_or_default(),
+  );
+
+  serde_json::Value::Object(settings)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn hits_only_with_matching_settings() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path().join("repo-0/commits");
+    let cache = CommitCache::new(dir.clone(), serde_json::json!({ "include_patch": false }), None);
+    let sha = "a".repeat(40);
+    let mut commit = Commit::fixture(&sha);
+    commit.subject = "Add widget".into();
+
+    assert!(cache.get(&sha).is_none());
+    cache.put(&commit);
+    assert_eq!(cache.get(&sha).unwrap().subject, "Add widget");
+
+    let other = CommitCache::new(dir.clone(), serde_json::json!({ "include_patch": true }), None);
+    assert!(other.get(&sha).is_none());
+
+    std::fs::write(dir.join(format!("{}.json", sha)), b"{not json").unwrap();
+    assert!(cache.get(&sha).is_none());
+  }
+
+  #[test]
+  fn enriched_entries_expire_after_the_ttl() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path().join("repo-0/commits");
+    let settings = serde_json::json!({ "github_prs": true });
+    let cache = CommitCache::new(dir.clone(), settings.clone(), Some(3600));
+    let sha = "b".repeat(40);
+
+    cache.put(&Commit::fixture(&sha));
+    assert!(cache.get(&sha).is_some());
+
+    let path = dir.join(format!("{}.json", sha));
+    let mut entry: serde_json::Value = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+    entry["stored_at"] = serde_json::json!(chrono::Utc::now().timestamp() - 7200);
+    std::fs::write(&path, serde_json::to_vec(&entry).unwrap()).unwrap();
+
+    assert!(cache.get(&sha).is_none(), "PR data older than the TTL is refetched");
+    assert!(CommitCache::new(dir, settings, None).get(&sha).is_some());
+  }
+
+  #[test]
+  fn repo_id_is_stable_and_path_specific() {
+    assert_eq!(repo_id("/src/widgets"), repo_id("/src/widgets"));
+    assert_ne!(repo_id("/src/widgets"), repo_id("/tmp/widgets"));
+    assert!(repo_id("/src/widgets").starts_with("widgets-"));
+  }
+}
@@ -0,0 +1,250 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Load defaults for common flags from a TOML config file (--config, <repo>/.gar.toml, or the user config) so they need not be typed every run
+// role: config/file-defaults
+// inputs: Parsed Cli and its clap ArgMatches (to tell flags given on the command line from defaults); the config file; XDG_CONFIG_HOME / HOME
+// outputs: Cli with config values filled in where the command line left a flag unset; GAR_EST_* tuning variables for [estimate] keys
+// side_effects: Reads one config file; sets GAR_EST_* environment variables that are not already set (so provenance.env records them)
+// invariants:
+// - file lookup: --config FILE (must exist), else <repo>/.gar.toml, else $XDG_CONFIG_HOME/git-activity-report/config.toml (~/.config when unset); only the first found is read
+// - a flag given on the command line always wins; a list in the file replaces the flag's default list (bot_authors, env_trailers) rather than extending it
+// - identity_map and service_map paths are relative to the config file's directory
+// - values pass through cli::normalize like flags, so the same validation applies (e.g. unknown --tz zones)
+// - [estimate] keys name GAR_EST_* knobs in lowercase without the prefix; an environment variable already set wins over the file
+// errors: unreadable or malformed files, unknown keys, and unknown [estimate] knobs fail with the file path
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use serde::Deserialize;
+
+use crate::cli::Cli;
+
+/// Repo-local config file name.
+pub const REPO_CONFIG: &str = ".gar.toml";
+
+/// `[estimate]` knobs: the GAR_EST_* variables read by enrichment::effort, lowercased without the prefix.
+const ESTIMATE_KEYS: &[&str] = &[
+  "base_commit_min",
+  "per_file_min",
+  "per_file_tail_min",
+  "sqrt_lines_coeff",
+  "rename_discount",
+  "heavy_delete_discount",
+  "test_only_discount",
+  "mixed_tests_uplift",
+  "cog_base_min",
+  "cog_ext_mix_coeff",
+  "cog_dir_mix_coeff",
+  "cog_balanced_edit_coeff",
+  "cog_lang_complexity_coeff",
+  "pr_review_approved_min",
+  "pr_review_changes_min",
+  "pr_review_commented_min",
+  "pr_files_overhead_per_review_min",
+  "pr_day_drag_min",
+  "pr_assembly_min",
+  "pr_approver_only_min",
+  "pr_cycle_time_cap_ratio",
+];
+
+/// Flag defaults read from a config file; every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+  pub tz: Option<String>,
+  pub include_merges: Option<bool>,
+  pub github_prs: Option<bool>,
+  pub github_repo: Option<String>,
+  pub estimate_effort: Option<bool>,
+  pub committers: Option<bool>,
+  pub releases: Option<bool>,
+  pub calendar: Option<bool>,
+  pub bot_authors: Option<Vec<String>>,
+  pub env_trailers: Option<Vec<String>>,
+  pub issue_patterns: Option<Vec<String>>,
+  pub issue_url_templates: Option<Vec<String>>,
+  pub exclude_paths: Option<Vec<String>>,
+  pub identity_map: Option<PathBuf>,
+  pub service_map: Option<PathBuf>,
+  #[serde(default)]
+  pub estimate: BTreeMap<String, f64>,
+}
+
+impl ConfigFile {
+  /// Parse `path`, checking `[estimate]` keys and resolving map paths against its directory.
+  pub fn load(path: &Path) -> Result<Self> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading config file {}", path.display()))?;
+    let mut cfg: ConfigFile =
+      toml::from_str(&text).with_context(|| format!("parsing config file {}", path.display()))?;
+
+    if let Some(key) = cfg.estimate.keys().find(|k| !ESTIMATE_KEYS.contains(&k.as_str())) {
+      bail!(
+        "config file {}: unknown [estimate] key {:?} (expected one of: {})",
+        path.display(),
+        key,
+        ESTIMATE_KEYS.join(", ")
+      );
+    }
+
+    let dir = path.parent().unwrap_or(Path::new("."));
+    for p in [&mut cfg.identity_map, &mut cfg.service_map].into_iter().flatten() {
+      *p = dir.join(&*p);
+    }
+
+    Ok(cfg)
+  }
+
+  /// Fill the flags `matches` did not get from the command line.
+  pub fn apply_to(self, cli: &mut Cli, matches: &ArgMatches) {
+    let unset = |id: &str| matches.value_source(id) != Some(ValueSource::CommandLine);
+
+    macro_rules! fill {
+      ($($field:ident),* $(,)?) => {
+        $(
+          if let Some(v) = self.$field {
+            if unset(stringify!($field)) {
+              cli.$field = v;
+            }
+          }
+        )*
+      };
+    }
+    macro_rules! fill_opt {
+      ($($field:ident),* $(,)?) => {
+        $(
+          if self.$field.is_some() && unset(stringify!($field)) {
+            cli.$field = self.$field;
+          }
+        )*
+      };
+    }
+
+    fill!(
+      tz,
+      include_merges,
+      github_prs,
+      estimate_effort,
+      committers,
+      releases,
+      calendar,
+      bot_authors,
+      env_trailers,
+      issue_patterns,
+      issue_url_templates,
+      exclude_paths,
+    );
+    fill_opt!(github_repo, identity_map, service_map);
+
+    for (key, value) in self.estimate {
+      let var = format!("GAR_EST_{}", key.to_ascii_uppercase());
+
+      if std::env::var_os(&var).is_none() {
+        std::env::set_var(var, value.to_string());
+      }
+    }
+  }
+}
+
+/// The config file to read: `--config`, else `<repo>/.gar.toml`, else the user config; None when none exists.
+fn locate(cli: &Cli) -> Result<Option<PathBuf>> {
+  if let Some(path) = &cli.config {
+    if !path.is_file() {
+      bail!("--config {} does not exist", path.display());
+    }
+
+    return Ok(Some(path.clone()));
+  }
+
+  let from_env = |k: &str| std::env::var_os(k).filter(|v| !v.is_empty()).map(PathBuf::from);
+  let user = from_env("XDG_CONFIG_HOME")
+    .or_else(|| from_env("HOME").map(|d| d.join(".config")))
+    .map(|d| d.join("git-activity-report").join("config.toml"));
+
+  Ok(
+    std::iter::once(cli.repo.join(REPO_CONFIG))
+      .chain(user)
+      .find(|p| p.is_file()),
+  )
+}
+
+/// Load the config file, if any, into `cli` beneath the flags given on the command line.
+pub fn apply(cli: &mut Cli, matches: &ArgMatches) -> Result<()> {
+  let Some(path) = locate(cli)? else {
+    return Ok(());
+  };
+
+  ConfigFile::load(&path)?.apply_to(cli, matches);
+  crate::util::log(
+    crate::util::LogLevel::Info,
+    "gar",
+    &format!("defaults from config file {}", path.display()),
+  );
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use clap::{CommandFactory, FromArgMatches};
+
+  fn parse(args: &[&str]) -> (Cli, ArgMatches) {
+    let matches = Cli::command().get_matches_from(std::iter::once("git-activity-report").chain(args.iter().copied()));
+    let cli = Cli::from_arg_matches(&matches).unwrap();
+
+    (cli, matches)
+  }
+
+  #[test]
+  fn file_values_fill_only_flags_left_unset() {
+    let td = tempfile::TempDir::new().unwrap();
+    let path = td.path().join(REPO_CONFIG);
+    std::fs::write(
+      &path,
+      r#"
+tz = "Europe/London"
+github_prs = true
+exclude_paths = ["vendor/"]
+bot_authors = ["^ci-bot$"]
+identity_map = "ids.toml"
+"#,
+    )
+    .unwrap();
+
+    let (mut cli, matches) = parse(&["--month", "2025-08", "--tz", "utc"]);
+    ConfigFile::load(&path).unwrap().apply_to(&mut cli, &matches);
+
+    assert_eq!(cli.tz, "utc");
+    assert!(cli.github_prs);
+    assert_eq!(cli.exclude_paths, vec!["vendor/"]);
+    assert_eq!(cli.bot_authors, vec!["^ci-bot$"]);
+    assert_eq!(cli.identity_map, Some(td.path().join("ids.toml")));
+
+    let (mut cli, matches) = parse(&["--month", "2025-08", "--exclude-path", "dist/"]);
+    ConfigFile::load(&path).unwrap().apply_to(&mut cli, &matches);
+    assert_eq!(cli.tz, "Europe/London");
+    assert_eq!(cli.exclude_paths, vec!["dist/"]);
+  }
+
+  #[test]
+  fn unknown_keys_are_rejected() {
+    let td = tempfile::TempDir::new().unwrap();
+    let path = td.path().join(REPO_CONFIG);
+
+    std::fs::write(&path, "timezone = \"utc\"\n").unwrap();
+    assert!(ConfigFile::load(&path).is_err());
+
+    std::fs::write(&path, "[estimate]\nbase_commit_min = 4.0\nper_line_min = 1.0\n").unwrap();
+    let err = format!("{:#}", ConfigFile::load(&path).unwrap_err());
+    assert!(err.contains("per_line_min"));
+
+    std::fs::write(&path, "[estimate]\nbase_commit_min = 4.0\n").unwrap();
+    assert_eq!(ConfigFile::load(&path).unwrap().estimate["base_commit_min"], 4.0);
+  }
+}
@@ -0,0 +1,146 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Summarize files that needed merge resolution in the window (--conflict-hotspots) to surface contention-heavy files
+// role: summary/conflict-hotspots
+// inputs: repo, since/until, optional PathRedactor
+// outputs: ConflictHotspots for summary.conflict_hotspots
+// side_effects: git rev-list --merges plus one combined diff (diff-tree -c) per merge commit
+// invariants:
+// - a file counts for a merge when the merge result differs from every parent (conflicts and manual resolutions; clean merges of one side do not count)
+// - merges are scanned even without --include-merges; file paths are redacted like commit file entries
+// - files are ordered by resolutions desc, then path
+// errors: git failures propagate with context (the flag is opt-in)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use crate::gitio;
+use crate::model::{ConflictHotspot, ConflictHotspots};
+use crate::redact::PathRedactor;
+use crate::util::short_sha;
+
+/// Scan merge commits in `[since, until]` and aggregate the files they had to resolve.
+pub fn build_conflict_hotspots(
+  repo: &str,
+  since: &str,
+  until: &str,
+  redactor: Option<&PathRedactor>,
+) -> Result<ConflictHotspots> {
+  let merges = gitio::merge_commits(repo, since, until).context("listing merge commits")?;
+  let resolved = merges
+    .iter()
+    .map(|sha| {
+      let files = gitio::merge_resolved_files(repo, sha).with_context(|| format!("combined diff of {}", sha))?;
+      let files = files
+        .into_iter()
+        .map(|f| redactor.and_then(|r| r.redact_path(&f)).unwrap_or(f))
+        .collect();
+
+      Ok((sha.clone(), files))
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  Ok(aggregate(&resolved))
+}
+
+/// Aggregate (merge sha, resolved files) pairs given earliest→latest.
+fn aggregate(resolved: &[(String, Vec<String>)]) -> ConflictHotspots {
+  let mut by_file: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+
+  for (sha, files) in resolved {
+    for f in files {
+      by_file.entry(f).or_default().push(short_sha(sha));
+    }
+  }
+
+  let mut files: Vec<ConflictHotspot> = by_file
+    .into_iter()
+    .map(|(file, merges)| ConflictHotspot {
+      file: file.to_string(),
+      resolutions: merges.len() as i64,
+      merges,
+    })
+    .collect();
+  files.sort_by(|a, b| b.resolutions.cmp(&a.resolutions).then_with(|| a.file.cmp(&b.file)));
+
+  ConflictHotspots {
+    merges_scanned: resolved.len(),
+    files,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::Path;
+  use std::process::Command;
+
+  fn git(dir: &Path, args: &[&str]) {
+    let out = Command::new("git")
+      .args(args)
+      .current_dir(dir)
+      .env("GIT_AUTHOR_NAME", "A")
+      .env("GIT_AUTHOR_EMAIL", "a@x")
+      .env("GIT_COMMITTER_NAME", "A")
+      .env("GIT_COMMITTER_EMAIL", "a@x")
+      .env("GIT_AUTHOR_DATE", "2025-08-10T12:00:00+00:00")
+      .env("GIT_COMMITTER_DATE", "2025-08-10T12:00:00+00:00")
+      .output()
+      .unwrap();
+    assert!(
+      out.status.success() || args[0] == "merge",
+      "{}",
+      String::from_utf8_lossy(&out.stderr)
+    );
+  }
+
+  #[test]
+  fn aggregates_by_file_most_resolved_first() {
+    let h = aggregate(&[
+      ("aaaaaaaaaaaaaaaa".into(), vec!["b.rs".into(), "a.rs".into()]),
+      ("bbbbbbbbbbbbbbbb".into(), vec![]),
+      ("cccccccccccccccc".into(), vec!["b.rs".into()]),
+    ]);
+
+    assert_eq!(h.merges_scanned, 3);
+    assert_eq!(h.files[0].file, "b.rs");
+    assert_eq!(h.files[0].resolutions, 2);
+    assert_eq!(h.files[0].merges, vec!["aaaaaaaaaaaa", "cccccccccccc"]);
+    assert_eq!(h.files[1].file, "a.rs");
+  }
+
+  #[test]
+  fn conflicted_merge_is_detected_but_clean_side_is_not() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path();
+    git(dir, &["init", "-q", "-b", "main"]);
+    std::fs::write(dir.join("hot.txt"), "base\n").unwrap();
+    std::fs::write(dir.join("calm.txt"), "base\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "base"]);
+    git(dir, &["checkout", "-q", "-b", "topic"]);
+    std::fs::write(dir.join("hot.txt"), "topic\n").unwrap();
+    std::fs::write(dir.join("calm.txt"), "topic\n").unwrap();
+    git(dir, &["commit", "-q", "-am", "topic"]);
+    git(dir, &["checkout", "-q", "main"]);
+    std::fs::write(dir.join("hot.txt"), "main\n").unwrap();
+    git(dir, &["commit", "-q", "-am", "main"]);
+    git(dir, &["merge", "-q", "topic"]);
+    std::fs::write(dir.join("hot.txt"), "resolved\n").unwrap();
+    git(dir, &["commit", "-q", "-am", "merge topic"]);
+
+    let repo = dir.to_string_lossy().to_string();
+    let h = build_conflict_hotspots(&repo, "2025-08-01", "2025-09-01", None).unwrap();
+
+    assert_eq!(h.merges_scanned, 1);
+    assert_eq!(h.files.len(), 1);
+    assert_eq!(h.files[0].file, "hot.txt");
+
+    let redactor = PathRedactor::new(&["hot.txt".into()]).unwrap();
+    let h = build_conflict_hotspots(&repo, "2025-08-01", "2025-09-01", Some(&redactor)).unwrap();
+    assert!(h.files[0].file.starts_with("redacted/"));
+  }
+}
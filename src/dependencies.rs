@@ -0,0 +1,442 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Summarize dependency additions/removals/updates per range from manifest changes (--dependency-changes)
+// role: summary/dependency-changes
+// inputs: repo, processed commits of the range (earliest→latest), their file entries
+// outputs: Vec<ManifestDependencyChanges> for summary.dependency_changes
+// side_effects: git reads of each touched manifest before the first and after the last touching commit
+// invariants:
+// - net change per manifest across the range: parsed pre-image of its first touching commit vs post-image of its last
+// - recognized manifests by file name: Cargo.toml, Cargo.lock, package.json, go.mod, requirements*.txt
+// - Cargo.toml and package.json keys are qualified by section ("dev-dependencies", "devDependencies") so moves show as remove+add
+// - manifests whose parsed dependencies did not change (e.g. only metadata edits) are omitted; redacted paths never match
+// errors: None; unreadable or unparsable manifests count as empty (best-effort)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use crate::gitio;
+use crate::model::{Commit, DependencyRef, DependencyUpdate, ManifestDependencyChanges};
+
+type Deps = BTreeMap<String, String>;
+
+/// Ecosystem for a manifest file name, when it is one we parse.
+fn ecosystem_for(path: &str) -> Option<&'static str> {
+  let name = path.rsplit('/').next().unwrap_or(path);
+
+  match name {
+    "Cargo.toml" | "Cargo.lock" => Some("cargo"),
+    "package.json" => Some("npm"),
+    "go.mod" => Some("go"),
+    n if n.starts_with("requirements") && n.ends_with(".txt") => Some("pip"),
+    _ => None,
+  }
+}
+
+fn parse_manifest(path: &str, text: &str) -> Deps {
+  let name = path.rsplit('/').next().unwrap_or(path);
+
+  match name {
+    "Cargo.toml" => parse_cargo_toml(text),
+    "Cargo.lock" => parse_cargo_lock(text),
+    "package.json" => parse_package_json(text),
+    "go.mod" => parse_go_mod(text),
+    _ => parse_requirements(text),
+  }
+}
+
+fn unquote(s: &str) -> String {
+  s.trim().trim_matches('"').trim_matches('\'').to_string()
+}
+
+/// Version from a Cargo dependency value: `"1.0"` or `{ version = "1.0", ... }`; git/path deps without one → "*".
+fn cargo_version(value: &str) -> String {
+  let value = value.trim();
+
+  if value.starts_with('"') || value.starts_with('\'') {
+    return unquote(value);
+  }
+
+  value
+    .split(',')
+    .filter_map(|kv| kv.trim_start_matches('{').split_once('='))
+    .find(|(k, _)| k.trim() == "version")
+    .map(|(_, v)| unquote(v.trim_end_matches('}')))
+    .unwrap_or_else(|| "*".into())
+}
+
+fn is_cargo_dep_table(table: &str) -> bool {
+  let last = table.rsplit('.').next().unwrap_or(table);
+
+  matches!(last, "dependencies" | "dev-dependencies" | "build-dependencies")
+}
+
+fn parse_cargo_toml(text: &str) -> Deps {
+  let mut deps = Deps::new();
+  let mut table = String::new();
+  // `[dependencies.foo]` tables: (qualified key) whose `version = ...` line we are waiting for
+  let mut dotted: Option<String> = None;
+
+  for line in text.lines() {
+    let line = line.split(" #").next().unwrap_or("").trim();
+
+    if line.starts_with('[') {
+      table = line.trim_matches(|c| c == '[' || c == ']').trim().to_string();
+      dotted = None;
+
+      if let Some((parent, name)) = table.rsplit_once('.') {
+        if is_cargo_dep_table(parent) {
+          let key = format!("{}:{}", parent, name);
+          deps.insert(key.clone(), "*".into());
+          dotted = Some(key);
+        }
+      }
+
+      continue;
+    }
+
+    let Some((k, v)) = line.split_once('=') else {
+      continue;
+    };
+
+    if let Some(key) = &dotted {
+      if k.trim() == "version" {
+        deps.insert(key.clone(), unquote(v));
+      }
+    } else if is_cargo_dep_table(&table) {
+      deps.insert(format!("{}:{}", table, unquote(k)), cargo_version(v));
+    }
+  }
+
+  deps
+}
+
+fn parse_cargo_lock(text: &str) -> Deps {
+  let mut deps = Deps::new();
+  let mut name: Option<String> = None;
+
+  for line in text.lines().map(str::trim) {
+    if line == "[[package]]" {
+      name = None;
+    } else if let Some(v) = line.strip_prefix("name = ") {
+      name = Some(unquote(v));
+    } else if let (Some(v), Some(n)) = (line.strip_prefix("version = "), name.take()) {
+      // Several versions of one crate can coexist in a lockfile
+      let entry = deps.entry(n).or_default();
+      if !entry.is_empty() {
+        entry.push_str(", ");
+      }
+      entry.push_str(&unquote(v));
+    }
+  }
+
+  deps
+}
+
+fn parse_package_json(text: &str) -> Deps {
+  let Ok(v) = serde_json::from_str::<serde_json::Value>(text) else {
+    return Deps::new();
+  };
+  let mut deps = Deps::new();
+
+  for section in [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+  ] {
+    if let Some(map) = v.get(section).and_then(|m| m.as_object()) {
+      for (name, ver) in map {
+        deps.insert(format!("{}:{}", section, name), ver.as_str().unwrap_or("*").to_string());
+      }
+    }
+  }
+
+  deps
+}
+
+fn parse_go_mod(text: &str) -> Deps {
+  let mut deps = Deps::new();
+  let mut in_block = false;
+
+  for line in text.lines() {
+    let line = line.split("//").next().unwrap_or("").trim();
+
+    let spec = if in_block {
+      if line == ")" {
+        in_block = false;
+        continue;
+      }
+      line
+    } else if line == "require (" {
+      in_block = true;
+      continue;
+    } else if let Some(rest) = line.strip_prefix("require ") {
+      rest
+    } else {
+      continue;
+    };
+
+    let mut parts = spec.split_whitespace();
+    if let (Some(module), Some(version)) = (parts.next(), parts.next()) {
+      deps.insert(module.to_string(), version.to_string());
+    }
+  }
+
+  deps
+}
+
+fn parse_requirements(text: &str) -> Deps {
+  let mut deps = Deps::new();
+
+  for line in text.lines() {
+    let line = line.split('#').next().unwrap_or("").trim();
+
+    if line.is_empty() || line.starts_with('-') {
+      continue;
+    }
+
+    let split = line.find(|c: char| "=<>!~;[ ".contains(c)).unwrap_or(line.len());
+    let (name, rest) = line.split_at(split);
+    let version = rest.split(';').next().unwrap_or("").trim();
+
+    deps.insert(
+      name.to_lowercase(),
+      if version.is_empty() {
+        "*".into()
+      } else {
+        version.to_string()
+      },
+    );
+  }
+
+  deps
+}
+
+fn read_deps(repo: &str, rev: Option<&str>, path: &str) -> Deps {
+  let Some(rev) = rev else {
+    return Deps::new();
+  };
+
+  gitio::file_at(repo, rev, path)
+    .ok()
+    .and_then(|(_, bytes)| String::from_utf8(bytes).ok())
+    .map(|text| parse_manifest(path, &text))
+    .unwrap_or_default()
+}
+
+/// Net dependency changes for every manifest touched by `commits` (earliest→latest).
+pub fn summarize_dependency_changes(repo: &str, commits: &[Commit]) -> Vec<ManifestDependencyChanges> {
+  // path → (first touching commit's parent, last touching commit sha, deleted at the end, short shas)
+  let mut touched: BTreeMap<String, (Option<String>, String, bool, Vec<String>)> = BTreeMap::new();
+
+  for c in commits {
+    for f in c.files.iter().filter(|f| ecosystem_for(&f.file).is_some()) {
+      let entry = touched
+        .entry(f.file.clone())
+        .or_insert_with(|| (c.parents.first().cloned(), c.sha.clone(), false, Vec::new()));
+      entry.1 = c.sha.clone();
+      entry.2 = f.status.starts_with('D');
+      entry.3.push(c.short_sha.clone());
+    }
+  }
+
+  touched
+    .into_iter()
+    .filter_map(|(file, (before_rev, after_rev, deleted, shas))| {
+      let before = read_deps(repo, before_rev.as_deref(), &file);
+      let after = if deleted {
+        Deps::new()
+      } else {
+        read_deps(repo, Some(&after_rev), &file)
+      };
+      let change = diff_deps(&file, &before, &after, shas);
+
+      (!change.added.is_empty() || !change.removed.is_empty() || !change.updated.is_empty()).then_some(change)
+    })
+    .collect()
+}
+
+fn diff_deps(file: &str, before: &Deps, after: &Deps, commits: Vec<String>) -> ManifestDependencyChanges {
+  let dep = |k: &String, v: &String| DependencyRef {
+    name: k.clone(),
+    version: v.clone(),
+  };
+
+  ManifestDependencyChanges {
+    file: file.to_string(),
+    ecosystem: ecosystem_for(file).unwrap_or_default().to_string(),
+    commits,
+    added: after
+      .iter()
+      .filter(|(k, _)| !before.contains_key(*k))
+      .map(|(k, v)| dep(k, v))
+      .collect(),
+    removed: before
+      .iter()
+      .filter(|(k, _)| !after.contains_key(*k))
+      .map(|(k, v)| dep(k, v))
+      .collect(),
+    updated: after
+      .iter()
+      .filter_map(|(k, to)| {
+        let from = before.get(k).filter(|from| *from != to)?;
+
+        Some(DependencyUpdate {
+          name: k.clone(),
+          from: from.clone(),
+          to: to.clone(),
+        })
+      })
+      .collect(),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::model::FileEntry;
+  use std::path::Path;
+  use std::process::Command;
+
+  fn git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+      .args(args)
+      .current_dir(dir)
+      .env("GIT_AUTHOR_NAME", "A")
+      .env("GIT_AUTHOR_EMAIL", "a@x")
+      .env("GIT_COMMITTER_NAME", "A")
+      .env("GIT_COMMITTER_EMAIL", "a@x")
+      .output()
+      .unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+  }
+
+  fn commit(sha: &str, parent: &str, files: &[(&str, &str)]) -> Commit {
+    let mut c = Commit::fixture(sha);
+    c.parents = vec![parent.to_string()];
+    c.files = files.iter().map(|(f, st)| FileEntry::fixture(f, st)).collect();
+    c
+  }
+
+  #[test]
+  fn net_change_spans_first_parent_to_last_touch() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path();
+    git(dir, &["init", "-q", "-b", "main"]);
+    std::fs::write(
+      dir.join("Cargo.toml"),
+      "[dependencies]\nanyhow = \"1.0\"\nold = \"0.1\"\n",
+    )
+    .unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "base"]);
+    let base = git(dir, &["rev-parse", "HEAD"]);
+    std::fs::write(
+      dir.join("Cargo.toml"),
+      "[dependencies]\nanyhow = \"1.0.80\"\nold = \"0.1\"\n",
+    )
+    .unwrap();
+    git(dir, &["commit", "-q", "-am", "bump"]);
+    let bump = git(dir, &["rev-parse", "HEAD"]);
+    std::fs::write(
+      dir.join("Cargo.toml"),
+      "[dependencies]\nanyhow = \"1.0.80\"\nserde = \"1\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("README.md"), "docs\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "swap"]);
+    let swap = git(dir, &["rev-parse", "HEAD"]);
+
+    let commits = vec![
+      commit(&bump, &base, &[("Cargo.toml", "M")]),
+      commit(&swap, &bump, &[("Cargo.toml", "M"), ("README.md", "A")]),
+    ];
+    let changes = summarize_dependency_changes(&dir.to_string_lossy(), &commits);
+
+    assert_eq!(changes.len(), 1);
+    let c = &changes[0];
+    assert_eq!(c.file, "Cargo.toml");
+    assert_eq!(c.commits, vec![bump[..12].to_string(), swap[..12].to_string()]);
+    assert_eq!(c.added[0].name, "dependencies:serde");
+    assert_eq!(c.removed[0].name, "dependencies:old");
+    assert_eq!(
+      (c.updated[0].from.as_str(), c.updated[0].to.as_str()),
+      ("1.0", "1.0.80")
+    );
+  }
+
+  #[test]
+  fn cargo_toml_sections_inline_tables_and_dotted_tables() {
+    let deps = parse_cargo_toml(
+      r#"
+[package]
+name = "x"
+version = "0.1.0"
+
+[dependencies]
+anyhow = "1.0"
+serde = { version = "1.0", features = ["derive"] }
+local = { path = "../local" }
+
+[dev-dependencies.tempfile]
+version = "3.10"
+"#,
+    );
+
+    assert_eq!(deps["dependencies:anyhow"], "1.0");
+    assert_eq!(deps["dependencies:serde"], "1.0");
+    assert_eq!(deps["dependencies:local"], "*");
+    assert_eq!(deps["dev-dependencies:tempfile"], "3.10");
+    assert!(!deps.keys().any(|k| k.ends_with(":version") || k.ends_with(":name")));
+  }
+
+  #[test]
+  fn lockfiles_go_mod_and_requirements() {
+    let lock = parse_cargo_lock(
+      "[[package]]\nname = \"syn\"\nversion = \"1.0.109\"\n\n[[package]]\nname = \"syn\"\nversion = \"2.0.87\"\n",
+    );
+    assert_eq!(lock["syn"], "1.0.109, 2.0.87");
+
+    let go =
+      parse_go_mod("module x\n\nrequire golang.org/x/net v0.1.0\nrequire (\n\tgithub.com/a/b v1.2.3 // indirect\n)\n");
+    assert_eq!(go["golang.org/x/net"], "v0.1.0");
+    assert_eq!(go["github.com/a/b"], "v1.2.3");
+
+    let req = parse_requirements("# pinned\nRequests==2.31.0\nflask>=2 ; python_version>'3'\n-r base.txt\nrich\n");
+    assert_eq!(req["requests"], "==2.31.0");
+    assert_eq!(req["flask"], ">=2");
+    assert_eq!(req["rich"], "*");
+  }
+
+  #[test]
+  fn diff_reports_added_removed_and_updated() {
+    let before = parse_package_json(r#"{"dependencies":{"left-pad":"1.0.0","react":"^17.0.0"}}"#);
+    let after = parse_package_json(r#"{"dependencies":{"react":"^18.2.0"},"devDependencies":{"jest":"^29"}}"#);
+
+    let d = diff_deps("web/package.json", &before, &after, vec!["abcdef123456".into()]);
+
+    assert_eq!(d.ecosystem, "npm");
+    assert_eq!(d.commits, vec!["abcdef123456"]);
+    assert_eq!(d.added[0].name, "devDependencies:jest");
+    assert_eq!(d.removed[0].name, "dependencies:left-pad");
+    assert_eq!(
+      d.updated,
+      vec![DependencyUpdate {
+        name: "dependencies:react".into(),
+        from: "^17.0.0".into(),
+        to: "^18.2.0".into(),
+      }]
+    );
+  }
+
+  #[test]
+  fn only_known_manifest_names_are_tracked() {
+    assert_eq!(ecosystem_for("crates/a/Cargo.toml"), Some("cargo"));
+    assert_eq!(ecosystem_for("requirements-dev.txt"), Some("pip"));
+    assert_eq!(ecosystem_for("docs/package.json.md"), None);
+  }
+}
@@ -0,0 +1,334 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --digest daily: compact report of the previous local calendar day with PR status, unmerged branches, and Markdown
+// role: entrypoint/digest
+// inputs: Cli (window flags must be absent), EffectiveConfig, the resolved day range, effective now
+// outputs: DailyDigest JSON (or just its `markdown` with --format markdown) on stdout or written to --out (file)
+// side_effects: git reads (commits of the day, local branches); GitHub API calls for linked PRs (best-effort)
+// invariants:
+// - the day is [local midnight yesterday, local midnight today) relative to the effective now (--now-override honored)
+// - commits and changeset come from the same single-range pipeline as a report (run_simple)
+// - PRs are those linked to the day's commits; opened/merged compare created_at/merged_at against the day
+// - unmerged branches are local branches other than the current one with commits in the day not reachable from HEAD
+//   (with --unmerged-base: every branch but the base, compared against the base)
+// - Markdown omits empty sections; PRs and branches are listed in number/name order
+// errors: window flags, --since-merge-base, or --split-apart combined with --digest; git/report errors bubble up
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Duration, Local, NaiveDateTime};
+
+use crate::cli::{Cli, EffectiveConfig};
+use crate::gitio;
+use crate::model::{
+  DailyDigest, DigestBranch, DigestCommit, DigestPullRequest, DigestPullRequests, GithubPullRequest, SimpleReport,
+};
+use crate::range_windows::LabeledRange;
+use crate::util;
+
+pub const DIGEST_KINDS: [&str; 1] = ["daily"];
+
+const NAIVE_FMT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Point the window at the previous local calendar day and turn on PR lookups.
+pub fn digest_window(cli: &mut Cli) -> Result<()> {
+  if !cli.month.is_empty()
+    || cli.for_str.is_some()
+    || cli.since.is_some()
+    || cli.until.is_some()
+    || cli.since_merge_base.is_some()
+    || cli.ranges_json.is_some()
+  {
+    bail!(
+      "--digest picks its own window (yesterday); drop --month | --for | --since/--until | --since-merge-base | --ranges-json"
+    );
+  }
+  if cli.split_apart {
+    bail!("--digest produces one compact JSON document; it cannot be combined with --split-apart");
+  }
+
+  let now = util::effective_now(crate::range_windows::parse_now(cli.now_override.as_deref()));
+  let today = now.date_naive().and_hms_opt(0, 0, 0).expect("midnight");
+
+  cli.since = Some((today - Duration::days(1)).format(NAIVE_FMT).to_string());
+  cli.until = Some(today.format(NAIVE_FMT).to_string());
+  cli.github_prs = true;
+
+  Ok(())
+}
+
+/// Generate the digest for `range` and print it (or write it to `--out`).
+pub fn run(cfg: &EffectiveConfig, range: &LabeledRange, now_opt: Option<DateTime<Local>>) -> Result<()> {
+  let date = range.since.get(..10).unwrap_or(&range.since).to_string();
+  let mut params = crate::render::build_report_params(cfg, range.since.clone(), range.until.clone());
+  params.label = Some(date.clone());
+  params.now_local = now_opt;
+
+  let report = crate::render::run_simple(&params)?;
+  crate::exit_summary::record_range(report.summary.count);
+  let branches = unmerged_branches_touched(cfg, &range.since, &range.until)?;
+  let mut digest = build_digest(report, branches, &date);
+  digest.markdown = render_markdown(&digest);
+  crate::link_check::check(
+    &digest.markdown,
+    Path::new(&cfg.out).parent().filter(|_| cfg.out != "-"),
+    if cfg.out == "-" { "digest" } else { &cfg.out },
+  );
+
+  let markdown = cfg.format == crate::render_markdown::ReportFormat::Markdown;
+  let bytes = if markdown {
+    digest.markdown.into_bytes()
+  } else {
+    serde_json::to_vec_pretty(&digest)?
+  };
+
+  crate::exit_summary::record_output(&cfg.out);
+
+  if cfg.out != "-" {
+    util::write_output(Path::new(&cfg.out), &bytes)?;
+  } else if markdown {
+    print!("{}", String::from_utf8_lossy(&bytes));
+  } else {
+    println!("{}", String::from_utf8_lossy(&bytes));
+  }
+
+  Ok(())
+}
+
+fn unmerged_branches_touched(cfg: &EffectiveConfig, since: &str, until: &str) -> Result<Vec<DigestBranch>> {
+  let base = cfg.unmerged_base.as_deref().unwrap_or("HEAD");
+  let mut touched = Vec::new();
+
+  if let Some(b) = &cfg.unmerged_base {
+    gitio::resolve_commit(&cfg.repo, b).context("--unmerged-base")?;
+  }
+
+  for name in gitio::unmerged_candidates(&cfg.repo, cfg.unmerged_base.as_deref())? {
+    let commits =
+      gitio::unmerged_commits_in_range(&cfg.repo, &name, base, since, until, cfg.include_merges, &cfg.paths)?.len();
+
+    if commits > 0 {
+      touched.push(DigestBranch { name, commits });
+    }
+  }
+
+  touched.sort_by(|a, b| a.name.cmp(&b.name));
+
+  Ok(touched)
+}
+
+fn local_epoch(naive: &str) -> Option<i64> {
+  NaiveDateTime::parse_from_str(naive, NAIVE_FMT)
+    .ok()?
+    .and_local_timezone(Local)
+    .earliest()
+    .map(|d| d.timestamp())
+}
+
+fn in_window(ts: Option<&str>, start: Option<i64>, end: Option<i64>) -> bool {
+  let Some(t) = ts
+    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+    .map(|d| d.timestamp())
+  else {
+    return false;
+  };
+
+  start.is_none_or(|s| t >= s) && end.is_none_or(|e| t < e)
+}
+
+fn digest_pr(pr: &GithubPullRequest) -> DigestPullRequest {
+  DigestPullRequest {
+    number: pr.number,
+    title: pr.title.clone(),
+    state: pr.state.clone(),
+    html_url: pr.html_url.clone(),
+  }
+}
+
+fn build_digest(report: SimpleReport, unmerged_branches: Vec<DigestBranch>, date: &str) -> DailyDigest {
+  let summary = report.summary;
+  let (start, end) = (local_epoch(&summary.range.start), local_epoch(&summary.range.end));
+
+  let mut prs: BTreeMap<i64, &GithubPullRequest> = BTreeMap::new();
+
+  for c in &report.commits {
+    for pr in c.github.iter().flat_map(|g| &g.pull_requests) {
+      prs.entry(pr.number).or_insert(pr);
+    }
+  }
+
+  let mut pull_requests = DigestPullRequests::default();
+
+  for pr in prs.values() {
+    if in_window(pr.created_at.as_deref(), start, end) {
+      pull_requests.opened.push(digest_pr(pr));
+    }
+    if in_window(pr.merged_at.as_deref(), start, end) {
+      pull_requests.merged.push(digest_pr(pr));
+    }
+    if pr.review_count.unwrap_or(0) > 0 || pr.approver.is_some() {
+      pull_requests.reviewed.push(digest_pr(pr));
+    }
+    if pr.state == "open" {
+      pull_requests.open.push(digest_pr(pr));
+    }
+  }
+
+  DailyDigest {
+    kind: "daily".into(),
+    repo: summary.repo,
+    date: date.to_string(),
+    range: summary.range,
+    commits: report
+      .commits
+      .iter()
+      .map(|c| DigestCommit {
+        short_sha: c.short_sha.clone(),
+        subject: c.subject.clone(),
+        author: c.author.canonical_name().to_string(),
+      })
+      .collect(),
+    authors: report.authors,
+    changeset: summary.changes,
+    pull_requests,
+    unmerged_branches,
+    markdown: String::new(),
+  }
+}
+
+fn render_markdown(d: &DailyDigest) -> String {
+  let repo_name = Path::new(&d.repo)
+    .file_name()
+    .map(|n| n.to_string_lossy().to_string())
+    .unwrap_or_else(|| d.repo.clone());
+  let mut md = format!("## Daily digest: {} ({})\n\n", repo_name, d.date);
+
+  if d.commits.is_empty() {
+    md.push_str("No commits.\n");
+  } else {
+    let _ = writeln!(
+      md,
+      "**{} commit(s)** by {} author(s), +{} / -{} across {} file(s)\n",
+      d.commits.len(),
+      d.authors.len(),
+      d.changeset.additions,
+      d.changeset.deletions,
+      d.changeset.files_touched
+    );
+
+    for c in &d.commits {
+      let _ = writeln!(md, "- `{}` {} ({})", c.short_sha, c.subject, c.author);
+    }
+  }
+
+  let sections = [
+    ("Opened", &d.pull_requests.opened),
+    ("Merged", &d.pull_requests.merged),
+    ("Reviewed", &d.pull_requests.reviewed),
+    ("Still open", &d.pull_requests.open),
+  ];
+
+  if sections.iter().any(|(_, prs)| !prs.is_empty()) {
+    md.push_str("\n### Pull requests\n\n");
+
+    for (heading, prs) in sections.iter().filter(|(_, prs)| !prs.is_empty()) {
+      let links: Vec<String> = prs
+        .iter()
+        .map(|p| format!("[#{}]({}) {}", p.number, p.html_url, p.title))
+        .collect();
+      let _ = writeln!(md, "- {}: {}", heading, links.join(", "));
+    }
+  }
+
+  if !d.unmerged_branches.is_empty() {
+    md.push_str("\n### Unmerged branches touched\n\n");
+
+    for b in &d.unmerged_branches {
+      let _ = writeln!(md, "- `{}` ({} commit(s))", b.name, b.commits);
+    }
+  }
+
+  md
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::model::Commit;
+  use serde_json::json;
+
+  fn commit(sha: &str, subject: &str, prs: serde_json::Value) -> serde_json::Value {
+    let mut c = Commit::fixture(sha).with_author("Ann", "ann@x").with_pull_requests(prs);
+    c.subject = subject.into();
+
+    serde_json::to_value(c).unwrap()
+  }
+
+  fn pr(number: i64, state: &str, created: &str, merged: Option<&str>, reviews: i64) -> serde_json::Value {
+    json!({
+      "number": number, "title": format!("PR {}", number), "state": state,
+      "html_url": format!("https://github.com/o/r/pull/{}", number),
+      "created_at": created, "merged_at": merged, "review_count": reviews
+    })
+  }
+
+  fn report() -> SimpleReport {
+    let day_start = local_epoch("2025-08-12T00:00:00").unwrap();
+    let at = |hours: i64| {
+      DateTime::from_timestamp(day_start + hours * 3600, 0)
+        .unwrap()
+        .to_rfc3339()
+    };
+
+    serde_json::from_value(json!({
+      "summary": {
+        "repo": "/src/widgets", "count": 2,
+        "range": { "label": "2025-08-12", "start": "2025-08-12T00:00:00", "end": "2025-08-13T00:00:00" },
+        "report_options": { "include_merges": false, "include_patch": false, "include_unmerged": false, "tz": "utc" },
+        "changeset": { "additions": 12, "deletions": 3, "files_touched": 2 }
+      },
+      "authors": { "Ann <ann@x>": 2 },
+      "commits": [
+        commit("aaa1111", "Add widget", json!([pr(7, "open", &at(9), None, 0)])),
+        commit("bbb2222", "Fix widget", json!([
+          pr(5, "closed", &at(-48), Some(&at(15)), 2),
+          pr(7, "open", &at(9), None, 0)
+        ]))
+      ]
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn pull_requests_are_classified_against_the_day() {
+    let d = build_digest(report(), vec![], "2025-08-12");
+    let numbers = |prs: &[DigestPullRequest]| prs.iter().map(|p| p.number).collect::<Vec<_>>();
+
+    assert_eq!(numbers(&d.pull_requests.opened), vec![7]);
+    assert_eq!(numbers(&d.pull_requests.merged), vec![5]);
+    assert_eq!(numbers(&d.pull_requests.reviewed), vec![5]);
+    assert_eq!(numbers(&d.pull_requests.open), vec![7]);
+    assert_eq!(d.commits[1].subject, "Fix widget");
+  }
+
+  #[test]
+  fn markdown_lists_commits_prs_and_branches() {
+    let branches = vec![DigestBranch {
+      name: "feature/x".into(),
+      commits: 3,
+    }];
+    let d = build_digest(report(), branches, "2025-08-12");
+    let md = render_markdown(&d);
+
+    assert!(md.starts_with("## Daily digest: widgets (2025-08-12)\n"));
+    assert!(md.contains("**2 commit(s)** by 1 author(s), +12 / -3 across 2 file(s)"));
+    assert!(md.contains("- `aaa1111` Add widget (Ann)"));
+    assert!(md.contains("- Merged: [#5](https://github.com/o/r/pull/5) PR 5"));
+    assert!(md.contains("- `feature/x` (3 commit(s))"));
+  }
+}
@@ -0,0 +1,372 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Environment diagnostics (`--doctor`): git, repo health, GitHub token/scopes, writable temp dir, timezone
+// role: diagnostics/entrypoint
+// inputs: Cli (repo path, tz) and the --github-repo override; process environment (GITHUB_TOKEN/GH_TOKEN, GITHUB_APP_*, TZ); optional `gh` CLI
+// outputs: Human-readable check list on stdout with actionable fixes
+// side_effects: Spawns git; one cheap GitHub API call (GET /rate_limit) when a token is found; writes/removes a probe file in the temp dir
+// invariants:
+// - every check yields one status line; failures and warnings add a `fix:` hint line
+// - the run errors (non-zero exit) only when at least one check fails; warnings never fail the run
+// errors: Returns an error summarizing the number of failed checks
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use anyhow::{Result, bail};
+use chrono_tz::Tz;
+
+use crate::cli::Cli;
+use crate::enrichment::github_api as ghapi;
+use crate::enrichment::github_app;
+use crate::gitio;
+use crate::util::{self, run_git};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+  Ok,
+  Warn,
+  Fail,
+}
+
+#[derive(Debug)]
+struct Check {
+  name: &'static str,
+  level: Level,
+  detail: String,
+  fix: Option<String>,
+}
+
+impl Check {
+  fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+    Self {
+      name,
+      level: Level::Ok,
+      detail: detail.into(),
+      fix: None,
+    }
+  }
+
+  fn warn(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+    Self {
+      name,
+      level: Level::Warn,
+      detail: detail.into(),
+      fix: Some(fix.into()),
+    }
+  }
+
+  fn fail(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+    Self {
+      name,
+      level: Level::Fail,
+      detail: detail.into(),
+      fix: Some(fix.into()),
+    }
+  }
+}
+
+/// Run all diagnostics, print them, and fail when any check failed.
+pub fn run(cli: &Cli) -> Result<()> {
+  let repo = util::canonicalize_lossy(&cli.repo);
+
+  let mut checks = vec![check_git()];
+  let repo_check = check_repo(&repo);
+  let repo_ok = repo_check.level == Level::Ok;
+  checks.push(repo_check);
+
+  if repo_ok {
+    checks.push(check_history_depth(&repo));
+    checks.push(check_partial_clone(&repo));
+    checks.extend(check_github(&repo));
+  }
+
+  checks.push(check_temp_dir());
+  checks.push(check_tz(&cli.tz));
+
+  print!("{}", render_checks(&checks));
+
+  let failures = checks.iter().filter(|c| c.level == Level::Fail).count();
+
+  if failures > 0 {
+    bail!("doctor found {} failing check(s)", failures);
+  }
+
+  Ok(())
+}
+
+fn render_checks(checks: &[Check]) -> String {
+  let mut out = String::new();
+
+  for c in checks {
+    let tag = match c.level {
+      Level::Ok => "ok",
+      Level::Warn => "warn",
+      Level::Fail => "FAIL",
+    };
+    out.push_str(&format!("[{}] {}: {}\n", tag, c.name, c.detail));
+
+    if let Some(fix) = &c.fix {
+      out.push_str(&format!("       fix: {}\n", fix));
+    }
+  }
+
+  out
+}
+
+fn check_git() -> Check {
+  match util::git_version() {
+    Some(v) if !util::git_at_least(2, 9) => Check::warn(
+      "git",
+      format!(
+        "git version {} predates 2.9; rename detection and strict ISO dates use fallbacks",
+        v
+      ),
+      "upgrade git to 2.9 or newer",
+    ),
+    Some(v) => Check::ok("git", format!("git version {}", v)),
+    None => Check::fail(
+      "git",
+      "git executable not found on PATH",
+      "install git and make sure `git --version` works",
+    ),
+  }
+}
+
+fn check_repo(repo: &str) -> Check {
+  match run_git(repo, &["rev-parse".into(), "--is-inside-work-tree".into()]) {
+    Ok(out) if out.trim() == "true" => Check::ok("repo", repo.to_string()),
+    _ => Check::fail(
+      "repo",
+      format!("{} is not a git work tree", repo),
+      "pass --repo <path to a git checkout>",
+    ),
+  }
+}
+
+fn check_history_depth(repo: &str) -> Check {
+  let shallow = run_git(repo, &["rev-parse".into(), "--is-shallow-repository".into()])
+    .map(|s| s.trim() == "true")
+    .unwrap_or(false);
+
+  if shallow {
+    Check::warn(
+      "history",
+      "shallow clone; commits before the graft point are missing from reports",
+      "run `git fetch --unshallow` (or clone without --depth)",
+    )
+  } else {
+    Check::ok("history", "full history available")
+  }
+}
+
+fn check_partial_clone(repo: &str) -> Check {
+  if gitio::is_partial_clone(repo) {
+    Check::warn(
+      "blobs",
+      "partial clone; per-file stats may trigger blob fetches or degrade",
+      "pass --no-blob-stats, or run `git fetch --refetch` for a full clone",
+    )
+  } else {
+    Check::ok("blobs", "blobs available locally")
+  }
+}
+
+fn check_github(repo: &str) -> Vec<Check> {
+  let Some((owner, name)) = ghapi::resolve_github_repo(repo) else {
+    let origin = Check::warn(
+      "github origin",
+      "remote.origin.url is not a GitHub remote and no --github-repo was given; --github-prs will be skipped",
+      "set origin to https://github.com/<owner>/<repo>.git or pass --github-repo <owner>/<repo> if PR enrichment is wanted",
+    );
+
+    return vec![origin];
+  };
+
+  let origin = Check::ok("github origin", format!("{}/{}", owner, name));
+  let mut checks = vec![origin];
+
+  if let Some(app) = app_check() {
+    checks.push(app);
+  }
+
+  let Some(token) = ghapi::get_github_token() else {
+    checks.push(Check::warn(
+      "github token",
+      "no token found; PR enrichment is silently skipped",
+      "export GITHUB_TOKEN=<token> or run `gh auth login`",
+    ));
+
+    return checks;
+  };
+
+  checks.push(token_check(ghapi::probe_token(&token)));
+  checks
+}
+
+/// GitHub App settings, when `GITHUB_APP_ID` is set; the token check below then probes the minted installation token.
+fn app_check() -> Option<Check> {
+  let check = match github_app::credentials_from_env()? {
+    Ok(c) => Check::ok(
+      "github app",
+      match c.installation_id {
+        Some(id) => format!("app {} (installation {})", c.app_id, id),
+        None => format!("app {} (installation discovered at runtime)", c.app_id),
+      },
+    ),
+    Err(e) => Check::fail(
+      "github app",
+      e,
+      "set GITHUB_APP_PRIVATE_KEY (PEM) or GITHUB_APP_PRIVATE_KEY_PATH, or unset GITHUB_APP_ID",
+    ),
+  };
+
+  Some(check)
+}
+
+fn token_check(probe: Option<ghapi::TokenProbe>) -> Check {
+  let Some(p) = probe else {
+    return Check::warn(
+      "github token",
+      "token found but api.github.com is unreachable",
+      "check network/proxy settings; enrichment will be skipped while offline",
+    );
+  };
+
+  if p.status == 401 {
+    return Check::fail(
+      "github token",
+      "token rejected (401 Unauthorized)",
+      "refresh GITHUB_TOKEN or run `gh auth refresh`",
+    );
+  }
+
+  let scopes = match &p.scopes {
+    Some(s) if s.is_empty() => "no classic scopes".to_string(),
+    Some(s) => format!("scopes: {}", s.join(", ")),
+    None => "fine-grained token".to_string(),
+  };
+  let remaining = p
+    .rate_remaining
+    .map(|r| format!("; {} requests remaining", r))
+    .unwrap_or_default();
+  let detail = format!("{}{}", scopes, remaining);
+
+  let private_access = p.scopes.as_ref().map(|s| s.iter().any(|x| x == "repo"));
+
+  match (p.rate_remaining, private_access) {
+    (Some(0), _) => Check::warn(
+      "github token",
+      detail,
+      "rate limit exhausted; wait for reset before using --github-prs",
+    ),
+    (_, Some(false)) => Check::warn(
+      "github token",
+      detail,
+      "add the `repo` scope if the repository is private",
+    ),
+    _ => Check::ok("github token", detail),
+  }
+}
+
+fn check_temp_dir() -> Check {
+  let dir = std::env::temp_dir();
+  let probe = dir.join(format!("gar-doctor-{}", std::process::id()));
+
+  match std::fs::write(&probe, b"ok") {
+    Ok(()) => {
+      let _ = std::fs::remove_file(&probe);
+
+      Check::ok("temp dir", dir.display().to_string())
+    }
+    Err(e) => Check::fail(
+      "temp dir",
+      format!("{} is not writable: {}", dir.display(), e),
+      "set TMPDIR to a writable directory or always pass --out",
+    ),
+  }
+}
+
+fn check_tz(tz: &str) -> Check {
+  if tz.eq_ignore_ascii_case("utc") {
+    return Check::ok("timezone", "utc");
+  }
+
+  if tz.eq_ignore_ascii_case("local") {
+    let offset = chrono::Local::now().format("%:z").to_string();
+    let env_tz = std::env::var("TZ").ok();
+    let detail = match env_tz {
+      Some(v) => format!("local (TZ={}, offset {})", v, offset),
+      None => format!("local (TZ unset, offset {})", offset),
+    };
+
+    return Check::ok("timezone", detail);
+  }
+
+  match tz.parse::<Tz>() {
+    Ok(_) => Check::ok("timezone", tz.to_string()),
+    Err(_) => Check::fail(
+      "timezone",
+      format!(
+        "unknown --tz value {:?}; timestamps would silently fall back to UTC",
+        tz
+      ),
+      "use local, utc, or an IANA zone such as America/Chicago",
+    ),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn tz_check_accepts_known_zones_and_rejects_unknown() {
+    assert_eq!(check_tz("utc").level, Level::Ok);
+    assert_eq!(check_tz("America/Chicago").level, Level::Ok);
+    assert_eq!(check_tz("Mars/Olympus").level, Level::Fail);
+  }
+
+  #[test]
+  fn repo_check_fails_outside_work_tree() {
+    let td = tempfile::TempDir::new().unwrap();
+    let c = check_repo(&td.path().to_string_lossy());
+
+    assert_eq!(c.level, Level::Fail);
+    assert!(c.fix.is_some());
+  }
+
+  #[test]
+  fn token_check_classifies_probe_results() {
+    let probe = |status, scopes: Option<Vec<&str>>, remaining| ghapi::TokenProbe {
+      status,
+      scopes: scopes.map(|v| v.into_iter().map(String::from).collect()),
+      rate_remaining: remaining,
+    };
+
+    assert_eq!(token_check(None).level, Level::Warn);
+    assert_eq!(token_check(Some(probe(401, None, None))).level, Level::Fail);
+    assert_eq!(
+      token_check(Some(probe(200, Some(vec!["repo"]), Some(4999)))).level,
+      Level::Ok
+    );
+    assert_eq!(
+      token_check(Some(probe(200, Some(vec!["gist"]), Some(10)))).level,
+      Level::Warn
+    );
+    assert_eq!(token_check(Some(probe(200, None, Some(0)))).level, Level::Warn);
+  }
+
+  #[test]
+  fn render_includes_fix_lines() {
+    let checks = vec![
+      Check::ok("git", "git version 2.45.0"),
+      Check::fail("repo", "/tmp/x is not a git work tree", "pass --repo"),
+    ];
+
+    let out = render_checks(&checks);
+
+    assert!(out.contains("[ok] git: git version 2.45.0"));
+    assert!(out.contains("[FAIL] repo:"));
+    assert!(out.contains("fix: pass --repo"));
+  }
+}
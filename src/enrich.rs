@@ -39,15 +39,18 @@ mod tests {
       sha: "deadbeef".into(),
       short_sha: "deadbee".into(),
       parents: vec![],
+      refs: None,
       author: crate::model::Person {
         name: "A".into(),
         email: "a@ex".into(),
         date: "".into(),
+        identity: None,
       },
       committer: crate::model::Person {
         name: "A".into(),
         email: "a@ex".into(),
         date: "".into(),
+        identity: None,
       },
       timestamps: crate::model::Timestamps {
         author: 0,
@@ -59,6 +62,7 @@ mod tests {
       subject: "s".into(),
       body: "".into(),
       files: vec![],
+      files_truncated: None,
       diffstat_text: "".into(),
       patch_references: crate::model::PatchReferences {
         embed: false,
@@ -75,6 +79,16 @@ mod tests {
       estimate_confidence: None,
       estimate_basis: None,
       github: None,
+      gitlab: None,
+      blob_stats_unavailable: None,
+      environments: None,
+      patch_allocation: None,
+      automated: None,
+      file_snapshots: None,
+      authored_by_committer: None,
+      issues: None,
+      merge_group: None,
+      release: None,
     }
   }
 
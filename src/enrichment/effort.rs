@@ -190,17 +190,15 @@ fn pr_params_from_env() -> PrEstimateParams {
   }
 }
 
-/// Return a language weight based on simple file extension heuristics.
+/// Return a language weight for `path`, by the language its extension maps to (see crate::languages).
 fn language_weight(path: &str) -> f64 {
-  let ext = path.rsplit('.').next().unwrap_or("").to_ascii_lowercase();
-
-  match ext.as_str() {
+  match crate::languages::language_for(path).unwrap_or_default() {
     // Higher cognitive load
-    "rs" | "ts" | "go" | "java" | "scala" => 1.25,
+    "Rust" | "TypeScript" | "Go" | "Java" | "Scala" => 1.25,
     // Moderate
-    "py" | "js" | "tsx" | "jsx" | "rb" | "kt" => 1.1,
+    "Python" | "JavaScript" | "TSX" | "JSX" | "Ruby" | "Kotlin" => 1.1,
     // Low
-    "md" | "json" | "yaml" | "yml" | "toml" => 0.8,
+    "Markdown" | "JSON" | "YAML" | "TOML" => 0.8,
     _ => 1.0,
   }
 }
@@ -491,15 +489,18 @@ mod tests {
       sha: "s".into(),
       short_sha: "s".into(),
       parents: vec![],
+      refs: None,
       author: crate::model::Person {
         name: "A".into(),
         email: "a@ex".into(),
         date: "".into(),
+        identity: None,
       },
       committer: crate::model::Person {
         name: "A".into(),
         email: "a@ex".into(),
         date: "".into(),
+        identity: None,
       },
       timestamps: crate::model::Timestamps {
         author: 0,
@@ -511,6 +512,7 @@ mod tests {
       subject: "s".into(),
       body: "".into(),
       files: vec![],
+      files_truncated: None,
       diffstat_text: "".into(),
       patch_references: crate::model::PatchReferences {
         embed: false,
@@ -527,6 +529,16 @@ mod tests {
       estimate_confidence: None,
       estimate_basis: None,
       github: None,
+      gitlab: None,
+      blob_stats_unavailable: None,
+      environments: None,
+      patch_allocation: None,
+      automated: None,
+      file_snapshots: None,
+      authored_by_committer: None,
+      issues: None,
+      merge_group: None,
+      release: None,
     };
     c.parents = (0..parents).map(|_| "p".into()).collect();
     c.files = files
@@ -537,6 +549,10 @@ mod tests {
         old_path: None,
         additions: Some(add),
         deletions: Some(del),
+        service: None,
+        language: None,
+        lfs: None,
+        lfs_size_delta: None,
       })
       .collect();
     c
@@ -601,11 +617,21 @@ mod tests {
       change_request_count: Some(1),
       time_to_first_review_seconds: None,
       time_to_merge_seconds: None,
+      review_responses: None,
+      rework_cycles: None,
+      rework_cycle_seconds: None,
       estimated_minutes: None,
       estimated_minutes_min: None,
       estimated_minutes_max: None,
       estimate_confidence: None,
       estimate_basis: None,
+      diff: None,
+      diff_clipped: None,
+      size_class: None,
+      review_depth: None,
+      merged_without_review: None,
+      merge_commit_sha: None,
+      files: None,
     };
     let e = estimate_pr_effort(&pr, &range);
     assert!(e.minutes > 0.0);
@@ -2,25 +2,32 @@
 // header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
 // purpose: Isolated GitHub API helpers used by enrichment (token discovery, REST calls)
 // role: enrichment/github-api
-// inputs: repo path for origin detection; env GITHUB_TOKEN; optional `gh` CLI for token fallback
+// inputs: repo path for origin detection; env GITHUB_TOKEN; GitHub App env (see github_app); optional `gh` CLI for token fallback
 // outputs: JSON values and typed commit snapshots for PRs
 // side_effects: Network calls to api.github.com; spawns `gh` subprocess when needed
 // invariants:
 // - Never panic; return None/empty on failures (best-effort enrichment)
-// - Token discovery prefers GITHUB_TOKEN, then `gh auth token`
+// - Token discovery prefers a GitHub App installation token (when GITHUB_APP_ID is set), then GITHUB_TOKEN, then `gh auth token`
 // - Origin parser only recognizes GitHub remotes (https or ssh)
-// errors: Swallowed; callers decide whether to surface warnings
+// - HTTP agent, retry, and the Link-header paginator (paginate_json) are shared with gitlab_api (pub(crate))
+// - a paginated list cut short (page cap or a later page failing) is returned with a warning and flagged partial (take_partial_list)
+// - --github-cache-dir wraps only the HTTP backend (below the per-run memory cache); mocks are never persisted
+// - Enrichment GETs spend the --github-max-requests budget and honor Retry-After / X-RateLimit-*; skips are counted, not silent
+// errors: Swallowed; callers decide whether to surface warnings (take_request_failure says why the latest request failed)
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
+use crate::enrichment::github_disk_cache::GithubDiskCache;
 use crate::ext::serde_json::JsonFetch;
-use crate::model::{GithubPullRequest, GithubUser, PullRequestCommit};
+use crate::model::{FileEntry, GithubPullRequest, GithubUser, PullRequestCommit, RepoPolicy, ReviewResponse};
 use crate::util::diff_seconds;
 use crate::util::run_git;
 use once_cell::sync::Lazy;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::sync::Mutex;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
 
 /// Parse `remote.origin.url` to extract (owner, repo) when hosted on GitHub.
 type OriginCache = Mutex<std::collections::HashMap<String, Option<(String, String)>>>;
@@ -64,8 +71,40 @@ pub fn parse_origin_github(repo: &str) -> Option<(String, String)> {
   res
 }
 
-/// Discover a GitHub token: env var first, then `gh auth token` if available.
+static REPO_OVERRIDE: once_cell::sync::OnceCell<(String, String)> = once_cell::sync::OnceCell::new();
+
+/// Parse an `owner/name` spec (as given to `--github-repo`).
+pub fn parse_repo_spec(spec: &str) -> Option<(String, String)> {
+  let (owner, name) = spec.trim().split_once('/')?;
+  let name = name.trim_end_matches(".git");
+
+  if owner.is_empty() || name.is_empty() || name.contains('/') {
+    return None;
+  }
+
+  Some((owner.to_string(), name.to_string()))
+}
+
+/// Pin the GitHub repository used for enrichment (e.g. when origin is a mirror). First call wins.
+pub fn set_github_repo_override(owner: String, name: String) {
+  let _ = REPO_OVERRIDE.set((owner, name));
+}
+
+/// The GitHub (owner, repo) for `repo`: the `--github-repo` override when set, else parsed from origin.
+pub fn resolve_github_repo(repo: &str) -> Option<(String, String)> {
+  if let Some(pinned) = REPO_OVERRIDE.get() {
+    return Some(pinned.clone());
+  }
+
+  parse_origin_github(repo)
+}
+
+/// Discover a GitHub token: a GitHub App installation token when configured, then env vars, then `gh auth token`.
 pub fn get_github_token() -> Option<String> {
+  if let Some(t) = crate::enrichment::github_app::installation_token() {
+    return Some(t);
+  }
+
   if let Ok(t) = std::env::var("GITHUB_TOKEN") {
     if !t.trim().is_empty() {
       return Some(t);
@@ -91,20 +130,541 @@ pub fn get_github_token() -> Option<String> {
   None
 }
 
+/// Timeout and retry policy for every GitHub HTTP call (`--http-timeout`, `--http-retries`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HttpConfig {
+  /// Whole-request timeout in seconds (0 = none)
+  pub timeout_secs: u64,
+  /// Extra attempts after a transport error, 429, or 5xx response
+  pub retries: u32,
+  /// Pages (100 items each) followed per list endpoint before the rest is dropped with a warning
+  pub max_pages: usize,
+  /// GitHub enrichment requests allowed per process (`--github-max-requests`); None = unlimited
+  pub max_requests: Option<u64>,
+}
+
+pub const DEFAULT_HTTP_TIMEOUT_SECS: u64 = 30;
+pub const DEFAULT_HTTP_RETRIES: u32 = 2;
+pub const DEFAULT_HTTP_MAX_PAGES: usize = 10;
+
+impl Default for HttpConfig {
+  fn default() -> Self {
+    Self {
+      timeout_secs: DEFAULT_HTTP_TIMEOUT_SECS,
+      retries: DEFAULT_HTTP_RETRIES,
+      max_pages: DEFAULT_HTTP_MAX_PAGES,
+      max_requests: None,
+    }
+  }
+}
+
+static HTTP_CONFIG: once_cell::sync::OnceCell<HttpConfig> = once_cell::sync::OnceCell::new();
+
+static DISK_CACHE: once_cell::sync::OnceCell<(std::path::PathBuf, u64)> = once_cell::sync::OnceCell::new();
+
+/// Persist HTTP responses under `dir` for `ttl_secs` (`--github-cache-dir`). First call wins.
+pub fn set_disk_cache(dir: std::path::PathBuf, ttl_secs: u64) {
+  let _ = DISK_CACHE.set((dir, ttl_secs));
+}
+
+/// Install the process-wide HTTP timeout/retry policy (first call wins).
+pub fn set_http_config(cfg: HttpConfig) {
+  let _ = HTTP_CONFIG.set(cfg);
+}
+
+/// The installed HTTP policy (defaults until `set_http_config` runs).
+pub fn http_config() -> HttpConfig {
+  HTTP_CONFIG.get().copied().unwrap_or_default()
+}
+
+/// A ureq agent honoring the configured timeout.
+pub(crate) fn http_agent(status_as_error: bool) -> ureq::Agent {
+  let timeout = match http_config().timeout_secs {
+    0 => None,
+    secs => Some(Duration::from_secs(secs)),
+  };
+
+  ureq::Agent::config_builder()
+    .timeout_global(timeout)
+    .http_status_as_error(status_as_error)
+    .build()
+    .into()
+}
+
+pub(crate) type HttpResult = Result<ureq::http::Response<ureq::Body>, ureq::Error>;
+
+fn retryable_status(status: u16) -> bool {
+  status == 429 || status >= 500
+}
+
+/// Transport failures and throttling/server errors are worth retrying; other 4xx are final.
+fn is_retryable(res: &HttpResult) -> bool {
+  match res {
+    Ok(r) => retryable_status(r.status().as_u16()),
+    Err(ureq::Error::StatusCode(code)) => retryable_status(*code),
+    Err(ureq::Error::Io(_) | ureq::Error::Timeout(_) | ureq::Error::ConnectionFailed | ureq::Error::HostNotFound) => {
+      true
+    }
+    Err(_) => false,
+  }
+}
+
+/// Exponential backoff (250ms doubling, capped at 8s) with jitter in [delay/2, delay].
+fn backoff_delay(attempt: u32) -> Duration {
+  let base_ms = (250u64 << attempt.min(5)).min(8_000);
+  let nanos = std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|d| d.subsec_nanos() as u64)
+    .unwrap_or(0);
+  let jitter = nanos % (base_ms / 2 + 1);
+
+  Duration::from_millis(base_ms / 2 + jitter)
+}
+
+/// Run `call` up to `1 + retries` times, sleeping with jittered backoff between retryable failures.
+pub(crate) fn with_retries(retries: u32, mut call: impl FnMut() -> HttpResult) -> HttpResult {
+  let mut attempt = 0;
+
+  loop {
+    let res = call();
+
+    if attempt >= retries || !is_retryable(&res) {
+      return res;
+    }
+
+    std::thread::sleep(backoff_delay(attempt));
+    attempt += 1;
+  }
+}
+
+/// Waits longer than this are not slept through: the request is skipped and later calls short-circuit until reset.
+const MAX_RATE_LIMIT_WAIT_SECS: u64 = 60;
+
+static REQUESTS_SENT: AtomicU64 = AtomicU64::new(0);
+static SKIPPED_BUDGET: AtomicU64 = AtomicU64::new(0);
+static SKIPPED_RATE_LIMIT: AtomicU64 = AtomicU64::new(0);
+/// Unix time until which the primary rate limit is known to be exhausted (0 = not limited)
+static RATE_LIMITED_UNTIL: AtomicI64 = AtomicI64::new(0);
+
+thread_local! {
+  /// Why this thread's latest GitHub request failed outright (error status or transport error), until taken.
+  static LAST_FAILURE: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
+  /// Whether a paginated list fetched on this thread came back incomplete, until taken.
+  static LAST_PARTIAL: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Take and clear this thread's latest request failure; bracket a lookup with it to tell "nothing found" from "failed".
+pub fn take_request_failure() -> Option<String> {
+  LAST_FAILURE.with(|f| f.borrow_mut().take())
+}
+
+/// Take and clear whether a list fetched on this thread since the last call stopped before its last page; bracket a
+/// lookup with it to tell a complete list from a truncated one.
+pub fn take_partial_list() -> bool {
+  LAST_PARTIAL.with(|p| p.replace(false))
+}
+
+/// Flag the list being fetched on this thread as incomplete (see `take_partial_list`).
+pub(crate) fn flag_partial_list() {
+  LAST_PARTIAL.with(|p| p.set(true));
+}
+
+/// Process-wide counters for GitHub enrichment requests; diff two snapshots to attribute them to one range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RequestStats {
+  pub sent: u64,
+  pub skipped_budget: u64,
+  pub skipped_rate_limit: u64,
+}
+
+impl RequestStats {
+  /// Counts accumulated since `earlier`.
+  pub fn since(&self, earlier: &RequestStats) -> RequestStats {
+    RequestStats {
+      sent: self.sent.saturating_sub(earlier.sent),
+      skipped_budget: self.skipped_budget.saturating_sub(earlier.skipped_budget),
+      skipped_rate_limit: self.skipped_rate_limit.saturating_sub(earlier.skipped_rate_limit),
+    }
+  }
+
+  /// Human-readable warnings for skipped lookups (empty when nothing was skipped).
+  pub fn warnings(&self, max_requests: Option<u64>) -> Vec<String> {
+    let mut out = Vec::new();
+
+    if self.skipped_budget > 0 {
+      out.push(format!(
+        "{} GitHub request(s) skipped after the --github-max-requests budget of {} was spent; enrichment is incomplete",
+        self.skipped_budget,
+        max_requests.unwrap_or(0)
+      ));
+    }
+
+    if self.skipped_rate_limit > 0 {
+      out.push(format!(
+        "{} GitHub request(s) skipped because the API rate limit was exhausted; enrichment is incomplete",
+        self.skipped_rate_limit
+      ));
+    }
+
+    out
+  }
+}
+
+/// Snapshot of the GitHub request counters.
+pub fn request_stats() -> RequestStats {
+  RequestStats {
+    sent: REQUESTS_SENT.load(Ordering::Relaxed),
+    skipped_budget: SKIPPED_BUDGET.load(Ordering::Relaxed),
+    skipped_rate_limit: SKIPPED_RATE_LIMIT.load(Ordering::Relaxed),
+  }
+}
+
+/// Reserve one request against `--github-max-requests`; false (and counted as skipped) once the budget is spent.
+fn take_request_budget() -> bool {
+  let max = http_config().max_requests;
+  let sent = REQUESTS_SENT.fetch_add(1, Ordering::Relaxed);
+
+  if max.is_some_and(|m| sent >= m) {
+    REQUESTS_SENT.fetch_sub(1, Ordering::Relaxed);
+    SKIPPED_BUDGET.fetch_add(1, Ordering::Relaxed);
+    return false;
+  }
+
+  true
+}
+
+/// Seconds to wait before retrying a rate-limited response, from `Retry-After` or `X-RateLimit-Reset`.
+/// None when the response is not a rate-limit rejection (403/429 without exhausted quota is a permission error).
+fn rate_limit_wait(
+  status: u16,
+  retry_after: Option<&str>,
+  remaining: Option<&str>,
+  reset: Option<&str>,
+  now: i64,
+) -> Option<u64> {
+  if status != 403 && status != 429 {
+    return None;
+  }
+
+  if let Some(secs) = retry_after.and_then(|v| v.trim().parse::<u64>().ok()) {
+    return Some(secs);
+  }
+
+  if remaining.map(str::trim) == Some("0") {
+    let reset = reset.and_then(|v| v.trim().parse::<i64>().ok())?;
+    return Some(reset.saturating_sub(now).max(0) as u64 + 1);
+  }
+
+  (status == 429).then_some(0)
+}
+
+/// GET a GitHub URL within the request budget, sleeping through short rate-limit windows.
+/// Transport errors, 5xx, and rate limits are retried up to `--http-retries` times; other errors return None.
+fn github_get(url: &str, token: &str, accept: &str) -> Option<ureq::http::Response<ureq::Body>> {
+  let now = || chrono::Utc::now().timestamp();
+
+  if RATE_LIMITED_UNTIL.load(Ordering::Relaxed) > now() {
+    SKIPPED_RATE_LIMIT.fetch_add(1, Ordering::Relaxed);
+    tracing::debug!(target: "github", url, reason = "rate limit", "request skipped");
+    return None;
+  }
+
+  let agent = http_agent(false);
+  let retries = http_config().retries;
+  let mut attempt = 0;
+
+  loop {
+    if !take_request_budget() {
+      tracing::debug!(target: "github", url, reason = "--github-max-requests", "request skipped");
+      return None;
+    }
+
+    let res = agent
+      .get(url)
+      .header("Accept", accept)
+      .header("User-Agent", "git-activity-report")
+      .header("Authorization", &format!("Bearer {}", token))
+      .call();
+
+    let wait = res.as_ref().ok().and_then(|r| {
+      let header = |k: &str| r.headers().get(k).and_then(|v| v.to_str().ok());
+      rate_limit_wait(
+        r.status().as_u16(),
+        header("retry-after"),
+        header("x-ratelimit-remaining"),
+        header("x-ratelimit-reset"),
+        now(),
+      )
+    });
+
+    match (res, wait) {
+      (Ok(r), None) if r.status().is_success() => return Some(r),
+      (_, Some(secs)) if secs > MAX_RATE_LIMIT_WAIT_SECS || attempt >= retries => {
+        RATE_LIMITED_UNTIL.store(now() + secs as i64, Ordering::Relaxed);
+        SKIPPED_RATE_LIMIT.fetch_add(1, Ordering::Relaxed);
+        tracing::debug!(target: "github", url, reason = "rate limit", wait_secs = secs, "request skipped");
+        return None;
+      }
+      (_, Some(secs)) => std::thread::sleep(Duration::from_secs(secs)),
+      (res, None) if attempt < retries && is_retryable(&res) => std::thread::sleep(backoff_delay(attempt)),
+      (res, None) => {
+        let reason = match res {
+          Ok(r) => format!("GitHub API returned HTTP {}", r.status().as_u16()),
+          Err(e) => format!("GitHub API request failed: {}", e),
+        };
+        LAST_FAILURE.with(|f| *f.borrow_mut() = Some(reason));
+
+        return None;
+      }
+    }
+
+    attempt += 1;
+  }
+}
+
 fn get_json(url: &str, token: &str) -> Option<serde_json::Value> {
-  let agent: ureq::Agent = ureq::Agent::config_builder().build().into();
+  let mut r = github_get(url, token, "application/vnd.github+json")?;
+  r.body_mut().read_json::<serde_json::Value>().ok()
+}
 
-  let resp = agent
-    .get(url)
-    .header("Accept", "application/vnd.github+json")
-    .header("User-Agent", "git-activity-report")
-    .header("Authorization", &format!("Bearer {}", token))
-    .call();
+/// Host every GitHub REST request goes to.
+const API_HOST: &str = "api.github.com";
 
-  match resp {
-    Ok(mut r) => r.body_mut().read_json::<serde_json::Value>().ok(),
-    Err(_) => None,
+/// GitHub caps PR commit listings at 250 (3 pages of 100), whatever --http-max-pages allows.
+const PR_COMMITS_MAX_PAGES: usize = 3;
+/// pulls/{n}/files stops at 3000 files (30 pages of 100).
+const PR_FILES_MAX_PAGES: usize = 30;
+
+/// Extract the `rel="next"` target from an RFC 8288 `Link` header.
+fn parse_next_link(link: &str) -> Option<String> {
+  link.split(',').find_map(|part| {
+    let (target, params) = part.split_once(';')?;
+    let is_next = params.split(';').any(|p| p.trim() == "rel=\"next\"");
+
+    is_next.then(|| target.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+  })
+}
+
+/// Warn that a list endpoint had more pages than `max_pages` allowed, so its items are incomplete.
+fn warn_page_cap(target: &str, url: &str, max_pages: usize) {
+  crate::util::log(
+    crate::util::LogLevel::Warn,
+    target,
+    &format!(
+      "{} has more than {} page(s); later items are dropped (raise --http-max-pages)",
+      url, max_pages
+    ),
+  );
+}
+
+/// Warn that a list endpoint failed after `pages` good pages, so its items are incomplete.
+fn warn_page_failure(target: &str, url: &str, pages: usize) {
+  crate::util::log(
+    crate::util::LogLevel::Warn,
+    target,
+    &format!(
+      "{} failed after {} page(s); later items are dropped (rerun to retry)",
+      url, pages
+    ),
+  );
+}
+
+/// GET a JSON array endpoint following `Link: rel="next"` up to `max_pages`, concatenating items.
+/// `get_page` makes the backend's request for one page URL (`target` labels the warnings).
+/// Returns None when the first page fails. A later page failing, or the page cap cutting the list short, keeps what
+/// was collected so far, logs a warning, and flags the list partial for `take_partial_list`.
+pub(crate) fn paginate_json(
+  target: &str,
+  url: &str,
+  max_pages: usize,
+  mut get_page: impl FnMut(&str) -> Option<ureq::http::Response<ureq::Body>>,
+) -> Option<serde_json::Value> {
+  let sep = if url.contains('?') { '&' } else { '?' };
+  let mut next = Some(format!("{}{}per_page=100", url, sep));
+  let mut items: Vec<serde_json::Value> = Vec::new();
+  let mut pages = 0;
+
+  while let Some(page_url) = next.take() {
+    if pages >= max_pages {
+      warn_page_cap(target, url, max_pages);
+      flag_partial_list();
+      break;
+    }
+
+    let Some(mut r) = get_page(&page_url) else {
+      if pages == 0 {
+        return None;
+      }
+      warn_page_failure(target, url, pages);
+      flag_partial_list();
+      break;
+    };
+
+    next = r
+      .headers()
+      .get("link")
+      .and_then(|v| v.to_str().ok())
+      .and_then(parse_next_link);
+
+    match r.body_mut().read_json::<serde_json::Value>() {
+      Ok(serde_json::Value::Array(arr)) => items.extend(arr),
+      Ok(other) if pages == 0 => return Some(other),
+      _ => {
+        warn_page_failure(target, url, pages);
+        flag_partial_list();
+        break;
+      }
+    }
+
+    pages += 1;
+  }
+
+  Some(serde_json::Value::Array(items))
+}
+
+fn get_json_paginated(url: &str, token: &str, max_pages: usize) -> Option<serde_json::Value> {
+  paginate_json("github", url, max_pages, |page_url| {
+    github_get(page_url, token, "application/vnd.github+json")
+  })
+}
+
+fn get_text(url: &str, token: &str, accept: &str) -> Option<String> {
+  let mut r = github_get(url, token, accept)?;
+  r.body_mut().read_to_string().ok()
+}
+
+/// Outcome of a cheap authenticated probe against `GET /rate_limit` (does not consume quota).
+#[derive(Debug, Clone)]
+pub struct TokenProbe {
+  pub status: u16,
+  /// Classic token scopes from `X-OAuth-Scopes`; None for fine-grained tokens (header absent)
+  pub scopes: Option<Vec<String>>,
+  pub rate_remaining: Option<i64>,
+}
+
+/// Probe `token` for validity, scopes, and remaining rate limit; None when the API is unreachable.
+pub fn probe_token(token: &str) -> Option<TokenProbe> {
+  let agent = http_agent(false);
+
+  let resp = with_retries(http_config().retries, || {
+    agent
+      .get("https://api.github.com/rate_limit")
+      .header("Accept", "application/vnd.github+json")
+      .header("User-Agent", "git-activity-report")
+      .header("Authorization", &format!("Bearer {}", token))
+      .call()
+  })
+  .ok()?;
+
+  let header = |k: &str| {
+    resp
+      .headers()
+      .get(k)
+      .and_then(|v| v.to_str().ok())
+      .map(|s| s.to_string())
+  };
+  let scopes = header("x-oauth-scopes").map(|s| {
+    s.split(',')
+      .map(|x| x.trim().to_string())
+      .filter(|x| !x.is_empty())
+      .collect()
+  });
+  let rate_remaining = header("x-ratelimit-remaining").and_then(|s| s.parse::<i64>().ok());
+
+  let probe = TokenProbe {
+    status: resp.status().as_u16(),
+    scopes,
+    rate_remaining,
+  };
+
+  Some(probe)
+}
+
+/// HTTP status of an authenticated GET (no body parsing); None when unreachable.
+fn probe_status(url: &str, token: &str) -> Option<u16> {
+  let agent = http_agent(false);
+
+  let resp = with_retries(http_config().retries, || {
+    agent
+      .get(url)
+      .header("Accept", "application/vnd.github+json")
+      .header("User-Agent", "git-activity-report")
+      .header("Authorization", &format!("Bearer {}", token))
+      .call()
+  })
+  .ok()?;
+
+  Some(resp.status().as_u16())
+}
+
+/// Startup check for `--github-prs`: probe the token and the repo's pulls endpoint, returning targeted warnings
+/// (logged under the `github` target).
+/// Empty when everything is readable, when no token is configured, or when a test/mock backend is active.
+pub fn preflight_pr_access(repo: &str) -> Vec<String> {
+  if env_wants_mock() {
+    return Vec::new();
+  }
+
+  let (Some((owner, name)), Some(token)) = (resolve_github_repo(repo), get_github_token()) else {
+    return Vec::new();
+  };
+  let Some(probe) = probe_token(&token) else {
+    return vec!["api.github.com is unreachable; PR enrichment will be empty".into()];
+  };
+
+  let base = format!("https://api.github.com/repos/{}/{}", owner, name);
+  let repo_status = probe_status(&base, &token);
+  let pulls_status = probe_status(&format!("{}/pulls?per_page=1&state=all", base), &token);
+
+  scope_warnings(&probe, repo_status, pulls_status, &format!("{}/{}", owner, name))
+}
+
+/// Translate probe results into warnings naming the missing permission (classic scope or fine-grained permission).
+pub fn scope_warnings(
+  probe: &TokenProbe,
+  repo_status: Option<u16>,
+  pulls_status: Option<u16>,
+  slug: &str,
+) -> Vec<String> {
+  let denied = |s: Option<u16>| matches!(s, Some(403) | Some(404));
+  let classic_without_repo = probe.scopes.as_ref().map(|s| !s.iter().any(|x| x == "repo"));
+  let mut out = Vec::new();
+
+  if probe.status == 401 {
+    out.push("token rejected (401 Unauthorized); refresh GITHUB_TOKEN or run `gh auth refresh`".into());
+    return out;
+  }
+
+  if probe.rate_remaining == Some(0) {
+    out.push("rate limit exhausted; PR enrichment will be empty until it resets".into());
+  }
+
+  if denied(repo_status) {
+    out.push(match classic_without_repo {
+      Some(true) => format!(
+        "token cannot see {}: classic token lacks the `repo` scope (required for private repositories)",
+        slug
+      ),
+      Some(false) => format!("token cannot see {}: check --github-repo and SSO authorization", slug),
+      None => format!(
+        "token cannot see {}: fine-grained token must include this repository with `Metadata: read`",
+        slug
+      ),
+    });
+  } else if denied(pulls_status) {
+    out.push(match classic_without_repo {
+      None => format!(
+        "token cannot list pull requests on {}: fine-grained token needs `Pull requests: read`",
+        slug
+      ),
+      _ => format!(
+        "token cannot list pull requests on {}: classic token needs the `repo` scope",
+        slug
+      ),
+    });
   }
+
+  out
 }
 
 // --- Trait seam for GitHub API ---
@@ -114,7 +674,13 @@ pub trait GithubApi {
   fn list_commits_in_pull(&self, owner: &str, name: &str, number: i64) -> Vec<PullRequestCommit>;
   fn list_reviews_for_pull_json(&self, owner: &str, name: &str, number: i64) -> Option<serde_json::Value>;
   fn list_commits_in_pull_json(&self, owner: &str, name: &str, number: i64) -> Option<serde_json::Value>;
+  /// The PR's changed files (pulls/{n}/files) as file entries; empty when unavailable.
+  fn list_files_for_pull(&self, owner: &str, name: &str, number: i64) -> Vec<FileEntry>;
   fn get_user_json(&self, login: &str) -> Option<serde_json::Value>;
+  fn get_pull_diff(&self, owner: &str, name: &str, number: i64) -> Option<String>;
+  fn get_repo_json(&self, owner: &str, name: &str) -> Option<serde_json::Value>;
+  fn get_branch_protection_json(&self, owner: &str, name: &str, branch: &str) -> Option<serde_json::Value>;
+  fn get_commit_status_json(&self, owner: &str, name: &str, sha: &str) -> Option<serde_json::Value>;
 }
 
 // --- Lightweight in-memory caching wrapper ---
@@ -126,7 +692,12 @@ struct GithubCachedApi {
   pull_reviews_json: RefCell<HashMap<String, Option<serde_json::Value>>>,
   pull_commits_json: RefCell<HashMap<String, Option<serde_json::Value>>>,
   pull_commits_typed: RefCell<HashMap<String, Vec<PullRequestCommit>>>,
+  pull_files: RefCell<HashMap<String, Vec<FileEntry>>>,
   user_json: RefCell<HashMap<String, Option<serde_json::Value>>>,
+  pull_diff: RefCell<HashMap<String, Option<String>>>,
+  repo_json: RefCell<HashMap<String, Option<serde_json::Value>>>,
+  branch_protection_json: RefCell<HashMap<String, Option<serde_json::Value>>>,
+  commit_status_json: RefCell<HashMap<String, Option<serde_json::Value>>>,
 }
 
 impl GithubCachedApi {
@@ -138,7 +709,12 @@ impl GithubCachedApi {
       pull_reviews_json: RefCell::new(HashMap::new()),
       pull_commits_json: RefCell::new(HashMap::new()),
       pull_commits_typed: RefCell::new(HashMap::new()),
+      pull_files: RefCell::new(HashMap::new()),
       user_json: RefCell::new(HashMap::new()),
+      pull_diff: RefCell::new(HashMap::new()),
+      repo_json: RefCell::new(HashMap::new()),
+      branch_protection_json: RefCell::new(HashMap::new()),
+      commit_status_json: RefCell::new(HashMap::new()),
     }
   }
 
@@ -214,6 +790,18 @@ impl GithubApi for GithubCachedApi {
     v
   }
 
+  fn list_files_for_pull(&self, owner: &str, name: &str, number: i64) -> Vec<FileEntry> {
+    let key = Self::key_num(owner, name, number);
+
+    if let Some(v) = self.pull_files.borrow().get(&key).cloned() {
+      return v;
+    }
+    let v = self.inner.list_files_for_pull(owner, name, number);
+    self.pull_files.borrow_mut().insert(key, v.clone());
+
+    v
+  }
+
   fn get_user_json(&self, login: &str) -> Option<serde_json::Value> {
     if let Some(v) = self.user_json.borrow().get(login).cloned() {
       return v;
@@ -223,6 +811,54 @@ impl GithubApi for GithubCachedApi {
 
     v
   }
+
+  fn get_pull_diff(&self, owner: &str, name: &str, number: i64) -> Option<String> {
+    let key = Self::key_num(owner, name, number);
+
+    if let Some(v) = self.pull_diff.borrow().get(&key).cloned() {
+      return v;
+    }
+    let v = self.inner.get_pull_diff(owner, name, number);
+    self.pull_diff.borrow_mut().insert(key, v.clone());
+
+    v
+  }
+
+  fn get_repo_json(&self, owner: &str, name: &str) -> Option<serde_json::Value> {
+    let key = format!("{}:{}", owner, name);
+
+    if let Some(v) = self.repo_json.borrow().get(&key).cloned() {
+      return v;
+    }
+    let v = self.inner.get_repo_json(owner, name);
+    self.repo_json.borrow_mut().insert(key, v.clone());
+
+    v
+  }
+
+  fn get_branch_protection_json(&self, owner: &str, name: &str, branch: &str) -> Option<serde_json::Value> {
+    let key = Self::key3(owner, name, branch);
+
+    if let Some(v) = self.branch_protection_json.borrow().get(&key).cloned() {
+      return v;
+    }
+    let v = self.inner.get_branch_protection_json(owner, name, branch);
+    self.branch_protection_json.borrow_mut().insert(key, v.clone());
+
+    v
+  }
+
+  fn get_commit_status_json(&self, owner: &str, name: &str, sha: &str) -> Option<serde_json::Value> {
+    let key = Self::key3(owner, name, sha);
+
+    if let Some(v) = self.commit_status_json.borrow().get(&key).cloned() {
+      return v;
+    }
+    let v = self.inner.get_commit_status_json(owner, name, sha);
+    self.commit_status_json.borrow_mut().insert(key, v.clone());
+
+    v
+  }
 }
 
 struct GithubHttpApi {
@@ -237,7 +873,7 @@ impl GithubHttpApi {
 impl GithubApi for GithubHttpApi {
   fn list_pulls_for_commit_json(&self, owner: &str, name: &str, sha: &str) -> Option<serde_json::Value> {
     let url = format!("https://api.github.com/repos/{}/{}/commits/{}/pulls", owner, name, sha);
-    get_json(&url, &self.token)
+    get_json_paginated(&url, &self.token, http_config().max_pages)
   }
 
   fn get_pull_details_json(&self, owner: &str, name: &str, number: i64) -> Option<serde_json::Value> {
@@ -251,7 +887,7 @@ impl GithubApi for GithubHttpApi {
       owner, name, number
     );
 
-    let Some(v) = get_json(&url, &self.token) else {
+    let Some(v) = get_json_paginated(&url, &self.token, PR_COMMITS_MAX_PAGES.min(http_config().max_pages)) else {
       return Vec::new();
     };
     let Some(arr) = v.as_array() else { return Vec::new() };
@@ -282,7 +918,7 @@ impl GithubApi for GithubHttpApi {
       "https://api.github.com/repos/{}/{}/pulls/{}/reviews",
       owner, name, number
     );
-    get_json(&url, &self.token)
+    get_json_paginated(&url, &self.token, http_config().max_pages)
   }
 
   fn list_commits_in_pull_json(&self, owner: &str, name: &str, number: i64) -> Option<serde_json::Value> {
@@ -290,13 +926,44 @@ impl GithubApi for GithubHttpApi {
       "https://api.github.com/repos/{}/{}/pulls/{}/commits",
       owner, name, number
     );
-    get_json(&url, &self.token)
+    get_json_paginated(&url, &self.token, PR_COMMITS_MAX_PAGES.min(http_config().max_pages))
+  }
+
+  fn list_files_for_pull(&self, owner: &str, name: &str, number: i64) -> Vec<FileEntry> {
+    let url = format!("https://api.github.com/repos/{}/{}/pulls/{}/files", owner, name, number);
+
+    get_json_paginated(&url, &self.token, PR_FILES_MAX_PAGES.min(http_config().max_pages))
+      .map(|v| pull_files_from_json(&v))
+      .unwrap_or_default()
   }
 
   fn get_user_json(&self, login: &str) -> Option<serde_json::Value> {
     let url = format!("https://api.github.com/users/{}", login);
     get_json(&url, &self.token)
   }
+
+  fn get_pull_diff(&self, owner: &str, name: &str, number: i64) -> Option<String> {
+    let url = format!("https://api.github.com/repos/{}/{}/pulls/{}", owner, name, number);
+    get_text(&url, &self.token, "application/vnd.github.diff")
+  }
+
+  fn get_repo_json(&self, owner: &str, name: &str) -> Option<serde_json::Value> {
+    let url = format!("https://api.github.com/repos/{}/{}", owner, name);
+    get_json(&url, &self.token)
+  }
+
+  fn get_branch_protection_json(&self, owner: &str, name: &str, branch: &str) -> Option<serde_json::Value> {
+    let url = format!(
+      "https://api.github.com/repos/{}/{}/branches/{}/protection",
+      owner, name, branch
+    );
+    get_json(&url, &self.token)
+  }
+
+  fn get_commit_status_json(&self, owner: &str, name: &str, sha: &str) -> Option<serde_json::Value> {
+    let url = format!("https://api.github.com/repos/{}/{}/commits/{}/status", owner, name, sha);
+    get_json(&url, &self.token)
+  }
 }
 
 struct GithubEnvApi;
@@ -363,6 +1030,12 @@ impl GithubApi for GithubEnvApi {
     }
   }
 
+  fn list_files_for_pull(&self, _owner: &str, _name: &str, _number: i64) -> Vec<FileEntry> {
+    env_json("GAR_TEST_PR_FILES_JSON")
+      .map(|v| pull_files_from_json(&v))
+      .unwrap_or_default()
+  }
+
   fn get_user_json(&self, login: &str) -> Option<serde_json::Value> {
     // Prefer a consolidated map when provided
     if let Ok(map_s) = std::env::var("GAR_TEST_USERS_JSON") {
@@ -382,13 +1055,45 @@ impl GithubApi for GithubEnvApi {
     }
     None
   }
+
+  fn get_pull_diff(&self, _owner: &str, _name: &str, _number: i64) -> Option<String> {
+    std::env::var("GAR_TEST_PR_DIFF").ok()
+  }
+
+  fn get_repo_json(&self, _owner: &str, _name: &str) -> Option<serde_json::Value> {
+    env_json("GAR_TEST_REPO_JSON")
+  }
+
+  fn get_branch_protection_json(&self, _owner: &str, _name: &str, _branch: &str) -> Option<serde_json::Value> {
+    env_json("GAR_TEST_BRANCH_PROTECTION_JSON")
+  }
+
+  fn get_commit_status_json(&self, _owner: &str, _name: &str, _sha: &str) -> Option<serde_json::Value> {
+    env_json("GAR_TEST_COMMIT_STATUS_JSON")
+  }
+}
+
+fn env_json(key: &str) -> Option<serde_json::Value> {
+  std::env::var(key)
+    .ok()
+    .and_then(|s| serde_json::from_str::<serde_json::Value>(&s).ok())
 }
 
 fn env_wants_mock() -> bool {
+  #[cfg(any(test, feature = "mock"))]
+  if std::env::var(crate::enrichment::github_mock::MOCK_SCRIPT_ENV).is_ok() {
+    return true;
+  }
+
   if std::env::var("GAR_TEST_PR_JSON").is_ok()
     || std::env::var("GAR_TEST_PULL_DETAILS_JSON").is_ok()
     || std::env::var("GAR_TEST_PR_COMMITS_JSON").is_ok()
     || std::env::var("GAR_TEST_USERS_JSON").is_ok()
+    || std::env::var("GAR_TEST_PR_DIFF").is_ok()
+    || std::env::var("GAR_TEST_PR_FILES_JSON").is_ok()
+    || std::env::var("GAR_TEST_REPO_JSON").is_ok()
+    || std::env::var("GAR_TEST_BRANCH_PROTECTION_JSON").is_ok()
+    || std::env::var("GAR_TEST_COMMIT_STATUS_JSON").is_ok()
   {
     return true;
   }
@@ -403,10 +1108,26 @@ fn env_wants_mock() -> bool {
 }
 
 fn build_api(token: Option<String>) -> Box<dyn GithubApi> {
+  #[cfg(any(test, feature = "mock"))]
+  if let Some(scripted) = crate::enrichment::github_mock::ScriptedGithubApi::from_env() {
+    return Box::new(GithubCachedApi::new(Box::new(scripted)));
+  }
+
   let inner: Box<dyn GithubApi> = if env_wants_mock() {
     Box::new(GithubEnvApi)
   } else if let Some(t) = token {
-    Box::new(GithubHttpApi::new(t))
+    let http = Box::new(GithubHttpApi::new(t));
+
+    match DISK_CACHE.get() {
+      Some((dir, ttl)) => Box::new(GithubDiskCache::new(
+        http,
+        dir.clone(),
+        *ttl,
+        API_HOST.into(),
+        http_config().max_pages,
+      )),
+      None => http,
+    }
   } else {
     Box::new(GithubEnvApi)
   };
@@ -439,7 +1160,7 @@ fn list_pulls_for_commit_json(owner: &str, name: &str, sha: &str, token: &str) -
 /// Best-effort: fetch PRs referencing a commit SHA using origin and token discovery.
 pub fn try_fetch_prs_for_commit(repo: &str, sha: &str) -> anyhow::Result<Vec<GithubPullRequest>> {
   // Phase 1: resolve origin owner/name; early guard when not GitHub
-  let (owner, name) = match parse_origin_github(repo) {
+  let (owner, name) = match resolve_github_repo(repo) {
     Some(pair) => pair,
     None => return Ok(Vec::new()),
   };
@@ -512,6 +1233,14 @@ pub fn try_fetch_prs_for_commit(repo: &str, sha: &str) -> anyhow::Result<Vec<Git
     }
 
     let (created_at, merged_at, closed_at) = resolve_timestamps(pr_json, details.as_ref());
+    let review_responses = reviews
+      .as_ref()
+      .and_then(|v| v.as_array())
+      .and_then(|arr| review_responses(arr, created_at.as_deref()));
+    let rework = reviews
+      .as_ref()
+      .and_then(|v| v.as_array())
+      .map(|arr| rework_cycles(arr, commits_json.as_ref()));
 
     let reviewers = if reviewers_vec.is_empty() {
       None
@@ -521,6 +1250,7 @@ pub fn try_fetch_prs_for_commit(repo: &str, sha: &str) -> anyhow::Result<Vec<Git
 
     let commits_vec = api.list_commits_in_pull(&owner, &name, common.number);
     let commits_opt = (!commits_vec.is_empty()).then_some(commits_vec);
+    let (size_class, review_depth, merged_without_review) = review_heuristics(details.as_ref(), review_count);
 
     let item = GithubPullRequest {
       number: common.number,
@@ -544,11 +1274,25 @@ pub fn try_fetch_prs_for_commit(repo: &str, sha: &str) -> anyhow::Result<Vec<Git
       change_request_count,
       time_to_first_review_seconds,
       time_to_merge_seconds,
+      review_responses,
+      rework_cycles: rework.as_ref().map(|r| r.len() as i64),
+      rework_cycle_seconds: rework.filter(|r| !r.is_empty()),
       estimated_minutes: None,
       estimated_minutes_min: None,
       estimated_minutes_max: None,
       estimate_confidence: None,
       estimate_basis: None,
+      diff: None,
+      diff_clipped: None,
+      size_class,
+      review_depth,
+      merged_without_review,
+      merge_commit_sha: pr_json.fetch("merge_commit_sha").to::<String>().or_else(|| {
+        details
+          .as_ref()
+          .and_then(|d| d.fetch("merge_commit_sha").to::<String>())
+      }),
+      files: None,
     };
     out.push(item);
   }
@@ -556,6 +1300,136 @@ pub fn try_fetch_prs_for_commit(repo: &str, sha: &str) -> anyhow::Result<Vec<Git
   Ok(out)
 }
 
+/// Best-effort: fetch the combined diff text for PR `number` using origin and token discovery.
+/// Results are memoized per process since the same PR is typically attached to several commits.
+pub fn try_fetch_pr_diff(repo: &str, number: i64) -> Option<String> {
+  type DiffCache = Mutex<HashMap<String, Option<String>>>;
+  static CACHE: Lazy<DiffCache> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+  let (owner, name) = resolve_github_repo(repo)?;
+  let key = format!("{}/{}#{}", owner, name, number);
+
+  if let Some(cached) = CACHE.lock().ok().and_then(|m| m.get(&key).cloned()) {
+    return cached;
+  }
+
+  let token = get_github_token();
+
+  if token.is_none() && !env_wants_mock() {
+    return None;
+  }
+
+  let api = build_api(token);
+  let diff = api.get_pull_diff(&owner, &name, number);
+
+  if let Ok(mut map) = CACHE.lock() {
+    map.insert(key, diff.clone());
+  }
+
+  diff
+}
+
+/// Best-effort: fetch PR `number`'s changed files (GitHub's view of the whole PR, not the local window's).
+/// Memoized per process like `try_fetch_pr_diff`; None only when the repo is not on GitHub or no backend is available.
+pub fn try_fetch_pr_files(repo: &str, number: i64) -> Option<Vec<FileEntry>> {
+  type FilesCache = Mutex<HashMap<String, Vec<FileEntry>>>;
+  static CACHE: Lazy<FilesCache> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+  let (owner, name) = resolve_github_repo(repo)?;
+  let key = format!("{}/{}#{}", owner, name, number);
+
+  if let Some(cached) = CACHE.lock().ok().and_then(|m| m.get(&key).cloned()) {
+    return Some(cached);
+  }
+
+  let token = get_github_token();
+
+  if token.is_none() && !env_wants_mock() {
+    return None;
+  }
+
+  let api = build_api(token);
+  let files = api.list_files_for_pull(&owner, &name, number);
+
+  if let Ok(mut map) = CACHE.lock() {
+    map.insert(key, files.clone());
+  }
+
+  Some(files)
+}
+
+/// Map a pulls/{n}/files payload to file entries (GitHub status words → git name-status letters).
+pub fn pull_files_from_json(v: &serde_json::Value) -> Vec<FileEntry> {
+  let Some(items) = v.as_array() else {
+    return Vec::new();
+  };
+
+  items
+    .iter()
+    .filter_map(|f| {
+      let file = f.fetch("filename").to::<String>()?;
+      let status = match f.fetch("status").to::<String>().as_deref() {
+        Some("added") => "A",
+        Some("removed") => "D",
+        Some("renamed") => "R",
+        Some("copied") => "C",
+        _ => "M",
+      };
+
+      Some(FileEntry {
+        file,
+        status: status.to_string(),
+        old_path: f.fetch("previous_filename").to::<String>(),
+        additions: f.fetch("additions").to::<i64>(),
+        deletions: f.fetch("deletions").to::<i64>(),
+        service: None,
+        language: None,
+        lfs: None,
+        lfs_size_delta: None,
+      })
+    })
+    .collect()
+}
+
+/// Best-effort: fetch `repo_policy` (default-branch protection + combined status of `head_sha`).
+/// Protection is fetched once per repo per process (multi-range runs reuse it); only the head status is per call.
+pub fn try_fetch_repo_policy(repo: &str, head_sha: Option<&str>) -> Option<RepoPolicy> {
+  static PROTECTION: Lazy<Mutex<HashMap<String, RepoPolicy>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+  let (owner, name) = resolve_github_repo(repo)?;
+  let token = get_github_token();
+
+  if token.is_none() && !env_wants_mock() {
+    return None;
+  }
+
+  let api = build_api(token);
+  let key = format!("{}/{}", owner, name);
+  let cached = PROTECTION.lock().ok().and_then(|m| m.get(&key).cloned());
+
+  let policy = match cached {
+    Some(base) => RepoPolicy {
+      head_status: crate::enrichment::repo_policy::build_repo_policy_status(api.as_ref(), &owner, &name, head_sha),
+      ..base
+    },
+    None => {
+      let fresh = crate::enrichment::repo_policy::build_repo_policy(api.as_ref(), &owner, &name, head_sha);
+
+      if let Ok(mut map) = PROTECTION.lock() {
+        let base = RepoPolicy {
+          head_status: None,
+          ..fresh.clone()
+        };
+        map.insert(key, base);
+      }
+
+      fresh
+    }
+  };
+
+  Some(policy)
+}
+
 /// Derive diff/patch URLs from a PR `html_url`.
 fn urls_from_html(html: &str) -> (Option<String>, Option<String>) {
   if html.is_empty() {
@@ -756,6 +1630,133 @@ fn resolve_timestamps(
   (created_at, merged_at, closed_at)
 }
 
+/// PR size class by total changed lines (additions + deletions).
+pub fn classify_pr_size(changed_lines: i64) -> &'static str {
+  match changed_lines {
+    i64::MIN..=9 => "xs",
+    10..=99 => "s",
+    100..=499 => "m",
+    500..=999 => "l",
+    _ => "xl",
+  }
+}
+
+/// Each reviewer's earliest submitted review, as seconds after `created_at`; ordered by reviewer login.
+/// Pending reviews (no `submitted_at`) are ignored; None when nothing qualifies.
+pub fn review_responses(rev_arr: &[serde_json::Value], created_at: Option<&str>) -> Option<Vec<ReviewResponse>> {
+  let created = created_at?;
+  let mut first: std::collections::BTreeMap<String, String> = std::collections::BTreeMap::new();
+
+  for r in rev_arr {
+    let (Some(login), Some(ts)) = (
+      r.fetch("user.login").to::<String>(),
+      r.fetch("submitted_at").to::<String>(),
+    ) else {
+      continue;
+    };
+    let entry = first.entry(login).or_insert_with(|| ts.clone());
+
+    if ts < *entry {
+      *entry = ts;
+    }
+  }
+
+  let out: Vec<ReviewResponse> = first
+    .into_iter()
+    .filter_map(|(reviewer, ts)| {
+      Some(ReviewResponse {
+        reviewer,
+        response_seconds: diff_seconds(created, &ts)?,
+      })
+    })
+    .collect();
+
+  (!out.is_empty()).then_some(out)
+}
+
+/// Derive (size_class, review_depth, merged_without_review) from the PR details payload.
+/// `review_depth` is review comments per 100 changed lines (2 decimals); `merged_without_review`
+/// is only set for merged PRs whose reviews were fetched (`review_count` known).
+/// Rework loops from the review timeline: a loop opens at a CHANGES_REQUESTED review and closes at the next
+/// APPROVED review with at least one commit in between; returns each closed loop's length in seconds.
+/// An approval with no new commits ends the loop without counting it (the request was waived, not reworked).
+pub fn rework_cycles(rev_arr: &[serde_json::Value], commits_json: Option<&serde_json::Value>) -> Vec<i64> {
+  let commit_times: Vec<String> = commits_json
+    .and_then(|v| v.as_array())
+    .map(|arr| {
+      arr
+        .iter()
+        .filter_map(|c| c.fetch("commit.committer.date").to::<String>())
+        .collect()
+    })
+    .unwrap_or_default();
+
+  let mut timeline: Vec<(String, String)> = rev_arr
+    .iter()
+    .filter_map(|r| {
+      Some((
+        r.fetch("submitted_at").to::<String>()?,
+        r.fetch("state").to::<String>()?,
+      ))
+    })
+    .collect();
+  timeline.sort();
+
+  let pushed_between = |from: &str, to: &str| {
+    commit_times
+      .iter()
+      .any(|c| matches!(diff_seconds(from, c), Some(s) if s > 0) && matches!(diff_seconds(c, to), Some(s) if s >= 0))
+  };
+
+  let mut open: Option<String> = None;
+  let mut out = Vec::new();
+
+  for (ts, state) in timeline {
+    match state.as_str() {
+      "CHANGES_REQUESTED" if open.is_none() => open = Some(ts),
+      "APPROVED" => {
+        if let Some(start) = open.take() {
+          if pushed_between(&start, &ts) {
+            out.extend(diff_seconds(&start, &ts));
+          }
+        }
+      }
+      _ => {}
+    }
+  }
+
+  out
+}
+
+pub fn review_heuristics(
+  details: Option<&serde_json::Value>,
+  review_count: Option<i64>,
+) -> (Option<String>, Option<f64>, Option<bool>) {
+  let Some(d) = details else {
+    return (None, None, None);
+  };
+
+  let changed = match (d.fetch("additions").to::<i64>(), d.fetch("deletions").to::<i64>()) {
+    (Some(a), Some(del)) => Some(a + del),
+    _ => None,
+  };
+  let review_comments = d.fetch("review_comments").to::<i64>();
+
+  let size_class = changed.map(|c| classify_pr_size(c).to_string());
+  let review_depth = match (changed, review_comments) {
+    (Some(c), Some(rc)) if c > 0 => Some(((rc as f64) * 100.0 / (c as f64) * 100.0).round() / 100.0),
+    _ => None,
+  };
+
+  let merged = d.fetch("merged").to::<bool>().unwrap_or(false) || d.fetch("merged_at").to::<String>().is_some();
+  let merged_without_review = match review_count {
+    Some(n) if merged => Some(n == 0 && review_comments.unwrap_or(0) == 0),
+    _ => None,
+  };
+
+  (size_class, review_depth, merged_without_review)
+}
+
 /// Build a `GithubUser` for the PR submitter, attempting to classify and resolve email.
 fn build_submitter_user(
   api: &dyn GithubApi,
@@ -842,6 +1843,109 @@ mod tests {
   use super::*;
   use serial_test::serial;
 
+  #[test]
+  fn scope_warnings_name_the_missing_permission() {
+    let probe = |status, scopes: Option<Vec<&str>>| TokenProbe {
+      status,
+      scopes: scopes.map(|v| v.into_iter().map(String::from).collect()),
+      rate_remaining: Some(100),
+    };
+
+    assert!(scope_warnings(&probe(200, Some(vec!["repo"])), Some(200), Some(200), "o/r").is_empty());
+
+    let fine = scope_warnings(&probe(200, None), Some(200), Some(403), "o/r");
+    assert_eq!(fine.len(), 1);
+    assert!(fine[0].contains("Pull requests: read"));
+
+    let classic = scope_warnings(&probe(200, Some(vec!["gist"])), Some(404), Some(404), "o/r");
+    assert!(classic[0].contains("`repo` scope"));
+
+    let rejected = scope_warnings(&probe(401, None), None, None, "o/r");
+    assert!(rejected[0].contains("401"));
+  }
+
+  #[test]
+  fn backoff_is_jittered_within_doubling_bounds() {
+    for attempt in 0..8 {
+      let base = (250u64 << attempt.min(5)).min(8_000);
+      let d = backoff_delay(attempt).as_millis() as u64;
+      assert!(d >= base / 2 && d <= base, "attempt {} → {}ms", attempt, d);
+    }
+  }
+
+  #[test]
+  fn retries_only_retryable_failures() {
+    let mut calls = 0;
+    let res = with_retries(2, || {
+      calls += 1;
+      Err(ureq::Error::StatusCode(503))
+    });
+    assert!(res.is_err());
+    assert_eq!(calls, 3);
+
+    let mut calls = 0;
+    let _ = with_retries(2, || {
+      calls += 1;
+      Err(ureq::Error::StatusCode(404))
+    });
+    assert_eq!(calls, 1);
+
+    let mut calls = 0;
+    let _ = with_retries(0, || {
+      calls += 1;
+      Err(ureq::Error::ConnectionFailed)
+    });
+    assert_eq!(calls, 1);
+  }
+
+  #[test]
+  fn rework_cycles_need_new_commits_between_request_and_approval() {
+    let review = |state: &str, at: &str| serde_json::json!({ "state": state, "submitted_at": at });
+    let commit = |at: &str| serde_json::json!({ "commit": { "committer": { "date": at } } });
+    let reviews = vec![
+      review("APPROVED", "2024-01-01T09:00:00Z"),
+      review("CHANGES_REQUESTED", "2024-01-01T10:00:00Z"),
+      review("COMMENTED", "2024-01-01T10:30:00Z"),
+      review("CHANGES_REQUESTED", "2024-01-01T10:45:00Z"),
+      review("APPROVED", "2024-01-01T12:00:00Z"),
+      review("CHANGES_REQUESTED", "2024-01-02T10:00:00Z"),
+      review("APPROVED", "2024-01-02T11:00:00Z"),
+    ];
+    let commits = serde_json::json!([
+      commit("2024-01-01T08:00:00Z"),
+      commit("2024-01-01T11:00:00Z"),
+      commit("2024-01-03T09:00:00Z")
+    ]);
+
+    // Loop 1: 10:00 → 12:00 with a push at 11:00; loop 2 is approved without new commits
+    assert_eq!(rework_cycles(&reviews, Some(&commits)), vec![7200]);
+    assert!(rework_cycles(&reviews, None).is_empty());
+    assert!(rework_cycles(&[], Some(&commits)).is_empty());
+  }
+
+  #[test]
+  fn review_heuristics_size_depth_and_unreviewed_merge() {
+    assert_eq!(classify_pr_size(0), "xs");
+    assert_eq!(classify_pr_size(100), "m");
+    assert_eq!(classify_pr_size(1000), "xl");
+
+    let merged = serde_json::json!({"merged": true, "additions": 300, "deletions": 0, "review_comments": 1});
+    let (size, depth, unreviewed) = review_heuristics(Some(&merged), Some(0));
+    assert_eq!(size.as_deref(), Some("m"));
+    assert_eq!(depth, Some(0.33));
+    assert_eq!(unreviewed, Some(false));
+
+    let bare = serde_json::json!({"merged_at": "2024-01-01T00:00:00Z", "additions": 5, "deletions": 5});
+    assert_eq!(review_heuristics(Some(&bare), Some(0)).2, Some(true));
+    // reviews unknown, or not merged → no verdict
+    assert_eq!(review_heuristics(Some(&bare), None).2, None);
+    assert_eq!(
+      review_heuristics(Some(&serde_json::json!({"merged": false})), Some(0)).2,
+      None
+    );
+    assert_eq!(review_heuristics(None, Some(0)), (None, None, None));
+  }
+
   #[test]
   #[serial]
   fn parse_origin_none_without_remote() {
@@ -856,6 +1960,30 @@ mod tests {
     assert_eq!(parse_origin_github(repo.to_str().unwrap()), None);
   }
 
+  #[test]
+  fn parse_next_link_finds_next_relation() {
+    let link = r#"<https://api.github.com/repositories/1/pulls/5/reviews?per_page=100&page=2>; rel="next", <https://api.github.com/repositories/1/pulls/5/reviews?per_page=100&page=4>; rel="last""#;
+    assert_eq!(
+      parse_next_link(link).as_deref(),
+      Some("https://api.github.com/repositories/1/pulls/5/reviews?per_page=100&page=2")
+    );
+
+    let last_page = r#"<https://api.github.com/x?page=1>; rel="prev", <https://api.github.com/x?page=1>; rel="first""#;
+    assert_eq!(parse_next_link(last_page), None);
+  }
+
+  #[test]
+  fn parse_repo_spec_accepts_owner_name_only() {
+    assert_eq!(
+      parse_repo_spec("imajes/git-activity-report"),
+      Some(("imajes".to_string(), "git-activity-report".to_string()))
+    );
+    assert_eq!(parse_repo_spec("o/r.git"), Some(("o".to_string(), "r".to_string())));
+    assert_eq!(parse_repo_spec("justname"), None);
+    assert_eq!(parse_repo_spec("/r"), None);
+    assert_eq!(parse_repo_spec("a/b/c"), None);
+  }
+
   #[test]
   #[serial]
   fn parse_origin_github_detects_owner_repo() {
@@ -1245,6 +2373,20 @@ mod tests {
 
     // first review 1h after created
     assert_eq!(pr.time_to_first_review_seconds, Some(3600));
+    // per reviewer: earliest of the bot's two reviews, then alice's
+    assert_eq!(
+      pr.review_responses,
+      Some(vec![
+        ReviewResponse {
+          reviewer: "alice".into(),
+          response_seconds: 3 * 3600
+        },
+        ReviewResponse {
+          reviewer: "ci-bot[bot]".into(),
+          response_seconds: 3600
+        },
+      ])
+    );
 
     let reviewers = pr.reviewers.as_ref().unwrap();
     assert_eq!(reviewers.len(), 2);
@@ -1467,4 +2609,141 @@ mod tests {
     handle.join().unwrap();
     assert_eq!(v.unwrap().fetch("ok").to::<bool>(), Some(true));
   }
+
+  #[test]
+  fn rate_limit_wait_reads_retry_after_and_reset_headers() {
+    let now = 1_700_000_000;
+    assert_eq!(rate_limit_wait(200, Some("5"), Some("0"), None, now), None);
+    assert_eq!(rate_limit_wait(403, Some("7"), None, None, now), Some(7));
+    assert_eq!(rate_limit_wait(403, None, Some("0"), Some("1700000030"), now), Some(31));
+    assert_eq!(rate_limit_wait(403, None, Some("12"), Some("1700000030"), now), None);
+    assert_eq!(rate_limit_wait(429, None, None, None, now), Some(0));
+  }
+
+  #[test]
+  fn request_stats_report_skips_since_a_snapshot() {
+    let before = RequestStats {
+      sent: 10,
+      skipped_budget: 1,
+      skipped_rate_limit: 0,
+    };
+    let after = RequestStats {
+      sent: 12,
+      skipped_budget: 4,
+      skipped_rate_limit: 2,
+    };
+    let delta = after.since(&before);
+    assert_eq!(delta.skipped_budget, 3);
+
+    let warnings = delta.warnings(Some(12));
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0].starts_with("3 GitHub request(s) skipped") && warnings[0].contains("budget of 12"));
+    assert!(warnings[1].contains("rate limit"));
+    assert!(before.since(&before).warnings(None).is_empty());
+  }
+
+  #[test]
+  fn get_json_waits_out_a_secondary_rate_limit() {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    // First response is a secondary rate limit (403 + Retry-After), the retry succeeds
+    let handle = std::thread::spawn(move || {
+      let responses = [
+        "HTTP/1.1 403 Forbidden\r\nRetry-After: 0\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}",
+        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 11\r\nConnection: close\r\n\r\n{\"ok\":true}",
+      ];
+      for resp in responses {
+        let (mut stream, _) = listener.accept().unwrap();
+        let _ = stream.set_read_timeout(Some(std::time::Duration::from_secs(1)));
+        let mut buf = [0u8; 1024];
+        let _ = stream.read(&mut buf);
+        let _ = stream.write_all(resp.as_bytes());
+      }
+    });
+
+    let v = get_json(&format!("http://{}", addr), "t");
+    handle.join().unwrap();
+
+    assert_eq!(v.unwrap().fetch("ok").to::<bool>(), Some(true));
+  }
+
+  #[test]
+  fn get_json_paginated_follows_links_up_to_the_page_cap() {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Three pages exist; with a cap of 2 the third is never requested
+    let handle = std::thread::spawn(move || {
+      for _ in 0..2 {
+        let (mut stream, _) = listener.accept().unwrap();
+        let _ = stream.set_read_timeout(Some(std::time::Duration::from_secs(1)));
+        let mut buf = [0u8; 1024];
+        let n = stream.read(&mut buf).unwrap_or(0);
+        let request = String::from_utf8_lossy(&buf[..n]).to_string();
+        let page: u32 = if request.contains("page=2") { 2 } else { 1 };
+        let body = format!("[{{\"page\":{}}}]", page);
+        let resp = format!(
+          "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nLink: <http://{}/pulls?per_page=100&page={}>; rel=\"next\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+          addr,
+          page + 1,
+          body.len(),
+          body
+        );
+        let _ = stream.write_all(resp.as_bytes());
+      }
+    });
+
+    let v = get_json_paginated(&format!("http://{}/pulls", addr), "t", 2).unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(v, serde_json::json!([{ "page": 1 }, { "page": 2 }]));
+    assert!(take_partial_list());
+  }
+
+  #[test]
+  fn get_json_paginated_flags_a_failed_later_page_as_partial() {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    // Page 1 links to page 2, which is gone
+    let handle = std::thread::spawn(move || {
+      let body = "[{\"page\":1}]";
+      let responses = [
+        format!(
+          "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nLink: <http://{}/reviews?per_page=100&page=2>; rel=\"next\"\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+          addr,
+          body.len(),
+          body
+        ),
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 2\r\nConnection: close\r\n\r\n{}".to_string(),
+      ];
+      for resp in responses {
+        let (mut stream, _) = listener.accept().unwrap();
+        let _ = stream.set_read_timeout(Some(std::time::Duration::from_secs(1)));
+        let mut buf = [0u8; 1024];
+        let _ = stream.read(&mut buf);
+        let _ = stream.write_all(resp.as_bytes());
+      }
+    });
+
+    let mark = crate::util::warning_count();
+    let v = get_json_paginated(&format!("http://{}/reviews", addr), "t", 10).unwrap();
+    handle.join().unwrap();
+
+    assert_eq!(v, serde_json::json!([{ "page": 1 }]));
+    assert!(take_partial_list());
+    assert!(!take_partial_list());
+    assert!(
+      crate::util::warnings_since(mark)
+        .iter()
+        .any(|w| w.message.contains("failed after 1 page(s)"))
+    );
+  }
 }
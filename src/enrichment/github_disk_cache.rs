@@ -0,0 +1,357 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --github-cache-dir: persist GitHub API responses on disk across runs, keyed by endpoint + args, with a TTL
+// role: enrichment/github-disk-cache
+// inputs: Cache dir and TTL (--github-cache-dir, --github-cache-ttl); API host and page cap (--http-max-pages); the wrapped GithubApi (HTTP backend)
+// outputs: GithubApi responses served from <dir>/<fnv1a-64 of key>.json when fresh; fetched and stored otherwise
+// side_effects: Reads/writes cache files (temp file + rename); write failures warn once and the run continues uncached
+// invariants:
+// - only successful responses are stored; failures (None / empty lists) are never cached, so a rate-limited run cannot poison later ones
+// - lists cut short by the page cap or a failed later page (github_api::take_partial_list) are served but not stored
+// - keys start with the API host; list keys also carry the page cap, so raising --http-max-pages refetches instead of serving the shorter list
+// - an entry is served only when its stored key matches (hash collisions miss) and it is younger than the TTL
+// - sits below the per-run in-memory cache and above HTTP; mock/env backends are never wrapped
+// errors: None surfaced; unreadable or corrupt entries count as misses
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::enrichment::github_api::{GithubApi, take_partial_list};
+use crate::model::{FileEntry, PullRequestCommit};
+use crate::util;
+
+/// Default entry lifetime: one day, long enough for back-to-back window runs, short enough to see new reviews.
+pub const DEFAULT_GITHUB_CACHE_TTL_SECS: u64 = 86_400;
+
+/// Bump whenever the stored entry shape changes.
+const CACHE_SCHEMA_VERSION: u32 = 1;
+
+static WRITE_WARNED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+  schema_version: u32,
+  key: String,
+  fetched_at: i64,
+  value: serde_json::Value,
+}
+
+/// A `GithubApi` that serves fresh on-disk responses and records new ones.
+pub struct GithubDiskCache {
+  inner: Box<dyn GithubApi>,
+  dir: PathBuf,
+  ttl_secs: u64,
+  host: String,
+  max_pages: usize,
+}
+
+impl GithubDiskCache {
+  pub fn new(inner: Box<dyn GithubApi>, dir: PathBuf, ttl_secs: u64, host: String, max_pages: usize) -> Self {
+    Self {
+      inner,
+      dir,
+      ttl_secs,
+      host,
+      max_pages,
+    }
+  }
+
+  /// Key for a single-object endpoint: the API host, then `path` (endpoint kind and arguments).
+  fn key(&self, path: String) -> String {
+    format!("{}/{}", self.host, path)
+  }
+
+  /// Key for a paginated list endpoint, which also depends on how many pages may be fetched.
+  fn list_key(&self, path: String) -> String {
+    format!("{}/{}?max_pages={}", self.host, path, self.max_pages)
+  }
+
+  fn entry_path(&self, key: &str) -> PathBuf {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for b in key.as_bytes() {
+      hash ^= *b as u64;
+      hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    self.dir.join(format!("{:016x}.json", hash))
+  }
+
+  fn read(&self, key: &str, now: i64) -> Option<serde_json::Value> {
+    let bytes = std::fs::read(self.entry_path(key)).ok()?;
+    let entry: CacheEntry = serde_json::from_slice(&bytes).ok()?;
+    let fresh = entry.schema_version == CACHE_SCHEMA_VERSION
+      && entry.key == key
+      && now.saturating_sub(entry.fetched_at) < self.ttl_secs as i64;
+
+    fresh.then_some(entry.value)
+  }
+
+  fn write(&self, key: &str, value: &serde_json::Value, now: i64) -> anyhow::Result<()> {
+    let entry = CacheEntry {
+      schema_version: CACHE_SCHEMA_VERSION,
+      key: key.to_string(),
+      fetched_at: now,
+      value: value.clone(),
+    };
+    let path = self.entry_path(key);
+    let tmp = path.with_extension(format!("{}.tmp", std::process::id()));
+
+    std::fs::create_dir_all(&self.dir)?;
+    std::fs::write(&tmp, serde_json::to_vec(&entry)?)?;
+    std::fs::rename(&tmp, &path)?;
+
+    Ok(())
+  }
+
+  /// Serve `key` from disk when fresh; otherwise run `fetch` and store a successful, complete result.
+  fn cached(&self, key: String, fetch: impl FnOnce() -> Option<serde_json::Value>) -> Option<serde_json::Value> {
+    let now = chrono::Utc::now().timestamp();
+
+    if let Some(v) = self.read(&key, now) {
+      return Some(v);
+    }
+
+    take_partial_list();
+    let v = fetch()?;
+
+    if take_partial_list() {
+      return Some(v);
+    }
+
+    if let Err(e) = self.write(&key, &v, now) {
+      if !WRITE_WARNED.swap(true, Ordering::Relaxed) {
+        util::log(
+          util::LogLevel::Warn,
+          "github",
+          &format!("GitHub cache {} is not writable: {:#}", self.dir.display(), e),
+        );
+      }
+    }
+
+    Some(v)
+  }
+}
+
+impl GithubApi for GithubDiskCache {
+  fn list_pulls_for_commit_json(&self, owner: &str, name: &str, sha: &str) -> Option<serde_json::Value> {
+    let key = self.list_key(format!("pulls_for_commit/{}/{}/{}", owner, name, sha));
+
+    self.cached(key, || self.inner.list_pulls_for_commit_json(owner, name, sha))
+  }
+
+  fn get_pull_details_json(&self, owner: &str, name: &str, number: i64) -> Option<serde_json::Value> {
+    let key = self.key(format!("pull/{}/{}/{}", owner, name, number));
+
+    self.cached(key, || self.inner.get_pull_details_json(owner, name, number))
+  }
+
+  fn list_commits_in_pull(&self, owner: &str, name: &str, number: i64) -> Vec<PullRequestCommit> {
+    let key = self.list_key(format!("pull_commits_typed/{}/{}/{}", owner, name, number));
+    let v = self.cached(key, || {
+      let commits = self.inner.list_commits_in_pull(owner, name, number);
+
+      if commits.is_empty() {
+        None
+      } else {
+        serde_json::to_value(commits).ok()
+      }
+    });
+
+    v.and_then(|v| serde_json::from_value(v).ok()).unwrap_or_default()
+  }
+
+  fn list_reviews_for_pull_json(&self, owner: &str, name: &str, number: i64) -> Option<serde_json::Value> {
+    let key = self.list_key(format!("pull_reviews/{}/{}/{}", owner, name, number));
+
+    self.cached(key, || self.inner.list_reviews_for_pull_json(owner, name, number))
+  }
+
+  fn list_commits_in_pull_json(&self, owner: &str, name: &str, number: i64) -> Option<serde_json::Value> {
+    let key = self.list_key(format!("pull_commits/{}/{}/{}", owner, name, number));
+
+    self.cached(key, || self.inner.list_commits_in_pull_json(owner, name, number))
+  }
+
+  fn list_files_for_pull(&self, owner: &str, name: &str, number: i64) -> Vec<FileEntry> {
+    let key = self.list_key(format!("pull_files/{}/{}/{}", owner, name, number));
+    let v = self.cached(key, || {
+      let files = self.inner.list_files_for_pull(owner, name, number);
+
+      if files.is_empty() {
+        None
+      } else {
+        serde_json::to_value(files).ok()
+      }
+    });
+
+    v.and_then(|v| serde_json::from_value(v).ok()).unwrap_or_default()
+  }
+
+  fn get_user_json(&self, login: &str) -> Option<serde_json::Value> {
+    let key = self.key(format!("user/{}", login));
+
+    self.cached(key, || self.inner.get_user_json(login))
+  }
+
+  fn get_pull_diff(&self, owner: &str, name: &str, number: i64) -> Option<String> {
+    let key = self.key(format!("pull_diff/{}/{}/{}", owner, name, number));
+    let v = self.cached(key, || {
+      self
+        .inner
+        .get_pull_diff(owner, name, number)
+        .map(serde_json::Value::String)
+    });
+
+    v.and_then(|v| v.as_str().map(str::to_string))
+  }
+
+  fn get_repo_json(&self, owner: &str, name: &str) -> Option<serde_json::Value> {
+    let key = self.key(format!("repo/{}/{}", owner, name));
+
+    self.cached(key, || self.inner.get_repo_json(owner, name))
+  }
+
+  fn get_branch_protection_json(&self, owner: &str, name: &str, branch: &str) -> Option<serde_json::Value> {
+    let key = self.key(format!("branch_protection/{}/{}/{}", owner, name, branch));
+
+    self.cached(key, || self.inner.get_branch_protection_json(owner, name, branch))
+  }
+
+  fn get_commit_status_json(&self, owner: &str, name: &str, sha: &str) -> Option<serde_json::Value> {
+    let key = self.key(format!("commit_status/{}/{}/{}", owner, name, sha));
+
+    self.cached(key, || self.inner.get_commit_status_json(owner, name, sha))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::cell::Cell;
+  use std::rc::Rc;
+
+  /// Counts calls; answers every user lookup with `{ "login": <login> }` and every review listing with a list cut
+  /// short after one page; other endpoints miss.
+  struct CountingApi {
+    calls: Rc<Cell<usize>>,
+  }
+
+  impl GithubApi for CountingApi {
+    fn list_pulls_for_commit_json(&self, _: &str, _: &str, _: &str) -> Option<serde_json::Value> {
+      self.calls.set(self.calls.get() + 1);
+      None
+    }
+    fn get_pull_details_json(&self, _: &str, _: &str, _: i64) -> Option<serde_json::Value> {
+      None
+    }
+    fn list_commits_in_pull(&self, _: &str, _: &str, _: i64) -> Vec<PullRequestCommit> {
+      Vec::new()
+    }
+    fn list_reviews_for_pull_json(&self, _: &str, _: &str, _: i64) -> Option<serde_json::Value> {
+      self.calls.set(self.calls.get() + 1);
+      crate::enrichment::github_api::flag_partial_list();
+      Some(serde_json::json!([{ "id": 1 }]))
+    }
+    fn list_commits_in_pull_json(&self, _: &str, _: &str, _: i64) -> Option<serde_json::Value> {
+      None
+    }
+    fn list_files_for_pull(&self, _: &str, _: &str, _: i64) -> Vec<FileEntry> {
+      Vec::new()
+    }
+    fn get_user_json(&self, login: &str) -> Option<serde_json::Value> {
+      self.calls.set(self.calls.get() + 1);
+      Some(serde_json::json!({ "login": login }))
+    }
+    fn get_pull_diff(&self, _: &str, _: &str, _: i64) -> Option<String> {
+      None
+    }
+    fn get_repo_json(&self, _: &str, _: &str) -> Option<serde_json::Value> {
+      None
+    }
+    fn get_branch_protection_json(&self, _: &str, _: &str, _: &str) -> Option<serde_json::Value> {
+      None
+    }
+    fn get_commit_status_json(&self, _: &str, _: &str, _: &str) -> Option<serde_json::Value> {
+      None
+    }
+  }
+
+  fn cache(dir: &std::path::Path, ttl_secs: u64) -> (GithubDiskCache, Rc<Cell<usize>>) {
+    let calls = Rc::new(Cell::new(0));
+    let inner = Box::new(CountingApi { calls: calls.clone() });
+
+    (
+      GithubDiskCache::new(inner, dir.to_path_buf(), ttl_secs, "api.github.com".into(), 10),
+      calls,
+    )
+  }
+
+  #[test]
+  fn responses_persist_across_instances_until_the_ttl() {
+    let td = tempfile::TempDir::new().unwrap();
+
+    let (first, calls) = cache(td.path(), 3600);
+    assert_eq!(first.get_user_json("octo").unwrap()["login"], "octo");
+    assert_eq!(calls.get(), 1);
+
+    // A later run (new instance) is served from disk
+    let (second, calls) = cache(td.path(), 3600);
+    assert_eq!(second.get_user_json("octo").unwrap()["login"], "octo");
+    assert_eq!(calls.get(), 0);
+
+    // With a zero TTL every entry is stale
+    let (expired, calls) = cache(td.path(), 0);
+    assert!(expired.get_user_json("octo").is_some());
+    assert_eq!(calls.get(), 1);
+  }
+
+  #[test]
+  fn failures_and_corrupt_entries_are_not_served() {
+    let td = tempfile::TempDir::new().unwrap();
+    let (api, calls) = cache(td.path(), 3600);
+
+    assert!(api.list_pulls_for_commit_json("o", "r", "abc").is_none());
+    assert!(api.list_pulls_for_commit_json("o", "r", "abc").is_none());
+    assert_eq!(calls.get(), 2);
+
+    api.get_user_json("octo");
+    std::fs::write(api.entry_path(&api.key("user/octo".into())), b"{not json").unwrap();
+    assert!(api.get_user_json("octo").is_some());
+    assert_eq!(calls.get(), 4);
+  }
+
+  #[test]
+  fn truncated_lists_are_served_but_not_stored() {
+    let td = tempfile::TempDir::new().unwrap();
+    let (api, calls) = cache(td.path(), 3600);
+
+    assert!(api.list_reviews_for_pull_json("o", "r", 1).is_some());
+    assert!(api.list_reviews_for_pull_json("o", "r", 1).is_some());
+    assert_eq!(calls.get(), 2);
+    assert!(!take_partial_list());
+  }
+
+  #[test]
+  fn keys_carry_the_host_and_list_keys_the_page_cap() {
+    let td = tempfile::TempDir::new().unwrap();
+    let (github, _) = cache(td.path(), 3600);
+    let cache_for = |host: &str, max_pages: usize| {
+      let calls = Rc::new(Cell::new(0));
+      let inner = Box::new(CountingApi { calls });
+
+      GithubDiskCache::new(inner, td.path().to_path_buf(), 3600, host.into(), max_pages)
+    };
+    let ghe = cache_for("ghe.example.com", 10);
+    let more_pages = cache_for("api.github.com", 20);
+    let path = "pull_reviews/o/r/1";
+
+    assert_ne!(github.key(path.into()), ghe.key(path.into()));
+    assert_eq!(github.key(path.into()), more_pages.key(path.into()));
+    assert_ne!(github.list_key(path.into()), more_pages.list_key(path.into()));
+  }
+}
@@ -0,0 +1,293 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Scriptable, deterministic GithubApi mock with latency, failure injection, and paged list responses
+// role: enrichment/github-mock (feature = "mock")
+// inputs: MockScript (built in code or loaded from the JSON file named by GAR_MOCK_GITHUB)
+// outputs: GithubApi responses per script; call log for assertions
+// side_effects: Sleeps for the scripted latency; reads the script file once
+// invariants:
+// - failure injection is driven by a seeded xorshift generator, so a given seed yields the same failure sequence
+// - list endpoints concatenate scripted pages in order; each page counts as one call (and one latency hit)
+// - "*" keys act as wildcards for any sha/number/login not scripted explicitly
+// errors: Injected failures surface as None/empty, matching the best-effort contract of the real backend
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::enrichment::github_api::{GithubApi, pull_files_from_json};
+use crate::ext::serde_json::JsonFetch;
+use crate::model::{FileEntry, PullRequestCommit};
+
+/// Env var naming a JSON `MockScript` file; when set (and built with `--features mock`) it replaces the HTTP backend.
+pub const MOCK_SCRIPT_ENV: &str = "GAR_MOCK_GITHUB";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct MockScript {
+  pub seed: u64,
+  pub latency_ms: u64,
+  /// Probability (0.0–1.0) that any single call fails
+  pub error_rate: f64,
+  pub pulls_for_commit: HashMap<String, serde_json::Value>,
+  pub pull_details: HashMap<String, serde_json::Value>,
+  /// Pages of review objects per PR number
+  pub reviews: HashMap<String, Vec<Vec<serde_json::Value>>>,
+  /// Pages of commit objects per PR number
+  pub pr_commits: HashMap<String, Vec<Vec<serde_json::Value>>>,
+  /// Pages of pulls/{n}/files objects per PR number
+  pub pr_files: HashMap<String, Vec<Vec<serde_json::Value>>>,
+  pub users: HashMap<String, serde_json::Value>,
+  pub pull_diff: HashMap<String, String>,
+  pub repo: Option<serde_json::Value>,
+  /// Branch protection payload per branch name
+  pub branch_protection: HashMap<String, serde_json::Value>,
+  /// Combined commit status payload per sha
+  pub commit_status: HashMap<String, serde_json::Value>,
+}
+
+pub struct ScriptedGithubApi {
+  script: MockScript,
+  rng: Cell<u64>,
+  calls: RefCell<Vec<String>>,
+}
+
+impl ScriptedGithubApi {
+  pub fn new(script: MockScript) -> Self {
+    // xorshift must not start at zero
+    let seed = if script.seed == 0 {
+      0x9E37_79B9_7F4A_7C15
+    } else {
+      script.seed
+    };
+
+    Self {
+      script,
+      rng: Cell::new(seed),
+      calls: RefCell::new(Vec::new()),
+    }
+  }
+
+  /// Load a script from the file named by `GAR_MOCK_GITHUB`, if set and readable.
+  pub fn from_env() -> Option<Self> {
+    let path = std::env::var(MOCK_SCRIPT_ENV).ok()?;
+    let text = std::fs::read_to_string(&path).ok()?;
+    let script: MockScript = serde_json::from_str(&text).ok()?;
+
+    Some(Self::new(script))
+  }
+
+  /// Endpoint calls made so far (one entry per page for list endpoints), e.g. `reviews:42`.
+  pub fn calls(&self) -> Vec<String> {
+    self.calls.borrow().clone()
+  }
+
+  /// Record the call, apply latency, and roll for an injected failure. Returns false when the call should fail.
+  fn hit(&self, endpoint: &str, key: &str) -> bool {
+    self.calls.borrow_mut().push(format!("{}:{}", endpoint, key));
+
+    if self.script.latency_ms > 0 {
+      std::thread::sleep(Duration::from_millis(self.script.latency_ms));
+    }
+
+    self.next_unit() >= self.script.error_rate
+  }
+
+  fn next_unit(&self) -> f64 {
+    let mut x = self.rng.get();
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    self.rng.set(x);
+
+    (x >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  fn lookup<'a, T>(map: &'a HashMap<String, T>, key: &str) -> Option<&'a T> {
+    map.get(key).or_else(|| map.get("*"))
+  }
+
+  fn paged(
+    &self,
+    endpoint: &str,
+    pages: &HashMap<String, Vec<Vec<serde_json::Value>>>,
+    number: i64,
+  ) -> Option<serde_json::Value> {
+    let key = number.to_string();
+    let pages = Self::lookup(pages, &key)?;
+    let mut items = Vec::new();
+
+    for (i, page) in pages.iter().enumerate() {
+      if !self.hit(endpoint, &format!("{}#{}", key, i + 1)) {
+        return None;
+      }
+
+      items.extend(page.iter().cloned());
+    }
+
+    Some(serde_json::Value::Array(items))
+  }
+}
+
+impl GithubApi for ScriptedGithubApi {
+  fn list_pulls_for_commit_json(&self, _owner: &str, _name: &str, sha: &str) -> Option<serde_json::Value> {
+    if !self.hit("pulls_for_commit", sha) {
+      return None;
+    }
+
+    Self::lookup(&self.script.pulls_for_commit, sha).cloned()
+  }
+
+  fn get_pull_details_json(&self, _owner: &str, _name: &str, number: i64) -> Option<serde_json::Value> {
+    let key = number.to_string();
+
+    if !self.hit("pull_details", &key) {
+      return None;
+    }
+
+    Self::lookup(&self.script.pull_details, &key).cloned()
+  }
+
+  fn list_commits_in_pull(&self, owner: &str, name: &str, number: i64) -> Vec<PullRequestCommit> {
+    let Some(v) = self.list_commits_in_pull_json(owner, name, number) else {
+      return Vec::new();
+    };
+    let Some(arr) = v.as_array() else { return Vec::new() };
+
+    arr
+      .iter()
+      .filter_map(|item| {
+        let sha = item.fetch("sha").to_or_default::<String>();
+        let msg = item.fetch("commit.message").to_or_default::<String>();
+
+        (!sha.is_empty()).then(|| PullRequestCommit {
+          short_sha: sha.chars().take(7).collect(),
+          subject: msg.lines().next().unwrap_or("").to_string(),
+          sha,
+        })
+      })
+      .collect()
+  }
+
+  fn list_reviews_for_pull_json(&self, _owner: &str, _name: &str, number: i64) -> Option<serde_json::Value> {
+    self.paged("reviews", &self.script.reviews, number)
+  }
+
+  fn list_commits_in_pull_json(&self, _owner: &str, _name: &str, number: i64) -> Option<serde_json::Value> {
+    self.paged("pr_commits", &self.script.pr_commits, number)
+  }
+
+  fn list_files_for_pull(&self, _owner: &str, _name: &str, number: i64) -> Vec<FileEntry> {
+    self
+      .paged("pr_files", &self.script.pr_files, number)
+      .map(|v| pull_files_from_json(&v))
+      .unwrap_or_default()
+  }
+
+  fn get_user_json(&self, login: &str) -> Option<serde_json::Value> {
+    if !self.hit("user", login) {
+      return None;
+    }
+
+    Self::lookup(&self.script.users, login).cloned()
+  }
+
+  fn get_pull_diff(&self, _owner: &str, _name: &str, number: i64) -> Option<String> {
+    let key = number.to_string();
+
+    if !self.hit("pull_diff", &key) {
+      return None;
+    }
+
+    Self::lookup(&self.script.pull_diff, &key).cloned()
+  }
+
+  fn get_repo_json(&self, owner: &str, name: &str) -> Option<serde_json::Value> {
+    if !self.hit("repo", &format!("{}/{}", owner, name)) {
+      return None;
+    }
+
+    self.script.repo.clone()
+  }
+
+  fn get_branch_protection_json(&self, _owner: &str, _name: &str, branch: &str) -> Option<serde_json::Value> {
+    if !self.hit("branch_protection", branch) {
+      return None;
+    }
+
+    Self::lookup(&self.script.branch_protection, branch).cloned()
+  }
+
+  fn get_commit_status_json(&self, _owner: &str, _name: &str, sha: &str) -> Option<serde_json::Value> {
+    if !self.hit("commit_status", sha) {
+      return None;
+    }
+
+    Self::lookup(&self.script.commit_status, sha).cloned()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn script() -> MockScript {
+    serde_json::from_value(json!({
+      "seed": 7,
+      "pulls_for_commit": { "*": [{ "number": 5 }] },
+      "reviews": { "5": [[{ "id": 1 }, { "id": 2 }], [{ "id": 3 }]] },
+      "users": { "octo": { "type": "User" } }
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn pages_are_concatenated_and_counted() {
+    let api = ScriptedGithubApi::new(script());
+
+    let reviews = api.list_reviews_for_pull_json("o", "r", 5).unwrap();
+
+    assert_eq!(reviews.as_array().unwrap().len(), 3);
+    assert_eq!(api.calls(), vec!["reviews:5#1", "reviews:5#2"]);
+  }
+
+  #[test]
+  fn wildcard_keys_match_any_sha() {
+    let api = ScriptedGithubApi::new(script());
+
+    assert!(api.list_pulls_for_commit_json("o", "r", "abc").is_some());
+    assert!(api.get_user_json("nobody").is_none());
+  }
+
+  #[test]
+  fn failure_injection_is_deterministic_per_seed() {
+    let run = || {
+      let mut s = script();
+      s.error_rate = 0.5;
+      let api = ScriptedGithubApi::new(s);
+
+      (0..32).map(|_| api.get_user_json("octo").is_some()).collect::<Vec<_>>()
+    };
+
+    let a = run();
+
+    assert_eq!(a, run());
+    assert!(a.iter().any(|ok| *ok));
+    assert!(a.iter().any(|ok| !*ok));
+  }
+
+  #[test]
+  fn full_error_rate_fails_every_call() {
+    let mut s = script();
+    s.error_rate = 1.0;
+    let api = ScriptedGithubApi::new(s);
+
+    assert!(api.list_pulls_for_commit_json("o", "r", "abc").is_none());
+    assert!(api.list_reviews_for_pull_json("o", "r", 5).is_none());
+  }
+}
@@ -3,12 +3,12 @@
 // purpose: Best-effort enrichment adding GitHub PR links and PR list to a commit
 // role: enrichment/integration
 // inputs: &mut Commit, repo path
-// outputs: Mutated commit.patch_ref (diff/patch URLs) and commit.github_prs
+// outputs: Mutated commit.patch_ref (diff/patch URLs) and commit.github_prs; optional clipped PR diff text and PR file lists
 // side_effects: Network or local API calls inside github_api::try_fetch_prs_for_commit (best-effort)
 // invariants:
 // - On success, preserves existing commit fields; sets URLs if present in first PR; attaches PR list
 // - On failure, commit remains valid; fields untouched
-// errors: None propagated (best-effort); enrichment failures are ignored
+// errors: None propagated (best-effort); failed lookups are logged per commit (util::warn_commit) and land in the report's warnings[]
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
@@ -20,6 +20,7 @@ use crate::ext::serde_json::JsonFetch;
 use crate::model::{Commit, CommitGithub, PatchReferencesGithub};
 #[cfg(any(test, feature = "testutil"))]
 use crate::model::{GithubPullRequest, GithubUser};
+use crate::util::clip_patch;
 #[cfg(any(test, feature = "testutil"))]
 use crate::util::diff_seconds;
 
@@ -30,9 +31,39 @@ fn commit_patch_refs(owner: &str, name: &str, sha: &str) -> PatchReferencesGithu
     commit_url: Some(base.clone()),
     diff_url: Some(format!("{}.diff", base)),
     patch_url: Some(format!("{}.patch", base)),
+    squash_merged_into: None,
+    note: None,
   }
 }
 
+/// Repoint GitHub patch links at the surviving merge commit when `commit` only reached upstream through a
+/// squash (or rebase) merge of one of its PRs: the PR is merged, its `merge_commit_sha` differs from the commit,
+/// and that merge commit is a local single-parent commit that does not contain this one.
+fn apply_squash_merge_refs(commit: &mut Commit, repo: &str, owner: &str, name: &str) {
+  let Some(gh) = commit.github.as_ref() else { return };
+
+  let squashed = gh.pull_requests.iter().find_map(|pr| {
+    let merge_sha = pr.merge_commit_sha.as_deref()?;
+    let is_squash = pr.merged_at.is_some()
+      && merge_sha != commit.sha
+      && crate::gitio::parent_count(repo, merge_sha) == Some(1)
+      && !crate::gitio::is_ancestor(repo, &commit.sha, merge_sha);
+
+    is_squash.then(|| (pr.number, merge_sha.to_string()))
+  });
+
+  let Some((number, merge_sha)) = squashed else { return };
+  let mut refs = commit_patch_refs(owner, name, &merge_sha);
+  refs.note = Some(format!(
+    "{} was squash-merged via PR #{}; links point at merge commit {}",
+    commit.short_sha,
+    number,
+    crate::util::short_sha(&merge_sha)
+  ));
+  refs.squash_merged_into = Some(merge_sha);
+  commit.patch_references.github = Some(refs);
+}
+
 #[cfg(any(test, feature = "testutil"))]
 fn urls_from_html(html_url: &str) -> (Option<String>, Option<String>) {
   if html_url.is_empty() {
@@ -124,14 +155,91 @@ fn compute_review_metrics(arr: &[serde_json::Value]) -> (i64, i64, Option<String
 /// Enriches a commit with its associated GitHub Pull Request info (best-effort).
 /// Default path uses repository origin and token discovery.
 pub fn enrich_with_github_prs(commit: &mut Commit, repo: &str) {
-  if let Some((owner, name)) = ghapi::parse_origin_github(repo) {
+  if let Some((owner, name)) = ghapi::resolve_github_repo(repo) {
     commit.patch_references.github = Some(commit_patch_refs(&owner, &name, &commit.sha));
   }
 
-  if let Ok(prs) = ghapi::try_fetch_prs_for_commit(repo, &commit.sha) {
-    if !prs.is_empty() {
-      commit.github = Some(CommitGithub { pull_requests: prs });
+  ghapi::take_request_failure();
+
+  match ghapi::try_fetch_prs_for_commit(repo, &commit.sha) {
+    Ok(prs) if !prs.is_empty() => commit.github = Some(CommitGithub { pull_requests: prs }),
+    Ok(_) => {}
+    Err(err) => {
+      crate::util::warn_commit("github", &commit.sha, &format!("pull request lookup failed: {:#}", err));
+    }
+  }
+
+  if let Some(reason) = ghapi::take_request_failure() {
+    crate::util::warn_commit(
+      "github",
+      &commit.sha,
+      &format!("pull request lookup incomplete: {}", reason),
+    );
+  }
+
+  if let Some((owner, name)) = ghapi::resolve_github_repo(repo) {
+    apply_squash_merge_refs(commit, repo, &owner, &name);
+  }
+}
+
+/// Attach the combined PR diff to each merged PR on `commit` (best-effort), clipped to `max_bytes` (0 = no cap).
+pub fn attach_pr_diffs(commit: &mut Commit, repo: &str, max_bytes: usize) {
+  let Some(gh) = commit.github.as_mut() else { return };
+
+  for pr in gh.pull_requests.iter_mut() {
+    if pr.merged_at.is_none() || pr.diff.is_some() {
+      continue;
+    }
+
+    ghapi::take_request_failure();
+
+    if let Some(text) = ghapi::try_fetch_pr_diff(repo, pr.number) {
+      let (diff, clipped) = clip_patch(text, max_bytes);
+      pr.diff = diff;
+      pr.diff_clipped = clipped;
+    } else if let Some(reason) = ghapi::take_request_failure() {
+      crate::util::warn_commit(
+        "github",
+        &commit.sha,
+        &format!("diff of PR #{} skipped: {}", pr.number, reason),
+      );
+    }
+  }
+}
+
+/// Attach GitHub's file list (with per-file additions/deletions) to each PR on `commit` (best-effort).
+/// When the details payload gave no size, classify the PR from these files instead of leaving it unset.
+pub fn attach_pr_files(commit: &mut Commit, repo: &str) {
+  let Some(gh) = commit.github.as_mut() else { return };
+
+  for pr in gh.pull_requests.iter_mut() {
+    if pr.files.is_some() {
+      continue;
+    }
+
+    ghapi::take_request_failure();
+
+    let Some(files) = ghapi::try_fetch_pr_files(repo, pr.number) else {
+      if let Some(reason) = ghapi::take_request_failure() {
+        crate::util::warn_commit(
+          "github",
+          &commit.sha,
+          &format!("files of PR #{} skipped: {}", pr.number, reason),
+        );
+      }
+
+      continue;
+    };
+
+    if files.is_empty() {
+      continue;
     }
+
+    if pr.size_class.is_none() {
+      let (additions, deletions) = crate::commit::sum_additions_deletions(&files);
+      pr.size_class = Some(ghapi::classify_pr_size(additions + deletions).to_string());
+    }
+    pr.files = Some(files);
   }
 }
 
@@ -139,7 +247,7 @@ pub fn enrich_with_github_prs(commit: &mut Commit, repo: &str) {
 #[cfg(any(test, feature = "testutil"))]
 pub fn enrich_with_github_prs_with_api(commit: &mut Commit, repo: &str, api: &dyn GithubApi) {
   // Phase 1: resolve origin; early guard when not a GitHub remote
-  let (owner, name) = match ghapi::parse_origin_github(repo) {
+  let (owner, name) = match ghapi::resolve_github_repo(repo) {
     Some(p) => p,
     None => return,
   };
@@ -212,11 +320,21 @@ pub fn enrich_with_github_prs_with_api(commit: &mut Commit, repo: &str, api: &dy
       change_request_count: None,
       time_to_first_review_seconds: None,
       time_to_merge_seconds: None,
+      review_responses: None,
+      rework_cycles: None,
+      rework_cycle_seconds: None,
       estimated_minutes: None,
       estimated_minutes_min: None,
       estimated_minutes_max: None,
       estimate_confidence: None,
       estimate_basis: None,
+      diff: None,
+      diff_clipped: None,
+      size_class: None,
+      review_depth: None,
+      merged_without_review: None,
+      merge_commit_sha: pr_json.fetch("merge_commit_sha").to::<String>(),
+      files: None,
     };
 
     out.push(item);
@@ -225,6 +343,8 @@ pub fn enrich_with_github_prs_with_api(commit: &mut Commit, repo: &str, api: &dy
   if !out.is_empty() {
     commit.github = Some(CommitGithub { pull_requests: out });
   }
+
+  apply_squash_merge_refs(commit, repo, &owner, &name);
 }
 
 /// Aggregate and enrich PRs across a commit set into a top-level array.
@@ -232,10 +352,14 @@ pub fn enrich_with_github_prs_with_api(commit: &mut Commit, repo: &str, api: &dy
 #[cfg(any(test, feature = "testutil"))]
 pub fn collect_pull_requests_for_commits(commits: &[Commit], repo: &str) -> Option<Vec<GithubPullRequest>> {
   // Phase 1: origin + token; early guards with operator messages
-  let (owner, name) = match ghapi::parse_origin_github(repo) {
+  let (owner, name) = match ghapi::resolve_github_repo(repo) {
     Some(p) => p,
     None => {
-      eprintln!("[github] Skipping PR aggregation: repo origin is not GitHub (origin.remote.url)");
+      crate::util::log(
+        crate::util::LogLevel::Warn,
+        "github",
+        "Skipping PR aggregation: repo origin is not GitHub (origin.remote.url)",
+      );
       return None;
     }
   };
@@ -243,7 +367,11 @@ pub fn collect_pull_requests_for_commits(commits: &[Commit], repo: &str) -> Opti
   let token = match ghapi::get_github_token() {
     Some(t) => t,
     None => {
-      eprintln!("[github] Missing token. Set GITHUB_TOKEN or run: gh auth login");
+      crate::util::log(
+        crate::util::LogLevel::Warn,
+        "github",
+        "Missing token. Set GITHUB_TOKEN or run: gh auth login",
+      );
       return None;
     }
   };
@@ -332,6 +460,8 @@ fn build_aggregated_pr(
   let mut approval_count: Option<i64> = None;
   let mut change_request_count: Option<i64> = None;
   let mut time_to_first_review_seconds: Option<i64> = None;
+  let mut review_responses = None;
+  let mut rework = None;
   let mut approver = None;
 
   if let Some(reviews_json) = api.list_reviews_for_pull_json(owner, name, number) {
@@ -347,6 +477,13 @@ fn build_aggregated_pr(
         time_to_first_review_seconds = diff_seconds(&created, &first);
       }
 
+      review_responses = ghapi::review_responses(arr, created_at.as_deref());
+      // Commit timestamps only matter once changes were requested; skip the extra listing otherwise
+      let commits_json = (changes > 0)
+        .then(|| api.list_commits_in_pull_json(owner, name, number))
+        .flatten();
+      rework = Some(ghapi::rework_cycles(arr, commits_json.as_ref()));
+
       if let Some(login) = latest_login {
         approver = Some(build_github_user(api, &login, None));
       }
@@ -363,6 +500,9 @@ fn build_aggregated_pr(
   let time_to_merge_seconds = merged_at
     .as_ref()
     .and_then(|m| created_at.as_ref().and_then(|c| diff_seconds(c, m)));
+  let (size_class, review_depth, merged_without_review) = ghapi::review_heuristics(Some(pr_json), review_count);
+  let rework_cycles = rework.as_ref().map(|r| r.len() as i64);
+  let rework_cycle_seconds = rework.filter(|r| !r.is_empty());
 
   GithubPullRequest {
     number,
@@ -386,11 +526,21 @@ fn build_aggregated_pr(
     change_request_count,
     time_to_first_review_seconds,
     time_to_merge_seconds,
+    review_responses,
+    rework_cycles,
+    rework_cycle_seconds,
     estimated_minutes: None,
     estimated_minutes_min: None,
     estimated_minutes_max: None,
     estimate_confidence: None,
     estimate_basis: None,
+    diff: None,
+    diff_clipped: None,
+    size_class,
+    review_depth,
+    merged_without_review,
+    merge_commit_sha: pr_json.fetch("merge_commit_sha").to::<String>(),
+    files: None,
   }
 }
 
@@ -405,15 +555,18 @@ mod tests {
       sha: "deadbeefdeadbeefdeadbeefdeadbeefdeadbeef".into(),
       short_sha: "deadbee".into(),
       parents: vec![],
+      refs: None,
       author: crate::model::Person {
         name: "A".into(),
         email: "a@ex".into(),
         date: "".into(),
+        identity: None,
       },
       committer: crate::model::Person {
         name: "A".into(),
         email: "a@ex".into(),
         date: "".into(),
+        identity: None,
       },
       timestamps: crate::model::Timestamps {
         author: 0,
@@ -425,6 +578,7 @@ mod tests {
       subject: "s".into(),
       body: "".into(),
       files: vec![],
+      files_truncated: None,
       diffstat_text: "".into(),
       patch_references: crate::model::PatchReferences {
         embed: false,
@@ -441,6 +595,16 @@ mod tests {
       estimate_confidence: None,
       estimate_basis: None,
       github: None,
+      gitlab: None,
+      blob_stats_unavailable: None,
+      environments: None,
+      patch_allocation: None,
+      automated: None,
+      file_snapshots: None,
+      authored_by_committer: None,
+      issues: None,
+      merge_group: None,
+      release: None,
     };
     c.github = Some(CommitGithub {
       pull_requests: vec![GithubPullRequest {
@@ -465,11 +629,21 @@ mod tests {
         change_request_count: None,
         time_to_first_review_seconds: None,
         time_to_merge_seconds: None,
+        review_responses: None,
+        rework_cycles: None,
+        rework_cycle_seconds: None,
         estimated_minutes: None,
         estimated_minutes_min: None,
         estimated_minutes_max: None,
         estimate_confidence: None,
         estimate_basis: None,
+        diff: None,
+        diff_clipped: None,
+        size_class: None,
+        review_depth: None,
+        merged_without_review: None,
+        merge_commit_sha: None,
+        files: None,
       }],
     });
     c
@@ -489,6 +663,116 @@ mod tests {
     td
   }
 
+  #[test]
+  #[serial]
+  fn attach_pr_diffs_only_for_merged_prs_and_clips() {
+    std::env::set_var("GAR_TEST_PR_DIFF", "diff --git a/x b/x\n+added line\n");
+    let td = init_git_repo_with_origin();
+    let repo = td.path().to_str().unwrap();
+    let mut c = minimal_commit_with_pr(4242);
+    let mut merged = c.github.as_ref().unwrap().pull_requests[0].clone();
+    merged.number = 4243;
+    merged.merged_at = Some("2025-08-10T00:00:00Z".into());
+    c.github.as_mut().unwrap().pull_requests.push(merged);
+
+    attach_pr_diffs(&mut c, repo, 10);
+
+    let prs = &c.github.as_ref().unwrap().pull_requests;
+    assert!(prs[0].diff.is_none());
+    assert_eq!(prs[1].diff_clipped, Some(true));
+    assert!(prs[1].diff.as_ref().unwrap().starts_with("diff --git"));
+    std::env::remove_var("GAR_TEST_PR_DIFF");
+  }
+
+  #[test]
+  #[serial]
+  fn attach_pr_files_maps_statuses_and_sizes_unsized_prs() {
+    std::env::set_var(
+      "GAR_TEST_PR_FILES_JSON",
+      r#"[
+        {"filename": "src/new.rs", "status": "added", "additions": 120, "deletions": 0},
+        {"filename": "src/lib.rs", "status": "renamed", "previous_filename": "src/old.rs", "additions": 3, "deletions": 1},
+        {"filename": "README.md", "status": "removed", "additions": 0, "deletions": 9}
+      ]"#,
+    );
+    let td = init_git_repo_with_origin();
+    let repo = td.path().to_str().unwrap();
+    let mut c = minimal_commit_with_pr(4251);
+    let mut sized = c.github.as_ref().unwrap().pull_requests[0].clone();
+    sized.number = 4252;
+    sized.size_class = Some("xl".into());
+    c.github.as_mut().unwrap().pull_requests.push(sized);
+
+    attach_pr_files(&mut c, repo);
+
+    let prs = &c.github.as_ref().unwrap().pull_requests;
+    let files = prs[0].files.as_ref().unwrap();
+    let statuses: Vec<&str> = files.iter().map(|f| f.status.as_str()).collect();
+    assert_eq!(statuses, vec!["A", "R", "D"]);
+    assert_eq!(files[1].old_path.as_deref(), Some("src/old.rs"));
+    assert_eq!(prs[0].size_class.as_deref(), Some("m"));
+    assert_eq!(prs[1].size_class.as_deref(), Some("xl"));
+    std::env::remove_var("GAR_TEST_PR_FILES_JSON");
+  }
+
+  #[test]
+  fn squash_merged_commit_links_point_at_merge_commit() {
+    let td = init_git_repo_with_origin();
+    let repo = td.path().to_str().unwrap();
+    let git = |args: &[&str]| {
+      let out = std::process::Command::new("git")
+        .args([
+          "-c",
+          "user.name=Fixture Bot",
+          "-c",
+          "user.email=fixture@example.com",
+          "-c",
+          "commit.gpgsign=false",
+        ])
+        .args(args)
+        .current_dir(repo)
+        .output()
+        .unwrap();
+      assert!(out.status.success(), "git {:?} failed", args);
+      String::from_utf8_lossy(&out.stdout).trim().to_string()
+    };
+    std::fs::write(td.path().join("a.txt"), "a\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "base"]);
+    git(&["checkout", "-q", "-b", "feature"]);
+    std::fs::write(td.path().join("b.txt"), "b\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "feature work"]);
+    let original = git(&["rev-parse", "HEAD"]);
+    git(&["checkout", "-q", "-"]);
+    git(&["merge", "-q", "--squash", "feature"]);
+    git(&["commit", "-q", "-m", "Feature work (#7)"]);
+    let squash = git(&["rev-parse", "HEAD"]);
+
+    let mut c = minimal_commit_with_pr(7);
+    c.sha = original.clone();
+    {
+      let pr = &mut c.github.as_mut().unwrap().pull_requests[0];
+      pr.merged_at = Some("2025-08-10T00:00:00Z".into());
+      pr.merge_commit_sha = Some(squash.clone());
+    }
+
+    apply_squash_merge_refs(&mut c, repo, "openai", "example");
+
+    let refs = c.patch_references.github.as_ref().unwrap();
+    assert_eq!(refs.squash_merged_into.as_deref(), Some(squash.as_str()));
+    assert!(refs.commit_url.as_ref().unwrap().ends_with(&squash));
+    assert!(refs.note.as_ref().unwrap().contains("PR #7"));
+
+    // The squash commit itself keeps its own links
+    let mut own = minimal_commit_with_pr(7);
+    own.sha = squash.clone();
+    own.github.as_mut().unwrap().pull_requests[0].merge_commit_sha = Some(squash);
+    own.github.as_mut().unwrap().pull_requests[0].merged_at = Some("2025-08-10T00:00:00Z".into());
+    apply_squash_merge_refs(&mut own, repo, "openai", "example");
+    assert!(own.patch_references.github.is_none());
+  }
+
   #[test]
   #[serial]
   fn enriches_commit_with_pr_links() {
@@ -720,7 +1004,10 @@ mod tests {
         "base": {"ref": "main"},
         "created_at": "2024-01-01T00:00:00Z",
         "closed_at": "2024-01-03T00:00:00Z",
-        "merged_at": "2024-01-03T00:00:00Z"
+        "merged_at": "2024-01-03T00:00:00Z",
+        "additions": 120,
+        "deletions": 30,
+        "review_comments": 3
       })
       .to_string(),
     );
@@ -742,6 +1029,9 @@ mod tests {
     let pr = &out[0];
     assert_eq!(pr.time_to_first_review_seconds, Some(12 * 3600));
     assert_eq!(pr.time_to_merge_seconds, Some(2 * 24 * 3600));
+    assert_eq!(pr.size_class.as_deref(), Some("m"));
+    assert_eq!(pr.review_depth, Some(2.0));
+    assert_eq!(pr.merged_without_review, Some(false));
     std::env::remove_var("GITHUB_TOKEN");
     std::env::remove_var("GAR_TEST_PULL_DETAILS_JSON");
     std::env::remove_var("GAR_TEST_PR_COMMITS_JSON");
@@ -815,6 +1105,9 @@ mod tests {
     fn list_commits_in_pull_json(&self, _o: &str, _n: &str, _num: i64) -> Option<serde_json::Value> {
       None
     }
+    fn list_files_for_pull(&self, _o: &str, _n: &str, _num: i64) -> Vec<crate::model::FileEntry> {
+      Vec::new()
+    }
     fn get_user_json(&self, login: &str) -> Option<serde_json::Value> {
       match login {
         "alice" => Some(json!({"email": "alice@example.com", "type": "User"})),
@@ -822,6 +1115,18 @@ mod tests {
         _ => None,
       }
     }
+    fn get_pull_diff(&self, _o: &str, _n: &str, _num: i64) -> Option<String> {
+      None
+    }
+    fn get_repo_json(&self, _o: &str, _n: &str) -> Option<serde_json::Value> {
+      None
+    }
+    fn get_branch_protection_json(&self, _o: &str, _n: &str, _b: &str) -> Option<serde_json::Value> {
+      None
+    }
+    fn get_commit_status_json(&self, _o: &str, _n: &str, _s: &str) -> Option<serde_json::Value> {
+      None
+    }
   }
 
   #[test]
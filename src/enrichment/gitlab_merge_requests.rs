@@ -0,0 +1,29 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Best-effort enrichment attaching GitLab merge requests to a commit (GitLab counterpart of github_pull_requests)
+// role: enrichment/integration
+// inputs: &mut Commit, repo path
+// outputs: Mutated commit.gitlab.merge_requests when at least one MR contains the commit
+// side_effects: Network or fixture-backed API calls inside gitlab_api::try_fetch_mrs_for_commit (best-effort)
+// invariants:
+// - On failure or when no MR matches, commit.gitlab stays None and other fields are untouched
+// errors: None propagated (best-effort); enrichment failures are ignored
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use crate::enrichment::gitlab_api;
+use crate::model::{Commit, CommitGitlab};
+
+/// True when `repo`'s origin is a GitLab project, so `--github-prs` should use this backend.
+pub fn is_gitlab_origin(repo: &str) -> bool {
+  gitlab_api::parse_origin_gitlab(repo).is_some()
+}
+
+/// Attach merge requests containing `commit` (best-effort).
+pub fn enrich_with_gitlab_mrs(commit: &mut Commit, repo: &str) {
+  if let Ok(mrs) = gitlab_api::try_fetch_mrs_for_commit(repo, &commit.sha) {
+    if !mrs.is_empty() {
+      commit.gitlab = Some(CommitGitlab { merge_requests: mrs });
+    }
+  }
+}
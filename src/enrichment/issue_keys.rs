@@ -0,0 +1,180 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Extract issue keys (Jira `PROJ-123`, GitHub `#123`, ...) from commit subjects/bodies and linked PR/MR titles
+// role: enrichment/issue_keys
+// inputs: --issue-pattern regexes; --issue-url-template templates ({key}, {num}); a processed Commit (PRs/MRs already attached)
+// outputs: commit.issues = [{key, source, url}], None when nothing matched
+// side_effects: None (pure)
+// invariants:
+// - sources are scanned subject → body → PR/MR titles; each key is listed once, with the first source it appeared in
+// - within one text, keys are ordered by position, whichever pattern matched them
+// - one template applies to every pattern; several pair with the patterns by position; no template means no url
+// - {key} is the whole match, {num} its last run of digits (so `#42` and `PROJ-42` both give 42)
+// errors: Invalid regexes, templates without a placeholder, and template/pattern count mismatches are rejected during normalize
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use anyhow::{Context, Result, bail};
+use regex::Regex;
+
+use crate::model::{Commit, IssueRef};
+
+#[derive(Debug, Clone)]
+pub struct IssueKeyMatcher {
+  rules: Vec<(Regex, Option<String>)>,
+}
+
+impl IssueKeyMatcher {
+  /// Compile `--issue-pattern`/`--issue-url-template`; None when no pattern is configured.
+  pub fn new(patterns: &[String], url_templates: &[String]) -> Result<Option<Self>> {
+    if patterns.is_empty() {
+      if !url_templates.is_empty() {
+        bail!("--issue-url-template needs at least one --issue-pattern");
+      }
+
+      return Ok(None);
+    }
+
+    if url_templates.len() > 1 && url_templates.len() != patterns.len() {
+      bail!(
+        "--issue-url-template: give one template for all patterns or one per --issue-pattern ({} patterns, {} templates)",
+        patterns.len(),
+        url_templates.len()
+      );
+    }
+
+    if let Some(t) = url_templates
+      .iter()
+      .find(|t| !t.contains("{key}") && !t.contains("{num}"))
+    {
+      bail!("--issue-url-template {:?} contains neither {{key}} nor {{num}}", t);
+    }
+
+    let rules = patterns
+      .iter()
+      .enumerate()
+      .map(|(i, p)| {
+        let re = Regex::new(p).with_context(|| format!("invalid --issue-pattern {:?}", p))?;
+        let template = match url_templates.len() {
+          1 => url_templates.first(),
+          _ => url_templates.get(i),
+        };
+
+        Ok((re, template.cloned()))
+      })
+      .collect::<Result<Vec<_>>>()?;
+
+    Ok(Some(Self { rules }))
+  }
+
+  /// Issue references in `commit`'s subject, body, and linked PR/MR titles.
+  pub fn find(&self, commit: &Commit) -> Option<Vec<IssueRef>> {
+    let mut out = Vec::new();
+
+    self.scan(&commit.subject, "subject", &mut out);
+    self.scan(&commit.body, "body", &mut out);
+
+    for pr in commit.github.iter().flat_map(|g| &g.pull_requests) {
+      self.scan(&pr.title, "pull_request", &mut out);
+    }
+    for mr in commit.gitlab.iter().flat_map(|g| &g.merge_requests) {
+      self.scan(&mr.title, "pull_request", &mut out);
+    }
+
+    (!out.is_empty()).then_some(out)
+  }
+
+  fn scan(&self, text: &str, source: &str, out: &mut Vec<IssueRef>) {
+    let mut found: Vec<(usize, &str, Option<&String>)> = self
+      .rules
+      .iter()
+      .flat_map(|(re, template)| {
+        re.find_iter(text)
+          .map(move |m| (m.start(), m.as_str(), template.as_ref()))
+      })
+      .collect();
+    found.sort_by_key(|(start, _, _)| *start);
+
+    for (_, key, template) in found {
+      if key.is_empty() || out.iter().any(|i| i.key == key) {
+        continue;
+      }
+
+      out.push(IssueRef {
+        key: key.to_string(),
+        source: source.to_string(),
+        url: template.map(|t| expand(t, key)),
+      });
+    }
+  }
+}
+
+fn expand(template: &str, key: &str) -> String {
+  let num = key
+    .rsplit(|c: char| !c.is_ascii_digit())
+    .find(|s| !s.is_empty())
+    .unwrap_or_default();
+
+  template.replace("{key}", key).replace("{num}", num)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn commit(subject: &str, body: &str, pr_title: &str) -> Commit {
+    let pr = json!({ "number": 9, "title": pr_title, "state": "closed", "html_url": "https://github.com/o/r/pull/9" });
+    let mut c = Commit::fixture("a1").with_pull_requests(json!([pr]));
+    c.subject = subject.into();
+    c.body = body.into();
+    c
+  }
+
+  #[test]
+  fn keys_are_found_in_order_with_urls() {
+    let m = IssueKeyMatcher::new(
+      &[r"\b[A-Z][A-Z0-9]+-\d+\b".into(), r"#\d+".into()],
+      &[
+        "https://jira.example.com/browse/{key}".into(),
+        "https://github.com/o/r/issues/{num}".into(),
+      ],
+    )
+    .unwrap()
+    .unwrap();
+    let c = commit("Fix login (#12) for OPS-7", "Refs OPS-7, WEB-31", "WEB-31: login #40");
+
+    let found: Vec<(&str, &str, Option<&str>)> = m
+      .find(&c)
+      .unwrap()
+      .iter()
+      .map(|i| (i.key.as_str(), i.source.as_str(), i.url.as_deref()))
+      .collect();
+
+    assert_eq!(
+      found,
+      vec![
+        ("#12", "subject", Some("https://github.com/o/r/issues/12")),
+        ("OPS-7", "subject", Some("https://jira.example.com/browse/OPS-7")),
+        ("WEB-31", "body", Some("https://jira.example.com/browse/WEB-31")),
+        ("#40", "pull_request", Some("https://github.com/o/r/issues/40")),
+      ]
+    );
+    assert!(m.find(&commit("Tidy", "", "Tidy")).is_none());
+  }
+
+  #[test]
+  fn configuration_errors_are_rejected() {
+    let p = |s: &str| vec![s.to_string()];
+
+    assert!(IssueKeyMatcher::new(&[], &[]).unwrap().is_none());
+    assert!(IssueKeyMatcher::new(&[], &p("https://x/{key}")).is_err());
+    assert!(IssueKeyMatcher::new(&p("(unclosed"), &[]).is_err());
+    assert!(IssueKeyMatcher::new(&p("#\\d+"), &p("https://x/issues")).is_err());
+    assert!(IssueKeyMatcher::new(&p("#\\d+"), &["https://a/{key}".into(), "https://b/{key}".into()]).is_err());
+
+    let one_template = IssueKeyMatcher::new(&["A-\\d+".into(), "B-\\d+".into()], &p("https://t/{key}")).unwrap();
+    let found = one_template.unwrap().find(&commit("B-2", "", "")).unwrap();
+    assert_eq!(found[0].url.as_deref(), Some("https://t/B-2"));
+  }
+}
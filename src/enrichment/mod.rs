@@ -1,6 +1,6 @@
 // === Module Header (agents-tooling) START ===
 // header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
-// purpose: Namespace for enrichment features (GitHub PRs, etc.)
+// purpose: Namespace for enrichment features (GitHub PRs, GitLab MRs, etc.)
 // role: enrichment/namespace
 // outputs: Public submodules implementing specific enrichments
 // invariants: Each enrichment isolates external integrations and remains best-effort
@@ -9,4 +9,12 @@
 
 pub mod effort;
 pub mod github_api;
+pub mod github_app;
+pub mod github_disk_cache;
+#[cfg(any(test, feature = "mock"))]
+pub mod github_mock;
 pub mod github_pull_requests;
+pub mod gitlab_api;
+pub mod gitlab_merge_requests;
+pub mod issue_keys;
+pub mod repo_policy;
@@ -0,0 +1,145 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Build the optional `repo_policy` summary section (default-branch protection + window-end commit status)
+// role: enrichment/repo-policy
+// inputs: GithubApi backend, owner/name, sha of the last commit at window end
+// outputs: RepoPolicy
+// side_effects: GitHub API calls via the injected backend (repo, branch protection, combined status)
+// invariants:
+// - protection fields default to false/empty when a sub-object is missing from the payload
+// - head_status reflects the legacy combined status API only (check runs are not included)
+// errors: None; missing payloads leave the corresponding field absent (best-effort)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use crate::enrichment::github_api::GithubApi;
+use crate::ext::serde_json::JsonFetch;
+use crate::model::{BranchProtection, CommitStatus, RepoPolicy};
+
+/// Fetch repo metadata, default-branch protection, and the combined status of `head_sha`.
+pub fn build_repo_policy(api: &dyn GithubApi, owner: &str, name: &str, head_sha: Option<&str>) -> RepoPolicy {
+  let default_branch = api
+    .get_repo_json(owner, name)
+    .and_then(|r| r.fetch("default_branch").to::<String>());
+  let branch_protection = default_branch
+    .as_deref()
+    .and_then(|b| api.get_branch_protection_json(owner, name, b))
+    .as_ref()
+    .map(parse_branch_protection);
+  let head_status = build_repo_policy_status(api, owner, name, head_sha);
+
+  RepoPolicy {
+    default_branch,
+    branch_protection,
+    head_status,
+  }
+}
+
+/// Combined status of `head_sha` only (used when protection was already fetched earlier in the run).
+pub fn build_repo_policy_status(
+  api: &dyn GithubApi,
+  owner: &str,
+  name: &str,
+  head_sha: Option<&str>,
+) -> Option<CommitStatus> {
+  let sha = head_sha?;
+  let v = api.get_commit_status_json(owner, name, sha)?;
+
+  Some(parse_commit_status(sha, &v))
+}
+
+fn parse_branch_protection(v: &serde_json::Value) -> BranchProtection {
+  let flag = |path: &str| v.fetch(path).to::<bool>().unwrap_or(false);
+
+  BranchProtection {
+    required_approving_review_count: v
+      .fetch("required_pull_request_reviews.required_approving_review_count")
+      .to::<i64>(),
+    dismiss_stale_reviews: flag("required_pull_request_reviews.dismiss_stale_reviews"),
+    require_code_owner_reviews: flag("required_pull_request_reviews.require_code_owner_reviews"),
+    required_status_checks: v
+      .fetch("required_status_checks.contexts")
+      .to_or_default::<Vec<String>>(),
+    strict_status_checks: flag("required_status_checks.strict"),
+    enforce_admins: flag("enforce_admins.enabled"),
+    required_signatures: flag("required_signatures.enabled"),
+    required_linear_history: flag("required_linear_history.enabled"),
+    allow_force_pushes: flag("allow_force_pushes.enabled"),
+    allow_deletions: flag("allow_deletions.enabled"),
+  }
+}
+
+fn parse_commit_status(sha: &str, v: &serde_json::Value) -> CommitStatus {
+  let contexts = v
+    .fetch("statuses")
+    .to_or_default::<Vec<serde_json::Value>>()
+    .iter()
+    .filter_map(|s| {
+      Some((
+        s.fetch("context").to::<String>()?,
+        s.fetch("state").to_or_default::<String>(),
+      ))
+    })
+    .collect();
+
+  CommitStatus {
+    sha: v.fetch("sha").to::<String>().unwrap_or_else(|| sha.to_string()),
+    state: v.fetch("state").to_or_default::<String>(),
+    total_count: v.fetch("total_count").to_or_default::<i64>(),
+    contexts,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::enrichment::github_mock::{MockScript, ScriptedGithubApi};
+  use serde_json::json;
+
+  fn api() -> ScriptedGithubApi {
+    let script: MockScript = serde_json::from_value(json!({
+      "repo": { "default_branch": "main" },
+      "branch_protection": { "main": {
+        "required_pull_request_reviews": { "required_approving_review_count": 2, "dismiss_stale_reviews": true },
+        "required_status_checks": { "strict": true, "contexts": ["ci/test"] },
+        "enforce_admins": { "enabled": true },
+        "allow_force_pushes": { "enabled": false }
+      }},
+      "commit_status": { "abc": {
+        "sha": "abc", "state": "success", "total_count": 1,
+        "statuses": [{ "context": "ci/test", "state": "success" }]
+      }}
+    }))
+    .unwrap();
+
+    ScriptedGithubApi::new(script)
+  }
+
+  #[test]
+  fn builds_protection_and_head_status() {
+    let p = build_repo_policy(&api(), "o", "r", Some("abc"));
+
+    assert_eq!(p.default_branch.as_deref(), Some("main"));
+    let bp = p.branch_protection.unwrap();
+    assert_eq!(bp.required_approving_review_count, Some(2));
+    assert!(bp.dismiss_stale_reviews && bp.strict_status_checks && bp.enforce_admins);
+    assert!(!bp.require_code_owner_reviews && !bp.allow_force_pushes);
+    assert_eq!(bp.required_status_checks, vec!["ci/test"]);
+    let st = p.head_status.unwrap();
+    assert_eq!(st.state, "success");
+    assert_eq!(st.contexts["ci/test"], "success");
+  }
+
+  #[test]
+  fn unprotected_branch_and_unknown_head_are_absent() {
+    let mut script = MockScript::default();
+    script.repo = Some(json!({ "default_branch": "trunk" }));
+    let api = ScriptedGithubApi::new(script);
+
+    let p = build_repo_policy(&api, "o", "r", Some("zzz"));
+
+    assert_eq!(p.default_branch.as_deref(), Some("trunk"));
+    assert!(p.branch_protection.is_none());
+    assert!(p.head_status.is_none());
+  }
+}
@@ -0,0 +1,119 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --exit-summary: one JSON line on stderr at process end summarizing the run for wrapper scripts
+// role: diagnostics/exit-summary
+// inputs: ranges and commit counts recorded by render, output paths recorded by the range/digest writers, util's warning count, run result
+// outputs: {"kind":"exit_summary","format_version":1,status,error?,ranges,commits,warnings,outputs[],duration_ms} as the last stderr line
+// side_effects: Writes to stderr (also under --quiet, since it was asked for) and to the --json-logs-to file
+// invariants:
+// - exactly one line, emitted after everything else the run prints; keys are stable and additive only (bump format_version otherwise)
+// - outputs are top-level artifacts (reports, manifest, rollup, markdown, archive, digest) in write order; "-" means stdout; shards are not listed
+// - ranges counts ranges processed in this run (not ranges skipped by --resume)
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::sync::Mutex;
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+
+use crate::util;
+
+#[derive(Default)]
+struct RunStats {
+  ranges: usize,
+  commits: usize,
+  outputs: Vec<String>,
+}
+
+static STATS: Lazy<Mutex<RunStats>> = Lazy::new(|| Mutex::new(RunStats::default()));
+
+/// Count one processed range report and its commits.
+pub fn record_range(commits: usize) {
+  if let Ok(mut s) = STATS.lock() {
+    s.ranges += 1;
+    s.commits += commits;
+  }
+}
+
+/// Record a top-level artifact path ("-" for stdout).
+pub fn record_output(path: impl Into<String>) {
+  if let Ok(mut s) = STATS.lock() {
+    s.outputs.push(path.into());
+  }
+}
+
+#[derive(Debug, Serialize)]
+struct ExitSummary {
+  kind: &'static str,
+  format_version: u32,
+  status: &'static str,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  error: Option<String>,
+  ranges: usize,
+  commits: usize,
+  warnings: usize,
+  outputs: Vec<String>,
+  duration_ms: u128,
+}
+
+fn build(started: Instant, result: &anyhow::Result<()>) -> ExitSummary {
+  let (ranges, commits, outputs) = STATS
+    .lock()
+    .map(|s| (s.ranges, s.commits, s.outputs.clone()))
+    .unwrap_or_default();
+
+  ExitSummary {
+    kind: "exit_summary",
+    format_version: 1,
+    status: if result.is_ok() { "ok" } else { "error" },
+    error: result.as_ref().err().map(|e| format!("{:#}", e)),
+    ranges,
+    commits,
+    warnings: util::warning_count(),
+    outputs,
+    duration_ms: started.elapsed().as_millis(),
+  }
+}
+
+/// Print the summary line for a run that started at `started` and ended with `result`.
+pub fn emit(started: Instant, result: &anyhow::Result<()>) {
+  let summary = build(started, result);
+
+  if let Ok(line) = serde_json::to_string(&summary) {
+    eprintln!("{}", line);
+    util::log_json(util::LogLevel::Info, "exit_summary", &line);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn summary_reports_status_counts_and_outputs() {
+    record_range(2);
+    record_output("/tmp/out/report-2025-08.json");
+
+    let ok = serde_json::to_value(build(Instant::now(), &Ok(()))).unwrap();
+    assert_eq!(ok["kind"], "exit_summary");
+    assert_eq!(ok["status"], "ok");
+    assert!(ok.get("error").is_none());
+    assert!(ok["ranges"].as_u64().unwrap() >= 1);
+    assert!(ok["commits"].as_u64().unwrap() >= 2);
+    assert!(
+      ok["outputs"]
+        .as_array()
+        .unwrap()
+        .contains(&"/tmp/out/report-2025-08.json".into())
+    );
+
+    let failed = serde_json::to_value(build(Instant::now(), &Err(anyhow::anyhow!("boom")))).unwrap();
+    assert_eq!(
+      (failed["status"].as_str(), failed["error"].as_str()),
+      (Some("error"), Some("boom"))
+    );
+  }
+}
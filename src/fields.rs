@@ -0,0 +1,226 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --fields projection: keep only the selected commit fields when commits are serialized into reports and shards
+// role: output/projection
+// inputs: --fields selectors (comma-separated or repeated): top-level commit fields, dotted subfields (author.name, files.file), and the files.stats shorthand
+// outputs: FieldSelection (validated at normalize) applied to each serialized commit object
+// side_effects: None
+// invariants:
+// - the first segment of every selector must name a commit field; typos fail during normalize, before any git work
+// - a bare field keeps its whole value, even when subfields of it are also selected
+// - subfields apply to nested objects and to each element of arrays of objects (files[], github.pull_requests[])
+// - projection runs after processing, so summaries, authors, and indexes still see full commits
+// errors: Unknown fields or empty selectors bail with the list of valid fields
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+
+/// Top-level keys of a serialized commit (see `model::Commit`).
+pub const COMMIT_FIELDS: [&str; 30] = [
+  "sha",
+  "short_sha",
+  "parents",
+  "refs",
+  "author",
+  "committer",
+  "timestamps",
+  "subject",
+  "body",
+  "files",
+  "files_truncated",
+  "diffstat_text",
+  "patch_references",
+  "patch_clipped",
+  "patch_lines",
+  "body_lines",
+  "estimated_minutes",
+  "estimated_minutes_min",
+  "estimated_minutes_max",
+  "estimate_confidence",
+  "estimate_basis",
+  "github",
+  "gitlab",
+  "blob_stats_unavailable",
+  "environments",
+  "patch_allocation",
+  "automated",
+  "file_snapshots",
+  "authored_by_committer",
+  "issues",
+];
+
+/// `files.stats`: the numstat view of each file entry.
+const FILES_STATS: [&str; 3] = ["files.file", "files.additions", "files.deletions"];
+
+#[derive(Debug, Clone, Default, PartialEq)]
+enum Node {
+  #[default]
+  All,
+  Some(BTreeMap<String, Node>),
+}
+
+impl Node {
+  fn insert(&mut self, path: &[&str]) {
+    let Some((head, rest)) = path.split_first() else {
+      return;
+    };
+    let Node::Some(children) = self else {
+      return;
+    };
+    let child = children.entry(head.to_string()).or_insert_with(|| {
+      if rest.is_empty() {
+        Node::All
+      } else {
+        Node::Some(BTreeMap::new())
+      }
+    });
+
+    if rest.is_empty() {
+      *child = Node::All;
+    } else {
+      child.insert(rest);
+    }
+  }
+
+  fn apply(&self, v: &mut serde_json::Value) {
+    let Node::Some(children) = self else {
+      return;
+    };
+
+    match v {
+      serde_json::Value::Object(obj) => {
+        obj.retain(|k, _| children.contains_key(k));
+        for (k, child) in children {
+          if let Some(inner) = obj.get_mut(k) {
+            child.apply(inner);
+          }
+        }
+      }
+      serde_json::Value::Array(items) => {
+        for item in items {
+          self.apply(item);
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+/// Parsed `--fields` selection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSelection {
+  selectors: Vec<String>,
+  tree: Node,
+}
+
+impl FieldSelection {
+  /// Validate `selectors`; `None` when nothing was selected (full commits).
+  pub fn parse(selectors: &[String]) -> Result<Option<Self>> {
+    if selectors.is_empty() {
+      return Ok(None);
+    }
+
+    let mut tree = Node::Some(BTreeMap::new());
+
+    for raw in selectors {
+      let selector = raw.trim();
+      let expanded: Vec<&str> = if selector == "files.stats" {
+        FILES_STATS.to_vec()
+      } else {
+        vec![selector]
+      };
+
+      for s in expanded {
+        let path: Vec<&str> = s.split('.').collect();
+
+        if path.iter().any(|p| p.is_empty()) {
+          anyhow::bail!("--fields: empty field name in {:?}", raw);
+        }
+        if !COMMIT_FIELDS.contains(&path[0]) {
+          anyhow::bail!(
+            "--fields: unknown commit field {:?}; expected one of: {}",
+            path[0],
+            COMMIT_FIELDS.join(", ")
+          );
+        }
+
+        tree.insert(&path);
+      }
+    }
+
+    Ok(Some(FieldSelection {
+      selectors: selectors.iter().map(|s| s.trim().to_string()).collect(),
+      tree,
+    }))
+  }
+
+  /// The selectors as given, echoed in `report_options.fields`.
+  pub fn selectors(&self) -> &[String] {
+    &self.selectors
+  }
+
+  /// Drop every field of a serialized commit that was not selected.
+  pub fn apply(&self, commit: &mut serde_json::Value) {
+    self.tree.apply(commit);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn selection(s: &str) -> FieldSelection {
+    let selectors: Vec<String> = s.split(',').map(String::from).collect();
+
+    FieldSelection::parse(&selectors).unwrap().unwrap()
+  }
+
+  #[test]
+  fn keeps_selected_fields_and_subfields() {
+    let mut commit = json!({
+      "sha": "abc", "subject": "Fix", "diffstat_text": "1 file",
+      "author": { "name": "Ann", "email": "a@x", "date": "d" },
+      "committer": { "name": "Ann", "email": "a@x", "date": "d" },
+      "files": [
+        { "file": "a.rs", "status": "M", "additions": 1, "deletions": 2 },
+        { "file": "b.rs", "status": "A", "additions": 3, "deletions": 0 }
+      ]
+    });
+
+    selection("sha, author.name,files.stats,subject").apply(&mut commit);
+
+    assert_eq!(
+      commit,
+      json!({
+        "sha": "abc", "subject": "Fix",
+        "author": { "name": "Ann" },
+        "files": [
+          { "file": "a.rs", "additions": 1, "deletions": 2 },
+          { "file": "b.rs", "additions": 3, "deletions": 0 }
+        ]
+      })
+    );
+  }
+
+  #[test]
+  fn bare_field_wins_over_its_subfields() {
+    let mut commit = json!({ "author": { "name": "Ann", "email": "a@x" }, "sha": "abc" });
+
+    selection("author.name,author").apply(&mut commit);
+
+    assert_eq!(commit, json!({ "author": { "name": "Ann", "email": "a@x" } }));
+  }
+
+  #[test]
+  fn rejects_unknown_and_empty_fields() {
+    let err = FieldSelection::parse(&["sha".into(), "subjet".into()]).unwrap_err();
+    assert!(err.to_string().contains("\"subjet\""));
+
+    assert!(FieldSelection::parse(&["author.".into()]).is_err());
+    assert_eq!(FieldSelection::parse(&[]).unwrap(), None);
+  }
+}
@@ -3,23 +3,158 @@
 // purpose: Provide thin, robust wrappers around `git` CLI to retrieve commit metadata, diffs, stats, and branch info for report generation
 // role: git/io-helpers
 // outputs: Parsed commit meta, numstat/name-status, shortstat, patch text; branch names and ahead/behind/merged signals
-// invariants: Commands include explicit flags; outputs parsed defensively; functions never panic on malformed git output
+// invariants: Commands include explicit flags; outputs parsed defensively; functions never panic on malformed git output; version-dependent flags fall back on older gits (iso dates < 2.2, -M < 2.9); rev_list_all walks --all and yields each SHA once; rev_list_refs walks --branch/--ref refs (HEAD when none); rev_list_first_parent keeps merges and follows first parents only, and merge_group_shas lists what a merge brought in; --path pathspecs always follow a `--` separator, with a leading `:/` when they only exclude; commit_meta/numstat/name_status/patch dispatch through the --git-backend GitBackend (CliBackend unless set)
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
 // --- Git I/O Helpers ---
 // Thin wrappers around `git` commands with small parsing utilities.
 
-use crate::util::run_git;
-use anyhow::Result;
+use crate::util::{git_at_least, run_git};
+use anyhow::{Context, Result};
 use std::collections::HashMap;
 
 type FileStat = (String, Option<i64>, Option<i64>);
 type NumStatMap = HashMap<String, (Option<i64>, Option<i64>)>;
-type NumStats = (Vec<FileStat>, NumStatMap);
+pub type NumStats = (Vec<FileStat>, NumStatMap);
 
-/// Returns commit SHAs in the given window, earliest→latest (date order + reverse).
-pub fn rev_list(repo: &str, since: &str, until: &str, include_merges: bool) -> Result<Vec<String>> {
+/// Returns commit SHAs in the given window, earliest→latest (date order + reverse). Non-empty `paths` keep only
+/// commits touching those pathspecs.
+pub fn rev_list(repo: &str, since: &str, until: &str, include_merges: bool, paths: &[String]) -> Result<Vec<String>> {
+  rev_list_from(repo, since, until, include_merges, &["HEAD"], paths)
+}
+
+/// Like `rev_list`, but walks every ref (`--all`): unmerged branches, remote branches, and tags. Stash and notes
+/// refs are excluded since they are not activity; each SHA appears once.
+pub fn rev_list_all(
+  repo: &str,
+  since: &str,
+  until: &str,
+  include_merges: bool,
+  paths: &[String],
+) -> Result<Vec<String>> {
+  let mut seen = std::collections::HashSet::new();
+  let shas = rev_list_from(repo, since, until, include_merges, &ALL_REFS_ARGS, paths)?;
+
+  Ok(shas.into_iter().filter(|s| seen.insert(s.clone())).collect())
+}
+
+const ALL_REFS_ARGS: [&str; 3] = ["--exclude=refs/stash", "--exclude=refs/notes/*", "--all"];
+
+/// Like `rev_list`, but walks the given refs (`--branch`/`--ref`, e.g. `origin/release/1.4` or a tag) instead of
+/// HEAD; an empty list means HEAD. Commits reachable from several refs appear once.
+pub fn rev_list_refs(
+  repo: &str,
+  since: &str,
+  until: &str,
+  include_merges: bool,
+  refs: &[String],
+  paths: &[String],
+) -> Result<Vec<String>> {
+  let start = start_revs(false, refs);
+  let start: Vec<&str> = start.iter().map(String::as_str).collect();
+
+  rev_list_from(repo, since, until, include_merges, &start, paths)
+}
+
+/// Like `rev_list_refs`, but follows only first parents (`--first-parent`), so each merge stands for the commits it
+/// brought in; merges are always kept.
+pub fn rev_list_first_parent(
+  repo: &str,
+  since: &str,
+  until: &str,
+  refs: &[String],
+  paths: &[String],
+) -> Result<Vec<String>> {
+  let start: Vec<String> = std::iter::once("--first-parent".to_string())
+    .chain(start_revs(false, refs))
+    .collect();
+  let start: Vec<&str> = start.iter().map(String::as_str).collect();
+
+  rev_list_from(repo, since, until, true, &start, paths)
+}
+
+/// Commits `merge` brought in: reachable from it but not from its first parent, earliest→latest, without `merge`
+/// itself. Nested merges are dropped unless `include_merges`.
+pub fn merge_group_shas(repo: &str, merge: &str, include_merges: bool, paths: &[String]) -> Result<Vec<String>> {
+  let mut args: Vec<String> = vec![
+    "-c".into(),
+    "log.showSignature=false".into(),
+    "rev-list".into(),
+    "--date-order".into(),
+    "--reverse".into(),
+    merge.into(),
+    format!("^{}^1", merge),
+  ];
+
+  if !include_merges {
+    args.insert(3, "--no-merges".into());
+  }
+  args.extend(pathspec_args(paths));
+
+  let out = run_git(repo, &args)?;
+
+  Ok(
+    out
+      .lines()
+      .map(str::trim)
+      .filter(|s| !s.is_empty() && *s != merge)
+      .map(str::to_string)
+      .collect(),
+  )
+}
+
+/// Revisions a window walks from: every ref when `all_refs`, else `refs`, else HEAD.
+fn start_revs(all_refs: bool, refs: &[String]) -> Vec<String> {
+  if all_refs {
+    ALL_REFS_ARGS.iter().map(|s| s.to_string()).collect()
+  } else if refs.is_empty() {
+    vec!["HEAD".into()]
+  } else {
+    refs.to_vec()
+  }
+}
+
+/// Pathspec leaving `path` out (`--exclude-path`).
+pub fn exclude_pathspec(path: &str) -> String {
+  format!(":(exclude){}", path)
+}
+
+/// `:(exclude)…`, `:!…`, or `:^…` pathspecs (including long magic that lists `exclude` among others).
+fn is_exclude_pathspec(spec: &str) -> bool {
+  if spec.starts_with(":!") || spec.starts_with(":^") {
+    return true;
+  }
+
+  spec
+    .strip_prefix(":(")
+    .and_then(|rest| rest.split_once(')'))
+    .is_some_and(|(magic, _)| magic.split(',').any(|m| m.trim() == "exclude"))
+}
+
+/// Trailing `-- <pathspec>...` arguments; nothing when `paths` is empty.
+fn pathspec_args(paths: &[String]) -> Vec<String> {
+  if paths.is_empty() {
+    return Vec::new();
+  }
+
+  // Git before 2.13 rejects pathspecs that only exclude; `:/` (the whole tree) gives them something to exclude from.
+  let whole_tree = paths.iter().all(|p| is_exclude_pathspec(p)).then(|| ":/".to_string());
+
+  std::iter::once("--".to_string())
+    .chain(whole_tree)
+    .chain(paths.iter().cloned())
+    .collect()
+}
+
+fn rev_list_from(
+  repo: &str,
+  since: &str,
+  until: &str,
+  include_merges: bool,
+  start: &[&str],
+  paths: &[String],
+) -> Result<Vec<String>> {
   let mut args: Vec<String> = vec![
     "-c".into(),
     "log.showSignature=false".into(),
@@ -28,8 +163,9 @@ pub fn rev_list(repo: &str, since: &str, until: &str, include_merges: bool) -> R
     format!("--until={}", until),
     "--date-order".into(),
     "--reverse".into(),
-    "HEAD".into(),
   ];
+  args.extend(start.iter().map(|s| s.to_string()));
+  args.extend(pathspec_args(paths));
 
   if !include_merges {
     args.insert(4, "--no-merges".into());
@@ -49,12 +185,218 @@ pub fn rev_list(repo: &str, since: &str, until: &str, include_merges: bool) -> R
   )
 }
 
+/// Map each SHA in the window to the short names of the refs (local/remote branches, tags) that contain it,
+/// in refname order. Symbolic refs (e.g. `origin/HEAD`) and refs that do not peel to a commit are skipped.
+pub fn refs_containing(
+  repo: &str,
+  since: &str,
+  until: &str,
+  include_merges: bool,
+) -> Result<HashMap<String, Vec<String>>> {
+  let listing = run_git(
+    repo,
+    &[
+      "for-each-ref".into(),
+      "--format=%(refname)%09%(refname:short)%09%(symref)".into(),
+      "refs/heads".into(),
+      "refs/remotes".into(),
+      "refs/tags".into(),
+    ],
+  )?;
+  let mut map: HashMap<String, Vec<String>> = HashMap::new();
+
+  for line in listing.lines() {
+    let mut fields = line.split('\t');
+    let (Some(full), Some(short)) = (fields.next(), fields.next()) else {
+      continue;
+    };
+
+    if full.is_empty() || fields.next().is_some_and(|symref| !symref.is_empty()) {
+      continue;
+    }
+
+    // Full refnames cannot be mistaken for paths
+    let Ok(shas) = rev_list_from(repo, since, until, include_merges, &[full], &[]) else {
+      continue;
+    };
+
+    for sha in shas {
+      map.entry(sha).or_default().push(short.to_string());
+    }
+  }
+
+  Ok(map)
+}
+
+/// Returns (sha, committer epoch) for commits in the given window, as `rev_list` selects them.
+pub fn commit_epochs(
+  repo: &str,
+  since: &str,
+  until: &str,
+  include_merges: bool,
+  paths: &[String],
+) -> Result<Vec<(String, i64)>> {
+  let mut args: Vec<String> = vec![
+    "log".into(),
+    format!("--since={}", since),
+    format!("--until={}", until),
+    "--format=%H %ct".into(),
+    "HEAD".into(),
+  ];
+  args.extend(pathspec_args(paths));
+
+  if !include_merges {
+    args.insert(1, "--no-merges".into());
+  }
+
+  let out = run_git(repo, &args)?;
+
+  Ok(
+    out
+      .lines()
+      .filter_map(|l| {
+        let (sha, ct) = l.trim().split_once(' ')?;
+
+        Some((sha.to_string(), ct.parse().ok()?))
+      })
+      .collect(),
+  )
+}
+
+/// Returns (sha, committer epoch, author email) for commits in the given window, as `rev_list` (or `rev_list_all`
+/// when `all_refs`, `rev_list_refs` when `refs` is non-empty) selects them.
+pub fn commit_epoch_authors(
+  repo: &str,
+  since: &str,
+  until: &str,
+  include_merges: bool,
+  all_refs: bool,
+  refs: &[String],
+  paths: &[String],
+) -> Result<Vec<(String, i64, String)>> {
+  let mut args: Vec<String> = vec![
+    "log".into(),
+    format!("--since={}", since),
+    format!("--until={}", until),
+    "--format=%H %ct %ae".into(),
+  ];
+  args.extend(start_revs(all_refs, refs));
+  args.extend(pathspec_args(paths));
+
+  if !include_merges {
+    args.insert(1, "--no-merges".into());
+  }
+
+  let out = run_git(repo, &args)?;
+
+  Ok(
+    out
+      .lines()
+      .filter_map(|l| {
+        let mut parts = l.trim().splitn(3, ' ');
+        let sha = parts.next()?.to_string();
+        let ct = parts.next()?.parse().ok()?;
+
+        Some((sha, ct, parts.next().unwrap_or_default().to_string()))
+      })
+      .collect(),
+  )
+}
+
+/// Merge commits in the window (regardless of --include-merges), earliest→latest.
+pub fn merge_commits(repo: &str, since: &str, until: &str) -> Result<Vec<String>> {
+  let args: Vec<String> = vec![
+    "rev-list".into(),
+    "--merges".into(),
+    format!("--since={}", since),
+    format!("--until={}", until),
+    "--date-order".into(),
+    "--reverse".into(),
+    "HEAD".into(),
+  ];
+
+  let out = run_git(repo, &args)?;
+
+  Ok(
+    out
+      .lines()
+      .map(str::trim)
+      .filter(|l| !l.is_empty())
+      .map(String::from)
+      .collect(),
+  )
+}
+
+/// Merge commits in the window (regardless of --include-merges) walked from `start_revs(all_refs, refs)`, as
+/// (sha, mailmapped committer name, committer email), earliest→latest.
+pub fn merge_committers(
+  repo: &str,
+  since: &str,
+  until: &str,
+  all_refs: bool,
+  refs: &[String],
+) -> Result<Vec<(String, String, String)>> {
+  let mut args: Vec<String> = vec![
+    "log".into(),
+    "--merges".into(),
+    format!("--since={}", since),
+    format!("--until={}", until),
+    "--date-order".into(),
+    "--reverse".into(),
+    "--format=%H%x09%cN%x09%cE".into(),
+  ];
+  args.extend(start_revs(all_refs, refs));
+
+  let out = run_git(repo, &args)?;
+
+  Ok(
+    out
+      .lines()
+      .filter_map(|l| {
+        let mut parts = l.trim().splitn(3, '\t');
+
+        Some((
+          parts.next()?.to_string(),
+          parts.next()?.to_string(),
+          parts.next()?.to_string(),
+        ))
+      })
+      .collect(),
+  )
+}
+
+/// Files whose merge result differs from every parent (combined diff), i.e. changed while resolving the merge.
+pub fn merge_resolved_files(repo: &str, sha: &str) -> Result<Vec<String>> {
+  let args: Vec<String> = vec![
+    "diff-tree".into(),
+    "-c".into(),
+    "-r".into(),
+    "--name-only".into(),
+    "--no-commit-id".into(),
+    sha.into(),
+  ];
+
+  let out = run_git(repo, &args)?;
+
+  Ok(
+    out
+      .lines()
+      .map(str::trim)
+      .filter(|l| !l.is_empty())
+      .map(String::from)
+      .collect(),
+  )
+}
+
 /// Parsed metadata for a commit.
 pub struct Meta {
   pub sha: String,
   pub parents: Vec<String>,
   pub author_name: String,
   pub author_email: String,
+  /// Author after .mailmap (%aN/%aE); equal to author_name/author_email when no mapping applies
+  pub author_mailmap_name: String,
+  pub author_mailmap_email: String,
   pub author_date: String,
   pub committer_name: String,
   pub committer_email: String,
@@ -67,7 +409,7 @@ pub struct Meta {
 
 // Mapping for the NUL (\0) separated pretty-format used in `commit_meta`.
 //
-// fmt = "%H%x00%P%x00%an%x00%ae%x00%ad%x00%cN%x00%cE%x00%cD%x00%at%x00%ct%x00%s%x00%b"
+// fmt = "%H%x00%P%x00%an%x00%ae%x00%ad%x00%cN%x00%cE%x00%cD%x00%at%x00%ct%x00%s%x00%b%x00%aN%x00%aE"
 //
 // Indices:
 //   0 -> %H   full commit SHA (40 hex chars)
@@ -82,6 +424,8 @@ pub struct Meta {
 //   9 -> %ct  committer timestamp (epoch seconds, UTC)
 //  10 -> %s   subject (first line / first sentence of commit message)
 //  11 -> %b   body (rest of message, can be multi-line; may be empty)
+//  12 -> %aN  author name after .mailmap
+//  13 -> %aE  author email after .mailmap
 const IDX_H: usize = 0;
 const IDX_P: usize = 1;
 const IDX_AN: usize = 2;
@@ -94,93 +438,230 @@ const IDX_AT: usize = 8;
 const IDX_CT: usize = 9;
 const IDX_S: usize = 10;
 const IDX_B: usize = 11;
+const IDX_AMN: usize = 12;
+const IDX_AME: usize = 13;
 
-/// Show commit metadata via `git show --no-patch` using a NUL-separated format.
-pub fn commit_meta(repo: &str, sha: &str) -> Result<Meta> {
-  let fmt = "%H%x00%P%x00%an%x00%ae%x00%ad%x00%cN%x00%cE%x00%cD%x00%at%x00%ct%x00%s%x00%b";
-  let args: Vec<String> = vec![
-    "show".into(),
-    "--no-patch".into(),
-    "--date=iso-strict".into(),
-    format!("--pretty=format:{}", fmt),
-    sha.into(),
-  ];
+/// `--date=iso-strict` arrived in git 2.2; older gits get `--date=iso`, rewritten to the strict form.
+const ISO_STRICT_SINCE: (u32, u32) = (2, 2);
+/// `diff.renames` defaults to true since git 2.9; older gits need `-M` for the same rename detection.
+const RENAMES_DEFAULT_SINCE: (u32, u32) = (2, 9);
 
-  let out = run_git(repo, &args)?;
+/// `--date=iso` output ("2025-08-12 10:00:00 +0200") in `--date=iso-strict` form ("2025-08-12T10:00:00+02:00").
+fn iso_to_strict(iso: &str) -> String {
+  let mut parts = iso.split_whitespace();
 
-  let parts: Vec<&str> = out.split('\u{0}').collect();
-  let get = |i: usize| -> String { parts.get(i).unwrap_or(&"").to_string() };
-  // See index mapping above for details on each field.
-  let at: i64 = get(IDX_AT).parse().unwrap_or(0);
-  let ct: i64 = get(IDX_CT).parse().unwrap_or(0);
-
-  Ok(Meta {
-    sha: get(IDX_H),
-    parents: if get(IDX_P).is_empty() {
-      vec![]
-    } else {
-      get(IDX_P).split_whitespace().map(|s| s.to_string()).collect()
-    },
-    author_name: get(IDX_AN),
-    author_email: get(IDX_AE),
-    author_date: get(IDX_AD),
-    committer_name: get(IDX_CN),
-    committer_email: get(IDX_CE),
-    committer_date: get(IDX_CD),
-    at,
-    ct,
-    subject: get(IDX_S),
-    body: get(IDX_B),
-  })
-}
-
-/// Show per-file additions/deletions with `--numstat` (path, additions, deletions).
-pub fn commit_numstat(repo: &str, sha: &str) -> Result<NumStats> {
-  let args: Vec<String> = vec![
-    "show".into(),
-    "--numstat".into(),
-    "--format=".into(),
-    "--no-color".into(),
-    sha.into(),
-  ];
+  match (parts.next(), parts.next(), parts.next()) {
+    (Some(d), Some(t), Some(z)) if z.len() == 5 => format!("{}T{}{}:{}", d, t, &z[..3], &z[3..]),
+    _ => iso.to_string(),
+  }
+}
 
-  let out = run_git(repo, &args)?;
+/// `show` plus `-M` on gits that do not detect renames by default.
+fn show_args() -> Vec<String> {
+  let mut args = vec!["show".to_string()];
 
-  let mut files = Vec::new();
-  let mut map: NumStatMap = HashMap::new();
+  if !git_at_least(RENAMES_DEFAULT_SINCE.0, RENAMES_DEFAULT_SINCE.1) {
+    args.push("-M".into());
+  }
 
-  for line in out.lines() {
-    let parts: Vec<&str> = line.split('\t').collect();
+  args
+}
 
-    if parts.len() != 3 {
-      continue;
+/// Per-commit reads made several times for every processed commit; `--git-backend` picks the implementation.
+pub trait GitBackend: Send + Sync {
+  fn commit_meta(&self, repo: &str, sha: &str) -> Result<Meta>;
+  fn commit_numstat(&self, repo: &str, sha: &str, paths: &[String]) -> Result<NumStats>;
+  fn commit_name_status(&self, repo: &str, sha: &str, paths: &[String]) -> Result<Vec<HashMap<String, String>>>;
+  fn commit_patch(&self, repo: &str, sha: &str, paths: &[String]) -> Result<String>;
+}
+
+/// One `git` subprocess per read; always available, and the fallback whenever libgit2 cannot answer exactly.
+pub struct CliBackend;
+
+impl GitBackend for CliBackend {
+  /// Show commit metadata via `git show --no-patch` using a NUL-separated format.
+  fn commit_meta(&self, repo: &str, sha: &str) -> Result<Meta> {
+    let fmt = "%H%x00%P%x00%an%x00%ae%x00%ad%x00%cN%x00%cE%x00%cD%x00%at%x00%ct%x00%s%x00%b%x00%aN%x00%aE";
+    let strict = git_at_least(ISO_STRICT_SINCE.0, ISO_STRICT_SINCE.1);
+    let args: Vec<String> = vec![
+      "show".into(),
+      "--no-patch".into(),
+      if strict { "--date=iso-strict" } else { "--date=iso" }.into(),
+      format!("--pretty=format:{}", fmt),
+      sha.into(),
+    ];
+
+    let out = run_git(repo, &args)?;
+
+    let parts: Vec<&str> = out.split('\u{0}').collect();
+    let get = |i: usize| -> String { parts.get(i).unwrap_or(&"").to_string() };
+    // See index mapping above for details on each field.
+    let at: i64 = get(IDX_AT).parse().unwrap_or(0);
+    let ct: i64 = get(IDX_CT).parse().unwrap_or(0);
+
+    Ok(Meta {
+      sha: get(IDX_H),
+      parents: if get(IDX_P).is_empty() {
+        vec![]
+      } else {
+        get(IDX_P).split_whitespace().map(|s| s.to_string()).collect()
+      },
+      author_name: get(IDX_AN),
+      author_email: get(IDX_AE),
+      author_mailmap_name: get(IDX_AMN),
+      author_mailmap_email: get(IDX_AME),
+      author_date: if strict {
+        get(IDX_AD)
+      } else {
+        iso_to_strict(&get(IDX_AD))
+      },
+      committer_name: get(IDX_CN),
+      committer_email: get(IDX_CE),
+      committer_date: get(IDX_CD),
+      at,
+      ct,
+      subject: get(IDX_S),
+      body: get(IDX_B),
+    })
+  }
+
+  /// Show per-file additions/deletions with `--numstat` (path, additions, deletions), limited to `paths` when given.
+  fn commit_numstat(&self, repo: &str, sha: &str, paths: &[String]) -> Result<NumStats> {
+    let mut args = show_args();
+    args.extend(["--numstat".into(), "--format=".into(), "--no-color".into(), sha.into()]);
+    args.extend(pathspec_args(paths));
+
+    let out = run_git(repo, &args)?;
+
+    let mut files = Vec::new();
+    let mut map: NumStatMap = HashMap::new();
+
+    for line in out.lines() {
+      let parts: Vec<&str> = line.split('\t').collect();
+
+      if parts.len() != 3 {
+        continue;
+      }
+      let to_int = |s: &str| -> Option<i64> { s.parse::<i64>().ok() };
+      let a = to_int(parts[0]);
+      let d = to_int(parts[1]);
+      let path = parts[2].to_string();
+
+      map.insert(path.clone(), (a, d));
+      files.push((path, a, d));
     }
-    let to_int = |s: &str| -> Option<i64> { s.parse::<i64>().ok() };
-    let a = to_int(parts[0]);
-    let d = to_int(parts[1]);
-    let path = parts[2].to_string();
 
-    map.insert(path.clone(), (a, d));
-    files.push((path, a, d));
+    Ok((files, map))
+  }
+
+  /// Show name-status with `--name-status -z` and parse into a vec of maps (status/file/old_path), limited to `paths`
+  /// when given.
+  fn commit_name_status(
+    &self,
+    repo: &str,
+    sha: &str,
+    paths: &[String],
+  ) -> Result<Vec<std::collections::HashMap<String, String>>> {
+    // Use -z to split by NUL
+    let mut args = show_args();
+    args.extend([
+      "--name-status".into(),
+      "-z".into(),
+      "--format=".into(),
+      "--no-color".into(),
+      sha.into(),
+    ]);
+    args.extend(pathspec_args(paths));
+
+    let out = run_git(repo, &args)?;
+
+    Ok(parse_name_status(&out))
+  }
+
+  /// Show full patch as a unified diff text, limited to `paths` when given.
+  fn commit_patch(&self, repo: &str, sha: &str, paths: &[String]) -> Result<String> {
+    let mut args = show_args();
+    args.extend(["--patch".into(), "--format=".into(), "--no-color".into(), sha.into()]);
+    args.extend(pathspec_args(paths));
+
+    run_git(repo, &args)
   }
+}
+
+/// Values accepted by `--git-backend`.
+pub const GIT_BACKENDS: [&str; 2] = ["cli", "libgit2"];
+
+static BACKEND: once_cell::sync::OnceCell<Box<dyn GitBackend>> = once_cell::sync::OnceCell::new();
 
-  Ok((files, map))
+/// Install the process-wide per-commit backend by `--git-backend` name (first call wins).
+pub fn set_backend(name: &str) -> Result<()> {
+  let backend: Box<dyn GitBackend> = match name {
+    "cli" => Box::new(CliBackend),
+    #[cfg(feature = "libgit2")]
+    "libgit2" => Box::new(crate::gitio_libgit2::Libgit2Backend),
+    #[cfg(not(feature = "libgit2"))]
+    "libgit2" => anyhow::bail!(
+      "--git-backend libgit2 requires a build with the `libgit2` feature (cargo install --features libgit2)"
+    ),
+    other => anyhow::bail!(
+      "unknown git backend {:?} (expected one of: {})",
+      other,
+      GIT_BACKENDS.join(", ")
+    ),
+  };
+
+  let _ = BACKEND.set(backend);
+  Ok(())
 }
 
-/// Show name-status with `--name-status -z` and parse into a vec of maps (status/file/old_path).
-pub fn commit_name_status(repo: &str, sha: &str) -> Result<Vec<std::collections::HashMap<String, String>>> {
-  // Use -z to split by NUL
-  let args: Vec<String> = vec![
+fn backend() -> &'static dyn GitBackend {
+  BACKEND.get().map(|b| b.as_ref()).unwrap_or(&CliBackend)
+}
+
+/// Commit metadata (author/committer identity and dates, subject, body) from the selected backend.
+pub fn commit_meta(repo: &str, sha: &str) -> Result<Meta> {
+  backend().commit_meta(repo, sha)
+}
+
+/// Per-file additions/deletions (path, additions, deletions; None for binary), limited to `paths` when given.
+pub fn commit_numstat(repo: &str, sha: &str, paths: &[String]) -> Result<NumStats> {
+  backend().commit_numstat(repo, sha, paths)
+}
+
+/// Name-status entries (status/file/old_path) with rename detection, limited to `paths` when given.
+pub fn commit_name_status(repo: &str, sha: &str, paths: &[String]) -> Result<Vec<HashMap<String, String>>> {
+  backend().commit_name_status(repo, sha, paths)
+}
+
+/// Full unified diff text, limited to `paths` when given.
+pub fn commit_patch(repo: &str, sha: &str, paths: &[String]) -> Result<String> {
+  backend().commit_patch(repo, sha, paths)
+}
+
+/// Name-status without rename detection; reads trees only, so it never needs blob content (partial clones).
+pub fn commit_name_status_no_renames(
+  repo: &str,
+  sha: &str,
+  paths: &[String],
+) -> Result<Vec<std::collections::HashMap<String, String>>> {
+  let mut args: Vec<String> = vec![
     "show".into(),
     "--name-status".into(),
+    "--no-renames".into(),
     "-z".into(),
     "--format=".into(),
     "--no-color".into(),
     sha.into(),
   ];
+  args.extend(pathspec_args(paths));
 
   let out = run_git(repo, &args)?;
 
+  Ok(parse_name_status(&out))
+}
+
+/// Parse `--name-status -z` output into a vec of maps (status/file/old_path).
+fn parse_name_status(out: &str) -> Vec<std::collections::HashMap<String, String>> {
   let parts: Vec<&str> = out.split('\u{0}').collect();
   let mut res: Vec<std::collections::HashMap<String, String>> = Vec::new();
   let mut index = 0;
@@ -221,7 +702,7 @@ pub fn commit_name_status(repo: &str, sha: &str) -> Result<Vec<std::collections:
     }
   }
 
-  Ok(res)
+  res
 }
 
 /// Show shortstat and return the trailing summary line.
@@ -242,17 +723,44 @@ pub fn commit_shortstat(repo: &str, sha: &str) -> Result<String> {
   Ok(s)
 }
 
-/// Show full patch as a unified diff text.
-pub fn commit_patch(repo: &str, sha: &str) -> Result<String> {
-  let args: Vec<String> = vec![
-    "show".into(),
-    "--patch".into(),
-    "--format=".into(),
-    "--no-color".into(),
-    sha.into(),
-  ];
+/// Blob id and raw bytes of `path` as of `sha` (post-image).
+pub fn file_at(repo: &str, sha: &str, path: &str) -> Result<(String, Vec<u8>)> {
+  let spec = format!("{}:{}", sha, path);
+  let blob = run_git(repo, &["rev-parse".into(), spec])?.trim().to_string();
+  let out = crate::util::git_command(repo)
+    .args(["cat-file", "blob", &blob])
+    .output()?;
+
+  if !out.status.success() {
+    anyhow::bail!(
+      "git cat-file blob {} failed: {}",
+      blob,
+      String::from_utf8_lossy(&out.stderr)
+    );
+  }
+
+  Ok((blob, out.stdout))
+}
+
+/// Whether the repository is a partial clone (promisor remote or `extensions.partialClone` set).
+pub fn is_partial_clone(repo: &str) -> bool {
+  let partial_ext = run_git(
+    repo,
+    &["config".into(), "--get".into(), "extensions.partialClone".into()],
+  );
+
+  if partial_ext.map(|v| !v.trim().is_empty()).unwrap_or(false) {
+    return true;
+  }
 
-  run_git(repo, &args)
+  let promisor = run_git(
+    repo,
+    &["config".into(), "--get-regexp".into(), r"^remote\..*\.promisor$".into()],
+  );
+
+  promisor
+    .map(|out| out.lines().any(|l| l.trim().ends_with("true")))
+    .unwrap_or(false)
 }
 
 /// Current branch name or None when HEAD detached.
@@ -288,15 +796,31 @@ pub fn list_local_branches(repo: &str) -> Result<Vec<String>> {
   )
 }
 
-/// Ahead/behind counts comparing HEAD to `branch` (`--left-right --count`).
-pub fn branch_ahead_behind(repo: &str, branch: &str) -> Result<(Option<i64>, Option<i64>)> {
+/// Local branches to check against an unmerged base: all but the current branch when comparing with HEAD (None),
+/// or all but the base itself when an explicit base ref is given (the checked-out branch counts then too).
+pub fn unmerged_candidates(repo: &str, base: Option<&str>) -> Result<Vec<String>> {
+  let skip = match base {
+    Some(b) => Some(b.to_string()),
+    None => current_branch(repo)?,
+  };
+
+  Ok(
+    list_local_branches(repo)?
+      .into_iter()
+      .filter(|b| Some(b) != skip.as_ref())
+      .collect(),
+  )
+}
+
+/// Behind/ahead counts of `branch` relative to `base` (`rev-list --left-right --count base...branch`).
+pub fn branch_ahead_behind(repo: &str, base: &str, branch: &str) -> Result<(Option<i64>, Option<i64>)> {
   let out = run_git(
     repo,
     &[
       "rev-list".into(),
       "--left-right".into(),
       "--count".into(),
-      format!("HEAD...{}", branch),
+      format!("{}...{}", base, branch),
     ],
   )?;
 
@@ -309,17 +833,120 @@ pub fn branch_ahead_behind(repo: &str, branch: &str) -> Result<(Option<i64>, Opt
   }
 }
 
-/// Whether `branch` is merged into HEAD (exit code of `merge-base --is-ancestor`).
-pub fn branch_merged_into_head(repo: &str, branch: &str) -> Result<Option<bool>> {
+/// Merge-base of HEAD and `branch` with its committer epoch.
+pub fn merge_base(repo: &str, branch: &str) -> Result<(String, i64)> {
+  let sha = run_git(repo, &["merge-base".into(), "HEAD".into(), branch.into()])
+    .with_context(|| format!("no merge-base between HEAD and {}", branch))?
+    .trim()
+    .to_string();
+  let ct = run_git(repo, &["show".into(), "-s".into(), "--format=%ct".into(), sha.clone()])?;
+
+  Ok((sha, ct.trim().parse()?))
+}
+
+/// A tag with its creation epoch (tagger date when annotated, else the commit date) and the commit it points at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TagInfo {
+  pub name: String,
+  pub created: i64,
+  pub commit: String,
+}
+
+/// Every tag pointing at a commit, oldest first (then by name).
+pub fn list_tags(repo: &str) -> Result<Vec<TagInfo>> {
+  let out = run_git(
+    repo,
+    &[
+      "for-each-ref".into(),
+      "--format=%(refname:short)%00%(creatordate:raw)%00%(objecttype)%00%(objectname)%00%(*objecttype)%00%(*objectname)"
+        .into(),
+      "refs/tags".into(),
+    ],
+  )?;
+
+  let mut tags: Vec<TagInfo> = out
+    .lines()
+    .filter_map(|line| {
+      let parts: Vec<&str> = line.split('\0').collect();
+      let [name, date, kind, object, peeled_kind, peeled] = parts.as_slice() else {
+        return None;
+      };
+      // Lightweight tags point at the commit; annotated ones peel to it (tags of trees or blobs are skipped)
+      let commit = match (*kind, *peeled_kind) {
+        ("commit", _) => object,
+        ("tag", "commit") => peeled,
+        _ => return None,
+      };
+
+      Some(TagInfo {
+        name: name.to_string(),
+        created: date.split_whitespace().next()?.parse().ok()?,
+        commit: commit.to_string(),
+      })
+    })
+    .collect();
+  tags.sort_by(|a, b| a.created.cmp(&b.created).then_with(|| a.name.cmp(&b.name)));
+
+  Ok(tags)
+}
+
+/// Nearest tag containing each of `shas`, as `git describe --contains` picks it (`git name-rev --tags`); shas that
+/// no tag contains are left out.
+pub fn containing_tags(repo: &str, shas: &[String]) -> Result<HashMap<String, String>> {
+  let mut found = HashMap::new();
+
+  for chunk in shas.chunks(256) {
+    let mut args: Vec<String> = vec!["name-rev".into(), "--tags".into(), "--name-only".into()];
+    args.extend(chunk.iter().cloned());
+    let out = run_git(repo, &args)?;
+
+    for (sha, name) in chunk.iter().zip(out.lines()) {
+      let name = name.trim();
+      // "v1.2~3" or "v1.2^2~1" → "v1.2"; older gits prefix "tags/"
+      let tag = name.split(['~', '^']).next().unwrap_or(name);
+      let tag = tag.strip_prefix("tags/").unwrap_or(tag);
+
+      if !tag.is_empty() && name != "undefined" {
+        found.insert(sha.clone(), tag.to_string());
+      }
+    }
+  }
+
+  Ok(found)
+}
+
+/// Epoch git reads `date` as in `--since`/`--until` (approxidate), None when git does not parse it.
+pub fn approxidate_epoch(repo: &str, date: &str) -> Option<i64> {
+  let out = run_git(repo, &["rev-parse".into(), format!("--since={}", date)]).ok()?;
+
+  out.trim().strip_prefix("--max-age=")?.parse().ok()
+}
+
+/// Full sha of `rev` as a commit; errors name the ref when it does not resolve (e.g. a typo in --unmerged-base).
+pub fn resolve_commit(repo: &str, rev: &str) -> Result<String> {
+  let out = run_git(
+    repo,
+    &[
+      "rev-parse".into(),
+      "--verify".into(),
+      "--quiet".into(),
+      format!("{}^{{commit}}", rev),
+    ],
+  )
+  .with_context(|| format!("{} does not name a commit", rev))?;
+
+  Ok(out.trim().to_string())
+}
+
+/// Whether `branch` is merged into `base` (exit code of `merge-base --is-ancestor`).
+pub fn branch_merged_into(repo: &str, branch: &str, base: &str) -> Result<Option<bool>> {
   // Use merge-base --is-ancestor (exit code indicates result)
-  let args: Vec<String> = vec![
-    "merge-base".into(),
-    "--is-ancestor".into(),
-    branch.into(),
-    "HEAD".into(),
-  ];
+  let args: Vec<String> = vec!["merge-base".into(), "--is-ancestor".into(), branch.into(), base.into()];
 
-  let res = std::process::Command::new("git").args(&args).current_dir(repo).status();
+  let res = crate::util::git_command(repo)
+    .args(&args)
+    .stderr(std::process::Stdio::null())
+    .status();
 
   match res {
     Ok(st) => Ok(Some(st.success())),
@@ -327,25 +954,136 @@ pub fn branch_merged_into_head(repo: &str, branch: &str) -> Result<Option<bool>>
   }
 }
 
-/// Commits in branch but not in HEAD across a window (earliest→latest).
+/// Number of parents of `sha`; None when the commit is not present locally.
+pub fn parent_count(repo: &str, sha: &str) -> Option<usize> {
+  let out = run_git(
+    repo,
+    &[
+      "rev-list".into(),
+      "--parents".into(),
+      "-n".into(),
+      "1".into(),
+      sha.into(),
+    ],
+  )
+  .ok()?;
+
+  out.split_whitespace().count().checked_sub(1)
+}
+
+/// Whether `ancestor` is reachable from `descendant` (false when either is missing locally).
+pub fn is_ancestor(repo: &str, ancestor: &str, descendant: &str) -> bool {
+  crate::util::git_command(repo)
+    .args(["merge-base", "--is-ancestor", ancestor, descendant])
+    .stderr(std::process::Stdio::null())
+    .status()
+    .map(|st| st.success())
+    .unwrap_or(false)
+}
+
+/// Earliest commit per author over all of HEAD's history, or every ref when `all_refs`: `key(name, email)` of the
+/// mailmapped author → (sha, committer epoch). Ties keep the commit git lists last, i.e. the older one in date order.
+pub fn first_commits_by_author(
+  repo: &str,
+  all_refs: bool,
+  key: impl Fn(&str, &str) -> String,
+) -> Result<HashMap<String, (String, i64)>> {
+  let mut args: Vec<String> = vec!["log".into(), "--format=%H%x09%ct%x09%aN%x09%aE".into()];
+  let start: &[&str] = if all_refs { &ALL_REFS_ARGS } else { &["HEAD"] };
+  args.extend(start.iter().map(|s| s.to_string()));
+
+  let out = run_git(repo, &args)?;
+  let mut first: HashMap<String, (String, i64)> = HashMap::new();
+
+  for line in out.lines() {
+    let mut parts = line.trim().splitn(4, '\t');
+    let (Some(sha), Some(Ok(ct)), Some(name), Some(email)) = (
+      parts.next(),
+      parts.next().map(str::parse::<i64>),
+      parts.next(),
+      parts.next(),
+    ) else {
+      continue;
+    };
+    let entry = first.entry(key(name, email)).or_insert_with(|| (sha.to_string(), ct));
+
+    if ct <= entry.1 {
+      *entry = (sha.to_string(), ct);
+    }
+  }
+
+  Ok(first)
+}
+
+/// Committer epoch of the repository's earliest root commit across all refs (None when there are no commits).
+pub fn first_commit_epoch(repo: &str) -> Result<Option<i64>> {
+  let out = run_git(
+    repo,
+    &[
+      "log".into(),
+      "--all".into(),
+      "--max-parents=0".into(),
+      "--format=%ct".into(),
+    ],
+  )?;
+
+  Ok(out.lines().filter_map(|l| l.trim().parse::<i64>().ok()).min())
+}
+
+/// Committer epoch of the tip commit of `branch` (None when the ref has no commits).
+pub fn branch_tip_epoch(repo: &str, branch: &str) -> Result<Option<i64>> {
+  let out = run_git(
+    repo,
+    &[
+      "log".into(),
+      "-1".into(),
+      "--format=%ct".into(),
+      branch.into(),
+      "--".into(),
+    ],
+  )?;
+
+  Ok(out.trim().parse::<i64>().ok())
+}
+
+/// Sha of the last commit reachable from HEAD at `until` (None when HEAD has no commits before it).
+pub fn head_at(repo: &str, until: &str) -> Result<Option<String>> {
+  let out = run_git(
+    repo,
+    &[
+      "rev-list".into(),
+      "-1".into(),
+      format!("--until={}", until),
+      "HEAD".into(),
+    ],
+  )?;
+  let sha = out.trim();
+
+  Ok((!sha.is_empty()).then(|| sha.to_string()))
+}
+
+/// Commits in `branch` but not in `base` across a window (earliest→latest).
 pub fn unmerged_commits_in_range(
   repo: &str,
   branch: &str,
+  base: &str,
   since: &str,
   until: &str,
   include_merges: bool,
+  paths: &[String],
 ) -> Result<Vec<String>> {
   let mut args: Vec<String> = vec![
     "-c".into(),
     "log.showSignature=false".into(),
     "rev-list".into(),
     branch.into(),
-    "^HEAD".into(),
+    format!("^{}", base),
     format!("--since={}", since),
     format!("--until={}", until),
     "--date-order".into(),
     "--reverse".into(),
   ];
+  args.extend(pathspec_args(paths));
 
   if !include_merges {
     args.insert(6, "--no-merges".into());
@@ -378,29 +1116,140 @@ mod tests {
   #[test]
   fn rev_list_and_meta_and_stats_work() {
     let repo = repo_path();
-    let shas = rev_list(&repo, "2025-08-01", "2025-09-01", true).unwrap();
+    let shas = rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
     assert!(shas.len() >= 2);
     let first = &shas[0];
     let meta = commit_meta(&repo, first).unwrap();
     assert!(!meta.sha.is_empty());
     assert!(!meta.subject.is_empty());
-    let (_list, map) = commit_numstat(&repo, first).unwrap();
+    let (_list, map) = commit_numstat(&repo, first, &[]).unwrap();
     assert!(!map.is_empty());
-    let ns = commit_name_status(&repo, first).unwrap();
+    let ns = commit_name_status(&repo, first, &[]).unwrap();
     assert!(!ns.is_empty());
     let short = commit_shortstat(&repo, first).unwrap();
     assert!(short.contains("file"));
-    let patch = commit_patch(&repo, first).unwrap();
+    let patch = commit_patch(&repo, first, &[]).unwrap();
     assert!(patch.contains("diff --git"));
   }
 
   #[test]
   fn rev_list_no_merges_branch() {
     let repo = repo_path();
-    let shas = rev_list(&repo, "2025-08-01", "2025-09-01", false).unwrap();
+    let shas = rev_list(&repo, "2025-08-01", "2025-09-01", false, &[]).unwrap();
     assert!(!shas.is_empty());
   }
 
+  #[test]
+  fn rev_list_all_covers_head_once_and_refs_contain_every_commit() {
+    let repo = repo_path();
+    let head = rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+    let all = rev_list_all(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+    let unique: std::collections::HashSet<&String> = all.iter().collect();
+    assert_eq!(unique.len(), all.len());
+    assert!(head.iter().all(|sha| unique.contains(sha)));
+
+    let refs = refs_containing(&repo, "2025-08-01", "2025-09-01", true).unwrap();
+    assert!(all.iter().all(|sha| refs.get(sha).is_some_and(|r| !r.is_empty())));
+    assert!(refs.values().flatten().all(|r| !r.ends_with("HEAD")));
+  }
+
+  #[test]
+  fn rev_list_refs_walks_named_refs_instead_of_head() {
+    let repo = repo_path();
+    let head = rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+    assert_eq!(
+      rev_list_refs(&repo, "2025-08-01", "2025-09-01", true, &[], &[]).unwrap(),
+      head
+    );
+
+    let alpha = rev_list_refs(&repo, "2025-08-01", "2025-09-01", true, &["feature/alpha".into()], &[]).unwrap();
+    assert!(!alpha.is_empty());
+    assert!(alpha.iter().all(|sha| is_ancestor(&repo, sha, "feature/alpha")));
+
+    let both = vec!["feature/alpha".to_string(), "main".to_string()];
+    let merged = rev_list_refs(&repo, "2025-08-01", "2025-09-01", true, &both, &[]).unwrap();
+    let unique: std::collections::HashSet<&String> = merged.iter().collect();
+    assert_eq!(unique.len(), merged.len());
+  }
+
+  #[test]
+  fn pathspecs_scope_rev_list_and_file_listings() {
+    let repo = repo_path();
+    let spec = vec!["spec/".to_string()];
+    let all = rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+    let scoped = rev_list(&repo, "2025-08-01", "2025-09-01", true, &spec).unwrap();
+    assert!(!scoped.is_empty() && scoped.len() < all.len());
+
+    for sha in &scoped {
+      let (files, _) = commit_numstat(&repo, sha, &spec).unwrap();
+      assert!(files.iter().all(|(path, _, _)| path.starts_with("spec/")));
+      let ns = commit_name_status(&repo, sha, &spec).unwrap();
+      assert!(ns.iter().all(|m| m["file"].starts_with("spec/")));
+      assert!(!commit_patch(&repo, sha, &spec).unwrap().contains("app/"));
+    }
+  }
+
+  #[test]
+  fn exclude_pathspecs_drop_files_and_commits_touching_only_them() {
+    let repo = repo_path();
+    let excluded = vec![exclude_pathspec("app/")];
+    let all = rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+    let kept = rev_list(&repo, "2025-08-01", "2025-09-01", true, &excluded).unwrap();
+    assert!(!kept.is_empty() && kept.len() < all.len());
+
+    for sha in &kept {
+      let (files, _) = commit_numstat(&repo, sha, &excluded).unwrap();
+      assert!(!files.is_empty() && files.iter().all(|(path, _, _)| !path.starts_with("app/")));
+    }
+
+    assert_eq!(pathspec_args(&excluded), vec!["--", ":/", ":(exclude)app/"]);
+    assert_eq!(
+      pathspec_args(&["src/".into(), ":!src/gen".into()]),
+      vec!["--", "src/", ":!src/gen"]
+    );
+    assert!(is_exclude_pathspec(":(glob,exclude)**/*.lock"));
+    assert!(!is_exclude_pathspec(":(glob)**/*.lock"));
+  }
+
+  #[test]
+  fn first_commits_by_author_picks_each_authors_earliest_commit() {
+    let repo = repo_path();
+    let first = first_commits_by_author(&repo, false, |_, email| email.to_ascii_lowercase()).unwrap();
+    let shas = rev_list(&repo, "1970-01-01", "2100-01-01", true, &[]).unwrap();
+    assert!(!first.is_empty());
+
+    for (email, (sha, epoch)) in &first {
+      assert_eq!(email, &email.to_ascii_lowercase());
+      assert!(shas.contains(sha));
+      let earliest = shas
+        .iter()
+        .map(|s| commit_meta(&repo, s).unwrap())
+        .filter(|m| m.author_email.eq_ignore_ascii_case(email))
+        .map(|m| m.ct)
+        .min();
+      assert_eq!(earliest, Some(*epoch));
+    }
+  }
+
+  #[test]
+  fn merge_committers_list_only_merges() {
+    let repo = repo_path();
+    let merges = merge_commits(&repo, "1970-01-01", "2100-01-01").unwrap();
+    let committers = merge_committers(&repo, "1970-01-01", "2100-01-01", false, &[]).unwrap();
+    let shas: Vec<&String> = committers.iter().map(|(sha, _, _)| sha).collect();
+    assert_eq!(shas, merges.iter().collect::<Vec<_>>());
+    assert!(committers.iter().all(|(_, _, email)| !email.is_empty()));
+  }
+
+  #[test]
+  fn commit_epochs_match_rev_list() {
+    let repo = repo_path();
+    let shas = rev_list(&repo, "2025-08-01", "2025-09-01", false, &[]).unwrap();
+    let epochs = commit_epochs(&repo, "2025-08-01", "2025-09-01", false, &[]).unwrap();
+    assert_eq!(epochs.len(), shas.len());
+    assert!(epochs.iter().all(|(sha, ct)| shas.contains(sha) && *ct > 0));
+  }
+
   #[test]
   fn branch_queries() {
     let repo = repo_path();
@@ -409,19 +1258,41 @@ mod tests {
     let branches = list_local_branches(&repo).unwrap();
     assert!(branches.iter().any(|b| b == "main"));
     // In the fixture, main is reset to the feature commit, so it's merged
-    let merged = branch_merged_into_head(&repo, "feature/alpha").unwrap();
+    let merged = branch_merged_into(&repo, "feature/alpha", "HEAD").unwrap();
     assert_eq!(merged, Some(true));
-    let (_behind, _ahead) = branch_ahead_behind(&repo, "feature/alpha").unwrap();
+    let (_behind, _ahead) = branch_ahead_behind(&repo, "HEAD", "feature/alpha").unwrap();
+    assert_eq!(
+      resolve_commit(&repo, "main").unwrap(),
+      resolve_commit(&repo, "HEAD").unwrap()
+    );
+    assert!(resolve_commit(&repo, "no-such-ref").is_err());
+  }
+
+  #[test]
+  fn head_at_window_end() {
+    let repo = repo_path();
+    let shas = rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+    let head = head_at(&repo, "2025-09-01").unwrap().expect("head in window");
+    assert!(shas.contains(&head));
+    assert_eq!(head_at(&repo, "2000-01-01").unwrap(), None);
   }
 
   #[test]
   fn unmerged_range() {
     let repo = repo_path();
-    let unmerged = unmerged_commits_in_range(&repo, "feature/alpha", "2025-08-01", "2025-09-01", true).unwrap();
+    let unmerged =
+      unmerged_commits_in_range(&repo, "feature/alpha", "HEAD", "2025-08-01", "2025-09-01", true, &[]).unwrap();
     // With main reset to feature commit, there should be no unmerged commits
     assert!(unmerged.is_empty());
   }
 
+  #[test]
+  fn iso_dates_convert_to_strict_form() {
+    assert_eq!(iso_to_strict("2025-08-12 10:00:00 +0200"), "2025-08-12T10:00:00+02:00");
+    assert_eq!(iso_to_strict("2025-08-12 10:00:00 -0430"), "2025-08-12T10:00:00-04:30");
+    assert_eq!(iso_to_strict("garbage"), "garbage");
+  }
+
   #[test]
   fn commit_name_status_parses_rename() {
     let td = tempfile::TempDir::new().unwrap();
@@ -449,11 +1320,88 @@ mod tests {
       .unwrap()
       .trim()
       .to_string();
-    let ns = commit_name_status(repo.to_str().unwrap(), &sha).unwrap();
+    let ns = commit_name_status(repo.to_str().unwrap(), &sha, &[]).unwrap();
     // Expect an R status entry with old_path and new file
     let has_rename = ns
       .iter()
       .any(|m| m.get("status").unwrap().starts_with('R') && m.get("old_path").is_some());
     assert!(has_rename, "expected rename entry in name-status");
+
+    // Without rename detection the same change is a plain delete + add
+    let plain = commit_name_status_no_renames(repo.to_str().unwrap(), &sha, &[]).unwrap();
+    let statuses: Vec<&str> = plain.iter().map(|m| m.get("status").unwrap().as_str()).collect();
+    assert!(statuses.contains(&"D") && statuses.contains(&"A"));
+  }
+
+  #[test]
+  fn first_parent_walk_keeps_merges_and_groups_what_they_brought_in() {
+    let td = tempfile::TempDir::new().unwrap();
+    let repo = td.path();
+    let sh = |date: &str, args: &[&str]| {
+      let st = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .status()
+        .unwrap();
+      assert!(st.success(), "git {:?} failed", args);
+    };
+    let commit = |date: &str, file: &str| {
+      std::fs::write(repo.join(file), date).unwrap();
+      sh(date, &["add", "."]);
+      sh(date, &["commit", "-q", "-m", file]);
+    };
+    sh("2025-08-01T10:00:00", &["init", "-q", "-b", "main"]);
+    sh("2025-08-01T10:00:00", &["config", "user.name", "Fixture Bot"]);
+    sh("2025-08-01T10:00:00", &["config", "user.email", "fixture@example.com"]);
+    sh("2025-08-01T10:00:00", &["config", "commit.gpgsign", "false"]);
+    commit("2025-08-01T10:00:00", "a.txt");
+    sh("2025-08-01T10:00:00", &["checkout", "-q", "-b", "feat"]);
+    commit("2025-08-02T10:00:00", "b.txt");
+    commit("2025-08-03T10:00:00", "c.txt");
+    sh("2025-08-03T10:00:00", &["checkout", "-q", "main"]);
+    commit("2025-08-04T10:00:00", "d.txt");
+    sh(
+      "2025-08-05T10:00:00",
+      &["merge", "-q", "--no-ff", "-m", "merge feat", "feat"],
+    );
+
+    let repo = repo.to_str().unwrap();
+    let walk = rev_list_first_parent(repo, "2025-07-01", "2025-09-01", &[], &[]).unwrap();
+    let subjects: Vec<String> = walk.iter().map(|sha| commit_meta(repo, sha).unwrap().subject).collect();
+    assert_eq!(subjects, vec!["a.txt", "d.txt", "merge feat"]);
+
+    let group = merge_group_shas(repo, &walk[2], false, &[]).unwrap();
+    let subjects: Vec<String> = group
+      .iter()
+      .map(|sha| commit_meta(repo, sha).unwrap().subject)
+      .collect();
+    assert_eq!(subjects, vec!["b.txt", "c.txt"]);
+    assert_eq!(
+      merge_group_shas(repo, &walk[2], false, &["c.txt".into()])
+        .unwrap()
+        .len(),
+      1
+    );
+  }
+
+  #[test]
+  fn partial_clone_detection_reads_config() {
+    let td = tempfile::TempDir::new().unwrap();
+    let repo = td.path().to_str().unwrap();
+    let sh = |args: &[&str]| {
+      let st = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .unwrap();
+      assert!(st.success(), "git {:?} failed", args);
+    };
+    sh(&["init", "-q"]);
+    assert!(!is_partial_clone(repo));
+
+    sh(&["config", "remote.origin.promisor", "true"]);
+    assert!(is_partial_clone(repo));
   }
 }
@@ -0,0 +1,342 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: In-process libgit2 implementation of gitio::GitBackend (--git-backend libgit2) to avoid one subprocess per read
+// role: git/io-backend
+// inputs: repo path (or the GIT_DIR override), commit SHA, --path pathspecs
+// outputs: Meta, numstat, name-status, and patch text matching the CLI backend's parsed output
+// side_effects: Opens one Repository per repo per worker thread (cached thread-locally for the process lifetime)
+// invariants:
+// - values are byte-for-byte what gitio::CliBackend parses: %ad iso-strict, %cD RFC 2822, mailmapped %cN/%cE, %s/%b split like git
+// - merges, renames/copies, submodule changes, magic pathspecs (`:(exclude)`, --exclude-path), and numstat paths git would quote are answered by CliBackend instead
+// - `--git-arg` (-c) settings are not seen by libgit2, so cli::normalize refuses them with this backend; only the GIT_DIR override is honored
+// errors: libgit2 failures fall back to CliBackend for that read, with a single warning per process
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, FixedOffset};
+use git2::{Delta, Diff, DiffFindOptions, DiffFormat, DiffOptions, FileMode, Repository, Time};
+
+use crate::gitio::{CliBackend, GitBackend, Meta, NumStats};
+use crate::util::{self, LogLevel};
+
+thread_local! {
+  static REPOS: RefCell<HashMap<String, Repository>> = RefCell::new(HashMap::new());
+}
+
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+/// libgit2 reads for ordinary commits; anything it cannot reproduce exactly goes to `CliBackend`.
+pub struct Libgit2Backend;
+
+/// Run `f` against the cached Repository for `repo` on this thread, opening it on first use.
+fn with_repo<T>(repo: &str, f: impl FnOnce(&Repository) -> Result<T>) -> Result<T> {
+  REPOS.with(|cell| {
+    let mut repos = cell.borrow_mut();
+
+    if !repos.contains_key(repo) {
+      let opened = match util::git_dir_override() {
+        Some(dir) => Repository::open(&dir),
+        None => Repository::discover(repo),
+      }
+      .with_context(|| format!("opening {} with libgit2", repo))?;
+      repos.insert(repo.to_string(), opened);
+    }
+
+    f(&repos[repo])
+  })
+}
+
+/// Answer from libgit2 when it can (`Some`), else from the CLI; errors warn once and also go to the CLI.
+fn or_cli<T>(attempt: Result<Option<T>>, cli: impl FnOnce() -> Result<T>) -> Result<T> {
+  match attempt {
+    Ok(Some(v)) => Ok(v),
+    Ok(None) => cli(),
+    Err(e) => {
+      if !WARNED.swap(true, Ordering::Relaxed) {
+        util::log(
+          LogLevel::Warn,
+          "gar",
+          &format!("libgit2 backend failed ({:#}); falling back to the git CLI", e),
+        );
+      }
+      cli()
+    }
+  }
+}
+
+fn offset_time(t: Time) -> Option<DateTime<FixedOffset>> {
+  let offset = FixedOffset::east_opt(t.offset_minutes() * 60)?;
+
+  DateTime::from_timestamp(t.seconds(), 0).map(|d| d.with_timezone(&offset))
+}
+
+/// `%ad` under `--date=iso-strict`: "2025-08-12T14:03:00+00:00".
+fn iso_strict(t: Time) -> String {
+  offset_time(t)
+    .map(|d| d.format("%Y-%m-%dT%H:%M:%S%:z").to_string())
+    .unwrap_or_default()
+}
+
+/// `%cD`: "Tue, 5 Aug 2025 14:03:00 +0000" (git does not pad the day).
+fn rfc2822(t: Time) -> String {
+  offset_time(t)
+    .map(|d| d.format("%a, %-d %b %Y %H:%M:%S %z").to_string())
+    .unwrap_or_default()
+}
+
+/// Split a raw commit message the way `%s` and `%b` do: the first paragraph joined into one line, then
+/// everything after the blank lines that follow it, verbatim.
+fn split_message(message: &str) -> (String, String) {
+  let mut rest = message.trim_start_matches('\n');
+  let mut subject: Vec<&str> = Vec::new();
+
+  while !rest.is_empty() {
+    let (line, tail) = rest.split_once('\n').unwrap_or((rest, ""));
+
+    if line.trim().is_empty() {
+      break;
+    }
+    subject.push(line.trim_end());
+    rest = tail;
+  }
+
+  while let Some((line, tail)) = rest.split_once('\n') {
+    if !line.trim().is_empty() {
+      break;
+    }
+    rest = tail;
+  }
+
+  if rest.trim().is_empty() {
+    rest = "";
+  }
+
+  (subject.join(" "), rest.to_string())
+}
+
+/// First-parent (or root) diff of `sha`, or None when git's output involves cases this backend leaves to the CLI.
+fn plain_diff<'r>(repo: &'r Repository, sha: &str, paths: &[String]) -> Result<Option<Diff<'r>>> {
+  let commit = repo.revparse_single(sha)?.peel_to_commit()?;
+
+  // libgit2 pathspecs know no `:(…)` magic (--exclude-path, `:/`); git applies those
+  if commit.parent_count() > 1 || paths.iter().any(|p| p.starts_with(':')) {
+    return Ok(None);
+  }
+
+  let parent_tree = match commit.parent_count() {
+    0 => None,
+    _ => Some(commit.parent(0)?.tree()?),
+  };
+  let mut opts = DiffOptions::new();
+
+  for p in paths {
+    opts.pathspec(p);
+  }
+
+  let mut diff = repo.diff_tree_to_tree(parent_tree.as_ref(), Some(&commit.tree()?), Some(&mut opts))?;
+  diff.find_similar(Some(DiffFindOptions::new().renames(true)))?;
+
+  let exotic = diff.deltas().any(|d| {
+    matches!(d.status(), Delta::Renamed | Delta::Copied)
+      || d.old_file().mode() == FileMode::Commit
+      || d.new_file().mode() == FileMode::Commit
+  });
+
+  Ok((!exotic).then_some(diff))
+}
+
+fn delta_path(file: git2::DiffFile<'_>) -> String {
+  file
+    .path_bytes()
+    .map(|p| String::from_utf8_lossy(p).into_owned())
+    .unwrap_or_default()
+}
+
+/// Paths `git --numstat` would print C-quoted (core.quotePath) rather than as-is.
+fn needs_quoting(path: &str) -> bool {
+  path.bytes().any(|b| b < 0x20 || b >= 0x80 || b == b'"' || b == b'\\')
+}
+
+impl Libgit2Backend {
+  fn meta(repo: &str, sha: &str) -> Result<Option<Meta>> {
+    with_repo(repo, |r| {
+      let commit = r.revparse_single(sha)?.peel_to_commit()?;
+      let author = commit.author();
+      let mailmap = r.mailmap()?;
+      let mapped_author = commit.author_with_mailmap(&mailmap)?;
+      let committer = commit.committer_with_mailmap(&mailmap)?;
+      let (subject, body) = split_message(&String::from_utf8_lossy(commit.message_bytes()));
+
+      Ok(Some(Meta {
+        sha: commit.id().to_string(),
+        parents: commit.parent_ids().map(|p| p.to_string()).collect(),
+        author_name: String::from_utf8_lossy(author.name_bytes()).into_owned(),
+        author_email: String::from_utf8_lossy(author.email_bytes()).into_owned(),
+        author_mailmap_name: String::from_utf8_lossy(mapped_author.name_bytes()).into_owned(),
+        author_mailmap_email: String::from_utf8_lossy(mapped_author.email_bytes()).into_owned(),
+        author_date: iso_strict(author.when()),
+        committer_name: String::from_utf8_lossy(committer.name_bytes()).into_owned(),
+        committer_email: String::from_utf8_lossy(committer.email_bytes()).into_owned(),
+        committer_date: rfc2822(committer.when()),
+        at: author.when().seconds(),
+        ct: commit.committer().when().seconds(),
+        subject,
+        body,
+      }))
+    })
+  }
+
+  fn numstat(repo: &str, sha: &str, paths: &[String]) -> Result<Option<NumStats>> {
+    with_repo(repo, |r| {
+      let Some(diff) = plain_diff(r, sha, paths)? else {
+        return Ok(None);
+      };
+      let mut files = Vec::new();
+      let mut map = HashMap::new();
+
+      for idx in 0..diff.deltas().len() {
+        let Some(patch) = git2::Patch::from_diff(&diff, idx)? else {
+          continue;
+        };
+        let path = delta_path(patch.delta().new_file());
+
+        if needs_quoting(&path) {
+          return Ok(None);
+        }
+        let (a, d) = if patch.delta().flags().is_binary() {
+          (None, None)
+        } else {
+          let (_, a, d) = patch.line_stats()?;
+          (Some(a as i64), Some(d as i64))
+        };
+
+        map.insert(path.clone(), (a, d));
+        files.push((path, a, d));
+      }
+
+      Ok(Some((files, map)))
+    })
+  }
+
+  fn name_status(repo: &str, sha: &str, paths: &[String]) -> Result<Option<Vec<HashMap<String, String>>>> {
+    with_repo(repo, |r| {
+      let Some(diff) = plain_diff(r, sha, paths)? else {
+        return Ok(None);
+      };
+
+      let entries = diff
+        .deltas()
+        .map(|d| {
+          let (status, file) = match d.status() {
+            Delta::Added => ("A", d.new_file()),
+            Delta::Deleted => ("D", d.old_file()),
+            Delta::Typechange => ("T", d.new_file()),
+            _ => ("M", d.new_file()),
+          };
+
+          HashMap::from([
+            ("status".to_string(), status.to_string()),
+            ("file".to_string(), delta_path(file)),
+          ])
+        })
+        .collect();
+
+      Ok(Some(entries))
+    })
+  }
+
+  fn patch(repo: &str, sha: &str, paths: &[String]) -> Result<Option<String>> {
+    with_repo(repo, |r| {
+      let Some(diff) = plain_diff(r, sha, paths)? else {
+        return Ok(None);
+      };
+      let mut out: Vec<u8> = Vec::new();
+
+      diff.print(DiffFormat::Patch, |_, _, line| {
+        if matches!(line.origin(), '+' | '-' | ' ') {
+          out.push(line.origin() as u8);
+        }
+        out.extend_from_slice(line.content());
+        true
+      })?;
+
+      Ok(Some(String::from_utf8_lossy(&out).into_owned()))
+    })
+  }
+}
+
+impl GitBackend for Libgit2Backend {
+  fn commit_meta(&self, repo: &str, sha: &str) -> Result<Meta> {
+    or_cli(Self::meta(repo, sha), || CliBackend.commit_meta(repo, sha))
+  }
+
+  fn commit_numstat(&self, repo: &str, sha: &str, paths: &[String]) -> Result<NumStats> {
+    or_cli(Self::numstat(repo, sha, paths), || {
+      CliBackend.commit_numstat(repo, sha, paths)
+    })
+  }
+
+  fn commit_name_status(&self, repo: &str, sha: &str, paths: &[String]) -> Result<Vec<HashMap<String, String>>> {
+    or_cli(Self::name_status(repo, sha, paths), || {
+      CliBackend.commit_name_status(repo, sha, paths)
+    })
+  }
+
+  fn commit_patch(&self, repo: &str, sha: &str, paths: &[String]) -> Result<String> {
+    or_cli(Self::patch(repo, sha, paths), || {
+      CliBackend.commit_patch(repo, sha, paths)
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn repo_path() -> String {
+    if let Ok(dir) = std::env::var("GAR_FIXTURE_REPO_DIR") {
+      return dir;
+    }
+    let p = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/.tmp/tmpdir");
+    std::fs::read_to_string(p).expect("fixture path").trim().to_string()
+  }
+
+  #[test]
+  fn split_message_matches_subject_and_body_placeholders() {
+    assert_eq!(split_message("Fix it\n"), ("Fix it".to_string(), String::new()));
+    assert_eq!(
+      split_message("Wrapped\nsubject\n\n\nBody line\n\nTrailer: x\n"),
+      ("Wrapped subject".to_string(), "Body line\n\nTrailer: x\n".to_string())
+    );
+  }
+
+  #[test]
+  fn matches_cli_backend_on_fixture_commits() {
+    let repo = repo_path();
+    let shas = crate::gitio::rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+
+    for sha in &shas {
+      let (cli, lg2) = (
+        CliBackend.commit_meta(&repo, sha).unwrap(),
+        Libgit2Backend::meta(&repo, sha).unwrap().unwrap(),
+      );
+      assert_eq!(
+        (cli.parents, cli.author_date, cli.committer_date, cli.subject, cli.body),
+        (lg2.parents, lg2.author_date, lg2.committer_date, lg2.subject, lg2.body)
+      );
+      assert_eq!(
+        CliBackend.commit_numstat(&repo, sha, &[]).unwrap().0,
+        Libgit2Backend::numstat(&repo, sha, &[]).unwrap().unwrap().0
+      );
+      assert_eq!(
+        CliBackend.commit_name_status(&repo, sha, &[]).unwrap(),
+        Libgit2Backend::name_status(&repo, sha, &[]).unwrap().unwrap()
+      );
+    }
+  }
+}
@@ -0,0 +1,113 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Merge author aliases into canonical identities via a TOML config (--identity-map) on top of git's .mailmap
+// role: identities/mapping
+// inputs: --identity-map TOML file ([[identities]] name, email, aliases); mailmapped author name/email (%aN/%aE)
+// outputs: IdentityMap; canonical (name, email) per author, recorded as Person.identity and used as the per-author key
+// side_effects: Reads the config file once during normalize
+// invariants:
+// - .mailmap is applied first (by git); the identity map then matches the mailmapped author
+// - an alias is an email (case-insensitive) or a full "Name <email>" (case-insensitive); an identity's own email always matches
+// - entries are evaluated in file order; the first matching identity wins
+// errors: Config read/parse failures bubble up with the config path as context
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IdentityEntry {
+  pub name: String,
+  pub email: String,
+  #[serde(default)]
+  pub aliases: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct IdentityMap {
+  pub identities: Vec<IdentityEntry>,
+}
+
+impl IdentityMap {
+  /// Load an identity map from a TOML file.
+  pub fn load(path: &str) -> Result<Self> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading identity map {}", path))?;
+    let map: IdentityMap = toml::from_str(&text).with_context(|| format!("parsing identity map {}", path))?;
+
+    Ok(map)
+  }
+
+  /// Identity whose email or aliases match `name <email>`, if any.
+  pub fn identity_for(&self, name: &str, email: &str) -> Option<&IdentityEntry> {
+    let contact = format!("{} <{}>", name, email);
+
+    self.identities.iter().find(|id| {
+      id.email.eq_ignore_ascii_case(email)
+        || id
+          .aliases
+          .iter()
+          .any(|a| a.eq_ignore_ascii_case(email) || a.eq_ignore_ascii_case(&contact))
+    })
+  }
+}
+
+/// Canonical (name, email) for an already-mailmapped author: the matching identity's, else unchanged.
+pub fn resolve<'a>(map: Option<&'a IdentityMap>, name: &'a str, email: &'a str) -> (&'a str, &'a str) {
+  match map.and_then(|m| m.identity_for(name, email)) {
+    Some(id) => (&id.name, &id.email),
+    None => (name, email),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CONFIG: &str = r#"
+[[identities]]
+name = "Jane Doe"
+email = "jane@example.com"
+aliases = ["jane@old-corp.com", "J. Doe <jd@users.noreply.github.com>"]
+"#;
+
+  #[test]
+  fn aliases_match_by_email_or_full_contact() {
+    let map: IdentityMap = toml::from_str(CONFIG).unwrap();
+
+    assert_eq!(
+      resolve(Some(&map), "jane", "Jane@Old-Corp.com"),
+      ("Jane Doe", "jane@example.com")
+    );
+    assert_eq!(
+      resolve(Some(&map), "J. Doe", "jd@users.noreply.github.com"),
+      ("Jane Doe", "jane@example.com")
+    );
+    assert_eq!(
+      resolve(Some(&map), "Jane", "JANE@example.com"),
+      ("Jane Doe", "jane@example.com")
+    );
+    // A contact alias needs the name too
+    assert_eq!(
+      resolve(Some(&map), "Someone", "jd@users.noreply.github.com"),
+      ("Someone", "jd@users.noreply.github.com")
+    );
+    assert_eq!(
+      resolve(None, "jane", "jane@old-corp.com"),
+      ("jane", "jane@old-corp.com")
+    );
+  }
+
+  #[test]
+  fn load_parses_toml_config() {
+    let td = tempfile::TempDir::new().unwrap();
+    let p = td.path().join("identities.toml");
+    std::fs::write(&p, CONFIG).unwrap();
+
+    let map = IdentityMap::load(&p.to_string_lossy()).unwrap();
+
+    assert_eq!(map.identities.len(), 1);
+    assert_eq!(map.identities[0].aliases.len(), 2);
+  }
+}
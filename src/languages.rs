@@ -0,0 +1,99 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Map file paths to a language name by extension (or well-known file name), for file entries, churn by language, and effort weights
+// role: classification/languages
+// inputs: File paths from commit entries
+// outputs: Language name per path (None when unknown); FileEntry.language
+// side_effects: None (pure)
+// invariants:
+// - only the final path component is inspected; extensions match case-insensitively, well-known file names exactly
+// - a path with no known extension or name has no language (callers bucket it as OTHER)
+// - enrichment/effort weights by these names, so estimates and `changes_by_language` always agree on what a file is
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use crate::model::FileEntry;
+
+/// Bucket for paths without a known language in `changes_by_language`.
+pub const OTHER: &str = "Other";
+
+/// Extensions (lowercase, without the dot) → language.
+const EXTENSIONS: &[(&[&str], &str)] = &[
+  (&["rs"], "Rust"),
+  (&["ts", "mts", "cts"], "TypeScript"),
+  (&["tsx"], "TSX"),
+  (&["js", "mjs", "cjs"], "JavaScript"),
+  (&["jsx"], "JSX"),
+  (&["go"], "Go"),
+  (&["java"], "Java"),
+  (&["scala"], "Scala"),
+  (&["kt", "kts"], "Kotlin"),
+  (&["py"], "Python"),
+  (&["rb", "rake", "gemspec"], "Ruby"),
+  (&["c", "h"], "C"),
+  (&["cc", "cpp", "cxx", "hh", "hpp", "hxx"], "C++"),
+  (&["cs"], "C#"),
+  (&["swift"], "Swift"),
+  (&["php"], "PHP"),
+  (&["sh", "bash", "zsh"], "Shell"),
+  (&["sql"], "SQL"),
+  (&["html", "htm"], "HTML"),
+  (&["css"], "CSS"),
+  (&["scss", "sass"], "SCSS"),
+  (&["md", "markdown"], "Markdown"),
+  (&["json"], "JSON"),
+  (&["yaml", "yml"], "YAML"),
+  (&["toml"], "TOML"),
+  (&["xml"], "XML"),
+  (&["proto"], "Protocol Buffers"),
+];
+
+/// Extensionless file names → language.
+const FILE_NAMES: &[(&str, &str)] = &[
+  ("Dockerfile", "Dockerfile"),
+  ("Makefile", "Makefile"),
+  ("Rakefile", "Ruby"),
+  ("Gemfile", "Ruby"),
+];
+
+/// Language of `path`, judged by its file name.
+pub fn language_for(path: &str) -> Option<&'static str> {
+  let name = path.rsplit('/').next().unwrap_or(path);
+
+  if let Some((_, lang)) = FILE_NAMES.iter().find(|(n, _)| *n == name) {
+    return Some(lang);
+  }
+
+  let (_, ext) = name.rsplit_once('.')?;
+  let ext = ext.to_ascii_lowercase();
+
+  EXTENSIONS
+    .iter()
+    .find(|(exts, _)| exts.contains(&ext.as_str()))
+    .map(|(_, lang)| *lang)
+}
+
+/// Attach `language` to each file entry whose path has a known language.
+pub fn tag_files(files: &mut [FileEntry]) {
+  for f in files.iter_mut() {
+    f.language = language_for(&f.file).map(str::to_string);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn languages_come_from_the_file_name() {
+    assert_eq!(language_for("src/main.rs"), Some("Rust"));
+    assert_eq!(language_for("web/App.TSX"), Some("TSX"));
+    assert_eq!(language_for(".github/workflows/ci.yml"), Some("YAML"));
+    assert_eq!(language_for("docker/Dockerfile"), Some("Dockerfile"));
+    assert_eq!(language_for("README.md"), Some("Markdown"));
+    // a dot in a directory name is not an extension
+    assert_eq!(language_for("v1.rs/LICENSE"), None);
+    assert_eq!(language_for("Cargo.lock"), None);
+  }
+}
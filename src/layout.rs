@@ -0,0 +1,162 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Own every naming/layout decision for on-disk output (report files, range dirs, commit shard paths) behind --layout
+// role: output/layout
+// inputs: OutputLayout kind; range label; optional unmerged branch; commit epoch, short SHA, and --tz
+// outputs: File names and paths relative to the output base dir
+// side_effects: None
+// invariants:
+// - report-<label>.json, report-<label>.md, and collaboration-<label>.dot sit in the base dir under every layout
+// - the range dir (patches, snapshots, side indexes) is <label>/, unmerged branches use <label>/unmerged/<branch>/
+// - nested (default): <range dir>/<shard>; flat: <label>--<shard> (unmerged: <label>--unmerged--<branch>--<shard>) in the base dir
+// - dated: <range dir>/<YYYY>/<MM>/<DD>/<HH.MM>-<short_sha>.json, in --tz like the shard name
+// - hashed: <range dir>/<first two chars of short_sha>/<shard>
+// - <shard> is util::format_shard_name; branch names replace '/' with "__"
+// errors: parse rejects unknown layout names
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+
+use crate::util::format_shard_name;
+
+/// Output layout strategy for split and multi-range runs (`--layout`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputLayout {
+  #[default]
+  Nested,
+  Flat,
+  Dated,
+  Hashed,
+}
+
+/// Accepted `--layout` values.
+pub const LAYOUTS: [&str; 4] = ["nested", "flat", "dated", "hashed"];
+
+impl OutputLayout {
+  pub fn parse(s: &str) -> Result<Self> {
+    match s.to_ascii_lowercase().as_str() {
+      "nested" => Ok(Self::Nested),
+      "flat" => Ok(Self::Flat),
+      "dated" => Ok(Self::Dated),
+      "hashed" => Ok(Self::Hashed),
+      _ => anyhow::bail!("--layout expects one of {}, got {:?}", LAYOUTS.join(", "), s),
+    }
+  }
+
+  /// Per-range report file in the base dir.
+  pub fn report_file(&self, label: &str) -> String {
+    format!("report-{}.json", label)
+  }
+
+  /// Markdown rendering of a range report (`--format markdown`) in the base dir.
+  pub fn markdown_report_file(&self, label: &str) -> String {
+    format!("report-{}.md", label)
+  }
+
+  /// CSV rendering of a range report (`--format csv`) in the base dir.
+  pub fn csv_report_file(&self, label: &str) -> String {
+    format!("report-{}.csv", label)
+  }
+
+  /// `--format ndjson` stream written into an `--out` directory.
+  pub fn ndjson_report_file(&self, label: &str) -> String {
+    format!("report-{}.ndjson", label)
+  }
+
+  /// Graphviz file for `--collaboration-dot` in the base dir.
+  pub fn collaboration_dot_file(&self, label: &str) -> String {
+    format!("collaboration-{}.dot", label)
+  }
+
+  /// Directory for a range's auxiliary artifacts (patches, snapshots, side indexes).
+  pub fn range_dir(&self, label: &str) -> PathBuf {
+    PathBuf::from(label)
+  }
+
+  /// Directory for an unmerged branch's auxiliary artifacts.
+  pub fn branch_dir(&self, label: &str, branch: &str) -> PathBuf {
+    self.range_dir(label).join("unmerged").join(branch_slug(branch))
+  }
+
+  /// Commit shard path relative to the base dir; `branch` is set for unmerged-branch commits.
+  pub fn shard_path(&self, label: &str, branch: Option<&str>, epoch: i64, short_sha: &str, tz: &str) -> PathBuf {
+    let name = format_shard_name(epoch, short_sha, tz);
+    let dir = match branch {
+      Some(b) => self.branch_dir(label, b),
+      None => self.range_dir(label),
+    };
+
+    match self {
+      Self::Nested => dir.join(name),
+      Self::Flat => {
+        let prefix = match branch {
+          Some(b) => format!("{}--unmerged--{}", label, branch_slug(b)),
+          None => label.to_string(),
+        };
+        PathBuf::from(format!("{}--{}", prefix, name))
+      }
+      Self::Dated => {
+        // shard names are YYYY.MM.DD-HH.MM-<sha>.json; the date part never contains '-'
+        let (date, rest) = name.split_once('-').unwrap_or(("", name.as_str()));
+        date.split('.').fold(dir, |d, part| d.join(part)).join(rest)
+      }
+      Self::Hashed => {
+        let fanout: String = short_sha.chars().take(2).collect();
+        dir.join(fanout).join(name)
+      }
+    }
+  }
+}
+
+fn branch_slug(branch: &str) -> String {
+  branch.replace('/', "__")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  // 2024-09-12T17:16:40Z
+  const EPOCH: i64 = 1_726_161_400;
+
+  fn shard(layout: &str, branch: Option<&str>) -> String {
+    OutputLayout::parse(layout)
+      .unwrap()
+      .shard_path("2024-09", branch, EPOCH, "abcdef1", "utc")
+      .to_string_lossy()
+      .to_string()
+  }
+
+  #[test]
+  fn each_layout_places_range_and_branch_shards() {
+    assert_eq!(shard("nested", None), "2024-09/2024.09.12-17.16-abcdef1.json");
+    assert_eq!(
+      shard("nested", Some("feature/x")),
+      "2024-09/unmerged/feature__x/2024.09.12-17.16-abcdef1.json"
+    );
+    assert_eq!(shard("flat", None), "2024-09--2024.09.12-17.16-abcdef1.json");
+    assert_eq!(
+      shard("flat", Some("feature/x")),
+      "2024-09--unmerged--feature__x--2024.09.12-17.16-abcdef1.json"
+    );
+    assert_eq!(shard("dated", None), "2024-09/2024/09/12/17.16-abcdef1.json");
+    assert_eq!(shard("hashed", None), "2024-09/ab/2024.09.12-17.16-abcdef1.json");
+    assert_eq!(
+      shard("hashed", Some("feature/x")),
+      "2024-09/unmerged/feature__x/ab/2024.09.12-17.16-abcdef1.json"
+    );
+  }
+
+  #[test]
+  fn report_names_are_shared_and_unknown_layouts_are_rejected() {
+    for name in LAYOUTS {
+      let layout = OutputLayout::parse(name).unwrap();
+      assert_eq!(layout.report_file("2024-09"), "report-2024-09.json");
+    }
+    assert_eq!(OutputLayout::default(), OutputLayout::Nested);
+    assert!(OutputLayout::parse("sideways").is_err());
+  }
+}
@@ -0,0 +1,202 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Recognize Git LFS-tracked files and replace pointer-line churn with the LFS object size delta
+// role: commit construction/lfs
+// inputs: repo, commit sha and first parent, FileEntry list (real paths, before redaction)
+// outputs: FileEntry.lfs = true, lfs_size_delta (bytes, post - pre), additions/deletions zeroed when known
+// side_effects: git reads: HEAD:.gitattributes once per repo, <sha>:.gitattributes and pointer blobs for LFS commits
+// invariants:
+// - LFS paths are those matched by a `filter=lfs` pattern in the root .gitattributes as of the commit
+// - repos whose HEAD .gitattributes has no `filter=lfs` skip detection entirely (one git read per run)
+// - size delta uses the pointer's `size` line; a missing side (added/deleted file, not a pointer) counts as 0 bytes
+// - status-only entries (blob stats unavailable) keep additions/deletions as None
+// errors: None; unreadable attributes or blobs leave entries unmarked / sides at 0 (best-effort)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::gitio;
+use crate::model::FileEntry;
+use crate::services::glob_match;
+
+const POINTER_PREFIX: &[u8] = b"version https://git-lfs.github.com/spec/";
+
+static REPO_USES_LFS: Lazy<Mutex<HashMap<String, bool>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Whether HEAD's root .gitattributes routes anything through the LFS filter (cached per repo).
+fn repo_uses_lfs(repo: &str) -> bool {
+  let mut cache = REPO_USES_LFS.lock().unwrap();
+
+  *cache
+    .entry(repo.to_string())
+    .or_insert_with(|| !lfs_patterns(repo, "HEAD").is_empty())
+}
+
+/// Patterns carrying `filter=lfs` in `<rev>:.gitattributes`.
+fn lfs_patterns(repo: &str, rev: &str) -> Vec<String> {
+  gitio::file_at(repo, rev, ".gitattributes")
+    .map(|(_, bytes)| parse_lfs_patterns(&String::from_utf8_lossy(&bytes)))
+    .unwrap_or_default()
+}
+
+fn parse_lfs_patterns(attributes: &str) -> Vec<String> {
+  attributes
+    .lines()
+    .filter_map(|line| {
+      let mut parts = line.split_whitespace();
+      let pattern = parts.next().filter(|p| !p.starts_with('#'))?;
+
+      parts.any(|attr| attr == "filter=lfs").then(|| pattern.to_string())
+    })
+    .collect()
+}
+
+/// gitattributes matching: patterns without a slash match the file name at any depth; others are root-relative.
+fn attr_matches(pattern: &str, path: &str) -> bool {
+  let anchored = pattern.trim_start_matches('/');
+
+  if pattern.contains('/') {
+    return glob_match(anchored.as_bytes(), path.as_bytes());
+  }
+
+  let name = path.rsplit('/').next().unwrap_or(path);
+
+  glob_match(pattern.as_bytes(), name.as_bytes())
+}
+
+/// Object size from LFS pointer text; None when `bytes` is not a pointer.
+fn pointer_size(bytes: &[u8]) -> Option<i64> {
+  if !bytes.starts_with(POINTER_PREFIX) {
+    return None;
+  }
+
+  String::from_utf8_lossy(bytes)
+    .lines()
+    .find_map(|l| l.strip_prefix("size ")?.trim().parse().ok())
+}
+
+fn size_at(repo: &str, rev: &str, path: &str) -> i64 {
+  gitio::file_at(repo, rev, path)
+    .ok()
+    .and_then(|(_, bytes)| pointer_size(&bytes))
+    .unwrap_or(0)
+}
+
+/// Mark LFS-tracked entries of commit `sha` and swap their line stats for the object size delta.
+pub fn mark_lfs_files(repo: &str, sha: &str, parent: Option<&str>, files: &mut [FileEntry]) {
+  if files.is_empty() || !repo_uses_lfs(repo) {
+    return;
+  }
+
+  let patterns = lfs_patterns(repo, sha);
+
+  for f in files
+    .iter_mut()
+    .filter(|f| patterns.iter().any(|p| attr_matches(p, &f.file)))
+  {
+    let before = match (parent, f.status.starts_with('A')) {
+      (Some(parent), false) => size_at(repo, parent, f.old_path.as_deref().unwrap_or(&f.file)),
+      _ => 0,
+    };
+    let after = if f.status.starts_with('D') {
+      0
+    } else {
+      size_at(repo, sha, &f.file)
+    };
+
+    f.lfs = Some(true);
+    f.lfs_size_delta = Some(after - before);
+    f.additions = f.additions.map(|_| 0);
+    f.deletions = f.deletions.map(|_| 0);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::path::Path;
+  use std::process::Command;
+
+  fn pointer(size: u64) -> String {
+    format!(
+      "version https://git-lfs.github.com/spec/v1\noid sha256:{:064x}\nsize {}\n",
+      size, size
+    )
+  }
+
+  fn git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+      .args(args)
+      .current_dir(dir)
+      .env("GIT_AUTHOR_NAME", "A")
+      .env("GIT_AUTHOR_EMAIL", "a@x")
+      .env("GIT_COMMITTER_NAME", "A")
+      .env("GIT_COMMITTER_EMAIL", "a@x")
+      .output()
+      .unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+  }
+
+  fn entry(file: &str, status: &str) -> FileEntry {
+    FileEntry {
+      file: file.into(),
+      status: status.into(),
+      old_path: None,
+      additions: Some(1),
+      deletions: Some(1),
+      service: None,
+      language: None,
+      lfs: None,
+      lfs_size_delta: None,
+    }
+  }
+
+  #[test]
+  fn parses_filter_lfs_patterns_and_matches_like_gitattributes() {
+    let patterns =
+      parse_lfs_patterns("# assets\n*.psd filter=lfs diff=lfs merge=lfs -text\n/media/** filter=lfs\n*.txt text\n");
+
+    assert_eq!(patterns, vec!["*.psd", "/media/**"]);
+    assert!(attr_matches("*.psd", "design/deep/logo.psd"));
+    assert!(attr_matches("/media/**", "media/a/b.mov"));
+    assert!(!attr_matches("/media/**", "docs/media/b.mov"));
+    assert_eq!(pointer_size(pointer(1234).as_bytes()), Some(1234));
+    assert_eq!(pointer_size(b"plain text"), None);
+  }
+
+  #[test]
+  fn pointer_changes_report_size_delta_instead_of_line_churn() {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path();
+    git(dir, &["init", "-q", "-b", "main"]);
+    std::fs::write(
+      dir.join(".gitattributes"),
+      "*.bin filter=lfs diff=lfs merge=lfs -text\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("model.bin"), pointer(1000)).unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "base"]);
+    let base = git(dir, &["rev-parse", "HEAD"]);
+    std::fs::write(dir.join("model.bin"), pointer(4096)).unwrap();
+    std::fs::write(dir.join("notes.txt"), "hi\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "grow model"]);
+    let head = git(dir, &["rev-parse", "HEAD"]);
+
+    let repo = dir.to_string_lossy().to_string();
+    let mut files = vec![entry("model.bin", "M"), entry("notes.txt", "A")];
+    mark_lfs_files(&repo, &head, Some(&base), &mut files);
+
+    assert_eq!(files[0].lfs, Some(true));
+    assert_eq!(files[0].lfs_size_delta, Some(3096));
+    assert_eq!((files[0].additions, files[0].deletions), (Some(0), Some(0)));
+    assert_eq!(files[1].lfs, None);
+    assert_eq!(files[1].additions, Some(1));
+  }
+}
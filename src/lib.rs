@@ -0,0 +1,134 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Library surface for tools that must agree with the reports (time-window resolution, processed commits)
+// role: library/public-api
+// inputs: n/a (re-exports)
+// outputs: git_activity_report::{range_windows, stream, model}
+// side_effects: none
+// invariants:
+// - the binary resolves windows and processes commits through these same modules, so library callers get identical results
+// - only the documented modules are public API; #[doc(hidden)] modules are the report pipeline shared with the binary and benches and may change freely
+// errors: n/a
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+//! Reusable pieces of `git-activity-report`.
+//!
+//! [`range_windows`] resolves `--month`, `--for "last week"`, `--for "every month for the last N months"`,
+//! and `--since/--until` into the labeled ranges the CLI reports on.
+//!
+//! [`stream::CommitStream`] yields the [`model::Commit`]s of a window one at a time, processed exactly as
+//! they appear in a report, without writing anything to disk.
+
+pub mod model;
+pub mod range_windows;
+pub mod stream;
+
+// Report pipeline internals shared with the binary and the benches; not part of the stable API.
+#[doc(hidden)]
+pub mod anomalies;
+#[doc(hidden)]
+pub mod archive;
+#[doc(hidden)]
+pub mod author_stats;
+#[doc(hidden)]
+pub mod automation;
+#[doc(hidden)]
+pub mod boundary;
+#[doc(hidden)]
+pub mod calendar;
+#[doc(hidden)]
+pub mod cli;
+#[doc(hidden)]
+pub mod collaboration;
+#[doc(hidden)]
+pub mod commit;
+#[doc(hidden)]
+pub mod commit_cache;
+#[doc(hidden)]
+pub mod conflict_hotspots;
+#[doc(hidden)]
+pub mod dependencies;
+#[doc(hidden)]
+pub mod digest;
+#[doc(hidden)]
+pub mod enrichment;
+#[doc(hidden)]
+pub mod exit_summary;
+#[doc(hidden)]
+pub mod ext;
+#[doc(hidden)]
+pub mod fields;
+#[doc(hidden)]
+pub mod gitio;
+#[cfg(feature = "libgit2")]
+#[doc(hidden)]
+pub mod gitio_libgit2;
+#[doc(hidden)]
+pub mod identities;
+#[doc(hidden)]
+pub mod languages;
+#[doc(hidden)]
+pub mod layout;
+#[doc(hidden)]
+pub mod lfs;
+#[doc(hidden)]
+pub mod link_check;
+#[doc(hidden)]
+pub mod logging;
+#[doc(hidden)]
+pub mod maintainers;
+#[doc(hidden)]
+pub mod manifest;
+#[doc(hidden)]
+pub mod merge_groups;
+#[doc(hidden)]
+pub mod migrate;
+#[doc(hidden)]
+pub mod new_contributors;
+#[doc(hidden)]
+pub mod out_lock;
+#[doc(hidden)]
+pub mod patch_budget;
+#[doc(hidden)]
+pub mod progress;
+#[doc(hidden)]
+pub mod provenance;
+#[doc(hidden)]
+pub mod redact;
+#[doc(hidden)]
+pub mod releases;
+#[doc(hidden)]
+pub mod render;
+#[doc(hidden)]
+pub mod render_csv;
+#[doc(hidden)]
+pub mod render_markdown;
+#[doc(hidden)]
+pub mod review_pairs;
+#[doc(hidden)]
+pub mod rework;
+#[doc(hidden)]
+pub mod rollup;
+#[doc(hidden)]
+pub mod sampling;
+#[doc(hidden)]
+pub mod schema;
+#[doc(hidden)]
+pub mod services;
+#[doc(hidden)]
+pub mod side_indexes;
+#[doc(hidden)]
+pub mod signoff;
+#[doc(hidden)]
+pub mod snapshots;
+#[doc(hidden)]
+pub mod telemetry;
+#[doc(hidden)]
+pub mod trends;
+#[doc(hidden)]
+pub mod util;
+#[doc(hidden)]
+pub mod validate_output;
+#[doc(hidden)]
+pub mod work_items;
@@ -0,0 +1,153 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --check-links: verify the links in rendered Markdown (PR/commit URLs well-formed, local report/shard paths exist)
+// role: output/link-check
+// inputs: rendered Markdown text; the directory it is written to (None when printed to stdout)
+// outputs: Broken links as warnings on stderr (util::log, counted in the exit summary); nothing is rewritten
+// side_effects: Stats local link targets; no network access
+// invariants:
+// - off by default; when off, check() returns before scanning anything
+// - only links the renderer generates are checked; quoted commit bodies ("> " lines) are skipped
+// - http(s) links need a scheme, a host, and no whitespace; fragments and mailto links are accepted as-is
+// - relative links resolve against the Markdown file's directory (the working directory for stdout)
+// errors: None surfaced; a broken link never fails the run
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::Path;
+
+use once_cell::sync::{Lazy, OnceCell};
+use regex::Regex;
+
+use crate::util;
+
+static ENABLED: OnceCell<bool> = OnceCell::new();
+
+/// `--check-links`: verify links in every Markdown rendering before it ships (first call wins).
+pub fn set_check_links(enabled: bool) {
+  let _ = ENABLED.set(enabled);
+}
+
+/// One link that would be dead in the rendered document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrokenLink {
+  pub line: usize,
+  pub target: String,
+  pub reason: &'static str,
+}
+
+/// With `--check-links`, warn about every broken link in `md`, which is written into `dir` (None = stdout).
+pub fn check(md: &str, dir: Option<&Path>, name: &str) {
+  if !ENABLED.get().copied().unwrap_or(false) {
+    return;
+  }
+
+  let broken = find_broken_links(md, dir.unwrap_or(Path::new(".")));
+
+  for b in &broken {
+    util::log(
+      util::LogLevel::Warn,
+      "links",
+      &format!("{}:{}: {} link {:?}", name, b.line, b.reason, b.target),
+    );
+  }
+}
+
+/// Links in `md` (1-based line numbers) whose target is malformed or, for relative paths, missing under `base`.
+pub fn find_broken_links(md: &str, base: &Path) -> Vec<BrokenLink> {
+  static LINK: Lazy<Regex> = Lazy::new(|| Regex::new(r"\[[^\]]*\]\(([^)]*)\)").expect("link regex"));
+  let mut out = Vec::new();
+
+  for (i, line) in md.lines().enumerate() {
+    if line.starts_with('>') {
+      continue;
+    }
+
+    for cap in LINK.captures_iter(line) {
+      if let Some(reason) = link_problem(cap[1].trim(), base) {
+        out.push(BrokenLink {
+          line: i + 1,
+          target: cap[1].to_string(),
+          reason,
+        });
+      }
+    }
+  }
+
+  out
+}
+
+fn link_problem(raw: &str, base: &Path) -> Option<&'static str> {
+  // `[text](target "title")`: a quoted title is not part of the target; any other whitespace breaks the link
+  let target = match raw.split_once(char::is_whitespace) {
+    Some((target, title)) if title.trim_start().starts_with(['"', '\'']) => target,
+    Some(_) => return Some("malformed"),
+    None => raw,
+  };
+
+  if target.is_empty() {
+    return Some("empty");
+  }
+  if target.starts_with('#') || target.starts_with("mailto:") {
+    return None;
+  }
+
+  if let Some((scheme, rest)) = target.split_once("://") {
+    return (!well_formed_url(scheme, rest)).then_some("malformed URL");
+  }
+
+  let path = target.split(['#', '?']).next().unwrap_or_default();
+
+  (!base.join(path).exists()).then_some("missing file")
+}
+
+fn well_formed_url(scheme: &str, rest: &str) -> bool {
+  let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+  let host = host.rsplit_once(':').map_or(host, |(h, port)| {
+    if port.chars().all(|c| c.is_ascii_digit()) {
+      h
+    } else {
+      host
+    }
+  });
+
+  matches!(scheme, "http" | "https")
+    && !host.is_empty()
+    && host.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+    && !rest.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn malformed_urls_and_missing_files_are_reported() {
+    let td = tempfile::TempDir::new().unwrap();
+    std::fs::write(td.path().join("report-2025-08.json"), "{}").unwrap();
+
+    let md = "\
+- [#5](https://github.com/o/r/pull/5) ok
+- [#6]() empty
+- [#7](github.com/o/r/pull/7) relative, so a missing file
+- [#8](https://gith ub.com/o/r/pull/8) space in host
+- [#9](https://github.com/o/r/pull/9 \"PR title\") [#10](https://git_hub.com/x)
+- [report](report-2025-08.json#summary \"title\") [missing](shards/abc.json)
+> body [link](nowhere.md) is not generated
+- [top](#totals) [mail](mailto:a@example.com) [local](http://localhost:8080/x)
+";
+    let broken = find_broken_links(md, td.path());
+    let found: Vec<(usize, &str, &str)> = broken.iter().map(|b| (b.line, b.target.as_str(), b.reason)).collect();
+
+    assert_eq!(
+      found,
+      vec![
+        (2, "", "empty"),
+        (3, "github.com/o/r/pull/7", "missing file"),
+        (4, "https://gith ub.com/o/r/pull/8", "malformed"),
+        (5, "https://git_hub.com/x", "malformed URL"),
+        (6, "shards/abc.json", "missing file"),
+      ]
+    );
+  }
+}
@@ -0,0 +1,151 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: tracing subscriber behind util::log and the progress events: -q/-v/-vv levels on stderr, every enabled event mirrored to --json-logs-to
+// role: observability/logging
+// inputs: --quiet / --verbose (count) from util::set_logging; tracing events from util::log and tracing::{debug,trace}! with one of TARGETS
+// outputs: `[stage] message key=value ...` lines on stderr; JSON lines through util::log_json (with a `fields` object when the event has any)
+// side_effects: Installs the process-wide tracing subscriber (first call wins)
+// invariants:
+// - levels: default shows info and warnings, -v adds debug progress (range started/finished, commits processed, lookups skipped), -vv adds trace (per commit and git call); --quiet shows errors only
+// - --quiet only narrows stderr: the JSON log still gets info and up (and debug/trace with -v/-vv)
+// - only events with a target in TARGETS are handled, so dependencies' tracing output never reaches stderr
+// - the `stage` field, when present, replaces the target in the `[stage]` prefix and the JSON `target`
+// errors: None (a second install is ignored)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::prelude::*;
+
+use crate::util::{self, LogLevel};
+
+/// Event targets this tool logs under (the `[target]` prefix on stderr).
+pub const TARGETS: &[&str] = &["gar", "github", "gitlab", "links", "otel"];
+
+/// Most verbose level enabled: info by default, debug with -v, trace with -vv.
+pub fn max_level(verbose: u8) -> Level {
+  match verbose {
+    0 => Level::INFO,
+    1 => Level::DEBUG,
+    _ => Level::TRACE,
+  }
+}
+
+fn log_level(level: &Level) -> LogLevel {
+  match *level {
+    Level::ERROR => LogLevel::Error,
+    Level::WARN => LogLevel::Warn,
+    Level::INFO => LogLevel::Info,
+    Level::DEBUG => LogLevel::Debug,
+    _ => LogLevel::Trace,
+  }
+}
+
+/// Writes this tool's events to stderr and the JSON log.
+struct LogLayer {
+  max: Level,
+  stderr: Level,
+}
+
+#[derive(Default)]
+struct Fields {
+  message: String,
+  stage: Option<String>,
+  rest: BTreeMap<String, String>,
+}
+
+impl Visit for Fields {
+  fn record_str(&mut self, field: &Field, value: &str) {
+    match field.name() {
+      "message" => self.message = value.to_string(),
+      "stage" => self.stage = Some(value.to_string()),
+      name => {
+        self.rest.insert(name.to_string(), value.to_string());
+      }
+    }
+  }
+
+  fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+    match field.name() {
+      "message" => self.message = format!("{:?}", value),
+      "stage" => self.stage = Some(format!("{:?}", value)),
+      name => {
+        self.rest.insert(name.to_string(), format!("{:?}", value));
+      }
+    }
+  }
+}
+
+impl<S: Subscriber> Layer<S> for LogLayer {
+  fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+    metadata.is_event() && *metadata.level() <= self.max && TARGETS.contains(&metadata.target())
+  }
+
+  fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+    let mut fields = Fields::default();
+    event.record(&mut fields);
+
+    let level = event.metadata().level();
+    let stage = fields.stage.as_deref().unwrap_or(event.metadata().target());
+
+    if *level <= self.stderr {
+      let mut line = format!("[{}] {}", stage, fields.message);
+      for (k, v) in &fields.rest {
+        let _ = write!(line, " {}={}", k, v);
+      }
+      crate::progress::clear_line();
+      eprintln!("{}", line);
+    }
+
+    util::log_json_fields(log_level(level), stage, &fields.message, &fields.rest);
+  }
+}
+
+/// Install the subscriber for `--quiet` / `--verbose`; later calls are ignored.
+pub fn install(quiet: bool, verbose: u8) {
+  let max = max_level(verbose);
+  let stderr = if quiet { Level::ERROR } else { max };
+  let _ = tracing::subscriber::set_global_default(tracing_subscriber::registry().with(LogLayer { max, stderr }));
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn verbosity_maps_to_levels_and_fields_are_split_out() {
+    assert_eq!(max_level(0), Level::INFO);
+    assert_eq!(max_level(1), Level::DEBUG);
+    assert_eq!(max_level(5), Level::TRACE);
+    assert!(Level::WARN <= max_level(0) && Level::DEBUG > max_level(0));
+
+    struct Capture(std::sync::Arc<std::sync::Mutex<Vec<Fields>>>);
+    impl<S: Subscriber> Layer<S> for Capture {
+      fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut f = Fields::default();
+        event.record(&mut f);
+        self.0.lock().unwrap().push(f);
+      }
+    }
+
+    let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+    let subscriber = tracing_subscriber::registry().with(Capture(seen.clone()));
+    tracing::subscriber::with_default(subscriber, || {
+      tracing::debug!(target: "gar", range = "2025-08", commits = 12, "range finished");
+      tracing::warn!(target: "gar", stage = "github", "lookup skipped");
+    });
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen[0].message, "range finished");
+    assert_eq!(seen[0].rest["range"], "2025-08");
+    assert_eq!(seen[0].rest["commits"], "12");
+    assert_eq!(seen[1].message, "lookup skipped");
+    assert_eq!(seen[1].stage.as_deref(), Some("github"));
+    assert!(seen[1].rest.is_empty());
+  }
+}
@@ -6,34 +6,100 @@
 // outputs: Either full JSON to stdout, or a pointer {dir,file}/{dir,manifest} to stdout; files on disk when split/multi
 // side_effects: Creates directories and writes files in split/multi modes via range_processor
 // invariants:
-// - when cfg.multi_windows == true, an overall manifest.json is written and a pointer with {dir, manifest} is printed
+// - --ranges-json always sets cfg.multi_windows, even for a single entry
+// - when cfg.multi_windows == true, manifest.json and rollup.json are written and a pointer with {dir, manifest, rollup} is printed
 // - when cfg.split_apart == true and cfg.multi_windows == false, a pointer {dir, file} is printed for the range report
 // - when cfg.split_apart == false and cfg.multi_windows == false, a full JSON report is printed to stdout or written to --out
-// errors: Bubbles up normalize/resolve/process errors with context
+// - stdout carries only data; progress and warnings go to stderr via util::log / tracing (--quiet hides them, -v/-vv add progress, --json-logs-to mirrors them)
+// errors: Bubbles up normalize/resolve/process errors with context; unknown --branch/--ref revisions fail before any range is processed; the final error is also recorded in --json-logs-to
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs (see AGENT_RUBRIC.md)
 // === Module Header END ===
 
-use anyhow::Result;
-use clap::Parser;
+use std::io::IsTerminal as _;
 
-mod cli;
-mod commit;
+use anyhow::{Context, Result};
+use clap::{CommandFactory, FromArgMatches};
+// The report pipeline is shared with the library crate (and the benches); `crate::<module>` paths resolve
+// through these imports.
+use git_activity_report::{
+  anomalies, archive, cli, collaboration, digest, enrichment, exit_summary, gitio, link_check, manifest, migrate,
+  model, out_lock, progress, range_windows, render, render_csv, render_markdown, rollup, schema, signoff, telemetry,
+  trends, util, validate_output,
+};
+
+mod browse;
+mod checkpoint;
+mod config_file;
+mod doctor;
 mod enrich;
-mod enrichment;
-mod ext;
-mod gitio;
-mod manifest;
-mod model;
+mod query;
 mod range_processor;
-mod range_windows;
-mod render;
-mod util;
 
 use crate::cli::{Cli, normalize};
 
+/// `--since-merge-base BRANCH`: window from just after the merge-base of HEAD and BRANCH until now.
+fn merge_base_window(cli: &mut Cli, branch: &str) -> Result<()> {
+  if !cli.month.is_empty()
+    || cli.for_str.is_some()
+    || cli.since.is_some()
+    || cli.until.is_some()
+    || cli.ranges_json.is_some()
+  {
+    anyhow::bail!("--since-merge-base replaces --month | --for | --since/--until | --ranges-json; choose only one");
+  }
+
+  let repo = util::canonicalize_lossy(&cli.repo);
+  let (sha, epoch) = gitio::merge_base(&repo, branch)?;
+  let now = util::effective_now(crate::range_windows::parse_now(cli.now_override.as_deref()));
+
+  util::log(
+    util::LogLevel::Info,
+    "gar",
+    &format!(
+      "window starts after merge-base {} with {}",
+      util::short_sha(&sha),
+      branch
+    ),
+  );
+  // git --since is inclusive; start one second later so the merge-base itself stays out
+  cli.since = Some(util::iso_in_tz(epoch + 1, "utc"));
+  cli.until = Some(now.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+
+  Ok(())
+}
+
 fn main() -> Result<()> {
-  let cli = Cli::parse();
+  let started = std::time::Instant::now();
+  let telemetry = telemetry::init();
+  let span = telemetry::span("run");
+  let matches = Cli::command().get_matches();
+  let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+  let want_exit_summary = cli.exit_summary;
+  let result = run(cli, &matches);
 
+  if let Err(err) = &result {
+    util::log_json(util::LogLevel::Error, "gar", &format!("{:#}", err));
+    span.fail(&format!("{:#}", err));
+  }
+
+  if want_exit_summary {
+    // Report the error ourselves so the summary stays the last stderr line
+    if let Err(err) = &result {
+      eprintln!("Error: {:?}", err);
+    }
+    exit_summary::emit(started, &result);
+
+    if result.is_err() {
+      drop(span);
+      drop(telemetry);
+      std::process::exit(1);
+    }
+  }
+
+  result
+}
+
+fn run(mut cli: Cli, matches: &clap::ArgMatches) -> Result<()> {
   if cli.gen_man {
     let page = util::render_man_page::<Cli>()?;
     print!("{}", page);
@@ -41,16 +107,129 @@ fn main() -> Result<()> {
     return Ok(());
   }
 
+  if let Some(kind) = &cli.emit_schema {
+    println!("{}", schema::emit(kind)?);
+
+    return Ok(());
+  }
+
+  if let Some(q) = &cli.query {
+    return query::run(&q[0], &q[1]);
+  }
+
+  if let Some(dir) = &cli.browse {
+    return browse::run(dir);
+  }
+
+  util::set_logging(cli.quiet, cli.verbose, cli.json_logs_to.as_deref())?;
+  progress::set_enabled(!cli.quiet && std::io::stderr().is_terminal());
+  config_file::apply(&mut cli, matches)?;
+  util::set_git_invocation(util::GitInvocation::from_env(cli.git_args.clone()));
+  gitio::set_backend(&cli.git_backend)?;
+  util::set_no_wait(cli.no_wait);
+  validate_output::set_validate_output(cli.validate_output);
+  link_check::set_check_links(cli.check_links);
+  util::set_output_encoding(util::OutputEncoding::parse(&cli.output_encoding)?);
+
+  if let Some(path) = &cli.migrate {
+    return migrate::run(path, &cli.to);
+  }
+
+  crate::enrichment::github_api::set_http_config(crate::enrichment::github_api::HttpConfig {
+    timeout_secs: cli.http_timeout,
+    retries: cli.http_retries,
+    max_pages: cli.http_max_pages,
+    max_requests: cli.github_max_requests,
+  });
+
+  if let Some(dir) = &cli.github_cache_dir {
+    crate::enrichment::github_api::set_disk_cache(std::path::PathBuf::from(dir), cli.github_cache_ttl);
+  }
+
+  if let Some(spec) = &cli.github_repo {
+    let (owner, name) = crate::enrichment::github_api::parse_repo_spec(spec)
+      .with_context(|| format!("--github-repo expects owner/name, got {:?}", spec))?;
+    crate::enrichment::github_api::set_github_repo_override(owner, name);
+  }
+
+  if cli.doctor {
+    return doctor::run(&cli);
+  }
+
+  if cli.signoff.is_some() || cli.verify_signoff {
+    if cli.out == "-" {
+      anyhow::bail!("--signoff/--verify-signoff require --out <output dir>");
+    }
+
+    return match &cli.signoff {
+      Some(reviewer) => {
+        let now = util::effective_now(crate::range_windows::parse_now(cli.now_override.as_deref()));
+        signoff::sign(&cli.out, reviewer, now)
+      }
+      None => signoff::verify(&cli.out),
+    };
+  }
+
+  if cli.digest.is_some() {
+    digest::digest_window(&mut cli)?;
+  }
+
+  if let Some(branch) = cli.since_merge_base.clone() {
+    merge_base_window(&mut cli, &branch)?;
+  }
+
   // Phase 1: normalize CLI
   let mut cfg = normalize(cli)?;
 
+  for r in &cfg.refs {
+    gitio::resolve_commit(&cfg.repo, r)
+      .with_context(|| format!("--branch/--ref {:?} is not a commit in {}", r, cfg.repo))?;
+  }
+
+  if cfg.github_prs {
+    for warning in crate::enrichment::github_api::preflight_pr_access(&cfg.repo) {
+      util::log(util::LogLevel::Warn, "github", &warning);
+    }
+  }
+
+  if let Some(v) = util::git_version().filter(|_| !util::git_at_least(2, 9)) {
+    util::log(
+      util::LogLevel::Warn,
+      "gar",
+      &format!(
+        "git {} predates 2.9; rename detection and ISO-8601 dates use fallbacks (see --doctor)",
+        v
+      ),
+    );
+  }
+
+  if !cfg.no_blob_stats && gitio::is_partial_clone(&cfg.repo) {
+    util::log(
+      util::LogLevel::Warn,
+      "gar",
+      "partial clone detected; consider --no-blob-stats to avoid blob fetches for per-file stats",
+    );
+  }
+
   // Phase 2: resolve now and ranges
   let now_opt = crate::range_windows::parse_now(cfg.now_override.as_deref());
-  eprintln!("[gar] resolving ranges...");
-  let ranges = crate::range_windows::resolve_ranges(&cfg.window, now_opt)?;
-  cfg.multi_windows = ranges.len() > 1;
+  util::log(util::LogLevel::Info, "gar", "resolving ranges...");
+  let ranges = {
+    let _span = telemetry::span("resolve_ranges");
+    crate::range_windows::resolve_ranges_in_history(&cfg.window, now_opt, || gitio::first_commit_epoch(&cfg.repo))?
+  };
+  // --ranges-json always runs as a multi-range job so external schedulers get a manifest even for one window
+  cfg.multi_windows = ranges.len() > 1 || matches!(cfg.window, crate::range_windows::WindowSpec::Explicit { .. });
+
+  if cfg.digest.is_some() {
+    return digest::run(&cfg, &ranges[0], now_opt);
+  }
 
   // Phase 3: process ranges (single or multi) in a unified flow
-  eprintln!("[gar] processing {} range(s)...", ranges.len());
+  util::log(
+    util::LogLevel::Info,
+    "gar",
+    &format!("processing {} range(s)...", ranges.len()),
+  );
   crate::range_processor::process_ranges(&cfg, ranges, now_opt)
 }
@@ -0,0 +1,144 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --committers: recognize integration work — per-commit authored_by_committer, the committers map, and summary.maintainer_activity
+// role: summary/maintainers
+// inputs: Processed commits of one range; merge commits of the window as (sha, committer name, committer email); optional --identity-map
+// outputs: authored_by_committer flags; committers map ("Name <email>" → commits); Vec<MaintainerActivity>, None when nobody integrated work
+// side_effects: None
+// invariants:
+// - author and committer are the same person when their canonical emails match case-insensitively
+// - committer keys use Person::key(), so they line up with the authors map; merge committers are resolved through the same identity map
+// - maintainer_activity lists committers with for_others > 0 or merges > 0, ordered by for_others + merges desc, then committer
+// - merges are counted from git even when --include-merges leaves them out of commits
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use crate::identities::{IdentityMap, resolve};
+use crate::model::{Commit, MaintainerActivity};
+
+/// Set `authored_by_committer` on every commit.
+pub fn mark_authored_by_committer(commits: &mut [Commit]) {
+  for c in commits {
+    let same = c
+      .author
+      .canonical_email()
+      .eq_ignore_ascii_case(c.committer.canonical_email());
+
+    c.authored_by_committer = Some(same);
+  }
+}
+
+/// Commits per committer, keyed like the authors map.
+pub fn committer_counts(commits: &[Commit]) -> BTreeMap<String, i64> {
+  let mut counts = BTreeMap::new();
+
+  for c in commits {
+    *counts.entry(c.committer.key()).or_insert(0) += 1;
+  }
+
+  counts
+}
+
+fn slot(map: &mut BTreeMap<String, MaintainerActivity>, key: String) -> &mut MaintainerActivity {
+  map.entry(key.clone()).or_insert(MaintainerActivity {
+    committer: key,
+    commits: 0,
+    for_others: 0,
+    merges: 0,
+  })
+}
+
+/// Per-committer integration work: commits landed for other authors plus merges performed.
+pub fn build_maintainer_activity(
+  commits: &[Commit],
+  merges: &[(String, String, String)],
+  identities: Option<&IdentityMap>,
+) -> Option<Vec<MaintainerActivity>> {
+  let mut by_committer: BTreeMap<String, MaintainerActivity> = BTreeMap::new();
+
+  for c in commits {
+    let a = slot(&mut by_committer, c.committer.key());
+    a.commits += 1;
+
+    if !c
+      .author
+      .canonical_email()
+      .eq_ignore_ascii_case(c.committer.canonical_email())
+    {
+      a.for_others += 1;
+    }
+  }
+
+  for (_, name, email) in merges {
+    let (name, email) = resolve(identities, name, email);
+    slot(&mut by_committer, format!("{} <{}>", name, email)).merges += 1;
+  }
+
+  let mut out: Vec<MaintainerActivity> = by_committer
+    .into_values()
+    .filter(|a| a.for_others > 0 || a.merges > 0)
+    .collect();
+
+  if out.is_empty() {
+    return None;
+  }
+
+  out.sort_by(|a, b| {
+    (b.for_others + b.merges)
+      .cmp(&(a.for_others + a.merges))
+      .then_with(|| a.committer.cmp(&b.committer))
+  });
+
+  Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(sha: &str, author: &str, committer: &str) -> Commit {
+    Commit::fixture(sha)
+      .with_author(author, &format!("{}@x", author.to_lowercase()))
+      .with_committer(committer, &format!("{}@X", committer.to_lowercase()))
+  }
+
+  #[test]
+  fn committers_and_integration_work_are_counted() {
+    let mut commits = vec![
+      commit("a1", "Ann", "Ann"),
+      commit("a2", "Bo", "Mae"),
+      commit("a3", "Cy", "Mae"),
+      commit("a4", "Bo", "Ann"),
+    ];
+    mark_authored_by_committer(&mut commits);
+    assert_eq!(commits[0].authored_by_committer, Some(true));
+    assert_eq!(commits[1].authored_by_committer, Some(false));
+
+    let counts = committer_counts(&commits);
+    assert_eq!(counts["Mae <mae@X>"], 2);
+    assert_eq!(counts["Ann <ann@X>"], 2);
+
+    let merges = vec![
+      ("m1".to_string(), "Ann".to_string(), "ann@X".to_string()),
+      ("m2".to_string(), "Zed".to_string(), "zed@x".to_string()),
+    ];
+    let activity = build_maintainer_activity(&commits, &merges, None).unwrap();
+    let rows: Vec<(&str, i64, i64, i64)> = activity
+      .iter()
+      .map(|a| (a.committer.as_str(), a.commits, a.for_others, a.merges))
+      .collect();
+
+    assert_eq!(
+      rows,
+      vec![
+        ("Ann <ann@X>", 2, 1, 1),
+        ("Mae <mae@X>", 2, 2, 0),
+        ("Zed <zed@x>", 0, 0, 1),
+      ]
+    );
+    assert!(build_maintainer_activity(&commits[..1], &[], None).is_none());
+  }
+}
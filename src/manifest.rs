@@ -9,12 +9,60 @@
 // - manifest contains ranges[] in chronological order of entries provided
 // - file paths in entries are relative to base_dir and point to report-<label>.json
 // - generated_at is serialized in %Y-%m-%dT%H:%M:%S (local)
+// - anomalies[] (see crate::anomalies) is present only when at least one range was flagged
+// - ranges[].trend (see crate::trends) is present only when the run has at least two ranges
+// - warnings[] (the run's util::log warnings, in order) is present only when something was logged
+// - merge_existing_entries keeps ranges another run recorded in the same dir (labels not regenerated here, report file still present); callers hold crate::out_lock::DirLock around read-merge-write
 // errors: IO errors surfaced with full path context
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
 use anyhow::Result;
 use chrono::{DateTime, Local};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::anomalies::RangeAnomaly;
+use crate::model::Warning;
+use crate::signoff::Signoff;
+use crate::trends::RangeTrend;
+
+/// manifest.json as written by OverallManifest (and stamped by --signoff); also the source of `--emit-schema overall`.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct Manifest {
+  pub repo: String,
+  pub generated_at: String,
+  pub split_apart: bool,
+  pub include_merges: bool,
+  pub include_patch: bool,
+  pub include_unmerged: bool,
+  pub ranges: Vec<ManifestRange>,
+  /// Ranges flagged as unusual for this run (see crate::anomalies)
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub anomalies: Vec<RangeAnomaly>,
+  /// Every warning logged during this run, range reports' included
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  pub warnings: Vec<Warning>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub signoff: Option<Signoff>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ManifestRange {
+  pub label: String,
+  pub range: ManifestBounds,
+  /// Range report, relative to the manifest's directory
+  pub file: String,
+  /// Rolling averages and direction (runs with at least two ranges)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub trend: Option<RangeTrend>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ManifestBounds {
+  pub start: String,
+  pub end: String,
+}
 
 /// Helper to build and write the overall/top manifest for multi-bucket runs.
 pub struct OverallManifest {
@@ -30,32 +78,65 @@ impl OverallManifest {
     include_patch: bool,
     include_unmerged: bool,
   ) -> Self {
-    let mut v = serde_json::json!({
-      "repo": repo,
-      "generated_at": generated_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
-      "split_apart": split_apart,
-      "include_merges": include_merges,
-      "include_patch": include_patch,
-      "include_unmerged": include_unmerged,
-      "ranges": [],
-    });
-    // ensure ranges is an array
-    let _ = v["ranges"].as_array_mut().expect("ranges array");
-    Self { value: v }
+    let manifest = Manifest {
+      repo: repo.to_string(),
+      generated_at: generated_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+      split_apart,
+      include_merges,
+      include_patch,
+      include_unmerged,
+      ranges: Vec::new(),
+      anomalies: Vec::new(),
+      warnings: Vec::new(),
+      signoff: None,
+    };
+    Self {
+      value: serde_json::to_value(manifest).expect("manifest serializes"),
+    }
   }
 
   pub fn push_simple_entry(&mut self, label: String, start: String, end: String, file_path: &str) {
-    let entry = serde_json::json!({
-      "label": label,
-      "range": {"start": start, "end": end},
-      "file": file_path,
-    });
+    let entry = ManifestRange {
+      label,
+      range: ManifestBounds { start, end },
+      file: file_path.to_string(),
+      trend: None,
+    };
+    let entry = serde_json::to_value(entry).expect("manifest range serializes");
     self.value["ranges"].as_array_mut().unwrap().push(entry);
   }
 
+  pub fn set_anomalies(&mut self, anomalies: &[RangeAnomaly]) -> Result<()> {
+    if !anomalies.is_empty() {
+      self.value["anomalies"] = serde_json::to_value(anomalies)?;
+    }
+
+    Ok(())
+  }
+
+  pub fn set_warnings(&mut self, warnings: &[Warning]) -> Result<()> {
+    if !warnings.is_empty() {
+      self.value["warnings"] = serde_json::to_value(warnings)?;
+    }
+
+    Ok(())
+  }
+
+  /// Attach each range's rolling averages and direction as `ranges[i].trend` (same order as the entries).
+  pub fn set_trends(&mut self, trends: &[RangeTrend]) -> Result<()> {
+    let ranges = self.value["ranges"].as_array_mut().expect("ranges array");
+
+    for (entry, trend) in ranges.iter_mut().zip(trends) {
+      entry["trend"] = serde_json::to_value(trend)?;
+    }
+
+    Ok(())
+  }
+
   pub fn write_to(&self, base_dir: &str) -> Result<std::path::PathBuf> {
     let path = std::path::Path::new(base_dir).join("manifest.json");
-    std::fs::write(&path, serde_json::to_vec_pretty(&self.value)?)?;
+    crate::validate_output::check(crate::validate_output::Artifact::Manifest, &path, &self.value)?;
+    crate::util::write_output(&path, &serde_json::to_vec_pretty(&self.value)?)?;
 
     Ok(path)
   }
@@ -73,6 +154,54 @@ pub struct RangeEntry {
   pub file: String,
 }
 
+/// Add the ranges an earlier or concurrent run recorded in `base_dir/manifest.json` to `entries`.
+///
+/// Entries of this run win on label clashes; kept entries must still have their report file. When anything
+/// was kept, the result is ordered by range start, then label.
+pub fn merge_existing_entries(base_dir: &str, mut entries: Vec<RangeEntry>) -> Vec<RangeEntry> {
+  let dir = std::path::Path::new(base_dir);
+  let Ok(bytes) = crate::util::read_output(&dir.join("manifest.json")) else {
+    return entries;
+  };
+  let Ok(existing) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+    crate::util::log(
+      crate::util::LogLevel::Warn,
+      "gar",
+      &format!(
+        "{}/manifest.json is not valid JSON; rewriting it from this run only",
+        base_dir
+      ),
+    );
+
+    return entries;
+  };
+
+  let before = entries.len();
+
+  for r in existing["ranges"].as_array().into_iter().flatten() {
+    let field = |v: &serde_json::Value| v.as_str().unwrap_or_default().to_string();
+    let e = RangeEntry {
+      label: field(&r["label"]),
+      start: field(&r["range"]["start"]),
+      end: field(&r["range"]["end"]),
+      file: field(&r["file"]),
+    };
+
+    if e.label.is_empty() || e.file.is_empty() || entries.iter().any(|x| x.label == e.label) {
+      continue;
+    }
+    if dir.join(&e.file).exists() {
+      entries.push(e);
+    }
+  }
+
+  if entries.len() > before {
+    entries.sort_by(|a, b| a.start.cmp(&b.start).then_with(|| a.label.cmp(&b.label)));
+  }
+
+  entries
+}
+
 /// Build and write an overall manifest given pre-computed entries.
 #[allow(clippy::too_many_arguments)]
 pub fn write_overall_manifest(
@@ -82,6 +211,9 @@ pub fn write_overall_manifest(
   include_merges: bool,
   include_patch: bool,
   include_unmerged: bool,
+  anomalies: &[RangeAnomaly],
+  trends: &[RangeTrend],
+  warnings: &[Warning],
   base_dir: &str,
   entries: &[RangeEntry],
 ) -> Result<std::path::PathBuf> {
@@ -97,6 +229,9 @@ pub fn write_overall_manifest(
   for e in entries {
     overall.push_simple_entry(e.label.clone(), e.start.clone(), e.end.clone(), &e.file);
   }
+  overall.set_anomalies(anomalies)?;
+  overall.set_trends(trends)?;
+  overall.set_warnings(warnings)?;
   overall.write_to(base_dir)
 }
 
@@ -127,8 +262,20 @@ mod tests {
         file: "report-2025-08.json".into(),
       },
     ];
-    let path =
-      write_overall_manifest("<repo>", gen_at, true, true, false, false, &base, &entries).expect("write manifest");
+    let path = write_overall_manifest(
+      "<repo>",
+      gen_at,
+      true,
+      true,
+      false,
+      false,
+      &[],
+      &[],
+      &[],
+      &base,
+      &entries,
+    )
+    .expect("write manifest");
     assert!(path.ends_with("manifest.json"));
     let buf = std::fs::read(path).unwrap();
     let v: serde_json::Value = serde_json::from_slice(&buf).unwrap();
@@ -137,5 +284,40 @@ mod tests {
     assert_eq!(ranges.len(), 2);
     assert_eq!(ranges[0]["file"].as_str().unwrap(), "report-2025-07.json");
     assert_eq!(ranges[1]["file"].as_str().unwrap(), "report-2025-08.json");
+    assert!(v.get("anomalies").is_none());
+    assert!(ranges[0].get("trend").is_none());
+  }
+
+  #[test]
+  fn merge_existing_entries_keeps_other_runs_ranges() {
+    let td = tempfile::TempDir::new().unwrap();
+    let base = td.path().to_string_lossy().to_string();
+    let entry = |label: &str, start: &str, end: &str| RangeEntry {
+      label: label.into(),
+      start: start.into(),
+      end: end.into(),
+      file: format!("report-{}.json", label),
+    };
+    let gen_at = Local::now();
+    let earlier = vec![
+      entry("2025-06", "2025-06-01T00:00:00", "2025-07-01T00:00:00"),
+      entry("2025-07", "2025-07-01T00:00:00", "2025-08-01T00:00:00"),
+      entry("2025-08", "2025-08-01T00:00:00", "2025-09-01T00:00:00"),
+    ];
+    write_overall_manifest("<repo>", gen_at, false, false, false, false, &[], &[], &base, &earlier).unwrap();
+    for label in ["2025-07", "2025-08"] {
+      std::fs::write(td.path().join(format!("report-{}.json", label)), "{}").unwrap();
+    }
+
+    let ours = vec![
+      entry("2025-08", "2025-08-01T00:00:00", "2025-09-01T00:00:00"),
+      entry("2025-05", "2025-05-01T00:00:00", "2025-06-01T00:00:00"),
+    ];
+    let merged = merge_existing_entries(&base, ours);
+    let labels: Vec<&str> = merged.iter().map(|e| e.label.as_str()).collect();
+
+    // 2025-06 is dropped: its report file is gone
+    assert_eq!(labels, vec!["2025-05", "2025-07", "2025-08"]);
+    assert!(merge_existing_entries(&format!("{}/missing", base), vec![]).is_empty());
   }
 }
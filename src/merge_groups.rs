@@ -0,0 +1,73 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --first-parent: nest the commits each first-parent merge brought in under commit.merge_group, so a merged PR reads as one unit of work
+// role: summary/merge-groups
+// inputs: Processed first-parent commits; each merge's constituent commits (gitio::merge_group_shas), processed like top-level commits
+// outputs: MergeGroup { commits, additions, deletions, files_touched } on merge commits
+// side_effects: None (callers run git and process the constituents)
+// invariants:
+// - only commits with more than one parent get a group; a merge whose group is empty (e.g. every commit filtered out by --path) gets none
+// - constituent commits are earliest→latest and never include the merge itself
+// - additions/deletions include entries dropped by --max-files-per-commit; files_touched counts distinct listed paths
+// - summary.changes counts the constituents' churn instead of a grouped merge's own file entries (git's merge numstat repeats what it brought in); authors and counts stay per unit
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeSet;
+
+use crate::model::{Commit, MergeGroup};
+
+/// Whether `commit` is a merge that can carry a group.
+pub fn is_merge(commit: &Commit) -> bool {
+  commit.parents.len() > 1
+}
+
+/// Group `commits` (already processed, earliest→latest) with their combined churn; None when empty.
+pub fn build_merge_group(commits: Vec<Commit>) -> Option<MergeGroup> {
+  if commits.is_empty() {
+    return None;
+  }
+
+  let mut group = MergeGroup::default();
+  let mut files: BTreeSet<&str> = BTreeSet::new();
+
+  for c in &commits {
+    let (additions, deletions) = crate::commit::commit_additions_deletions(c);
+    group.additions += additions;
+    group.deletions += deletions;
+    files.extend(c.files.iter().map(|f| f.file.as_str()));
+  }
+  group.files_touched = files.len();
+  group.commits = commits;
+
+  Some(group)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(sha: &str, parents: &[&str], files: &[(&str, i64, i64)]) -> Commit {
+    let mut c = Commit::fixture(sha).with_files(files);
+    c.parents = parents.iter().map(|p| p.to_string()).collect();
+    c
+  }
+
+  #[test]
+  fn groups_sum_churn_and_count_distinct_paths() {
+    assert!(is_merge(&commit("m1", &["p1", "p2"], &[])));
+    assert!(!is_merge(&commit("c1", &["p1"], &[])));
+
+    let group = build_merge_group(vec![
+      commit("c1", &["p1"], &[("a.rs", 3, 1), ("b.rs", 2, 1)]),
+      commit("c2", &["c1"], &[("a.rs", 4, 1)]),
+    ])
+    .unwrap();
+
+    assert_eq!(group.commits.len(), 2);
+    assert_eq!((group.additions, group.deletions), (9, 3));
+    assert_eq!(group.files_touched, 2);
+    assert!(build_merge_group(Vec::new()).is_none());
+  }
+}
@@ -0,0 +1,275 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --migrate mode: upgrade archived report/manifest/shard JSON in place to a newer output schema version
+// role: entrypoint/migrate
+// inputs: A .json file or an output dir (every *.json below it); target schema version (--to, default current)
+// outputs: Rewritten files (only those that changed); one "migrated <path>" line each plus a total on stdout
+// side_effects: Reads and rewrites JSON files in place via util::write_output (temp + rename, --output-encoding applies)
+// invariants:
+// - migrations only upgrade; each step is idempotent, so re-running on migrated files changes nothing
+// - steps apply in version order and only up to --to; the rest of each document is left as is
+// - v1 → v2: patch_ref → patch_references (github_*_url → github.*_url), patch → patch_lines,
+//   range {since, until} → {start, end}, manifest buckets → ranges
+// errors: unreadable or non-JSON files abort with the path in context (files already rewritten stay migrated)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+/// Output schema versions, oldest first; the last one is what this build writes.
+pub const SCHEMA_VERSIONS: [&str; 2] = ["1", "2"];
+pub const CURRENT_SCHEMA_VERSION: &str = SCHEMA_VERSIONS[SCHEMA_VERSIONS.len() - 1];
+
+struct Migration {
+  to: &'static str,
+  apply: fn(&mut Value) -> bool,
+}
+
+const MIGRATIONS: &[Migration] = &[Migration {
+  to: "2",
+  apply: v1_to_v2,
+}];
+
+/// Migrate `path` (a file, or every .json under a directory) up to schema version `to`.
+pub fn run(path: &str, to: &str) -> Result<()> {
+  let root = Path::new(path);
+  let mut files = Vec::new();
+
+  if root.is_dir() {
+    crate::util::collect_files(root, &mut files)?;
+    files.retain(|f| f.extension().is_some_and(|e| e == "json"));
+    files.sort();
+  } else {
+    files.push(root.to_path_buf());
+  }
+
+  let mut migrated = 0;
+
+  for file in &files {
+    if migrate_file(file, to)? {
+      println!("migrated {}", file.display());
+      migrated += 1;
+    }
+  }
+
+  println!(
+    "{} of {} file(s) migrated to schema version {}",
+    migrated,
+    files.len(),
+    to
+  );
+
+  Ok(())
+}
+
+/// Rewrite one file when any migration up to `to` changed it; returns whether it was rewritten.
+fn migrate_file(path: &Path, to: &str) -> Result<bool> {
+  let bytes = crate::util::read_output(path)?;
+  let mut doc: Value = serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))?;
+
+  if !migrate_value(&mut doc, to) {
+    return Ok(false);
+  }
+
+  crate::util::write_output(path, &serde_json::to_vec_pretty(&doc)?)?;
+
+  Ok(true)
+}
+
+fn version_index(v: &str) -> usize {
+  SCHEMA_VERSIONS.iter().position(|s| *s == v).unwrap_or(0)
+}
+
+/// Apply every migration up to `to`; returns whether the document changed.
+fn migrate_value(doc: &mut Value, to: &str) -> bool {
+  let mut changed = false;
+
+  for m in MIGRATIONS.iter().filter(|m| version_index(m.to) <= version_index(to)) {
+    changed |= (m.apply)(doc);
+  }
+
+  changed
+}
+
+// --- v1 (prototype-era output) → v2 ---
+
+fn v1_to_v2(doc: &mut Value) -> bool {
+  let mut changed = false;
+
+  if let Some(obj) = doc.as_object_mut() {
+    if !obj.contains_key("ranges") {
+      if let Some(buckets) = obj.remove("buckets") {
+        obj.insert("ranges".into(), buckets);
+        changed = true;
+      }
+    }
+  }
+
+  changed | walk_v1(doc)
+}
+
+fn walk_v1(v: &mut Value) -> bool {
+  match v {
+    Value::Object(obj) => {
+      let mut changed = upgrade_patch_ref(obj) | upgrade_patch_text(obj);
+
+      if let Some(Value::Object(range)) = obj.get_mut("range") {
+        changed |= rename_key(range, "since", "start") | rename_key(range, "until", "end");
+      }
+
+      for child in obj.values_mut() {
+        changed |= walk_v1(child);
+      }
+
+      changed
+    }
+    Value::Array(items) => items.iter_mut().fold(false, |acc, item| walk_v1(item) | acc),
+    _ => false,
+  }
+}
+
+fn rename_key(obj: &mut Map<String, Value>, from: &str, to: &str) -> bool {
+  if obj.contains_key(to) {
+    return false;
+  }
+
+  match obj.remove(from) {
+    Some(v) => {
+      obj.insert(to.into(), v);
+      true
+    }
+    None => false,
+  }
+}
+
+/// `patch_ref {embed, git_show_cmd: [..], local_patch_file, github_diff_url, github_patch_url}` → `patch_references`.
+fn upgrade_patch_ref(obj: &mut Map<String, Value>) -> bool {
+  if obj.contains_key("patch_references") {
+    return false;
+  }
+
+  let Some(Value::Object(old)) = obj.remove("patch_ref") else {
+    return false;
+  };
+
+  let git_show_cmd = match old.get("git_show_cmd") {
+    Some(Value::Array(parts)) => parts.iter().filter_map(|p| p.as_str()).collect::<Vec<_>>().join(" "),
+    Some(Value::String(s)) => s.clone(),
+    _ => String::new(),
+  };
+  let mut refs = Map::new();
+
+  refs.insert("embed".into(), old.get("embed").cloned().unwrap_or(Value::Bool(false)));
+  refs.insert("git_show_cmd".into(), Value::String(git_show_cmd));
+
+  if let Some(file) = old.get("local_patch_file").filter(|v| !v.is_null()) {
+    refs.insert("local_patch_file".into(), file.clone());
+  }
+
+  let github: Map<String, Value> = [("github_diff_url", "diff_url"), ("github_patch_url", "patch_url")]
+    .into_iter()
+    .filter_map(|(from, to)| Some((to.to_string(), old.get(from).filter(|v| !v.is_null())?.clone())))
+    .collect();
+
+  if !github.is_empty() {
+    refs.insert("github".into(), Value::Object(github));
+  }
+
+  obj.insert("patch_references".into(), Value::Object(refs));
+
+  true
+}
+
+/// Embedded `patch` text → `patch_lines` (commit objects only; PR diffs live under other keys).
+fn upgrade_patch_text(obj: &mut Map<String, Value>) -> bool {
+  if !obj.contains_key("patch_references") || obj.contains_key("patch_lines") {
+    return false;
+  }
+
+  match obj.remove("patch") {
+    Some(Value::String(text)) => {
+      let lines = text.lines().map(|l| Value::String(l.to_string())).collect();
+      obj.insert("patch_lines".into(), Value::Array(lines));
+      true
+    }
+    Some(_) => true,
+    None => false,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn v1_report() -> Value {
+    json!({
+      "label": "2025-08",
+      "range": { "since": "2025-08-01", "until": "2025-09-01" },
+      "commits": [{
+        "sha": "abc",
+        "patch_ref": {
+          "embed": true,
+          "git_show_cmd": ["git", "show", "--patch", "--format=", "--no-color", "abc"],
+          "local_patch_file": null,
+          "github_diff_url": "https://github.com/o/r/pull/1.diff",
+          "github_patch_url": null
+        },
+        "patch": "diff --git a/x b/x\n+1\n",
+        "patch_clipped": false
+      }]
+    })
+  }
+
+  #[test]
+  fn v1_documents_upgrade_to_current_shape() {
+    let mut doc = v1_report();
+
+    assert!(migrate_value(&mut doc, CURRENT_SCHEMA_VERSION));
+    assert_eq!(doc["range"], json!({ "start": "2025-08-01", "end": "2025-09-01" }));
+
+    let commit = &doc["commits"][0];
+    assert_eq!(
+      commit["patch_references"],
+      json!({
+        "embed": true,
+        "git_show_cmd": "git show --patch --format= --no-color abc",
+        "github": { "diff_url": "https://github.com/o/r/pull/1.diff" }
+      })
+    );
+    assert_eq!(commit["patch_lines"], json!(["diff --git a/x b/x", "+1"]));
+    assert!(commit.get("patch_ref").is_none() && commit.get("patch").is_none());
+  }
+
+  #[test]
+  fn migrations_are_idempotent_and_bounded_by_target() {
+    let mut doc = v1_report();
+    assert!(!migrate_value(&mut doc, "1"));
+    assert_eq!(doc, v1_report());
+
+    migrate_value(&mut doc, "2");
+    let once = doc.clone();
+    assert!(!migrate_value(&mut doc, "2"));
+    assert_eq!(doc, once);
+  }
+
+  #[test]
+  fn directory_runs_rewrite_only_legacy_files() {
+    let td = tempfile::TempDir::new().unwrap();
+    let legacy = td.path().join("2025-08/report-2025-08.json");
+    let current = td.path().join("manifest.json");
+    std::fs::create_dir_all(legacy.parent().unwrap()).unwrap();
+    std::fs::write(&legacy, serde_json::to_vec(&v1_report()).unwrap()).unwrap();
+    let manifest = json!({ "ranges": [{ "label": "2025-08", "range": { "start": "s", "end": "e" } }] });
+    std::fs::write(&current, serde_json::to_vec(&manifest).unwrap()).unwrap();
+
+    run(td.path().to_str().unwrap(), CURRENT_SCHEMA_VERSION).unwrap();
+
+    let upgraded: Value = serde_json::from_slice(&std::fs::read(&legacy).unwrap()).unwrap();
+    assert!(upgraded["commits"][0].get("patch_references").is_some());
+    assert_eq!(std::fs::read(&current).unwrap(), serde_json::to_vec(&manifest).unwrap());
+  }
+}
@@ -7,16 +7,41 @@
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Person {
   pub name: String,
   pub email: String,
   pub date: String,
+  // Canonical author after .mailmap and --identity-map, when it differs from name/email
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub identity: Option<Identity>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct Identity {
+  pub name: String,
+  pub email: String,
+}
+
+impl Person {
+  /// "Name <email>" of the canonical identity (or of the person as recorded); the key of every per-author map.
+  pub fn key(&self) -> String {
+    format!("{} <{}>", self.canonical_name(), self.canonical_email())
+  }
+
+  pub fn canonical_name(&self) -> &str {
+    self.identity.as_ref().map_or(&self.name, |i| &i.name)
+  }
+
+  pub fn canonical_email(&self) -> &str {
+    self.identity.as_ref().map_or(&self.email, |i| &i.email)
+  }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Timestamps {
   pub author: i64,
   pub commit: i64,
@@ -25,7 +50,7 @@ pub struct Timestamps {
   pub timezone: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct FileEntry {
   pub file: String,
   pub status: String,
@@ -35,9 +60,20 @@ pub struct FileEntry {
   pub additions: Option<i64>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub deletions: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub service: Option<String>,
+  /// Language by extension or file name (Rust, YAML, Markdown, ...); absent when unknown
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub language: Option<String>,
+  /// Git LFS-tracked path; additions/deletions are then 0 and the change is `lfs_size_delta`
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub lfs: Option<bool>,
+  /// LFS object size change in bytes (post - pre), from the pointer files
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub lfs_size_delta: Option<i64>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct PatchReferencesGithub {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub commit_url: Option<String>,
@@ -45,9 +81,14 @@ pub struct PatchReferencesGithub {
   pub diff_url: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub patch_url: Option<String>,
+  /// Set when this commit only survives upstream through a squash/rebase merge: the links above point at this sha
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub squash_merged_into: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub note: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct PatchReferences {
   pub embed: bool,
   pub git_show_cmd: String,
@@ -57,17 +98,23 @@ pub struct PatchReferences {
   pub github: Option<PatchReferencesGithub>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct Commit {
   pub sha: String,
   pub short_sha: String,
   pub parents: Vec<String>,
+  // Refs (branches, remote branches, tags) whose history contains this commit; set with --all-refs
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub refs: Option<Vec<String>>,
   pub author: Person,
   pub committer: Person,
   pub timestamps: Timestamps,
   pub subject: String,
   pub body: String,
   pub files: Vec<FileEntry>,
+  // Set when --max-files-per-commit dropped file entries; totals cover only the dropped entries
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub files_truncated: Option<FilesTruncated>,
   pub diffstat_text: String,
   pub patch_references: PatchReferences,
   #[serde(skip_serializing_if = "Option::is_none")]
@@ -89,31 +136,349 @@ pub struct Commit {
   pub estimate_basis: Option<String>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub github: Option<CommitGithub>,
+  // Merge requests when origin is a GitLab remote (--github-prs picks the backend from origin)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub gitlab: Option<CommitGitlab>,
+  // Set when per-file additions/deletions were skipped (blobless partial clone / --no-blob-stats)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub blob_stats_unavailable: Option<bool>,
+  // Target environments from deployment trailers (e.g. `Deployed-To:`), in message order
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub environments: Option<Vec<String>>,
+  // How --patch-budget-bytes treated this commit's patch (set only when a budget is active)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub patch_allocation: Option<PatchAllocation>,
+  // Set when the author/committer matches --bot-authors or is GitHub Actions
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub automated: Option<bool>,
+  // Post-image content of files matching --snapshot-path
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub file_snapshots: Option<Vec<FileSnapshot>>,
+  // Set with --committers: whether the author and committer are the same (canonical) email
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub authored_by_committer: Option<bool>,
+  // Issue keys matched by --issue-pattern in the subject, body, or linked PR/MR titles
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub issues: Option<Vec<IssueRef>>,
+  // Set with --first-parent on merge commits: the commits the merge brought in
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub merge_group: Option<MergeGroup>,
+  // Set with --releases: the nearest tag containing this commit (absent while unreleased)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub release: Option<ReleaseRef>,
+}
+
+/// Test fixtures: a minimal commit and file entries, adjusted per test with the `with_*`/`at` overrides or field writes.
+#[cfg(test)]
+impl Commit {
+  /// Commit `sha` (subject "s") by `A <a@x>` at epoch 0 in UTC, with no parents, files, or enrichment.
+  pub fn fixture(sha: &str) -> Self {
+    let json = serde_json::json!({
+      "sha": sha, "short_sha": crate::util::short_sha(sha), "parents": [],
+      "author": { "name": "A", "email": "a@x", "date": "" },
+      "committer": { "name": "A", "email": "a@x", "date": "" },
+      "timestamps": { "author": 0, "commit": 0, "author_local": "", "commit_local": "", "timezone": "utc" },
+      "subject": "s", "body": "", "files": [], "diffstat_text": "",
+      "patch_references": { "embed": false, "git_show_cmd": format!("git show {}", sha) }
+    });
+
+    serde_json::from_value(json).unwrap()
+  }
+
+  /// Author and committer both `name <email>`.
+  pub fn with_author(mut self, name: &str, email: &str) -> Self {
+    self.author.name = name.into();
+    self.author.email = email.into();
+    self.committer = self.author.clone();
+    self
+  }
+
+  /// Committer `name <email>`, leaving the author as is.
+  pub fn with_committer(mut self, name: &str, email: &str) -> Self {
+    self.committer.name = name.into();
+    self.committer.email = email.into();
+    self
+  }
+
+  /// Authored and committed at `epoch`, rendered as `local` in the report zone.
+  pub fn at(mut self, epoch: i64, local: &str) -> Self {
+    self.timestamps.author = epoch;
+    self.timestamps.commit = epoch;
+    self.timestamps.author_local = local.into();
+    self.timestamps.commit_local = local.into();
+    self.author.date = local.into();
+    self.committer.date = local.into();
+    self
+  }
+
+  /// Modified files with their (additions, deletions).
+  pub fn with_files(mut self, files: &[(&str, i64, i64)]) -> Self {
+    self.files = files
+      .iter()
+      .map(|&(file, additions, deletions)| FileEntry {
+        additions: Some(additions),
+        deletions: Some(deletions),
+        ..FileEntry::fixture(file, "M")
+      })
+      .collect();
+    self
+  }
+
+  /// GitHub pull requests from their JSON form (`number`, `title`, `state`, and `html_url` are required).
+  pub fn with_pull_requests(mut self, prs: serde_json::Value) -> Self {
+    self.github = Some(serde_json::from_value(serde_json::json!({ "pull_requests": prs })).unwrap());
+    self
+  }
+}
+
+#[cfg(test)]
+impl FileEntry {
+  /// `file` with git status `status` and no stats.
+  pub fn fixture(file: &str, status: &str) -> Self {
+    FileEntry {
+      file: file.into(),
+      status: status.into(),
+      old_path: None,
+      additions: None,
+      deletions: None,
+      service: None,
+      language: None,
+      lfs: None,
+      lfs_size_delta: None,
+    }
+  }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// The tag a commit first shipped in (`release`, --releases), as `git describe --contains` names it.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct ReleaseRef {
+  pub tag: String,
+  /// GitHub release page, when the repo is on GitHub
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub url: Option<String>,
+}
+
+/// A tag created inside the window (`summary.releases[]`, --releases).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct Release {
+  pub tag: String,
+  /// Short SHA of the tagged commit
+  pub commit: String,
+  /// Tagger date (commit date for lightweight tags) in --tz
+  pub created_at: String,
+  /// Window commits whose release is this tag
+  pub commits: usize,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub url: Option<String>,
+}
+
+/// Commits a first-parent merge brought in (`--first-parent`), i.e. reachable from the merge but not its first parent.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct MergeGroup {
+  /// Constituent commits, earliest→latest
+  pub commits: Vec<Commit>,
+  pub additions: i64,
+  pub deletions: i64,
+  /// Distinct paths changed across the constituent commits
+  pub files_touched: usize,
+}
+
+/// An issue key found in a commit (`--issue-pattern`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct IssueRef {
+  pub key: String,
+  /// Where the key was first seen: "subject", "body", or "pull_request" (a linked PR/MR title)
+  pub source: String,
+  /// --issue-url-template expanded for this key
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub url: Option<String>,
+}
+
+/// File entries dropped by `--max-files-per-commit` (lowest churn first).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Default)]
+pub struct FilesTruncated {
+  pub omitted: usize,
+  pub total_additions: i64,
+  pub total_deletions: i64,
+}
+
+/// Content of one changed file as of the commit (`--snapshot-path`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct FileSnapshot {
+  pub file: String,
+  /// Git blob id of the post-image
+  pub blob: String,
+  /// Full content size in bytes
+  pub size: usize,
+  /// Embedded UTF-8 content, capped by --snapshot-max-bytes (absent for binary or written-out snapshots)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub content: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub clipped: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub binary: Option<bool>,
+  /// Full content written next to the shard (split-apart), relative to the output base dir
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub snapshot_file: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchAllocation {
+  Full,
+  Clipped,
+  ReferenceOnly,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ChangeSet {
   pub additions: i64,
   pub deletions: i64,
   pub files_touched: usize,
+  /// Language (as in `files[].language`, "Other" when unknown) → churn of the listed file entries
+  #[serde(skip_serializing_if = "std::collections::BTreeMap::is_empty", default)]
+  pub changes_by_language: std::collections::BTreeMap<String, LanguageChanges>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// One language's share of a range's changes (`changeset.changes_by_language`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Default)]
+pub struct LanguageChanges {
+  pub additions: i64,
+  pub deletions: i64,
+  /// Distinct paths in this language
+  pub files_touched: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ReportOptions {
   pub include_merges: bool,
   pub include_patch: bool,
   pub include_unmerged: bool,
   pub tz: String,
+  /// Set by --summary-only: the report carries no commits array and no shards were written
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub summary_only: bool,
+  /// Set by --all-refs: commits come from every ref, not just HEAD
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub all_refs: bool,
+  /// Set by --first-parent: commits follow first parents, with merged commits nested under merge_group
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub first_parent: bool,
+  /// --path pathspecs the report is limited to (empty = whole tree)
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub paths: Vec<String>,
+  /// --fields selectors commits were projected to (empty = full commits)
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub fields: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RangeInfo {
   pub label: String,
   pub start: String,
   pub end: String,
+  /// --branch/--ref revisions the range was walked from (empty = HEAD)
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub refs: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Effective configuration and environment overrides that shaped a run (for methodological diffs).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct Provenance {
+  pub tool_version: String,
+  pub window: crate::range_windows::WindowSpec,
+  pub split_apart: bool,
+  pub include_merges: bool,
+  /// --all-refs: commits come from every ref instead of HEAD
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub all_refs: bool,
+  /// --branch/--ref refs walked instead of HEAD
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub refs: Vec<String>,
+  /// --first-parent: history walked along first parents only
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub first_parent: bool,
+  pub include_patch: bool,
+  pub include_pr_patch: bool,
+  pub pr_files: bool,
+  pub max_patch_bytes: usize,
+  pub save_patches: bool,
+  pub github_prs: bool,
+  pub include_unmerged: bool,
+  pub estimate_effort: bool,
+  pub no_blob_stats: bool,
+  pub redact_paths: bool,
+  pub side_indexes: bool,
+  pub stale_days: i64,
+  pub env_trailers: Vec<String>,
+  pub git_args: Vec<String>,
+  pub bot_authors: Vec<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub issue_patterns: Vec<String>,
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub issue_url_templates: Vec<String>,
+  pub repo_policy: bool,
+  pub http_timeout: u64,
+  pub http_retries: u32,
+  pub http_max_pages: usize,
+  pub no_wait: bool,
+  pub collaboration: bool,
+  /// --path / --exclude-path pathspecs limiting the report
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub paths: Vec<String>,
+  pub snapshot_paths: Vec<String>,
+  pub snapshot_max_bytes: usize,
+  pub conflict_hotspots: bool,
+  pub dependency_changes: bool,
+  pub new_contributors: bool,
+  pub no_bodies: bool,
+  /// --work-item-gap minutes when --work-items is on
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub work_items: Option<i64>,
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub calendar: bool,
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub releases: bool,
+  /// --committers: committers map, authored_by_committer, and maintainer activity were reported
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub committers: bool,
+  pub tz: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub now_override: Option<String>,
+  pub env: std::collections::BTreeMap<String, String>,
+  /// GIT_DIR or GIT_WORK_TREE was set (values omitted: they are machine-local paths)
+  #[serde(skip_serializing_if = "std::ops::Not::not", default)]
+  pub git_dir_override: bool,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub service_map: Option<crate::services::ServiceMap>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub identity_map: Option<crate::identities::IdentityMap>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub github_repo: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub patch_budget_bytes: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub max_files_per_commit: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub github_max_requests: Option<u64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sample: Option<usize>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub unmerged_base: Option<String>,
+  /// Installed git version (`git --version`), when detected
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub git_version: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct ServiceSummary {
+  pub commits: i64,
+  pub additions: i64,
+  pub deletions: i64,
+  pub authors: std::collections::BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ReportSummary {
   pub repo: String,
   pub range: RangeInfo,
@@ -121,20 +486,439 @@ pub struct ReportSummary {
   pub report_options: ReportOptions,
   #[serde(rename = "changeset")]
   pub changes: ChangeSet,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub provenance: Option<Provenance>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub services: Option<std::collections::BTreeMap<String, ServiceSummary>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub github: Option<GithubRepoInfo>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub environments: Option<std::collections::BTreeMap<String, EnvironmentSummary>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub patch_budget: Option<PatchBudgetSummary>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub automation: Option<AutomationSplit>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub repo_policy: Option<RepoPolicy>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub collaboration: Option<Collaboration>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub boundary_commits: Option<Vec<BoundaryCommit>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub conflict_hotspots: Option<ConflictHotspots>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub dependency_changes: Option<Vec<ManifestDependencyChanges>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub sample: Option<SampleEstimate>,
+  /// PR author login → reviewer login → first-review latency over the window's PRs
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub review_pairs: Option<std::collections::BTreeMap<String, std::collections::BTreeMap<String, ReviewLatency>>>,
+  /// Authors whose first commit in the repository falls inside the window (--new-contributors)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub new_contributors: Option<Vec<NewContributor>>,
+  /// Sum of the commits' estimated_minutes (--estimate-effort)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub estimated_minutes: Option<f64>,
+  /// Change-request → rework → approval loops across the window's reviewed PRs (--github-prs)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rework_cycles: Option<ReworkSummary>,
+  /// Integration work per committer: commits landed for other authors and merges performed (--committers)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub maintainer_activity: Option<Vec<MaintainerActivity>>,
+  /// Tags created in the window, oldest first (--releases)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub releases: Option<Vec<Release>>,
+  /// Enrichment lookups skipped in this range (GitHub request budget or rate limit)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub warnings: Option<Vec<String>>,
+}
+
+/// An author making their first-ever commit in the window (`summary.new_contributors[]`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct NewContributor {
+  /// "Name <email>", as keyed in `authors`
+  pub author: String,
+  /// Short SHA of the author's first commit
+  pub first_commit: String,
+  /// Commit time of that first commit in --tz
+  pub first_commit_at: String,
+  /// The author's commits in the window
+  pub commits: i64,
+}
+
+/// One author's activity in the range (`author_stats[]`), ordered by commits.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct AuthorStats {
+  /// "Name <email>", as keyed in `authors`
+  pub author: String,
+  pub commits: i64,
+  pub additions: i64,
+  pub deletions: i64,
+  /// Distinct paths across the author's commits
+  pub files_touched: usize,
+  /// Distinct days (author time in --tz) with at least one commit
+  pub active_days: usize,
+  /// Author time of the earliest and latest commit, in --tz
+  pub first_commit_at: String,
+  pub last_commit_at: String,
+  /// One char per window day from the range start: '1' with a commit (author time in --tz), '0' without
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub activity_calendar: String,
+}
+
+/// Commit counts by author time in --tz (`calendar`, --calendar), for heatmaps and punch cards.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct ActivityCalendar {
+  /// --tz the buckets are in
+  pub timezone: String,
+  /// Local day (YYYY-MM-DD) → commits; days without commits are omitted
+  pub days: std::collections::BTreeMap<String, i64>,
+  /// 7 rows (Monday first) of 24 hourly commit counts
+  pub punch_card: Vec<Vec<i64>>,
+}
+
+/// Consecutive commits by one author on overlapping files (`work_items[]`, --work-items).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq, Default)]
+pub struct WorkItem {
+  /// "Name <email>", as keyed in `authors`
+  pub author: String,
+  /// Short SHAs in author-time order
+  pub commits: Vec<String>,
+  /// Subject of the first commit
+  pub subject: String,
+  /// Author time of the first and last commit, in --tz
+  pub started_at: String,
+  pub ended_at: String,
+  /// Seconds from the first to the last commit (0 for a single commit)
+  pub duration_seconds: i64,
+  pub additions: i64,
+  pub deletions: i64,
+  /// Distinct paths across the item's commits, sorted
+  pub files: Vec<String>,
+}
+
+/// Distribution of PR rework loops over one range (`summary.rework_cycles`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct ReworkSummary {
+  /// Reviewed PRs in the window (each counted once)
+  pub pull_requests: i64,
+  /// Loop count ("0", "1", "2", "3+") → PRs
+  pub distribution: std::collections::BTreeMap<String, i64>,
+  /// Median length of a completed loop, change request to approval
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub median_cycle_seconds: Option<i64>,
+}
+
+/// One committer's integration work in a range (`summary.maintainer_activity`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct MaintainerActivity {
+  /// "Name <email>" of the canonical committer identity
+  pub committer: String,
+  /// Window commits they committed
+  pub commits: i64,
+  /// Of those, commits authored by someone else (applied patches, rebases, squash merges)
+  pub for_others: i64,
+  /// Merge commits they created in the window, whether or not --include-merges lists them
+  pub merges: i64,
+}
+
+/// How quickly one reviewer first responded to one author's PRs (`summary.review_pairs`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct ReviewLatency {
+  /// Distinct PRs the reviewer reviewed
+  pub pull_requests: i64,
+  pub median_response_seconds: i64,
+}
+
+/// A commit whose inclusion depends on how the window boundary is read (within 24h of since/until).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct BoundaryCommit {
+  pub sha: String,
+  /// "since" or "until"
+  pub boundary: String,
+  /// Whether this run included the commit
+  pub included: bool,
+  /// Committer timestamp (what --since/--until compare against) in UTC
+  pub commit_utc: String,
+  /// Same instant in the report tz
+  pub commit_local: String,
+  /// Commit time minus boundary time
+  pub offset_seconds: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Files changed while resolving merges in the window (`--conflict-hotspots`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+pub struct ConflictHotspots {
+  pub merges_scanned: usize,
+  /// Most-resolved first
+  pub files: Vec<ConflictHotspot>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+pub struct ConflictHotspot {
+  pub file: String,
+  /// Merges in which the file differed from every parent
+  pub resolutions: i64,
+  /// Short SHAs of those merges, earliest first
+  pub merges: Vec<String>,
+}
+
+/// Net dependency changes of one manifest across the range (`--dependency-changes`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct ManifestDependencyChanges {
+  pub file: String,
+  /// "cargo", "npm", "go", or "pip"
+  pub ecosystem: String,
+  /// Short SHAs of the commits touching the manifest, earliest first
+  pub commits: Vec<String>,
+  pub added: Vec<DependencyRef>,
+  pub removed: Vec<DependencyRef>,
+  pub updated: Vec<DependencyUpdate>,
+}
+
+/// Dependency name (section-qualified for Cargo.toml/package.json, e.g. "dev-dependencies:serde") and version spec.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct DependencyRef {
+  pub name: String,
+  pub version: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct DependencyUpdate {
+  pub name: String,
+  pub from: String,
+  pub to: String,
+}
+
+/// Range totals extrapolated from a `--sample N` run; the rest of the report covers only the sampled commits.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct SampleEstimate {
+  pub method: String,
+  /// Commits processed
+  pub sampled: usize,
+  /// Commits in the window
+  pub population: usize,
+  /// Non-empty (ISO week, author) strata
+  pub strata: usize,
+  /// Confidence level of every low/high interval
+  pub confidence: f64,
+  pub additions: EstimatedTotal,
+  pub deletions: EstimatedTotal,
+  pub file_changes: EstimatedTotal,
+}
+
+/// Estimated range total with its confidence interval.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Copy, PartialEq)]
+pub struct EstimatedTotal {
+  pub estimate: i64,
+  pub low: i64,
+  pub high: i64,
+}
+
+/// `--digest daily` output: the previous calendar day's commits, linked PR activity, and touched unmerged branches.
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct DailyDigest {
+  pub kind: String,
+  pub repo: String,
+  /// Local calendar day covered (YYYY-MM-DD)
+  pub date: String,
+  pub range: RangeInfo,
+  pub commits: Vec<DigestCommit>,
+  pub authors: std::collections::BTreeMap<String, i64>,
+  pub changeset: ChangeSet,
+  pub pull_requests: DigestPullRequests,
+  pub unmerged_branches: Vec<DigestBranch>,
+  /// Short Markdown rendering of everything above, ready to post
+  pub markdown: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct DigestCommit {
+  pub short_sha: String,
+  pub subject: String,
+  pub author: String,
+}
+
+/// PRs linked to the day's commits (requires GitHub access; empty lists otherwise).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+pub struct DigestPullRequests {
+  /// Created during the day
+  pub opened: Vec<DigestPullRequest>,
+  /// Merged during the day
+  pub merged: Vec<DigestPullRequest>,
+  /// With at least one review or approval
+  pub reviewed: Vec<DigestPullRequest>,
+  /// Still open now
+  pub open: Vec<DigestPullRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct DigestPullRequest {
+  pub number: i64,
+  pub title: String,
+  pub state: String,
+  pub html_url: String,
+}
+
+/// Local branch (other than the current one) with commits in the day that are not on HEAD.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct DigestBranch {
+  pub name: String,
+  pub commits: usize,
+}
+
+/// Collaboration graph over the window (`--collaboration`): authors as nodes, undirected weighted edges.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+pub struct Collaboration {
+  pub nodes: Vec<CollaborationNode>,
+  pub edges: Vec<CollaborationEdge>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+pub struct CollaborationNode {
+  /// "Name <email>", matching the `authors` map
+  pub id: String,
+  /// Commits authored in the window
+  pub commits: i64,
+  /// Commits where this person appears only as a `Co-authored-by` trailer
+  pub co_authored: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, PartialEq)]
+pub struct CollaborationEdge {
+  /// Lexicographically smaller node id
+  pub source: String,
+  pub target: String,
+  /// co_authored_commits + shared_files
+  pub weight: i64,
+  pub co_authored_commits: i64,
+  pub shared_files: i64,
+}
+
+/// Human vs automated activity; present only when at least one commit was classified as automated.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct AutomationSplit {
+  pub human: ActivityTotals,
+  pub automated: ActivityTotals,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct ActivityTotals {
+  pub commits: i64,
+  pub additions: i64,
+  pub deletions: i64,
+  pub authors: std::collections::BTreeMap<String, i64>,
+}
+
+/// Outcome of `--patch-budget-bytes` allocation across a range.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default, Clone, PartialEq, Eq)]
+pub struct PatchBudgetSummary {
+  pub budget_bytes: usize,
+  pub requested_bytes: usize,
+  pub embedded_bytes: usize,
+  pub full: usize,
+  pub clipped: usize,
+  pub reference_only: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct EnvironmentSummary {
+  pub commits: i64,
+  /// Commit counts keyed by ISO week (`YYYY-Www`)
+  pub weeks: std::collections::BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct GithubRepoInfo {
+  pub owner: String,
+  pub repo: String,
+  pub host: String,
+}
+
+/// Repository policy context (`--repo-policy`): default-branch protection and the combined status of the window-end commit.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default)]
+pub struct RepoPolicy {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub default_branch: Option<String>,
+  /// Absent when the branch is unprotected or the token cannot read protection settings (requires admin read)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub branch_protection: Option<BranchProtection>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub head_status: Option<CommitStatus>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct BranchProtection {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub required_approving_review_count: Option<i64>,
+  pub dismiss_stale_reviews: bool,
+  pub require_code_owner_reviews: bool,
+  pub required_status_checks: Vec<String>,
+  pub strict_status_checks: bool,
+  pub enforce_admins: bool,
+  pub required_signatures: bool,
+  pub required_linear_history: bool,
+  pub allow_force_pushes: bool,
+  pub allow_deletions: bool,
+}
+
+/// Combined commit status (legacy status API contexts) for one sha.
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Default, PartialEq)]
+pub struct CommitStatus {
+  pub sha: String,
+  pub state: String,
+  pub total_count: i64,
+  /// Context name → state
+  pub contexts: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SimpleReport {
   pub summary: ReportSummary,
   pub authors: std::collections::BTreeMap<String, i64>,
-  pub commits: Vec<Commit>,
+  // Commits per committer ("Name <email>"); set with --committers
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub committers: Option<std::collections::BTreeMap<String, i64>>,
+  #[serde(default)]
+  pub author_stats: Vec<AuthorStats>,
+  // Runs of one author's commits on overlapping files; set with --work-items
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub work_items: Option<Vec<WorkItem>>,
+  // Commits by local day and weekday × hour; set with --calendar
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub calendar: Option<ActivityCalendar>,
+  #[serde(default)]
+  pub commits: Vec<Commit>, // omitted from output with --summary-only
   #[serde(skip_serializing_if = "Option::is_none")]
   pub items: Option<Vec<ManifestItem>>, // present when split-apart
   #[serde(skip_serializing_if = "Option::is_none")]
   pub unmerged_activity: Option<UnmergedActivity>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub indexes: Option<SideIndexes>, // present when split-apart with --side-indexes
+  // Warnings logged while building this range (skipped lookups, git fallbacks, ...)
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub warnings: Vec<Warning>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// A best-effort step that failed or fell back (`warnings[]` in range reports and manifest.json).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct Warning {
+  /// Where it happened: the stderr log target (`gar` for git and report building, `github`, `gitlab`, `links`, ...)
+  pub stage: String,
+  /// The commit concerned, when the warning is about one
+  #[serde(skip_serializing_if = "Option::is_none", default)]
+  pub sha: Option<String>,
+  pub message: String,
+}
+
+/// Side index files for one range, keyed by author ("Name <email>") and by local day (paths relative to the base dir).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Default)]
+pub struct SideIndexes {
+  pub authors: std::collections::BTreeMap<String, String>,
+  pub days: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct GithubUser {
   #[serde(skip_serializing_if = "Option::is_none")]
   pub login: Option<String>,
@@ -146,7 +930,7 @@ pub struct GithubUser {
   pub email: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct GithubPullRequest {
   pub number: i64,
   pub title: String,
@@ -187,6 +971,15 @@ pub struct GithubPullRequest {
   pub time_to_first_review_seconds: Option<i64>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub time_to_merge_seconds: Option<i64>,
+  /// Each reviewer's first submitted review, relative to PR creation
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub review_responses: Option<Vec<ReviewResponse>>,
+  /// Completed change-request → new commits → approval loops (0 when reviewed without rework)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rework_cycles: Option<i64>,
+  /// Seconds from the first change request of each loop to the approval that closed it
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub rework_cycle_seconds: Option<Vec<i64>>,
   // Optional effort estimation (best-effort, minutes)
   #[serde(skip_serializing_if = "Option::is_none")]
   pub estimated_minutes: Option<f64>,
@@ -198,39 +991,85 @@ pub struct GithubPullRequest {
   pub estimate_confidence: Option<f64>,
   #[serde(skip_serializing_if = "Option::is_none")]
   pub estimate_basis: Option<String>,
+  // Combined PR diff text (merged PRs only; --include-pr-patch), clipped like commit patches
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub diff: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub diff_clipped: Option<bool>,
+  // Review heuristics from the PR details payload: xs/s/m/l/xl by additions+deletions,
+  // review comments per 100 changed lines, and merged PRs that never got a review
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub size_class: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub review_depth: Option<f64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub merged_without_review: Option<bool>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub merge_commit_sha: Option<String>,
+  /// Files changed by the whole PR per GitHub (--pr-files); can differ from the window's commits for squash merges
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub files: Option<Vec<FileEntry>>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct ReviewResponse {
+  pub reviewer: String,
+  /// First review submitted_at minus PR created_at
+  pub response_seconds: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct PullRequestCommit {
   pub sha: String,
   pub short_sha: String,
   pub subject: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ManifestItem {
   pub sha: String,
   pub file: String,
   pub subject: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BranchItems {
   pub name: String,
   pub merged_into_head: Option<bool>,
   pub ahead_of_head: Option<i64>,
   pub behind_head: Option<i64>,
   pub items: Vec<ManifestItem>,
+  // Branch hygiene: who authored the unmerged commits and how old the branch tip is
+  pub authors: std::collections::BTreeMap<String, i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub last_commit_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub last_commit_age_days: Option<i64>,
+  pub stale: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct StaleBranch {
+  pub name: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub last_commit_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub last_commit_age_days: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct UnmergedActivity {
+  /// Ref branches were compared against (--unmerged-base); absent means HEAD
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub base: Option<String>,
   pub branches_scanned: usize,
   pub total_unmerged_commits: usize,
   pub branches: Vec<BranchItems>,
+  pub stale_after_days: i64,
+  pub stale_branches: Vec<StaleBranch>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct RangeManifest {
   pub label: Option<String>,
   pub range: RangeInfo,
@@ -243,8 +1082,73 @@ pub struct RangeManifest {
   pub unmerged_activity: Option<UnmergedActivity>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
 pub struct CommitGithub {
   #[serde(skip_serializing_if = "Vec::is_empty", default)]
   pub pull_requests: Vec<GithubPullRequest>,
 }
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct CommitGitlab {
+  #[serde(skip_serializing_if = "Vec::is_empty", default)]
+  pub merge_requests: Vec<GitlabMergeRequest>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, PartialEq)]
+pub struct GitlabUser {
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub username: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub name: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub profile_url: Option<String>,
+}
+
+/// A GitLab merge request linked to a commit (`commit.gitlab.merge_requests[]`).
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
+pub struct GitlabMergeRequest {
+  /// Project-scoped MR number (`!iid`)
+  pub iid: i64,
+  pub title: String,
+  /// opened, closed, merged, or locked
+  pub state: String,
+  pub web_url: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub body_lines: Option<Vec<String>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub created_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub merged_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub closed_at: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub source_branch: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub target_branch: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub author: Option<GitlabUser>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub merged_by: Option<GitlabUser>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub approvers: Option<Vec<GitlabUser>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub reviewers: Option<Vec<GitlabUser>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub commits: Option<Vec<PullRequestCommit>>,
+  // Optional metrics (best-effort); reviews are non-system notes by anyone but the author
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub review_count: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub approval_count: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub time_to_first_review_seconds: Option<i64>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub time_to_merge_seconds: Option<i64>,
+  /// Each reviewer's first note, relative to MR creation
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub review_responses: Option<Vec<ReviewResponse>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub merge_commit_sha: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub squash_commit_sha: Option<String>,
+}
@@ -0,0 +1,118 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Detect authors whose first-ever commit in the repository lands inside the window (--new-contributors)
+// role: summary/new-contributors
+// inputs: repo, all_refs, processed window commits, tz, optional --identity-map
+// outputs: Vec<NewContributor> for summary.new_contributors
+// side_effects: one git log over full history per (repo, all_refs), cached for the process lifetime
+// invariants:
+// - authors are matched by lowercased canonical email (.mailmap, then --identity-map); history is HEAD's, or every ref with --all-refs
+// - an author is new when their earliest commit is one of the window's processed commits (with --sample, only sampled commits are seen)
+// - entries are ordered by first commit time, then author
+// errors: git failures propagate with context (the flag is opt-in)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+
+use crate::gitio;
+use crate::identities::{IdentityMap, resolve};
+use crate::model::{Commit, NewContributor};
+use crate::util::{iso_in_tz, short_sha};
+
+type FirstCommits = HashMap<String, (String, i64)>;
+
+static FIRST_COMMITS: Lazy<Mutex<HashMap<(String, bool), FirstCommits>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Authors in `commits` whose earliest commit in the repository is among them.
+pub fn build_new_contributors(
+  repo: &str,
+  all_refs: bool,
+  commits: &[Commit],
+  tz: &str,
+  identities: Option<&IdentityMap>,
+) -> Result<Vec<NewContributor>> {
+  let mut cache = FIRST_COMMITS.lock().unwrap();
+  let key = (repo.to_string(), all_refs);
+
+  if !cache.contains_key(&key) {
+    let first = gitio::first_commits_by_author(repo, all_refs, |name, email| {
+      resolve(identities, name, email).1.to_ascii_lowercase()
+    })
+    .context("finding each author's first commit")?;
+    cache.insert(key.clone(), first);
+  }
+
+  let window: Vec<(&str, &str, &str)> = commits
+    .iter()
+    .map(|c| (c.sha.as_str(), c.author.canonical_name(), c.author.canonical_email()))
+    .collect();
+
+  Ok(select(&cache[&key], &window, tz))
+}
+
+/// Match window commits `(sha, author name, author email)` against each email's first commit.
+fn select(first: &FirstCommits, window: &[(&str, &str, &str)], tz: &str) -> Vec<NewContributor> {
+  let mut counts: HashMap<String, i64> = HashMap::new();
+  for (_, _, email) in window {
+    *counts.entry(email.to_ascii_lowercase()).or_default() += 1;
+  }
+
+  let mut found: Vec<(i64, NewContributor)> = window
+    .iter()
+    .filter_map(|(sha, name, email)| {
+      let email_key = email.to_ascii_lowercase();
+      let (first_sha, epoch) = first.get(&email_key)?;
+      (first_sha == sha).then(|| {
+        (
+          *epoch,
+          NewContributor {
+            author: format!("{} <{}>", name, email),
+            first_commit: short_sha(sha),
+            first_commit_at: iso_in_tz(*epoch, tz),
+            commits: counts[&email_key],
+          },
+        )
+      })
+    })
+    .collect();
+
+  found.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.author.cmp(&b.1.author)));
+  found.into_iter().map(|(_, c)| c).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn first() -> FirstCommits {
+    HashMap::from([
+      ("old@x".to_string(), ("aaaaaaaaaaaaaaaa".to_string(), 100)),
+      ("new@x".to_string(), ("cccccccccccccccc".to_string(), 300)),
+      ("early@x".to_string(), ("dddddddddddddddd".to_string(), 200)),
+    ])
+  }
+
+  #[test]
+  fn lists_authors_whose_first_commit_is_in_window() {
+    let window = [
+      ("bbbbbbbbbbbbbbbb", "Old", "old@x"),
+      ("cccccccccccccccc", "New", "New@X"),
+      ("eeeeeeeeeeeeeeee", "New", "new@x"),
+      ("dddddddddddddddd", "Early", "early@x"),
+    ];
+
+    let found = select(&first(), &window, "utc");
+
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].author, "Early <early@x>");
+    assert_eq!(found[1].author, "New <New@X>");
+    assert_eq!(found[1].first_commit, "cccccccccccc");
+    assert_eq!(found[1].commits, 2);
+    assert_eq!(found[1].first_commit_at, "1970-01-01T00:05:00Z");
+  }
+}
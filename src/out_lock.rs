@@ -0,0 +1,200 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Advisory lock on an output directory so concurrent runs (e.g. two cron jobs) update manifest.json/rollup.json one at a time
+// role: persistence/locking
+// inputs: Output directory; wait timeout (--lock-timeout, 0 with --no-wait)
+// outputs: A guard holding <dir>/.gar.lock (pid and acquisition time inside); the file is removed when the guard drops
+// side_effects: Creates, renames, and removes lock files; sleeps while another run holds it
+// invariants:
+// - acquisition is create_new (O_EXCL), so exactly one process holds the lock at a time on local filesystems
+// - a lock older than STALE_LOCK_SECS is treated as left behind by a crashed run: it is renamed aside (atomic, so only one waiter wins) with a warning and acquisition retried; a waiter that loses the race or grabs a fresh lock puts it back and keeps waiting
+// - dropping the guard removes the lock file only while it still holds this guard's own stamp
+// - advisory only: runs that never take the lock (older versions, manual edits) are not blocked
+// errors: Timing out names the lock file and its holder; other IO errors carry the path
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, bail};
+
+use crate::util;
+
+pub const LOCK_FILE: &str = ".gar.lock";
+
+/// Default wait for another run's lock (`--lock-timeout`), matching the wait for git's own locks.
+pub const DEFAULT_LOCK_TIMEOUT_SECS: u64 = 30;
+
+/// Manifest updates take well under a second; a lock this old belongs to a run that died holding it.
+const STALE_LOCK_SECS: u64 = 600;
+
+const POLL: Duration = Duration::from_millis(100);
+
+/// Held lock on an output directory; dropping it releases the lock.
+#[derive(Debug)]
+pub struct DirLock {
+  path: PathBuf,
+  stamp: String,
+}
+
+impl DirLock {
+  /// Take `<dir>/.gar.lock`, waiting up to `timeout` for another run to release it.
+  pub fn acquire(dir: &Path, timeout: Duration) -> Result<Self> {
+    let path = dir.join(LOCK_FILE);
+    let deadline = Instant::now() + timeout;
+    let mut announced = false;
+
+    loop {
+      match std::fs::OpenOptions::new().write(true).create_new(true).open(&path) {
+        Ok(mut f) => {
+          let stamp = format!("pid={} at={}", std::process::id(), chrono::Utc::now().to_rfc3339());
+          let _ = writeln!(f, "{}", stamp);
+
+          return Ok(Self { path, stamp });
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+        Err(e) => return Err(e).with_context(|| format!("creating {}", path.display())),
+      }
+
+      if is_stale(&path) && break_stale_lock(&path)? {
+        continue;
+      }
+
+      if Instant::now() >= deadline {
+        bail!(
+          "{} is held by another run ({}); retry later, raise --lock-timeout, or remove the file if that run is gone",
+          path.display(),
+          holder(&path)
+        );
+      }
+
+      if !announced {
+        util::log(
+          util::LogLevel::Info,
+          "gar",
+          &format!("waiting for {} ({})", path.display(), holder(&path)),
+        );
+        announced = true;
+      }
+
+      std::thread::sleep(POLL.min(deadline.saturating_duration_since(Instant::now())));
+    }
+  }
+}
+
+impl Drop for DirLock {
+  fn drop(&mut self) {
+    // A waiter may have broken this lock as stale and taken it over; that lock is theirs to release
+    if holder(&self.path) != self.stamp {
+      return;
+    }
+
+    let _ = std::fs::remove_file(&self.path);
+  }
+}
+
+fn lock_age(path: &Path) -> Option<Duration> {
+  std::fs::metadata(path).ok()?.modified().ok()?.elapsed().ok()
+}
+
+fn is_stale(path: &Path) -> bool {
+  lock_age(path).is_some_and(|age| age >= Duration::from_secs(STALE_LOCK_SECS))
+}
+
+/// Move a stale lock aside under a name unique to this process; true when the stale lock is gone.
+///
+/// Rename is atomic, so of several waiters that saw the same stale lock only one moves it. A waiter that finds the
+/// lock already gone just retries; one whose rename picked up a fresh lock (taken after its staleness check) links it
+/// back in place.
+fn break_stale_lock(path: &Path) -> Result<bool> {
+  let aside = path.with_extension(format!("lock.stale.{}", std::process::id()));
+
+  match std::fs::rename(path, &aside) {
+    Ok(()) => {}
+    Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+    Err(e) => return Err(e).with_context(|| format!("moving stale lock {}", path.display())),
+  }
+
+  if !is_stale(&aside) {
+    let _ = std::fs::hard_link(&aside, path);
+    let _ = std::fs::remove_file(&aside);
+
+    return Ok(false);
+  }
+
+  util::log(
+    util::LogLevel::Warn,
+    "gar",
+    &format!("removed stale lock {} ({})", path.display(), holder(&aside)),
+  );
+  let _ = std::fs::remove_file(&aside);
+
+  Ok(true)
+}
+
+fn holder(path: &Path) -> String {
+  std::fs::read_to_string(path)
+    .map(|s| s.trim().to_string())
+    .ok()
+    .filter(|s| !s.is_empty())
+    .unwrap_or_else(|| "unknown holder".into())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn second_holder_times_out_until_the_first_drops() {
+    let td = tempfile::TempDir::new().unwrap();
+    let first = DirLock::acquire(td.path(), Duration::ZERO).unwrap();
+    assert!(td.path().join(LOCK_FILE).exists());
+
+    let err = DirLock::acquire(td.path(), Duration::from_millis(150)).unwrap_err();
+    assert!(format!("{:#}", err).contains("held by another run (pid="));
+
+    drop(first);
+    assert!(!td.path().join(LOCK_FILE).exists());
+    assert!(DirLock::acquire(td.path(), Duration::ZERO).is_ok());
+  }
+
+  #[test]
+  fn stale_locks_are_broken() {
+    let td = tempfile::TempDir::new().unwrap();
+    let path = td.path().join(LOCK_FILE);
+    std::fs::write(&path, "pid=1 at=2020-01-01T00:00:00Z").unwrap();
+    let old = std::time::SystemTime::now() - Duration::from_secs(STALE_LOCK_SECS + 60);
+    std::fs::File::options()
+      .write(true)
+      .open(&path)
+      .unwrap()
+      .set_modified(old)
+      .unwrap();
+
+    let lock = DirLock::acquire(td.path(), Duration::ZERO).unwrap();
+    assert!(
+      std::fs::read_to_string(&path)
+        .unwrap()
+        .contains(&format!("pid={}", std::process::id()))
+    );
+    drop(lock);
+  }
+
+  #[test]
+  fn guards_only_release_their_own_lock() {
+    let td = tempfile::TempDir::new().unwrap();
+    let path = td.path().join(LOCK_FILE);
+    let lock = DirLock::acquire(td.path(), Duration::ZERO).unwrap();
+
+    // Another waiter broke this lock as stale and took it over
+    std::fs::write(&path, "pid=1 at=2020-01-01T00:00:00Z").unwrap();
+    drop(lock);
+    assert!(path.exists());
+
+    // A stale lock someone else already moved aside just means retry
+    std::fs::remove_file(&path).unwrap();
+    assert!(break_stale_lock(&path).unwrap());
+  }
+}
@@ -0,0 +1,126 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Allocate a range-wide byte budget for embedded patches (--patch-budget-bytes)
+// role: processing/patch-budget
+// inputs: Commits with embedded patch_lines (already capped per commit by --max-patch-bytes); budget in bytes
+// outputs: Commits with full, clipped, or removed patches and `patch_allocation` set; PatchBudgetSummary for the report
+// side_effects: None (pure; mutates the given commits)
+// invariants:
+// - patches are considered smallest first (ties by sha), so small commits keep full patches
+// - at most one commit is clipped: the first that no longer fits takes the remaining bytes; larger ones become reference-only
+// - embedded_bytes never exceeds budget_bytes; reference-only commits set patch_references.embed = false
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use crate::model::{Commit, PatchAllocation, PatchBudgetSummary};
+use crate::util::clip_patch;
+
+/// Apply the range-wide patch budget to `commits` and summarize the decisions.
+pub fn apply_patch_budget(commits: &mut [Commit], budget_bytes: usize) -> PatchBudgetSummary {
+  let mut sized: Vec<(usize, usize)> = commits
+    .iter()
+    .enumerate()
+    .filter_map(|(i, c)| c.patch_lines.as_ref().map(|l| (i, patch_len(l))))
+    .collect();
+  sized.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| commits[a.0].sha.cmp(&commits[b.0].sha)));
+
+  let mut summary = PatchBudgetSummary {
+    budget_bytes,
+    ..Default::default()
+  };
+  let mut remaining = budget_bytes;
+
+  for (i, size) in sized {
+    summary.requested_bytes += size;
+    let commit = &mut commits[i];
+
+    if size <= remaining {
+      remaining -= size;
+      summary.embedded_bytes += size;
+      summary.full += 1;
+      commit.patch_allocation = Some(PatchAllocation::Full);
+
+      continue;
+    }
+
+    if remaining > 0 {
+      let text = commit.patch_lines.take().unwrap_or_default().join("\n");
+      let (clipped, _) = clip_patch(text, remaining);
+      let clipped = clipped.unwrap_or_default();
+
+      summary.embedded_bytes += clipped.len();
+      summary.clipped += 1;
+      remaining = 0;
+
+      commit.patch_lines = Some(clipped.lines().map(String::from).collect());
+      commit.patch_clipped = Some(true);
+      commit.patch_allocation = Some(PatchAllocation::Clipped);
+
+      continue;
+    }
+
+    summary.reference_only += 1;
+    commit.patch_lines = None;
+    commit.patch_clipped = None;
+    commit.patch_references.embed = false;
+    commit.patch_allocation = Some(PatchAllocation::ReferenceOnly);
+  }
+
+  summary
+}
+
+/// Byte length of the patch as embedded (lines joined by '\n').
+fn patch_len(lines: &[String]) -> usize {
+  lines.iter().map(|l| l.len()).sum::<usize>() + lines.len().saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(sha: &str, patch_bytes: usize) -> Commit {
+    let mut c = Commit::fixture(sha);
+    c.patch_references.embed = true;
+    c.patch_lines = Some(vec!["x".repeat(patch_bytes)]);
+    c.patch_clipped = Some(false);
+    c
+  }
+
+  #[test]
+  fn small_patches_embed_first_then_clip_then_reference() {
+    let mut commits = vec![commit("big", 500), commit("small", 10), commit("mid", 100)];
+
+    let s = apply_patch_budget(&mut commits, 150);
+
+    assert_eq!(commits[1].patch_allocation, Some(PatchAllocation::Full));
+    assert_eq!(commits[2].patch_allocation, Some(PatchAllocation::Full));
+    assert_eq!(commits[0].patch_allocation, Some(PatchAllocation::Clipped));
+    assert_eq!(commits[0].patch_lines.as_ref().unwrap()[0].len(), 40);
+    assert_eq!(s.embedded_bytes, 150);
+    assert_eq!(s.requested_bytes, 610);
+    assert_eq!((s.full, s.clipped, s.reference_only), (2, 1, 0));
+  }
+
+  #[test]
+  fn exhausted_budget_leaves_reference_only() {
+    let mut commits = vec![commit("a", 100), commit("b", 100), commit("c", 100)];
+
+    let s = apply_patch_budget(&mut commits, 100);
+
+    assert_eq!((s.full, s.clipped, s.reference_only), (1, 0, 2));
+    assert_eq!(commits[0].patch_allocation, Some(PatchAllocation::Full));
+    assert!(commits[2].patch_lines.is_none());
+    assert!(!commits[2].patch_references.embed);
+  }
+
+  #[test]
+  fn zero_budget_embeds_nothing() {
+    let mut commits = vec![commit("a", 1)];
+
+    let s = apply_patch_budget(&mut commits, 0);
+
+    assert_eq!(s.reference_only, 1);
+    assert_eq!(s.embedded_bytes, 0);
+  }
+}
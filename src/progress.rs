@@ -0,0 +1,117 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Per-range progress line on stderr (commits processed / total and the latest SHA) for long interactive runs
+// role: observability/progress
+// inputs: set_enabled from main (stderr is a terminal and not --quiet); range label and commit total; one tick per processed commit
+// outputs: A single self-overwriting stderr line, e.g. `[gar] 2025-08: 120/500 commits (3f2a9c1d0b7e)`
+// side_effects: Writes to stderr only while enabled; the line is cleared when the bar drops and before any log line
+// invariants:
+// - disabled (the default) means nothing is ever written, so pipes, CI logs, and tests see no progress output
+// - redraws at most every REDRAW interval, plus the final count; ticks from --jobs worker threads are safe
+// - a log line never lands on top of the bar: crate::logging clears it first and the next tick redraws it
+// errors: None (stderr write failures are ignored)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::io::Write as _;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+const REDRAW: Duration = Duration::from_millis(100);
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Whether a progress line is currently on screen (and must be cleared before other stderr output).
+static DRAWN: AtomicBool = AtomicBool::new(false);
+
+/// Turn progress lines on or off for the rest of the run.
+pub fn set_enabled(enabled: bool) {
+  ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Erase the progress line, if one is showing, so the next stderr write starts on a clean line.
+pub fn clear_line() {
+  if DRAWN.swap(false, Ordering::Relaxed) {
+    let mut err = std::io::stderr().lock();
+    let _ = write!(err, "\r\x1b[2K");
+    let _ = err.flush();
+  }
+}
+
+/// Progress for one range; cleared when dropped.
+pub struct Progress {
+  label: String,
+  total: usize,
+  done: AtomicUsize,
+  last_draw: Mutex<Option<Instant>>,
+}
+
+impl Progress {
+  /// A bar for `total` commits; inert unless enabled and there is more than one commit to show.
+  pub fn new(label: &str, total: usize) -> Option<Self> {
+    (ENABLED.load(Ordering::Relaxed) && total > 1).then(|| Self {
+      label: label.to_string(),
+      total,
+      done: AtomicUsize::new(0),
+      last_draw: Mutex::new(None),
+    })
+  }
+
+  /// Count one processed commit and redraw when due.
+  pub fn tick(&self, sha: &str) {
+    let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+    let Ok(mut last) = self.last_draw.lock() else {
+      return;
+    };
+
+    if done < self.total && last.is_some_and(|t| t.elapsed() < REDRAW) {
+      return;
+    }
+    *last = Some(Instant::now());
+
+    let mut err = std::io::stderr().lock();
+    let _ = write!(err, "\r\x1b[2K{}", line(&self.label, done, self.total, sha));
+    let _ = err.flush();
+    DRAWN.store(true, Ordering::Relaxed);
+  }
+}
+
+impl Drop for Progress {
+  fn drop(&mut self) {
+    clear_line();
+  }
+}
+
+/// Tick `progress` when there is one.
+pub fn tick(progress: Option<&Progress>, sha: &str) {
+  if let Some(p) = progress {
+    p.tick(sha);
+  }
+}
+
+/// The progress text (without the carriage return and erase sequence).
+fn line(label: &str, done: usize, total: usize, sha: &str) -> String {
+  format!(
+    "[gar] {}: {}/{} commits ({})",
+    label,
+    done,
+    total,
+    crate::util::short_sha(sha)
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn line_shows_counts_and_short_sha_and_disabled_bars_are_inert() {
+    assert_eq!(
+      line("2025-08", 120, 500, "3f2a9c1d0b7e55aa66bb77cc88dd99ee00ff1122"),
+      "[gar] 2025-08: 120/500 commits (3f2a9c1d0b7e)"
+    );
+
+    set_enabled(false);
+    assert!(Progress::new("2025-08", 500).is_none());
+  }
+}
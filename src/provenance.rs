@@ -0,0 +1,138 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Capture the effective configuration and environment overrides that shaped a run, for methodological diffs
+// role: provenance/snapshot
+// inputs: EffectiveConfig; process environment (GAR_EST_* tuning overrides, TZ, whether GIT_DIR/GIT_WORK_TREE are set)
+// outputs: Provenance struct embedded under summary.provenance
+// side_effects: Reads environment variables; runs `git --version` once per process
+// invariants:
+// - Paths (repo, out, save_patches, service map file) are excluded so provenance compares cleanly across machines; the service map rules themselves are recorded
+// - --path / --exclude-path pathspecs are repo-relative and change which commits and files are reported, so they are recorded (as in report_options.paths)
+// - Redaction globs are reduced to a flag since they may themselves name sensitive paths
+// - env keys are sorted (BTreeMap) and only variables that are actually set are recorded
+// - GIT_DIR / GIT_WORK_TREE hold machine-local paths, so only whether either is set is recorded (git_dir_override)
+// errors: None (pure aside from env reads and git version detection)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use crate::cli::EffectiveConfig;
+use crate::model::Provenance;
+
+/// Environment variable prefixes that alter output methodology (effort tuning knobs).
+const ENV_PREFIXES: &[&str] = &["GAR_EST_"];
+
+/// Exact environment variables that alter output methodology.
+const ENV_EXACT: &[&str] = &["TZ"];
+
+/// Variables that redirect git to another repository; recorded as a flag since their values are local paths.
+const GIT_DIR_ENV: &[&str] = &["GIT_DIR", "GIT_WORK_TREE"];
+
+fn is_tracked_env(key: &str) -> bool {
+  ENV_EXACT.contains(&key) || ENV_PREFIXES.iter().any(|p| key.starts_with(p))
+}
+
+/// Collect the tracked environment overrides currently set for this process.
+pub fn collect_env_overrides() -> BTreeMap<String, String> {
+  std::env::vars().filter(|(k, _)| is_tracked_env(k)).collect()
+}
+
+/// Whether GIT_DIR or GIT_WORK_TREE points git somewhere other than --repo.
+fn git_dir_override() -> bool {
+  GIT_DIR_ENV.iter().any(|k| std::env::var_os(k).is_some())
+}
+
+/// Build a provenance snapshot from the effective configuration and current environment.
+pub fn from_config(cfg: &EffectiveConfig) -> Provenance {
+  let env = collect_env_overrides();
+
+  Provenance {
+    tool_version: env!("CARGO_PKG_VERSION").to_string(),
+    window: cfg.window.clone(),
+    split_apart: cfg.split_apart,
+    include_merges: cfg.include_merges,
+    all_refs: cfg.all_refs,
+    refs: cfg.refs.clone(),
+    first_parent: cfg.first_parent,
+    include_patch: cfg.include_patch,
+    include_pr_patch: cfg.include_pr_patch,
+    pr_files: cfg.pr_files,
+    max_patch_bytes: cfg.max_patch_bytes,
+    save_patches: cfg.save_patches.is_some(),
+    github_prs: cfg.github_prs,
+    include_unmerged: cfg.include_unmerged,
+    estimate_effort: cfg.estimate_effort,
+    no_blob_stats: cfg.no_blob_stats,
+    redact_paths: !cfg.redact_paths.is_empty(),
+    side_indexes: cfg.side_indexes,
+    stale_days: cfg.stale_days,
+    env_trailers: cfg.env_trailers.clone(),
+    git_args: cfg.git_args.clone(),
+    bot_authors: cfg.bot_authors.clone(),
+    issue_patterns: cfg.issue_patterns.clone(),
+    issue_url_templates: cfg.issue_url_templates.clone(),
+    repo_policy: cfg.repo_policy,
+    http_timeout: cfg.http_timeout,
+    http_retries: cfg.http_retries,
+    http_max_pages: cfg.http_max_pages,
+    no_wait: cfg.no_wait,
+    collaboration: cfg.collaboration,
+    paths: cfg.paths.clone(),
+    snapshot_paths: cfg.snapshot_paths.clone(),
+    snapshot_max_bytes: cfg.snapshot_max_bytes,
+    conflict_hotspots: cfg.conflict_hotspots,
+    dependency_changes: cfg.dependency_changes,
+    new_contributors: cfg.new_contributors,
+    no_bodies: cfg.no_bodies,
+    work_items: cfg.work_items,
+    calendar: cfg.calendar,
+    releases: cfg.releases,
+    committers: cfg.committers,
+    tz: cfg.tz.clone(),
+    now_override: cfg.now_override.clone(),
+    env,
+    git_dir_override: git_dir_override(),
+    service_map: cfg.service_map.clone(),
+    identity_map: cfg.identity_map.clone(),
+    github_repo: cfg.github_repo.clone(),
+    patch_budget_bytes: cfg.patch_budget_bytes,
+    max_files_per_commit: cfg.max_files_per_commit,
+    github_max_requests: cfg.github_max_requests,
+    sample: cfg.sample,
+    unmerged_base: cfg.unmerged_base.clone(),
+    git_version: crate::util::git_version().map(|v| v.to_string()),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use clap::Parser;
+
+  #[test]
+  fn tracked_env_matches_prefixes_and_exact_names() {
+    assert!(is_tracked_env("TZ"));
+    assert!(!is_tracked_env("GIT_DIR"));
+    assert!(!is_tracked_env("GIT_WORK_TREE"));
+    assert!(is_tracked_env("GAR_EST_BASE_COMMIT_MIN"));
+    assert!(!is_tracked_env("GITHUB_TOKEN"));
+    assert!(!is_tracked_env("GAR_TEST_PR_JSON"));
+  }
+
+  #[test]
+  fn pathspecs_including_exclusions_are_recorded() {
+    let cli = crate::cli::Cli::parse_from([
+      "git-activity-report",
+      "--month",
+      "2025-08",
+      "--path",
+      "src/",
+      "--exclude-path",
+      "src/vendor/",
+    ]);
+    let cfg = crate::cli::normalize(cli).unwrap();
+
+    assert_eq!(from_config(&cfg).paths, vec!["src/", ":(exclude)src/vendor/"]);
+  }
+}
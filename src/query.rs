@@ -0,0 +1,318 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --query mode: read one field (JSON Pointer or $.path) or an aggregate from a report/manifest without jq
+// role: entrypoint/query
+// inputs: Path to report-*.json or manifest.json; expression "/ptr", "$.a.b[0]", or sum/count/min/max(<path>)
+// outputs: One line per matched value on stdout: strings raw, other scalars as JSON, arrays/objects pretty JSON
+// side_effects: Reads the given file and, for aggregates over a manifest, every range report it lists
+// invariants:
+// - "*" (pointer segment) and "[*]"/".*" (path) fan out over array elements or object values
+// - plain paths are read from the given document; a plain path with no match is an error (non-zero exit)
+// - aggregates over a manifest.json evaluate their path in each ranges[].file report (relative to the manifest dir)
+// - matched arrays are flattened one level inside aggregates, so count(/commits) is the number of commits
+// errors: unreadable/invalid JSON, malformed expressions, and non-numeric values inside sum/min/max
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+
+#[derive(Debug, PartialEq)]
+enum Segment {
+  Key(String),
+  Wildcard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Aggregate {
+  Sum,
+  Count,
+  Min,
+  Max,
+}
+
+/// Evaluate `expr` against `report` and print the result(s).
+pub fn run(report: &str, expr: &str) -> Result<()> {
+  for value in evaluate_file(report, expr)? {
+    match value {
+      Value::String(s) => println!("{}", s),
+      Value::Array(_) | Value::Object(_) => println!("{}", serde_json::to_string_pretty(&value)?),
+      other => println!("{}", other),
+    }
+  }
+
+  Ok(())
+}
+
+fn read_json(path: &Path) -> Result<Value> {
+  let bytes = crate::util::read_output(path)?;
+
+  serde_json::from_slice(&bytes).with_context(|| format!("parsing {}", path.display()))
+}
+
+fn evaluate_file(report: &str, expr: &str) -> Result<Vec<Value>> {
+  let path = Path::new(report);
+  let doc = read_json(path)?;
+  let expr = expr.trim();
+
+  let Some((agg, inner)) = parse_aggregate(expr) else {
+    let segments = parse_path(expr)?;
+    let found: Vec<Value> = select(&doc, &segments).into_iter().cloned().collect();
+
+    if found.is_empty() {
+      bail!("no value at {} in {}", expr, report);
+    }
+
+    return Ok(found);
+  };
+
+  let segments = parse_path(inner)?;
+  let mut found: Vec<Value> = Vec::new();
+
+  match manifest_reports(&doc) {
+    Some(files) => {
+      let base = path.parent().unwrap_or(Path::new("."));
+
+      for file in files {
+        let range_doc = read_json(&base.join(file))?;
+        found.extend(select(&range_doc, &segments).into_iter().cloned());
+      }
+    }
+    None => found.extend(select(&doc, &segments).into_iter().cloned()),
+  }
+
+  Ok(vec![aggregate(agg, &found, expr)?])
+}
+
+/// `ranges[].file` of an overall manifest; None for any other document.
+fn manifest_reports(doc: &Value) -> Option<Vec<&str>> {
+  let ranges = doc.get("ranges")?.as_array()?;
+
+  if doc.get("summary").is_some() {
+    return None;
+  }
+
+  Some(ranges.iter().filter_map(|r| r.get("file")?.as_str()).collect())
+}
+
+fn parse_aggregate(expr: &str) -> Option<(Aggregate, &str)> {
+  let (name, rest) = expr.split_once('(')?;
+  let inner = rest.strip_suffix(')')?;
+  let agg = match name.trim() {
+    "sum" => Aggregate::Sum,
+    "count" => Aggregate::Count,
+    "min" => Aggregate::Min,
+    "max" => Aggregate::Max,
+    _ => return None,
+  };
+
+  Some((agg, inner.trim()))
+}
+
+/// Parse a JSON Pointer ("/summary/count", RFC 6901 escapes) or a `$`-rooted path ("$.commits[0].sha").
+fn parse_path(expr: &str) -> Result<Vec<Segment>> {
+  if expr.is_empty() {
+    return Ok(Vec::new());
+  }
+
+  if let Some(ptr) = expr.strip_prefix('/') {
+    return Ok(
+      ptr
+        .split('/')
+        .map(|s| match s {
+          "*" => Segment::Wildcard,
+          _ => Segment::Key(s.replace("~1", "/").replace("~0", "~")),
+        })
+        .collect(),
+    );
+  }
+
+  let Some(mut rest) = expr.strip_prefix('$') else {
+    bail!(
+      "query expression must start with '/' (JSON Pointer) or '$' (path), or be sum/count/min/max(<path>): {}",
+      expr
+    );
+  };
+  let mut segments = Vec::new();
+
+  while !rest.is_empty() {
+    if let Some(r) = rest.strip_prefix('.') {
+      let end = r.find(['.', '[']).unwrap_or(r.len());
+      let key = &r[..end];
+
+      if key.is_empty() {
+        bail!("empty key in query path: {}", expr);
+      }
+
+      segments.push(if key == "*" {
+        Segment::Wildcard
+      } else {
+        Segment::Key(key.to_string())
+      });
+      rest = &r[end..];
+    } else if let Some(r) = rest.strip_prefix('[') {
+      let Some(end) = r.find(']') else {
+        bail!("unclosed '[' in query path: {}", expr);
+      };
+      let inner = r[..end].trim();
+
+      segments.push(match inner {
+        "*" => Segment::Wildcard,
+        _ => Segment::Key(inner.trim_matches(|c| c == '\'' || c == '"').to_string()),
+      });
+      rest = &r[end + 1..];
+    } else {
+      bail!("unexpected {:?} in query path: {}", rest, expr);
+    }
+  }
+
+  Ok(segments)
+}
+
+fn select<'a>(doc: &'a Value, segments: &[Segment]) -> Vec<&'a Value> {
+  let mut current = vec![doc];
+
+  for seg in segments {
+    current = current
+      .into_iter()
+      .flat_map(|v| -> Vec<&Value> {
+        match (seg, v) {
+          (Segment::Wildcard, Value::Array(items)) => items.iter().collect(),
+          (Segment::Wildcard, Value::Object(map)) => map.values().collect(),
+          (Segment::Key(k), Value::Object(map)) => map.get(k).into_iter().collect(),
+          (Segment::Key(k), Value::Array(items)) => {
+            k.parse::<usize>().ok().and_then(|i| items.get(i)).into_iter().collect()
+          }
+          _ => Vec::new(),
+        }
+      })
+      .collect();
+  }
+
+  current
+}
+
+fn aggregate(agg: Aggregate, found: &[Value], expr: &str) -> Result<Value> {
+  let values: Vec<&Value> = found
+    .iter()
+    .flat_map(|v| match v {
+      Value::Array(items) => items.iter().collect(),
+      other => vec![other],
+    })
+    .collect();
+
+  if agg == Aggregate::Count {
+    return Ok(Value::from(values.len()));
+  }
+
+  let mut ints: Vec<i64> = Vec::new();
+  let mut floats: Vec<f64> = Vec::new();
+
+  for v in values.iter().filter(|v| !v.is_null()) {
+    match (v.as_i64(), v.as_f64()) {
+      (Some(i), _) => ints.push(i),
+      (None, Some(f)) => floats.push(f),
+      _ => bail!("{}: non-numeric value {}", expr, v),
+    }
+  }
+
+  if floats.is_empty() {
+    return Ok(match agg {
+      Aggregate::Sum => Value::from(ints.iter().sum::<i64>()),
+      Aggregate::Min => ints.iter().min().map_or(Value::Null, |m| Value::from(*m)),
+      _ => ints.iter().max().map_or(Value::Null, |m| Value::from(*m)),
+    });
+  }
+
+  let all = floats.into_iter().chain(ints.into_iter().map(|i| i as f64));
+
+  Ok(match agg {
+    Aggregate::Sum => Value::from(all.sum::<f64>()),
+    Aggregate::Min => Value::from(all.fold(f64::INFINITY, f64::min)),
+    _ => Value::from(all.fold(f64::NEG_INFINITY, f64::max)),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn report(additions: i64, shas: &[&str]) -> Value {
+    let commits: Vec<Value> = shas.iter().map(|s| json!({ "sha": s, "files": [] })).collect();
+
+    json!({ "summary": { "count": shas.len(), "changeset": { "additions": additions } }, "commits": commits })
+  }
+
+  fn write(dir: &Path, name: &str, v: &Value) -> String {
+    let path = dir.join(name);
+    std::fs::write(&path, serde_json::to_vec(v).unwrap()).unwrap();
+    path.to_string_lossy().to_string()
+  }
+
+  #[test]
+  fn pointer_and_dollar_paths_select_the_same_values() {
+    let doc = report(7, &["a1", "b2"]);
+
+    assert_eq!(
+      parse_path("/commits/1/sha").unwrap(),
+      parse_path("$.commits[1].sha").unwrap()
+    );
+    assert_eq!(select(&doc, &parse_path("/summary/count").unwrap()), vec![&json!(2)]);
+    assert_eq!(
+      select(&doc, &parse_path("$.commits[*].sha").unwrap()),
+      vec![&json!("a1"), &json!("b2")]
+    );
+    assert_eq!(
+      parse_path("/a~1b/~0c").unwrap(),
+      vec![Segment::Key("a/b".into()), Segment::Key("~c".into())]
+    );
+    assert!(parse_path("summary.count").is_err());
+  }
+
+  #[test]
+  fn plain_query_with_no_match_is_an_error() {
+    let td = tempfile::TempDir::new().unwrap();
+    let file = write(td.path(), "report.json", &report(1, &["a1"]));
+
+    assert_eq!(evaluate_file(&file, "/summary/count").unwrap(), vec![json!(1)]);
+    assert!(evaluate_file(&file, "/summary/missing").is_err());
+  }
+
+  #[test]
+  fn aggregates_fan_out_over_manifest_ranges() {
+    let td = tempfile::TempDir::new().unwrap();
+    write(td.path(), "report-a.json", &report(10, &["a1"]));
+    std::fs::create_dir(td.path().join("b")).unwrap();
+    write(td.path(), "b/report-b.json", &report(5, &["b1", "b2"]));
+    let manifest = write(
+      td.path(),
+      "manifest.json",
+      &json!({ "ranges": [
+        { "label": "a", "range": { "start": "s", "end": "e" }, "file": "report-a.json" },
+        { "label": "b", "range": { "start": "s", "end": "e" }, "file": "b/report-b.json" }
+      ]}),
+    );
+
+    assert_eq!(
+      evaluate_file(&manifest, "sum(/summary/changeset/additions)").unwrap(),
+      vec![json!(15)]
+    );
+    assert_eq!(evaluate_file(&manifest, "count(/commits)").unwrap(), vec![json!(3)]);
+    assert_eq!(
+      evaluate_file(&manifest, "max($.summary.count)").unwrap(),
+      vec![json!(2)]
+    );
+    // plain paths read the manifest itself
+    assert_eq!(evaluate_file(&manifest, "/ranges/1/label").unwrap(), vec![json!("b")]);
+  }
+
+  #[test]
+  fn sum_rejects_non_numeric_values() {
+    let err = aggregate(Aggregate::Sum, &[json!(1), json!("x")], "sum(/x)").unwrap_err();
+
+    assert!(err.to_string().contains("non-numeric"));
+  }
+}
@@ -7,20 +7,27 @@
 // side_effects: Creates directories; writes JSON files; prints to stdout
 // invariants:
 // - base_dir is prepared when split_apart || multi_windows
-// - per-range report file name is report-<label>.json when written to disk
-// - multi_windows ⇒ manifest.json exists and pointer {dir, manifest} printed
-// - single split ⇒ pointer {dir, file} printed; single non-split ⇒ JSON printed or written to --out
+// - per-range report file name comes from OutputLayout (report-<label>.json) when written to disk
+// - multi_windows ⇒ manifest.json and rollup.json exist and pointer {dir, manifest, rollup} printed
+// - multi_windows ⇒ manifest.json/rollup.json are rewritten under the --out dir lock (crate::out_lock), keeping ranges other runs recorded there
+// - multi_windows ⇒ backfill-state.json records each completed range under the same dir lock; --resume reuses those instead of regenerating
+// - single split ⇒ pointer {dir, file} printed; single non-split ⇒ JSON (Markdown/CSV with --format markdown|csv) printed or written to --out
+// - --format markdown|csv with a base_dir ⇒ report-<label>.md|.csv beside each freshly written range report
+// - --format ndjson ⇒ one window, streamed to stdout or --out (file, or report-<label>.ndjson in a dir) via a temp file + rename
 // errors: Propagates generation/save/write errors with file path context
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 
+use crate::checkpoint::BackfillState;
 use crate::cli;
 use crate::manifest::{RangeEntry, write_overall_manifest};
+use crate::model::SimpleReport;
 use crate::range_windows::LabeledRange;
 use crate::render::build_report_params;
-use crate::render::run_report;
+use crate::render::{run_ndjson, run_report};
+use crate::render_markdown::ReportFormat;
 use crate::util;
 
 fn commit_count(report: &serde_json::Value) -> u64 {
@@ -32,7 +39,8 @@ fn commit_count(report: &serde_json::Value) -> u64 {
 }
 
 fn write_pretty_json<P: AsRef<std::path::Path>>(path: P, v: &serde_json::Value) -> anyhow::Result<()> {
-  std::fs::write(path.as_ref(), serde_json::to_vec_pretty(v)?)?;
+  util::write_output(path.as_ref(), &serde_json::to_vec_pretty(v)?)?;
+  crate::exit_summary::record_output(path.as_ref().display().to_string());
 
   Ok(())
 }
@@ -49,7 +57,7 @@ pub struct SaveOutcome {
 /// Resolve the relative report filename for a range, depending on mode.
 ///
 /// - When `split_apart` is true, the renderer returns a JSON pointer with a `file` we reuse.
-/// - When `multi_windows` (non-split) is active, standardize to `report-<label>.json`.
+/// - When `multi_windows` (non-split) is active, use the layout's `report-<label>.json`.
 /// - For a single non-split run, there is no relative file (we print JSON or write to `--out`).
 fn resolve_file_rel(
   report_json: &serde_json::Value,
@@ -65,7 +73,7 @@ fn resolve_file_rel(
   }
 
   if base_dir_opt.is_some() {
-    let file_rel = format!("report-{}.json", range.label);
+    let file_rel = cfg.layout.report_file(&range.label);
 
     return Some(file_rel);
   }
@@ -79,7 +87,7 @@ fn resolve_file_rel(
 fn write_or_print(
   out_path_or_dir: &str,
   report_json: serde_json::Value,
-  label: &str,
+  file_name: &str,
 ) -> anyhow::Result<Option<serde_json::Value>> {
   if out_path_or_dir == "-" {
     return Ok(Some(report_json));
@@ -91,7 +99,7 @@ fn write_or_print(
   if is_dir_like {
     std::fs::create_dir_all(out_path)?;
 
-    let file_path = out_path.join(format!("report-{}.json", label));
+    let file_path = out_path.join(file_name);
     let count = commit_count(&report_json);
 
     if count == 0 {
@@ -118,6 +126,103 @@ fn write_or_print(
   Ok(None)
 }
 
+/// Render `report` as `--format markdown` or `csv` text.
+fn render_text(cfg: &cli::EffectiveConfig, report: &SimpleReport) -> String {
+  match cfg.format {
+    ReportFormat::Csv => crate::render_csv::render_report(report, &cfg.csv_columns),
+    _ => crate::render_markdown::render_report(report),
+  }
+}
+
+/// `--format markdown|csv` for a single non-split run: print the rendering, or write it to `--out` (file or dir).
+fn write_or_print_rendered(cfg: &cli::EffectiveConfig, report_json: &serde_json::Value, file_name: &str) -> Result<()> {
+  let out_path_or_dir = cfg.out.as_str();
+  let report: SimpleReport = serde_json::from_value(report_json.clone())?;
+  let text = render_text(cfg, &report);
+  let markdown = cfg.format == ReportFormat::Markdown;
+
+  if out_path_or_dir == "-" {
+    if markdown {
+      crate::link_check::check(&text, None, "stdout");
+    }
+    print!("{}", text);
+    crate::exit_summary::record_output("-");
+
+    return Ok(());
+  }
+
+  let out_path = std::path::Path::new(out_path_or_dir);
+  let path = if out_path_or_dir.ends_with('/') || out_path.is_dir() {
+    std::fs::create_dir_all(out_path)?;
+    out_path.join(file_name)
+  } else {
+    if let Some(parent) = out_path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    out_path.to_path_buf()
+  };
+
+  if markdown {
+    crate::link_check::check(&text, path.parent(), &path.display().to_string());
+  }
+  util::write_output(&path, text.as_bytes())?;
+  crate::exit_summary::record_output(path.display().to_string());
+
+  Ok(())
+}
+
+/// `--format ndjson`: stream the range to stdout, or to `--out` (a file, or `report-<label>.ndjson` in a dir).
+///
+/// File output streams into a sibling temp file that is renamed into place, so readers never see a partial stream.
+fn write_ndjson(
+  cfg: &cli::EffectiveConfig,
+  range: &LabeledRange,
+  now_opt: Option<chrono::DateTime<chrono::Local>>,
+) -> Result<()> {
+  let mut params = build_report_params(cfg, range.since.clone(), range.until.clone());
+  params.label = Some(range.label.clone());
+  params.now_local = now_opt;
+
+  if cfg.out == "-" {
+    let mut w = std::io::BufWriter::new(std::io::stdout().lock());
+    let count = run_ndjson(&params, &mut w)?;
+    crate::exit_summary::record_range(count);
+    crate::exit_summary::record_output("-");
+
+    return Ok(());
+  }
+
+  let out_path = std::path::Path::new(&cfg.out);
+  let path = if cfg.out.ends_with('/') || out_path.is_dir() {
+    std::fs::create_dir_all(out_path)?;
+    out_path.join(cfg.layout.ndjson_report_file(&range.label))
+  } else {
+    if let Some(parent) = out_path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    out_path.to_path_buf()
+  };
+  let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+  let tmp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+  let file = std::fs::File::create(&tmp).with_context(|| format!("writing {}", tmp.display()))?;
+  let mut w = std::io::BufWriter::new(file);
+  let count = match run_ndjson(&params, &mut w) {
+    Ok(n) => n,
+    Err(e) => {
+      drop(w);
+      let _ = std::fs::remove_file(&tmp);
+      return Err(e);
+    }
+  };
+  drop(w);
+  std::fs::rename(&tmp, &path).with_context(|| format!("writing {}", path.display()))?;
+  crate::exit_summary::record_range(count);
+  crate::exit_summary::record_output(path.display().to_string());
+
+  Ok(())
+}
+
 pub fn generate_range_report(
   cfg: &cli::EffectiveConfig,
   range: &LabeledRange,
@@ -146,22 +251,53 @@ pub fn save_range_report(
   base_dir_opt: Option<&str>,
 ) -> Result<SaveOutcome> {
   let file_rel = resolve_file_rel(&report, cfg, range, base_dir_opt);
+  let rendered_file = match cfg.format {
+    ReportFormat::Markdown => Some(cfg.layout.markdown_report_file(&range.label)),
+    ReportFormat::Csv => Some(cfg.layout.csv_report_file(&range.label)),
+    ReportFormat::Json | ReportFormat::Ndjson => None,
+  };
 
   let mut print_json: Option<serde_json::Value> = None;
 
   if !cfg.split_apart {
+    // Split runs validate inside render, where the report file is written; `report` here is just the pointer
+    let dest = match (base_dir_opt, file_rel.as_deref()) {
+      (Some(base_dir), Some(file)) => std::path::Path::new(base_dir).join(file),
+      _ if cfg.out == "-" => std::path::PathBuf::from("<stdout>"),
+      _ => std::path::PathBuf::from(&cfg.out),
+    };
+    crate::validate_output::check(crate::validate_output::Artifact::Report, &dest, &report)?;
+
     if let Some(base_dir) = base_dir_opt {
       let file_name = file_rel.as_ref().expect("file name for multi");
       let file_path = std::path::Path::new(base_dir).join(file_name);
 
       write_pretty_json(&file_path, &report)?;
+    } else if let Some(file_name) = &rendered_file {
+      write_or_print_rendered(cfg, &report, file_name)?;
     } else {
-      print_json = write_or_print(&cfg.out, report, &range.label)?;
+      print_json = write_or_print(&cfg.out, report, &cfg.layout.report_file(&range.label))?;
     }
   } else if !cfg.multi_windows {
     print_json = Some(report);
   }
 
+  if cfg.split_apart {
+    if let (Some(base_dir), Some(file_rel)) = (base_dir_opt, file_rel.as_deref()) {
+      crate::exit_summary::record_output(std::path::Path::new(base_dir).join(file_rel).display().to_string());
+    }
+  }
+
+  if cfg.collaboration_dot {
+    if let (Some(base_dir), Some(file_rel)) = (base_dir_opt, file_rel.as_deref()) {
+      write_collaboration_dot(base_dir, file_rel, &cfg.layout.collaboration_dot_file(&range.label))?;
+    }
+  }
+
+  if let (Some(base_dir), Some(file_rel), Some(out_file)) = (base_dir_opt, file_rel.as_deref(), rendered_file) {
+    write_rendered_report(cfg, base_dir, file_rel, &out_file)?;
+  }
+
   let entry = if cfg.multi_windows {
     Some(RangeEntry {
       label: range.label.clone(),
@@ -181,25 +317,128 @@ pub fn save_range_report(
   Ok(outcome)
 }
 
+/// Write `collaboration-<label>.dot` from the range report's `summary.collaboration`.
+///
+/// The report is read back from disk because split runs only hand back a pointer.
+fn write_collaboration_dot(base_dir: &str, file_rel: &str, dot_file: &str) -> Result<()> {
+  let report_path = std::path::Path::new(base_dir).join(file_rel);
+  let bytes = util::read_output(&report_path)?;
+  let report: serde_json::Value = serde_json::from_slice(&bytes)?;
+  let graph: crate::model::Collaboration =
+    serde_json::from_value(report["summary"]["collaboration"].clone()).unwrap_or_default();
+
+  crate::collaboration::write_dot(base_dir, dot_file, &graph)?;
+
+  Ok(())
+}
+
+/// Write `report-<label>.md` (or `.csv`) next to the range report for `--format markdown|csv` in split/multi runs.
+fn write_rendered_report(cfg: &cli::EffectiveConfig, base_dir: &str, file_rel: &str, out_file: &str) -> Result<()> {
+  let report_path = std::path::Path::new(base_dir).join(file_rel);
+  let report: SimpleReport = serde_json::from_slice(&util::read_output(&report_path)?)
+    .with_context(|| format!("parsing {}", report_path.display()))?;
+
+  let out_path = std::path::Path::new(base_dir).join(out_file);
+  let text = render_text(cfg, &report);
+  if cfg.format == ReportFormat::Markdown {
+    crate::link_check::check(&text, out_path.parent(), &out_path.display().to_string());
+  }
+  util::write_output(&out_path, text.as_bytes())?;
+  crate::exit_summary::record_output(out_path.display().to_string());
+
+  Ok(())
+}
+
+/// Package `base_dir` when `--archive` is set and record it on the stdout pointer.
+///
+/// The pointer's `file`/`manifest` values double as member paths inside the archive.
+fn attach_archive(
+  cfg: &cli::EffectiveConfig,
+  base_dir: &str,
+  now_opt: Option<chrono::DateTime<chrono::Local>>,
+  pointer: &mut serde_json::Value,
+) -> Result<()> {
+  let Some(dest) = cfg.archive.as_deref() else {
+    return Ok(());
+  };
+
+  util::log(util::LogLevel::Info, "gar", &format!("writing archive {}...", dest));
+  let summary = crate::archive::write_archive(base_dir, dest, util::effective_now(now_opt))?;
+  crate::exit_summary::record_output(dest);
+
+  pointer["archive"] = serde_json::to_value(summary)?;
+
+  Ok(())
+}
+
 pub fn process_ranges(
   cfg: &cli::EffectiveConfig,
   ranges: Vec<LabeledRange>,
   now_opt: Option<chrono::DateTime<chrono::Local>>,
 ) -> Result<()> {
+  if cfg.archive.is_some() && !(cfg.split_apart || cfg.multi_windows) {
+    anyhow::bail!("--archive packages an output directory; use it with --split-apart or a multi-range window");
+  }
+  if cfg.collaboration_dot && !(cfg.split_apart || cfg.multi_windows) {
+    anyhow::bail!(
+      "--collaboration-dot writes into an output directory; use it with --split-apart or a multi-range window"
+    );
+  }
+
+  if cfg.resume && (!cfg.multi_windows || cfg.out == "-") {
+    anyhow::bail!("--resume continues a multi-range run; use it with a multi-range window and the same --out <dir>");
+  }
+
+  if cfg.format == ReportFormat::Ndjson {
+    if cfg.multi_windows {
+      anyhow::bail!("--format ndjson streams a single window; multi-range windows and --ranges-json write reports");
+    }
+
+    return write_ndjson(cfg, &ranges[0], now_opt);
+  }
+
   let base_dir_opt = if cfg.split_apart || cfg.multi_windows {
     Some(util::prepare_out_dir(&cfg.out, now_opt)?)
   } else {
     None
   };
 
+  let mut state = match (cfg.multi_windows, base_dir_opt.as_deref()) {
+    (true, Some(base_dir)) => Some(BackfillState::start(cfg, base_dir)?),
+    _ => None,
+  };
+
   let mut entries: Vec<RangeEntry> = Vec::new();
   let mut last_single_output: Option<serde_json::Value> = None;
 
   for r in ranges.iter() {
+    let done = state
+      .as_ref()
+      .zip(base_dir_opt.as_deref())
+      .and_then(|(s, dir)| s.completed_entry(dir, r));
+
+    if let Some(e) = done {
+      util::log(
+        util::LogLevel::Info,
+        "gar",
+        &format!("skipping {} (completed in a previous run)", r.label),
+      );
+      entries.push(e);
+      continue;
+    }
+
+    let _span = crate::telemetry::span("process_range").attr("range.label", r.label.clone());
     let out = generate_range_report(cfg, r, now_opt, base_dir_opt.as_deref())?;
     let outcome = save_range_report(cfg, r, out, base_dir_opt.as_deref())?;
 
     if let Some(e) = outcome.entry {
+      if let (Some(s), Some(dir)) = (state.as_mut(), base_dir_opt.as_deref()) {
+        let _lock = crate::out_lock::DirLock::acquire(
+          std::path::Path::new(dir),
+          std::time::Duration::from_secs(cfg.lock_timeout),
+        )?;
+        s.record(dir, &e)?;
+      }
       entries.push(e);
     }
 
@@ -210,26 +449,50 @@ pub fn process_ranges(
 
   if cfg.multi_windows {
     let base_dir = base_dir_opt.as_deref().expect("base_dir for multi");
-    let _manifest_path = write_overall_manifest(
+    let lock = crate::out_lock::DirLock::acquire(
+      std::path::Path::new(base_dir),
+      std::time::Duration::from_secs(cfg.lock_timeout),
+    )?;
+    let entries = crate::manifest::merge_existing_entries(base_dir, entries);
+    let rollup = crate::rollup::build_rollup(&cfg.repo, util::effective_now(now_opt), base_dir, &entries)?;
+    let manifest_path = write_overall_manifest(
       &cfg.repo,
       util::effective_now(now_opt),
       cfg.split_apart,
       cfg.include_merges,
       cfg.include_patch,
       cfg.include_unmerged,
+      &crate::anomalies::detect_anomalies(&rollup),
+      &crate::trends::build_trends(&rollup),
+      &util::warnings_since(0),
       base_dir,
       &entries,
     )?;
-    println!(
-      "{}",
-      serde_json::to_string_pretty(&serde_json::json!({"dir": base_dir, "manifest": "manifest.json"}))?
-    );
+    let rollup_path = crate::rollup::write_rollup(base_dir, &rollup)?;
+    crate::exit_summary::record_output(manifest_path.display().to_string());
+    crate::exit_summary::record_output(rollup_path.display().to_string());
+    drop(lock);
+    let mut pointer = serde_json::json!({
+      "dir": base_dir,
+      "manifest": "manifest.json",
+      "rollup": crate::rollup::ROLLUP_FILE,
+    });
+    attach_archive(cfg, base_dir, now_opt, &mut pointer)?;
+    println!("{}", serde_json::to_string_pretty(&pointer)?);
 
     return Ok(());
   }
 
+  if let (Some(base_dir), Some(v)) = (base_dir_opt.as_deref(), last_single_output.as_mut()) {
+    attach_archive(cfg, base_dir, now_opt, v)?;
+  }
+
   if let Some(v) = last_single_output {
     println!("{}", serde_json::to_string_pretty(&v)?);
+
+    if !cfg.split_apart {
+      crate::exit_summary::record_output("-");
+    }
   }
 
   Ok(())
@@ -253,13 +516,65 @@ mod tests {
       include_merges: true,
       include_patch: false,
       max_patch_bytes: 0,
+      max_files_per_commit: None,
       save_patches: None,
+      no_blob_stats: false,
       out: "-".into(),
+      layout: Default::default(),
+      format: Default::default(),
+      csv_columns: vec![],
       github_prs: false,
       include_unmerged: false,
+      unmerged_base: None,
+      stale_days: 30,
       tz: "utc".into(),
       now_override: None,
       estimate_effort: false,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      github_repo: None,
+      redact_paths: vec![],
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      archive: None,
+      patch_budget_bytes: None,
+      side_indexes: false,
+      git_args: vec![],
+      bot_authors: vec![],
+      issue_patterns: vec![],
+      issue_url_templates: vec![],
+      repo_policy: false,
+      http_timeout: 30,
+      http_retries: 2,
+      http_max_pages: 10,
+      github_max_requests: None,
+      github_cache_ttl: 86_400,
+      no_wait: false,
+      collaboration: false,
+      collaboration_dot: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 65536,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      resume: false,
+      lock_timeout: 0,
+      cache: false,
+      sample: None,
+      jobs: 0,
+      digest: None,
     }
   }
 
@@ -332,4 +647,101 @@ mod tests {
     assert_eq!(e.file, "report-2025-08.json");
     assert!(std::path::Path::new(&cfg.out).join(&e.file).exists());
   }
+
+  #[test]
+  fn resume_skips_ranges_completed_by_an_earlier_run() {
+    let repo = fixture_repo();
+    let mut cfg = base_cfg(repo);
+    cfg.multi_windows = true;
+    let td = tempfile::TempDir::new().unwrap();
+    cfg.out = td.path().to_string_lossy().to_string();
+    let ranges = vec![
+      LabeledRange {
+        label: "2025-07".into(),
+        since: "2025-07-01".into(),
+        until: "2025-08-01".into(),
+      },
+      LabeledRange {
+        label: "2025-08".into(),
+        since: "2025-08-01".into(),
+        until: "2025-09-01".into(),
+      },
+    ];
+    process_ranges(&cfg, ranges.clone(), None).expect("first run");
+    assert!(td.path().join(crate::checkpoint::STATE_FILE).exists());
+
+    // Mark the July report so a regenerated one would be detectable; drop August as if interrupted.
+    let july = td.path().join("report-2025-07.json");
+    let mut v: serde_json::Value = serde_json::from_slice(&std::fs::read(&july).unwrap()).unwrap();
+    v["kept_from_first_run"] = serde_json::json!(true);
+    write_pretty_json(&july, &v).unwrap();
+    std::fs::remove_file(td.path().join("report-2025-08.json")).unwrap();
+
+    cfg.resume = true;
+    process_ranges(&cfg, ranges, None).expect("resumed run");
+
+    let v: serde_json::Value = serde_json::from_slice(&std::fs::read(&july).unwrap()).unwrap();
+    assert_eq!(v["kept_from_first_run"], true);
+    assert!(td.path().join("report-2025-08.json").exists());
+    assert!(td.path().join("manifest.json").exists());
+  }
+
+  #[test]
+  fn resume_requires_multi_range_out_dir() {
+    let mut cfg = base_cfg(fixture_repo());
+    cfg.resume = true;
+    let err = process_ranges(&cfg, vec![], None).unwrap_err();
+    assert!(err.to_string().contains("--resume"));
+  }
+
+  #[test]
+  fn collaboration_dot_is_written_next_to_multi_report() {
+    let repo = fixture_repo();
+    let mut cfg = base_cfg(repo);
+    cfg.multi_windows = true;
+    cfg.collaboration = true;
+    cfg.collaboration_dot = true;
+    let td = tempfile::TempDir::new().unwrap();
+    cfg.out = td.path().to_string_lossy().to_string();
+    let range = LabeledRange {
+      label: "2025-08".into(),
+      since: "2025-08-01".into(),
+      until: "2025-09-01".into(),
+    };
+    let out = generate_range_report(&cfg, &range, None, Some(&cfg.out)).expect("gen");
+    save_range_report(&cfg, &range, out, Some(&cfg.out)).expect("save");
+    let dot = std::fs::read_to_string(td.path().join("collaboration-2025-08.dot")).expect("dot file");
+    assert!(dot.starts_with("graph collaboration {"));
+  }
+
+  #[test]
+  fn ndjson_streams_commit_lines_then_a_summary_line() {
+    let repo = fixture_repo();
+    let mut cfg = base_cfg(repo);
+    cfg.format = ReportFormat::Ndjson;
+    let td = tempfile::TempDir::new().unwrap();
+    cfg.out = format!("{}/", td.path().display());
+    let range = LabeledRange {
+      label: "2025-08".into(),
+      since: "2025-08-01".into(),
+      until: "2025-09-01".into(),
+    };
+
+    process_ranges(&cfg, vec![range], None).expect("ndjson");
+
+    let text = std::fs::read_to_string(td.path().join("report-2025-08.ndjson")).expect("ndjson file");
+    let lines: Vec<serde_json::Value> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+    let (summary, commits) = lines.split_last().unwrap();
+    assert!(!commits.is_empty());
+    assert!(
+      commits
+        .iter()
+        .all(|c| c.get("sha").is_some() && c.get("summary").is_none())
+    );
+    assert_eq!(summary["summary"]["count"].as_u64(), Some(commits.len() as u64));
+    assert!(summary.get("commits").is_none());
+
+    cfg.multi_windows = true;
+    assert!(process_ranges(&cfg, vec![], None).is_err());
+  }
 }
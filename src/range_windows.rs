@@ -2,21 +2,44 @@
 // header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
 // purpose: Resolve time windows into labeled ranges; parse "now" overrides; helpers for natural language buckets
 // role: resolution/parser
-// inputs: WindowSpec (Month | ForPhrase | SinceUntil); optional now override
+// inputs: WindowSpec (Month | Months | ForPhrase | SinceUntil | Since | Until | Explicit); optional now override; --ranges-json files; optional first-commit lookup for Until
 // outputs: Vec<LabeledRange> (chronological earliest→latest); parsed DateTime for now when requested
-// side_effects: none (pure)
+// side_effects: none (pure); resolve_ranges_in_history calls the caller's first-commit lookup
+// api: public through the library crate (git_activity_report::range_windows); signatures are a contract for other tools
 // invariants:
 // - resolve_ranges returns at least one range; ForPhrase buckets are ordered earliest→latest
-// - month_bounds yields [start_of_month, start_of_next_month]
+// - month_bounds yields [start_of_month, start_of_next_month]; month inputs accept YYYY-MM, YYYY-M, and "aug 2025"
+// - Since runs until now; Until starts at OPEN_START, or at the first commit via resolve_ranges_in_history (what the CLI uses); their labels name the given side
+// - Months holds canonical YYYY-MM values, sorted and deduplicated, and yields one range per month
+// - Explicit ranges (--ranges-json) are used verbatim in file order: no phrase parsing, labels unique and path-safe
 // - parse_now accepts RFC3339 or naive %Y-%m-%dT%H:%M:%S and never panics
 // errors: Invalid month/phrase formats return contextual errors; non-fatal fallbacks choose git approxidate-friendly strings
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
+//! Time-window resolution shared by the CLI and other tools.
+//!
+//! A [`WindowSpec`] (`--month`, `--for`, `--since/--until`) resolves to one or more [`LabeledRange`]s whose
+//! `since`/`until` strings are passed to `git rev-list` unchanged. Pass an explicit `now` to get the same
+//! answer the report would have produced at that instant.
+//!
+//! ```
+//! use git_activity_report::range_windows::{WindowSpec, parse_now, resolve_ranges};
+//!
+//! let now = parse_now(Some("2025-09-10T12:00:00"));
+//! let window = WindowSpec::ForPhrase { phrase: "every month for the last 2 months".into() };
+//! let ranges = resolve_ranges(&window, now).unwrap();
+//!
+//! assert_eq!(ranges[0].label, "2025-07");
+//! assert_eq!(ranges[1].since, "2025-08-01T00:00:00");
+//! assert_eq!(ranges[1].until, "2025-09-01T00:00:00");
+//! ```
+
 use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Datelike, Local, NaiveDate, Timelike};
 use chrono_english::{Interval, parse_duration};
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use two_timer::parse as parse_natural;
 
@@ -24,14 +47,38 @@ use two_timer::parse as parse_natural;
 
 // Tz enum removed in favor of free-form tz strings ("local", "utc", or IANA zone)
 
-#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
+/// How the report window was selected.
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
 pub enum WindowSpec {
-  Month { ym: String },
-  ForPhrase { phrase: String },
-  SinceUntil { since: String, until: String },
+  Month {
+    ym: String,
+  },
+  Months {
+    yms: Vec<String>,
+  },
+  ForPhrase {
+    phrase: String,
+  },
+  SinceUntil {
+    since: String,
+    until: String,
+  },
+  /// `--since` alone: from `since` until now.
+  Since {
+    since: String,
+  },
+  /// `--until` alone: from the start of history until `until`.
+  Until {
+    until: String,
+  },
+  /// Caller-supplied ranges (`--ranges-json`), resolved as-is.
+  Explicit {
+    ranges: Vec<LabeledRange>,
+  },
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// One resolved range: `label` names its report (e.g. `2025-08`, `2025-W32`, or `window`).
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LabeledRange {
   pub label: String,
   pub since: String,
@@ -70,18 +117,61 @@ fn normalize_number_word(s: &str) -> Option<i32> {
   None
 }
 
-pub fn month_bounds(year_month: &str) -> Result<(String, String)> {
-  let parts: Vec<&str> = year_month.split('-').collect();
+const MONTH_NAMES: [&str; 12] = [
+  "january",
+  "february",
+  "march",
+  "april",
+  "may",
+  "june",
+  "july",
+  "august",
+  "september",
+  "october",
+  "november",
+  "december",
+];
+
+/// Parse a `--month` value into (year, month): `2025-08`, `2025-8`, `aug 2025`, `August 2025`, or `aug, 2025`.
+fn parse_month(input: &str) -> Result<(i32, i32)> {
+  let s = input.trim().to_lowercase();
+
+  if let Some((y, m)) = s.split_once('-') {
+    let y: i32 = y.parse().context("parsing year in --month")?;
+    let m: i32 = m.parse().context("parsing month in --month")?;
+
+    if !(1..=12).contains(&m) {
+      bail!("invalid month in --month");
+    }
 
-  if parts.len() != 2 {
-    bail!("invalid --month, expected YYYY-MM");
+    return Ok((y, m));
   }
-  let y: i32 = parts[0].parse().context("parsing year in --month")?;
-  let m: i32 = parts[1].parse().context("parsing month in --month")?;
 
-  if !(1..=12).contains(&m) {
-    bail!("invalid month in --month");
-  }
+  let mut words = s
+    .split(|c: char| c.is_whitespace() || c == ',')
+    .filter(|w| !w.is_empty());
+  let (Some(name), Some(year), None) = (words.next(), words.next(), words.next()) else {
+    bail!("invalid --month {:?}, expected YYYY-MM, YYYY-M, or \"aug 2025\"", input);
+  };
+  let m = MONTH_NAMES
+    .iter()
+    .position(|full| name.len() >= 3 && full.starts_with(name))
+    .with_context(|| format!("unknown month name {:?} in --month", name))?;
+  let y: i32 = year.parse().context("parsing year in --month")?;
+
+  Ok((y, m as i32 + 1))
+}
+
+/// Canonical `YYYY-MM` form of a `--month` value (used for labels and provenance).
+pub fn normalize_month(input: &str) -> Result<String> {
+  let (y, m) = parse_month(input)?;
+
+  Ok(format!("{y:04}-{m:02}"))
+}
+
+/// `[first day of month, first day of next month)` as naive local timestamps; accepts the `--month` shorthands.
+pub fn month_bounds(year_month: &str) -> Result<(String, String)> {
+  let (y, m) = parse_month(year_month)?;
   let next_y = if m == 12 { y + 1 } else { y };
   let next_m = if m == 12 { 1 } else { m + 1 };
 
@@ -93,19 +183,88 @@ pub fn month_bounds(year_month: &str) -> Result<(String, String)> {
 
 /// Compute (since, until) for a window.
 ///
-/// Supports an optional `now` override for deterministic testing.
-#[cfg(any(test, feature = "testutil"))]
+/// Supports an optional `now` override for deterministic testing. Multi-range windows collapse to their overall span.
 pub fn compute_window_strings(
   window: &WindowSpec,
   now: Option<chrono::DateTime<chrono::Local>>,
 ) -> Result<(String, String)> {
   match window {
     WindowSpec::SinceUntil { since, until } => Ok((since.clone(), until.clone())),
+    WindowSpec::Since { since } => Ok((since.clone(), iso_naive(now.unwrap_or_else(Local::now)))),
+    WindowSpec::Until { until } => Ok((OPEN_START.to_string(), until.clone())),
     WindowSpec::Month { ym } => month_bounds(ym),
+    WindowSpec::Months { yms } => {
+      let (since, _) = month_bounds(yms.first().context("empty month list")?)?;
+      let (_, until) = month_bounds(yms.last().context("empty month list")?)?;
+
+      Ok((since, until))
+    }
     WindowSpec::ForPhrase { phrase } => for_phrase_bounds(phrase, now),
+    WindowSpec::Explicit { ranges } => {
+      let first = ranges.first().context("empty range list")?;
+      let last = ranges.last().context("empty range list")?;
+
+      Ok((first.since.clone(), last.until.clone()))
+    }
   }
 }
 
+/// Lower bound of an `Until` window when no first commit is known (see [`resolve_ranges_in_history`]).
+pub const OPEN_START: &str = "1970-01-01T00:00:00";
+
+/// Label of a one-sided range, e.g. `since-2025-06-01`: `side` then the bound with anything but ASCII letters,
+/// digits, and `-` turned into `-` (labels name output files).
+fn open_window_label(side: &str, bound: &str) -> String {
+  let slug: String = bound
+    .trim()
+    .chars()
+    .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c } else { '-' })
+    .collect();
+
+  format!("{}-{}", side, slug)
+}
+
+/// Load `--ranges-json`: an array of `{label, since, until}` objects, used verbatim as the run's ranges.
+///
+/// Labels name report files and directories, so they must be non-empty, unique, and free of path separators.
+pub fn load_ranges_json(path: &str) -> Result<Vec<LabeledRange>> {
+  let text = std::fs::read_to_string(path).with_context(|| format!("reading --ranges-json {}", path))?;
+  let ranges: Vec<LabeledRange> = serde_json::from_str(&text)
+    .with_context(|| format!("parsing --ranges-json {} (expected [{{label, since, until}}])", path))?;
+
+  validate_explicit_ranges(&ranges).with_context(|| format!("invalid --ranges-json {}", path))?;
+
+  Ok(ranges)
+}
+
+fn validate_explicit_ranges(ranges: &[LabeledRange]) -> Result<()> {
+  if ranges.is_empty() {
+    bail!("expected at least one range");
+  }
+
+  let mut seen = std::collections::HashSet::new();
+
+  for (i, r) in ranges.iter().enumerate() {
+    let label = r.label.trim();
+
+    if label.is_empty() || label == "." || label == ".." || label.contains(['/', '\\']) {
+      bail!(
+        "range {} has an unusable label {:?} (labels name output files)",
+        i,
+        r.label
+      );
+    }
+    if r.since.trim().is_empty() || r.until.trim().is_empty() {
+      bail!("range {:?} needs both since and until", r.label);
+    }
+    if !seen.insert(label) {
+      bail!("duplicate range label {:?}", r.label);
+    }
+  }
+
+  Ok(())
+}
+
 // --- Helpers for `--for` parsing ---
 
 fn start_of_week(dt: chrono::DateTime<chrono::Local>) -> chrono::DateTime<chrono::Local> {
@@ -176,8 +335,6 @@ fn compute_interval_bounds(
 /// Parse a `--now-override` string into a local DateTime.
 /// Accepts RFC3339 (e.g. 2025-08-15T12:00:00Z) or a naive local timestamp
 /// formatted as `%Y-%m-%dT%H:%M:%S`.
-/// Parse a `--now-override` string into a local DateTime.
-/// Public entry used by main orchestration. Accepts RFC3339 or naive local.
 pub fn parse_now(s: Option<&str>) -> Option<DateTime<Local>> {
   s.and_then(|raw| {
     chrono::DateTime::parse_from_rfc3339(raw)
@@ -191,9 +348,12 @@ pub fn parse_now(s: Option<&str>) -> Option<DateTime<Local>> {
   })
 }
 
-/// Compute range for a natural-language phrase, with optional `now` override for tests.
-fn for_phrase_bounds(input: &str, now: Option<chrono::DateTime<chrono::Local>>) -> Result<(String, String)> {
+/// Compute the single range for a natural-language phrase, with optional `now` override for tests.
+/// Unrecognized phrases fall back to `(phrase, "now")` so git approxidate can interpret them; with a frozen
+/// `now` the until bound is that instant instead of git's clock.
+pub fn for_phrase_bounds(input: &str, now: Option<chrono::DateTime<chrono::Local>>) -> Result<(String, String)> {
   let phrase = input.trim().to_lowercase();
+  let frozen = now;
   let now = now.unwrap_or_else(Local::now);
 
   if phrase == "today" {
@@ -266,7 +426,9 @@ fn for_phrase_bounds(input: &str, now: Option<chrono::DateTime<chrono::Local>>)
   }
 
   // Natural ranges via two_timer (today, yesterday, last week, last tuesday, last month, last year)
-  if let Ok((start_naive, end_naive, _lit)) = parse_natural(&phrase, None) {
+  let config = two_timer::Config::new().now(now.naive_local());
+
+  if let Ok((start_naive, end_naive, _lit)) = parse_natural(&phrase, Some(config)) {
     let start = start_naive.and_local_timezone(Local).single().unwrap();
     let end = end_naive.and_local_timezone(Local).single().unwrap();
 
@@ -277,43 +439,42 @@ fn for_phrase_bounds(input: &str, now: Option<chrono::DateTime<chrono::Local>>)
 
   // Fallback: delegate to git approxidate by passing raw phrase and using "now" until
 
-  Ok((input.to_string(), "now".to_string()))
+  let until = frozen.map(iso_naive).unwrap_or_else(|| "now".to_string());
+
+  Ok((input.to_string(), until))
 }
 
 /// If the phrase is a multi-bucket request (e.g., "every month for the last N months"),
 /// compute labeled buckets (chronological, earliest→latest). Otherwise, return None.
-/// Build labeled ranges for multi-bucket phrases, with optional `now` override for tests.
+///
+/// Units are day, week, month, quarter, and year; both mentions must name the same unit. Buckets are the last N
+/// complete periods before the one containing `now`.
 pub fn for_phrase_buckets(input: &str, now: Option<chrono::DateTime<chrono::Local>>) -> Option<Vec<LabeledRange>> {
   let phrase = input.trim().to_lowercase();
   let now = now.unwrap_or_else(Local::now);
 
-  // (every|each) month for the last N months
-  if let Some(caps) = regex::Regex::new(r"^(?:every|each)\s+month\s+for\s+the\s+last\s+([a-z0-9\-]+)\s+months?$")
-    .ok()?
-    .captures(&phrase)
-  {
-    let raw = caps.get(1).unwrap().as_str();
-    let n: i32 = normalize_number_word(raw)?;
-
-    let out = buckets_for_last_n_months(now, n);
+  // (every|each) <unit> for the last N <unit>s
+  let caps = regex::Regex::new(
+    r"^(?:every|each)\s+(day|week|month|quarter|year)\s+for\s+the\s+last\s+([a-z0-9\-]+)\s+(day|week|month|quarter|year)s?$",
+  )
+  .ok()?
+  .captures(&phrase)?;
 
-    return Some(out);
+  if caps[1] != caps[3] {
+    return None;
   }
 
-  // (every|each) week for the last N weeks
-  if let Some(caps) = regex::Regex::new(r"^(?:every|each)\s+week\s+for\s+the\s+last\s+([a-z0-9\-]+)\s+weeks?$")
-    .ok()?
-    .captures(&phrase)
-  {
-    let raw = caps.get(1).unwrap().as_str();
-    let n: i32 = normalize_number_word(raw)?;
-
-    let out = buckets_for_last_n_weeks(now, n);
+  let n: i32 = normalize_number_word(&caps[2])?;
 
-    return Some(out);
-  }
+  let out = match &caps[1] {
+    "day" => buckets_for_last_n_days(now, n),
+    "week" => buckets_for_last_n_weeks(now, n),
+    "month" => buckets_for_last_n_months(now, n),
+    "quarter" => buckets_for_last_n_quarters(now, n),
+    _ => buckets_for_last_n_years(now, n),
+  };
 
-  None
+  Some(out)
 }
 
 // --- Bucket builders ---
@@ -383,15 +544,74 @@ fn buckets_for_last_n_weeks(now: chrono::DateTime<Local>, n: i32) -> Vec<Labeled
   out
 }
 
+/// A `[start, end)` bucket between two local midnights.
+fn day_bounds_range(label: String, start: NaiveDate, end: NaiveDate) -> LabeledRange {
+  LabeledRange {
+    label,
+    since: format!("{}T00:00:00", start.format("%Y-%m-%d")),
+    until: format!("{}T00:00:00", end.format("%Y-%m-%d")),
+  }
+}
+
+/// Build daily buckets for the last `n` days before today (earliest → latest), labeled YYYY-MM-DD.
+fn buckets_for_last_n_days(now: chrono::DateTime<Local>, n: i32) -> Vec<LabeledRange> {
+  let today = now.date_naive();
+
+  (1..=n as i64)
+    .rev()
+    .map(|back| {
+      let start = today - chrono::Duration::days(back);
+
+      day_bounds_range(
+        start.format("%Y-%m-%d").to_string(),
+        start,
+        start + chrono::Duration::days(1),
+      )
+    })
+    .collect()
+}
+
+/// Build quarterly buckets for the last `n` calendar quarters before the current one (earliest → latest),
+/// labeled YYYY-Qn.
+fn buckets_for_last_n_quarters(now: chrono::DateTime<Local>, n: i32) -> Vec<LabeledRange> {
+  let current = now.year() * 4 + (now.month0() / 3) as i32;
+  let quarter_start =
+    |index: i32| NaiveDate::from_ymd_opt(index.div_euclid(4), index.rem_euclid(4) as u32 * 3 + 1, 1).unwrap();
+
+  (1..=n)
+    .rev()
+    .map(|back| {
+      let index = current - back;
+      let label = format!("{:04}-Q{}", index.div_euclid(4), index.rem_euclid(4) + 1);
+
+      day_bounds_range(label, quarter_start(index), quarter_start(index + 1))
+    })
+    .collect()
+}
+
+/// Build yearly buckets for the last `n` calendar years before the current one (earliest → latest), labeled YYYY.
+fn buckets_for_last_n_years(now: chrono::DateTime<Local>, n: i32) -> Vec<LabeledRange> {
+  (1..=n)
+    .rev()
+    .map(|back| {
+      let year = now.year() - back;
+      let start = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+      let end = NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap();
+
+      day_bounds_range(format!("{:04}", year), start, end)
+    })
+    .collect()
+}
+
 /// Resolve any `WindowSpec` into one or more labeled ranges.
-/// - Month and Since/Until always yield one range.
+/// - Month, Since/Until, Since, and Until always yield one range; Months yields one range per month.
+/// - Since runs until `now`; Until starts at `OPEN_START` (use [`resolve_ranges_in_history`] to start at the first
+///   commit, as the CLI does).
 /// - ForPhrase yields multiple if a bucket phrase is detected; otherwise one range.
-pub fn resolve_ranges(
-  window: &crate::range_windows::WindowSpec,
-  now: Option<chrono::DateTime<chrono::Local>>,
-) -> anyhow::Result<Vec<LabeledRange>> {
+/// - Explicit yields its ranges unchanged.
+pub fn resolve_ranges(window: &WindowSpec, now: Option<chrono::DateTime<chrono::Local>>) -> Result<Vec<LabeledRange>> {
   match window {
-    crate::range_windows::WindowSpec::Month { ym } => {
+    WindowSpec::Month { ym } => {
       let (s, u) = month_bounds(ym)?;
 
       Ok(vec![LabeledRange {
@@ -400,12 +620,34 @@ pub fn resolve_ranges(
         until: u,
       }])
     }
-    crate::range_windows::WindowSpec::SinceUntil { since, until } => Ok(vec![LabeledRange {
+    WindowSpec::Months { yms } => yms
+      .iter()
+      .map(|ym| {
+        let (s, u) = month_bounds(ym)?;
+
+        Ok(LabeledRange {
+          label: ym.clone(),
+          since: s,
+          until: u,
+        })
+      })
+      .collect(),
+    WindowSpec::SinceUntil { since, until } => Ok(vec![LabeledRange {
       label: "window".into(),
       since: since.clone(),
       until: until.clone(),
     }]),
-    crate::range_windows::WindowSpec::ForPhrase { phrase } => {
+    WindowSpec::Since { since } => Ok(vec![LabeledRange {
+      label: open_window_label("since", since),
+      since: since.clone(),
+      until: iso_naive(now.unwrap_or_else(Local::now)),
+    }]),
+    WindowSpec::Until { until } => Ok(vec![LabeledRange {
+      label: open_window_label("until", until),
+      since: OPEN_START.to_string(),
+      until: until.clone(),
+    }]),
+    WindowSpec::ForPhrase { phrase } => {
       if let Some(multi) = for_phrase_buckets(phrase, now) {
         Ok(multi)
       } else {
@@ -418,9 +660,32 @@ pub fn resolve_ranges(
         }])
       }
     }
+    WindowSpec::Explicit { ranges } => Ok(ranges.clone()),
   }
 }
 
+/// [`resolve_ranges`], with an `Until` window starting at the repository's first commit instead of `OPEN_START`.
+///
+/// `first_commit` returns that commit's epoch (None for an empty repository) and is only called for `Until`. The CLI
+/// passes the earliest root commit across all refs, so callers after the report's exact ranges pass the same.
+pub fn resolve_ranges_in_history(
+  window: &WindowSpec,
+  now: Option<chrono::DateTime<chrono::Local>>,
+  first_commit: impl FnOnce() -> Result<Option<i64>>,
+) -> Result<Vec<LabeledRange>> {
+  let mut ranges = resolve_ranges(window, now)?;
+
+  if !matches!(window, WindowSpec::Until { .. }) {
+    return Ok(ranges);
+  }
+
+  if let Some(start) = first_commit()?.and_then(|epoch| chrono::DateTime::from_timestamp(epoch, 0)) {
+    ranges[0].since = start.to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+  }
+
+  Ok(ranges)
+}
+
 fn last_day_of_month(year: i32, month: u32) -> u32 {
   // Advance to first day of next month, subtract one day
   let (ny, nm) = if month == 12 { (year + 1, 1) } else { (year, month + 1) };
@@ -454,6 +719,30 @@ mod tests {
   #[test]
   fn month_bounds_invalid_errors() {
     assert!(month_bounds("2025-13").is_err());
+    assert!(month_bounds("au 2025").is_err());
+    assert!(month_bounds("aug").is_err());
+  }
+
+  #[test]
+  fn month_shorthand_variations_normalize() {
+    assert_eq!(normalize_month("2025-8").unwrap(), "2025-08");
+    assert_eq!(normalize_month("aug 2025").unwrap(), "2025-08");
+    assert_eq!(normalize_month("August 2025").unwrap(), "2025-08");
+    assert_eq!(normalize_month(" Sept, 2025 ").unwrap(), "2025-09");
+    assert_eq!(month_bounds("dec 2025").unwrap().1, "2026-01-01T00:00:00");
+  }
+
+  #[test]
+  fn months_resolve_to_one_range_each() {
+    let win = WindowSpec::Months {
+      yms: vec!["2025-07".into(), "2025-08".into()],
+    };
+    let ranges = resolve_ranges(&win, None).unwrap();
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges[0].label, "2025-07");
+    assert_eq!(ranges[1].until, "2025-09-01T00:00:00");
+    let (s, u) = compute_window_strings(&win, None).unwrap();
+    assert_eq!((s.as_str(), u.as_str()), ("2025-07-01T00:00:00", "2025-09-01T00:00:00"));
   }
 
   #[test]
@@ -467,6 +756,40 @@ mod tests {
     assert_eq!(u, "2025-09-01");
   }
 
+  #[test]
+  fn open_ended_windows_fill_the_missing_side() {
+    let now = parse_now(Some("2025-09-10T12:00:00"));
+
+    let since_only = WindowSpec::Since {
+      since: "2025-06-01".into(),
+    };
+    let ranges = resolve_ranges(&since_only, now).unwrap();
+    assert_eq!(ranges[0].label, "since-2025-06-01");
+    assert_eq!(
+      (ranges[0].since.as_str(), ranges[0].until.as_str()),
+      ("2025-06-01", "2025-09-10T12:00:00")
+    );
+
+    let until_only = WindowSpec::Until {
+      until: "2025/07/01 10:00".into(),
+    };
+    let ranges = resolve_ranges(&until_only, now).unwrap();
+    assert_eq!(ranges[0].label, "until-2025-07-01-10-00");
+    assert_eq!(ranges[0].since, OPEN_START);
+    assert_eq!(
+      compute_window_strings(&until_only, now).unwrap(),
+      (OPEN_START.to_string(), "2025/07/01 10:00".to_string())
+    );
+
+    // With a first-commit lookup the window starts there; other windows never call it
+    let ranges = resolve_ranges_in_history(&until_only, now, || Ok(Some(1_754_000_000))).unwrap();
+    assert_eq!(ranges[0].since, "2025-07-31T22:13:20Z");
+    let ranges = resolve_ranges_in_history(&since_only, now, || panic!("looked up")).unwrap();
+    assert_eq!(ranges[0].since, "2025-06-01");
+    let ranges = resolve_ranges_in_history(&until_only, now, || Ok(None)).unwrap();
+    assert_eq!(ranges[0].since, OPEN_START);
+  }
+
   #[test]
   fn for_phrase_last_month_basic() {
     let win = WindowSpec::ForPhrase {
@@ -496,6 +819,10 @@ mod tests {
     let (s, u) = compute_window_strings(&win, None).unwrap();
     assert_eq!(s, p);
     assert_eq!(u, "now");
+
+    let frozen = parse_now(Some("2025-08-15T12:00:00")).unwrap();
+    let (_, u) = compute_window_strings(&win, Some(frozen)).unwrap();
+    assert_eq!(u, "2025-08-15T12:00:00");
   }
 
   #[test]
@@ -591,4 +918,70 @@ mod future_tests {
     assert_eq!(sn.time(), un.time());
     assert!(un.month() == 2 || un.month() == 3);
   }
+
+  #[test]
+  fn day_quarter_and_year_buckets_cover_the_last_complete_periods() {
+    let now = parse_now(Some("2025-01-02T12:00:00"));
+    let spans = |phrase: &str| -> Vec<(String, String, String)> {
+      for_phrase_buckets(phrase, now)
+        .unwrap()
+        .into_iter()
+        .map(|r| (r.label, r.since, r.until))
+        .collect()
+    };
+    let span = |l: &str, s: &str, u: &str| (l.to_string(), s.to_string(), u.to_string());
+
+    assert_eq!(
+      spans("every day for the last 2 days"),
+      vec![
+        span("2024-12-31", "2024-12-31T00:00:00", "2025-01-01T00:00:00"),
+        span("2025-01-01", "2025-01-01T00:00:00", "2025-01-02T00:00:00"),
+      ]
+    );
+    assert_eq!(
+      spans("each quarter for the last three quarters"),
+      vec![
+        span("2024-Q2", "2024-04-01T00:00:00", "2024-07-01T00:00:00"),
+        span("2024-Q3", "2024-07-01T00:00:00", "2024-10-01T00:00:00"),
+        span("2024-Q4", "2024-10-01T00:00:00", "2025-01-01T00:00:00"),
+      ]
+    );
+    assert_eq!(
+      spans("every year for the last 2 years"),
+      vec![
+        span("2023", "2023-01-01T00:00:00", "2024-01-01T00:00:00"),
+        span("2024", "2024-01-01T00:00:00", "2025-01-01T00:00:00"),
+      ]
+    );
+    assert_eq!(spans("every month for the last 1 month")[0].0, "2024-12");
+    assert!(for_phrase_buckets("every quarter for the last 2 years", now).is_none());
+  }
+
+  #[test]
+  fn ranges_json_is_used_verbatim_and_validated() {
+    let td = tempfile::TempDir::new().unwrap();
+    let path = td.path().join("ranges.json");
+    let write = |body: &str| std::fs::write(&path, body).unwrap();
+    let p = path.to_str().unwrap();
+
+    write(
+      r#"[{"label":"sprint-41","since":"2025-08-04","until":"2025-08-18"},
+          {"label":"sprint-42","since":"2025-08-18","until":"2025-09-01"}]"#,
+    );
+    let ranges = load_ranges_json(p).unwrap();
+    let win = WindowSpec::Explicit { ranges: ranges.clone() };
+    assert_eq!(resolve_ranges(&win, None).unwrap(), ranges);
+    assert_eq!(ranges[1].label, "sprint-42");
+    let (s, u) = compute_window_strings(&win, None).unwrap();
+    assert_eq!((s.as_str(), u.as_str()), ("2025-08-04", "2025-09-01"));
+
+    write("[]");
+    assert!(load_ranges_json(p).is_err());
+    write(r#"[{"label":"../x","since":"a","until":"b"}]"#);
+    assert!(load_ranges_json(p).is_err());
+    write(r#"[{"label":"a","since":"x","until":"y"},{"label":"a","since":"y","until":"z"}]"#);
+    assert!(format!("{:#}", load_ranges_json(p).unwrap_err()).contains("duplicate"));
+    write(r#"[{"label":"a","since":"x"}]"#);
+    assert!(load_ranges_json(p).is_err());
+  }
 }
@@ -0,0 +1,177 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Mask sensitive file paths (--redact-paths globs) throughout commits, patches, PR diffs, and PR file lists
+// role: redaction/privacy
+// inputs: Glob list from CLI; Commit objects and patch text
+// outputs: Commits whose file paths (and occurrences in patch/diff text) are replaced by stable hashed placeholders
+// side_effects: None (pure)
+// invariants:
+// - a path always maps to the same placeholder (FNV-1a), so distinct-file counts and per-file aggregates are preserved
+// - the file extension is kept so language-level statistics still make sense
+// - redaction runs after estimation/service tagging, so derived numbers are unaffected
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use crate::model::{Commit, FileEntry};
+use crate::services::glob_match;
+
+#[derive(Debug, Clone)]
+pub struct PathRedactor {
+  globs: Vec<String>,
+}
+
+impl PathRedactor {
+  /// Build a redactor; returns None when no globs are configured.
+  pub fn new(globs: &[String]) -> Option<Self> {
+    if globs.is_empty() {
+      return None;
+    }
+
+    Some(Self { globs: globs.to_vec() })
+  }
+
+  fn matches(&self, path: &str) -> bool {
+    self.globs.iter().any(|g| glob_match(g.as_bytes(), path.as_bytes()))
+  }
+
+  /// Placeholder for `path` when it matches a redaction glob.
+  pub fn redact_path(&self, path: &str) -> Option<String> {
+    if !self.matches(path) {
+      return None;
+    }
+
+    Some(placeholder(path))
+  }
+
+  /// Replace matching paths named in diff headers everywhere they occur in `text`.
+  pub fn redact_patch_text(&self, text: &str) -> String {
+    let mut paths: Vec<&str> = text
+      .lines()
+      .flat_map(header_paths)
+      .filter(|p| self.matches(p))
+      .collect();
+    // Longest first so a path never gets partially replaced by a shorter prefix match
+    paths.sort_by_key(|p| std::cmp::Reverse(p.len()));
+    paths.dedup();
+
+    let mut out = text.to_string();
+
+    for p in paths {
+      out = out.replace(p, &placeholder(p));
+    }
+
+    out
+  }
+
+  /// Redact file entries, embedded patch lines, and PR diffs on `commit`.
+  pub fn redact_commit(&self, commit: &mut Commit) {
+    self.redact_files(&mut commit.files);
+
+    if let Some(lines) = commit.patch_lines.take() {
+      let redacted = self.redact_patch_text(&lines.join("\n"));
+      commit.patch_lines = Some(redacted.lines().map(String::from).collect());
+    }
+
+    if let Some(gh) = commit.github.as_mut() {
+      for pr in gh.pull_requests.iter_mut() {
+        pr.diff = pr.diff.as_deref().map(|d| self.redact_patch_text(d));
+
+        if let Some(files) = pr.files.as_mut() {
+          self.redact_files(files);
+        }
+      }
+    }
+  }
+
+  fn redact_files(&self, files: &mut [FileEntry]) {
+    for f in files.iter_mut() {
+      if let Some(masked) = self.redact_path(&f.file) {
+        f.file = masked;
+      }
+
+      if let Some(old) = f.old_path.as_deref().and_then(|p| self.redact_path(p)) {
+        f.old_path = Some(old);
+      }
+    }
+  }
+}
+
+/// Paths named by a unified-diff header line (`diff --git`, `---`/`+++`, rename/copy lines).
+fn header_paths(line: &str) -> Vec<&str> {
+  if let Some(rest) = line.strip_prefix("diff --git a/") {
+    return match rest.split_once(" b/") {
+      Some((a, b)) => vec![a, b],
+      None => Vec::new(),
+    };
+  }
+
+  let prefixes = [
+    "--- a/",
+    "+++ b/",
+    "rename from ",
+    "rename to ",
+    "copy from ",
+    "copy to ",
+  ];
+
+  prefixes
+    .iter()
+    .find_map(|p| line.strip_prefix(p))
+    .map(|p| vec![p.trim_end()])
+    .unwrap_or_default()
+}
+
+/// Stable placeholder: `redacted/<fnv1a-64 hex>` plus the original extension.
+fn placeholder(path: &str) -> String {
+  let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+  for b in path.as_bytes() {
+    hash ^= *b as u64;
+    hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+  }
+
+  let file_name = path.rsplit('/').next().unwrap_or(path);
+  let ext = file_name
+    .rsplit_once('.')
+    .filter(|(stem, _)| !stem.is_empty())
+    .map(|(_, e)| format!(".{}", e))
+    .unwrap_or_default();
+
+  format!("redacted/{:016x}{}", hash, ext)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn redactor() -> PathRedactor {
+    PathRedactor::new(&["clients/**".to_string()]).unwrap()
+  }
+
+  #[test]
+  fn placeholder_is_stable_and_keeps_extension() {
+    let r = redactor();
+
+    let a = r.redact_path("clients/acme/report.rs").unwrap();
+
+    assert_eq!(a, r.redact_path("clients/acme/report.rs").unwrap());
+    assert!(a.starts_with("redacted/") && a.ends_with(".rs"));
+    assert!(!a.contains("acme"));
+    assert_ne!(a, r.redact_path("clients/globex/report.rs").unwrap());
+    assert!(r.redact_path("src/main.rs").is_none());
+    assert!(PathRedactor::new(&[]).is_none());
+  }
+
+  #[test]
+  fn patch_text_redacts_header_paths_everywhere() {
+    let r = redactor();
+    let patch = "diff --git a/clients/acme/x.txt b/clients/acme/x.txt\n--- a/clients/acme/x.txt\n+++ b/clients/acme/x.txt\n@@ -1 +1 @@\n-see clients/acme/x.txt\n+hello\ndiff --git a/src/lib.rs b/src/lib.rs\n";
+
+    let out = r.redact_patch_text(patch);
+
+    assert!(!out.contains("acme"));
+    assert!(out.contains("diff --git a/src/lib.rs b/src/lib.rs"));
+    assert_eq!(out.matches("redacted/").count(), 5);
+  }
+}
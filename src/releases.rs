@@ -0,0 +1,137 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --releases: tag each commit with the nearest tag containing it and list the tags created in the window ("what shipped")
+// role: enrichment/releases
+// inputs: repo, processed commits, window since/until strings, --tz; GitHub coordinates from origin or --github-repo for URLs
+// outputs: Commit.release { tag, url }; Vec<Release> for summary.releases
+// side_effects: git name-rev once per 256 commits; one git for-each-ref and two git rev-parse calls per range
+// invariants:
+// - a commit's release is the tag `git describe --contains` would name; commits no tag contains get none (unreleased)
+// - a tag is in the window when its creation time (tagger date, or commit date for lightweight tags) is at or after since and before until, both read by git's approxidate; an unparseable bound leaves that side open
+// - releases are ordered by creation time, then tag; commits counts the window's commits whose release is that tag
+// - url is the GitHub release page when the repo resolves to GitHub, else absent
+// errors: git failures propagate with context (the flag is opt-in)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+
+use crate::gitio;
+use crate::model::{Commit, Release, ReleaseRef};
+use crate::util::{iso_in_tz, short_sha};
+
+/// GitHub release page for `tag`, when the repo resolves to a GitHub repository.
+fn release_url(repo: &str, tag: &str) -> Option<String> {
+  let (owner, name) = crate::enrichment::github_api::resolve_github_repo(repo)?;
+
+  Some(format!("https://github.com/{}/{}/releases/tag/{}", owner, name, tag))
+}
+
+/// Set `release` on every commit some tag contains.
+pub fn tag_commits(repo: &str, commits: &mut [Commit]) -> Result<()> {
+  let shas: Vec<String> = commits.iter().map(|c| c.sha.clone()).collect();
+  let tags = gitio::containing_tags(repo, &shas).context("finding the tags that contain each commit")?;
+
+  for c in commits.iter_mut() {
+    c.release = tags.get(&c.sha).map(|tag| ReleaseRef {
+      tag: tag.clone(),
+      url: release_url(repo, tag),
+    });
+  }
+
+  Ok(())
+}
+
+/// Window commits per release tag, for `Release.commits`.
+pub fn count_by_tag<'a>(commits: impl IntoIterator<Item = &'a Commit>, counts: &mut BTreeMap<String, usize>) {
+  for c in commits {
+    if let Some(r) = &c.release {
+      *counts.entry(r.tag.clone()).or_insert(0) += 1;
+    }
+  }
+}
+
+/// Tags created in `[since, until)`, oldest first.
+pub fn build_releases(
+  repo: &str,
+  since: &str,
+  until: &str,
+  tz: &str,
+  counts: &BTreeMap<String, usize>,
+) -> Result<Vec<Release>> {
+  let from = gitio::approxidate_epoch(repo, since);
+  let to = gitio::approxidate_epoch(repo, until);
+  let tags = gitio::list_tags(repo).context("listing tags")?;
+
+  Ok(
+    tags
+      .into_iter()
+      .filter(|t| from.is_none_or(|f| t.created >= f) && to.is_none_or(|u| t.created < u))
+      .map(|t| Release {
+        url: release_url(repo, &t.name),
+        commits: counts.get(&t.name).copied().unwrap_or(0),
+        created_at: iso_in_tz(t.created, tz),
+        commit: short_sha(&t.commit),
+        tag: t.name,
+      })
+      .collect(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn window_tags_are_listed_with_their_commits() {
+    let td = tempfile::TempDir::new().unwrap();
+    let repo = td.path();
+    let sh = |date: &str, args: &[&str]| {
+      let st = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .status()
+        .unwrap();
+      assert!(st.success(), "git {:?} failed", args);
+    };
+    let commit = |date: &str, file: &str| {
+      std::fs::write(repo.join(file), date).unwrap();
+      sh(date, &["add", "."]);
+      sh(date, &["commit", "-q", "-m", file]);
+    };
+    sh("2025-07-01T10:00:00Z", &["init", "-q", "-b", "main"]);
+    sh("2025-07-01T10:00:00Z", &["config", "user.name", "Fixture Bot"]);
+    sh("2025-07-01T10:00:00Z", &["config", "user.email", "fixture@example.com"]);
+    sh("2025-07-01T10:00:00Z", &["config", "commit.gpgsign", "false"]);
+    commit("2025-07-01T10:00:00Z", "a.txt");
+    sh("2025-07-01T10:00:00Z", &["tag", "v1.0"]);
+    commit("2025-08-05T10:00:00Z", "b.txt");
+    commit("2025-08-06T10:00:00Z", "c.txt");
+    sh("2025-08-07T10:00:00Z", &["tag", "-a", "-m", "v1.1", "v1.1"]);
+    commit("2025-08-20T10:00:00Z", "d.txt");
+
+    let repo = repo.to_str().unwrap();
+    let shas = gitio::rev_list(repo, "2025-08-01", "2025-09-01", false, &[]).unwrap();
+    let mut commits: Vec<Commit> = shas.iter().map(|sha| Commit::fixture(sha)).collect();
+
+    tag_commits(repo, &mut commits).unwrap();
+    let tags: Vec<Option<&str>> = commits
+      .iter()
+      .map(|c| c.release.as_ref().map(|r| r.tag.as_str()))
+      .collect();
+    assert_eq!(tags, vec![Some("v1.1"), Some("v1.1"), None]);
+
+    let mut counts = BTreeMap::new();
+    count_by_tag(&commits, &mut counts);
+    let releases = build_releases(repo, "2025-08-01T00:00:00Z", "2025-09-01T00:00:00Z", "utc", &counts).unwrap();
+    assert_eq!(releases.len(), 1);
+    assert_eq!(releases[0].tag, "v1.1");
+    assert_eq!(releases[0].created_at, "2025-08-07T10:00:00Z");
+    assert_eq!(releases[0].commit, commits[1].short_sha);
+    assert_eq!(releases[0].commits, 2);
+  }
+}
@@ -4,11 +4,15 @@
 // role: assembly/render
 // inputs: ReportParams (repo, since/until, flags, label, out dir)
 // outputs: SimpleReport JSON (non-split) or pointer {dir, file} (split)
-// side_effects: In split mode, writes shard files under <base>/<label>/ and report-<label>.json; may write .patch files if requested
+// side_effects: In split mode, writes shard files at their --layout paths under <base>/ and report-<label>.json; may write .patch files if requested
 // invariants:
 // - run_simple returns fully in-memory report consistent with schema
 // - run_report returns pointer JSON when split; otherwise full report JSON; file names are stable
-// - shard filenames follow YYYY.MM.DD-HH.MM-<shortsha>.json
+// - shard paths come from OutputLayout (default nested: <label>/YYYY.MM.DD-HH.MM-<shortsha>.json)
+// - commits are processed on a --jobs thread pool (0 = one per CPU) but always kept in rev-list order; shards are written after processing
+// - --all-refs ⇒ commits come from rev-list --all (each SHA once) and carry refs[] naming every branch/tag containing them
+// - --summary-only ⇒ no commits key, shards, snapshot files, items, or side indexes; unmerged branches keep counts and authors with empty items
+// - run_ndjson never holds more than one processing batch of commits; its last line is the summary, its other lines commits
 // errors: Propagates git and IO errors with context (paths, git args)
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
@@ -16,18 +20,37 @@
 use std::collections::{BTreeMap, HashSet};
 use std::path::Path;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Local};
 
+use crate::author_stats::AuthorStatsAccumulator;
+use crate::automation::AutomationDetector;
+use crate::calendar::CalendarAccumulator;
+use crate::commit_cache::CommitCache;
+use crate::enrichment::github_api as ghapi;
+use crate::enrichment::issue_keys::IssueKeyMatcher;
 use crate::gitio;
 use crate::model::{
-  BranchItems, ChangeSet, Commit, ManifestItem, Person, RangeInfo, ReportOptions, ReportSummary, SimpleReport,
-  UnmergedActivity,
+  AuthorStats, AutomationSplit, BoundaryCommit, BranchItems, ChangeSet, Commit, ConflictHotspots, EnvironmentSummary,
+  GithubRepoInfo, MaintainerActivity, ManifestItem, NewContributor, PatchBudgetSummary, Provenance, RangeInfo, Release,
+  RepoPolicy, ReportOptions, ReportSummary, ServiceSummary, SimpleReport, StaleBranch, UnmergedActivity,
 };
-use crate::util::format_shard_name;
+use crate::progress::Progress;
+use crate::redact::PathRedactor;
+use crate::sampling::SamplePlan;
+use crate::services::ServiceMap;
+use crate::util;
+use crate::validate_output::Artifact;
 
 // Clippy: factor complex tuple into a named alias for readability.
-type ProcessRangeOut = (Vec<Commit>, Vec<ManifestItem>, ChangeSet, BTreeMap<String, i64>);
+type ProcessRangeOut = (
+  Vec<Commit>,
+  Vec<ManifestItem>,
+  ChangeSet,
+  BTreeMap<String, i64>,
+  Vec<AuthorStats>,
+  Option<PatchBudgetSummary>,
+);
 
 // --- Local helpers to unify repeated patterns ---
 fn build_process_context<'a>(params: &'a ReportParams) -> ProcessContext<'a> {
@@ -37,7 +60,21 @@ fn build_process_context<'a>(params: &'a ReportParams) -> ProcessContext<'a> {
     github_prs: params.github_prs,
     include_patch: params.include_patch,
     max_patch_bytes: params.max_patch_bytes,
+    max_files_per_commit: params.max_files_per_commit,
     estimate_effort: params.estimate_effort,
+    no_blob_stats: params.no_blob_stats,
+    service_map: params.service_map.as_ref(),
+    identity_map: params.identity_map.as_ref(),
+    include_pr_patch: params.include_pr_patch,
+    pr_files: params.pr_files,
+    redactor: params.redactor.as_ref(),
+    env_trailers: &params.env_trailers,
+    automation: params.automation.as_ref(),
+    issue_keys: params.issue_keys.as_ref(),
+    snapshot_paths: &params.snapshot_paths,
+    snapshot_max_bytes: params.snapshot_max_bytes,
+    no_bodies: params.no_bodies,
+    paths: &params.paths,
   }
 }
 
@@ -47,34 +84,358 @@ fn build_report_options(params: &ReportParams) -> ReportOptions {
     include_patch: params.include_patch,
     include_unmerged: params.include_unmerged,
     tz: params.tz.clone(),
+    summary_only: params.summary_only,
+    all_refs: params.all_refs,
+    first_parent: params.first_parent,
+    paths: params.paths.clone(),
+    fields: params
+      .fields
+      .as_ref()
+      .map(|f| f.selectors().to_vec())
+      .unwrap_or_default(),
   }
 }
 
-fn author_key_for(p: &Person) -> String {
-  format!("{} <{}>", p.name, p.email)
+/// Resolved GitHub coordinates for the repo (override or origin), when detected.
+fn github_repo_info(repo: &str) -> Option<GithubRepoInfo> {
+  let (owner, name) = crate::enrichment::github_api::resolve_github_repo(repo)?;
+
+  Some(GithubRepoInfo {
+    owner,
+    repo: name,
+    host: "github.com".into(),
+  })
+}
+
+/// `summary.repo_policy` when `--repo-policy` is set; the head is the last commit on HEAD at window end.
+fn fetch_repo_policy(params: &ReportParams) -> Option<RepoPolicy> {
+  if !params.repo_policy {
+    return None;
+  }
+
+  let head = gitio::head_at(&params.repo, &params.until).ok().flatten();
+
+  crate::enrichment::github_api::try_fetch_repo_policy(&params.repo, head.as_deref())
+}
+
+/// `summary.boundary_commits`; warns on stderr because totals near the edges shift with the timezone of the run.
+fn boundary_commits(params: &ReportParams, commits: &[Commit]) -> Option<Vec<BoundaryCommit>> {
+  let included: HashSet<&str> = commits.iter().map(|c| c.sha.as_str()).collect();
+  let found = crate::boundary::find_boundary_commits(
+    &params.repo,
+    &params.since,
+    &params.until,
+    params.include_merges,
+    &params.paths,
+    &included,
+    &params.tz,
+  );
+
+  if found.is_empty() {
+    return None;
+  }
+
+  crate::util::log(
+    crate::util::LogLevel::Warn,
+    "gar",
+    &format!(
+      "{} commit(s) within 24h of the window boundary; totals may differ when run in another timezone (see summary.boundary_commits)",
+      found.len()
+    ),
+  );
+
+  Some(found)
+}
+
+/// `summary.conflict_hotspots` when `--conflict-hotspots` is set.
+fn conflict_hotspots(params: &ReportParams) -> Result<Option<ConflictHotspots>> {
+  if !params.conflict_hotspots {
+    return Ok(None);
+  }
+
+  crate::conflict_hotspots::build_conflict_hotspots(
+    &params.repo,
+    &params.since,
+    &params.until,
+    params.redactor.as_ref(),
+  )
+  .map(Some)
+}
+
+/// `summary.new_contributors` when `--new-contributors` is set.
+fn new_contributors(params: &ReportParams, commits: &[Commit]) -> Result<Option<Vec<NewContributor>>> {
+  if !params.new_contributors {
+    return Ok(None);
+  }
+
+  crate::new_contributors::build_new_contributors(
+    &params.repo,
+    params.all_refs,
+    commits,
+    &params.tz,
+    params.identity_map.as_ref(),
+  )
+  .map(Some)
+}
+
+/// `summary.releases` when `--releases` is set; `counts` holds the window's commits per release tag.
+fn release_summary(params: &ReportParams, counts: &BTreeMap<String, usize>) -> Result<Option<Vec<Release>>> {
+  if !params.releases {
+    return Ok(None);
+  }
+
+  crate::releases::build_releases(&params.repo, &params.since, &params.until, &params.tz, counts).map(Some)
+}
+
+/// Window commits per release tag (empty without `--releases`).
+fn release_counts(commits: &[Commit]) -> BTreeMap<String, usize> {
+  let mut counts = BTreeMap::new();
+  crate::releases::count_by_tag(commits, &mut counts);
+
+  counts
+}
+
+/// `summary.maintainer_activity` when `--committers` is set; merges come from git even without --include-merges.
+fn maintainer_activity(params: &ReportParams, commits: &[Commit]) -> Result<Option<Vec<MaintainerActivity>>> {
+  if !params.committers {
+    return Ok(None);
+  }
+
+  let merges = gitio::merge_committers(
+    &params.repo,
+    &params.since,
+    &params.until,
+    params.all_refs,
+    &params.refs,
+  )
+  .context("listing merge committers")?;
+
+  Ok(crate::maintainers::build_maintainer_activity(
+    commits,
+    &merges,
+    params.identity_map.as_ref(),
+  ))
+}
+
+/// `summary.estimated_minutes` when `--estimate-effort` is set, rounded to one decimal.
+fn estimated_minutes(params: &ReportParams, commits: &[Commit]) -> Option<f64> {
+  params.estimate_effort.then(|| {
+    let total: f64 = commits.iter().filter_map(|c| c.estimated_minutes).sum();
+
+    (total * 10.0).round() / 10.0
+  })
+}
+
+/// -v progress: a range is about to be processed.
+fn range_started(params: &ReportParams) {
+  tracing::debug!(
+    target: "gar",
+    range = params.label.as_deref().unwrap_or("window"),
+    since = %params.since,
+    until = %params.until,
+    "range started"
+  );
+}
+
+/// -v progress: a range's report is built.
+fn range_finished(params: &ReportParams, commits: usize) {
+  tracing::debug!(target: "gar", range = params.label.as_deref().unwrap_or("window"), commits, "range finished");
+}
+
+/// `summary.warnings`: GitHub lookups skipped since `before` (request budget or rate limit), also logged on stderr.
+fn enrichment_warnings(before: &ghapi::RequestStats) -> Option<Vec<String>> {
+  let skipped = ghapi::request_stats().since(before);
+  let warnings = skipped.warnings(ghapi::http_config().max_requests);
+
+  for w in &warnings {
+    util::log(util::LogLevel::Warn, "github", w);
+  }
+
+  (!warnings.is_empty()).then_some(warnings)
+}
+
+/// Commits to process, earliest→latest; with `--sample N` over a larger window, a stratified subset and its plan.
+fn range_shas(params: &ReportParams) -> Result<(Vec<String>, Option<SamplePlan>)> {
+  let shas = if params.first_parent {
+    gitio::rev_list_first_parent(&params.repo, &params.since, &params.until, &params.refs, &params.paths)?
+  } else if params.all_refs {
+    gitio::rev_list_all(
+      &params.repo,
+      &params.since,
+      &params.until,
+      params.include_merges,
+      &params.paths,
+    )?
+  } else {
+    gitio::rev_list_refs(
+      &params.repo,
+      &params.since,
+      &params.until,
+      params.include_merges,
+      &params.refs,
+      &params.paths,
+    )?
+  };
+
+  let Some(n) = params.sample.filter(|n| shas.len() > *n) else {
+    return Ok((shas, None));
+  };
+
+  let population = gitio::commit_epoch_authors(
+    &params.repo,
+    &params.since,
+    &params.until,
+    params.include_merges,
+    params.all_refs,
+    &params.refs,
+    &params.paths,
+  )?;
+  let plan = crate::sampling::stratified_sample(&population, n);
+  let picked: HashSet<&String> = plan.picked().collect();
+  let sampled: Vec<String> = shas.iter().filter(|s| picked.contains(s)).cloned().collect();
+
+  crate::util::log(
+    crate::util::LogLevel::Info,
+    "gar",
+    &format!(
+      "--sample: processing {} of {} commits; summary.sample holds estimated totals for the whole window",
+      sampled.len(),
+      shas.len()
+    ),
+  );
+
+  Ok((sampled, Some(plan)))
+}
+
+/// Human vs automated totals; None when no commit was classified as automated.
+fn summarize_automation(commits: &[Commit]) -> Option<AutomationSplit> {
+  if !commits.iter().any(|c| c.automated == Some(true)) {
+    return None;
+  }
+
+  let mut split = AutomationSplit::default();
+
+  for commit in commits {
+    let bucket = if commit.automated == Some(true) {
+      &mut split.automated
+    } else {
+      &mut split.human
+    };
+    let (add, del) = crate::commit::commit_additions_deletions(commit);
+
+    bucket.commits += 1;
+    bucket.additions += add;
+    bucket.deletions += del;
+    *bucket.authors.entry(commit.author.key()).or_insert(0) += 1;
+  }
+
+  Some(split)
 }
 
-/// Write a single commit shard JSON under `subdir`, named with `tz`-relative timestamp and short SHA.
-fn write_commit_shard(subdir: &Path, commit: &Commit, tz: &str) -> anyhow::Result<String> {
-  let fname = format_shard_name(commit.timestamps.commit, &commit.short_sha, tz);
-  let shard_path = subdir.join(&fname);
+/// Per-environment rollup (commits per ISO week) over commits carrying deployment trailers; None when absent.
+fn summarize_environments(commits: &[Commit]) -> Option<BTreeMap<String, EnvironmentSummary>> {
+  let mut envs: BTreeMap<String, EnvironmentSummary> = BTreeMap::new();
+
+  for commit in commits {
+    let Some(targets) = commit.environments.as_ref() else {
+      continue;
+    };
+    let week = util::iso_week_label(&commit.timestamps.commit_local).unwrap_or_else(|| "unknown".into());
+
+    for env in targets {
+      let entry = envs.entry(env.clone()).or_default();
+      entry.commits += 1;
+      *entry.weeks.entry(week.clone()).or_insert(0) += 1;
+    }
+  }
+
+  (!envs.is_empty()).then_some(envs)
+}
+
+/// Per-service rollup (commits, churn, authors) over file entries tagged with a `service`.
+fn summarize_services(commits: &[Commit]) -> BTreeMap<String, ServiceSummary> {
+  let mut services: BTreeMap<String, ServiceSummary> = BTreeMap::new();
+
+  for commit in commits {
+    let mut touched: HashSet<&str> = HashSet::new();
+
+    for f in &commit.files {
+      let Some(service) = f.service.as_deref() else { continue };
+      let entry = services.entry(service.to_string()).or_default();
+      entry.additions += f.additions.unwrap_or(0);
+      entry.deletions += f.deletions.unwrap_or(0);
+      touched.insert(service);
+    }
+
+    for service in touched {
+      let entry = services.entry(service.to_string()).or_default();
+      entry.commits += 1;
+      *entry.authors.entry(commit.author.key()).or_insert(0) += 1;
+    }
+  }
+
+  services
+}
+
+/// Write a single commit shard JSON at its `--layout` path under `base_dir`; returns that path relative to `base_dir`.
+fn write_commit_shard(
+  base_dir: &Path,
+  params: &ReportParams,
+  label: &str,
+  branch: Option<&str>,
+  commit: &Commit,
+) -> anyhow::Result<String> {
+  let rel = params
+    .layout
+    .shard_path(label, branch, commit.timestamps.commit, &commit.short_sha, &params.tz);
+  let shard_path = base_dir.join(&rel);
 
   if let Some(parent) = shard_path.parent() {
     std::fs::create_dir_all(parent)?;
   }
-  std::fs::write(&shard_path, serde_json::to_vec(&commit)?)?;
+  crate::validate_output::check(Artifact::Commit, &shard_path, commit)?;
+  let bytes = match &params.fields {
+    Some(fields) => {
+      let mut v = serde_json::to_value(commit)?;
+      fields.apply(&mut v);
+      serde_json::to_vec(&v)?
+    }
+    None => serde_json::to_vec(&commit)?,
+  };
+  util::write_output(&shard_path, &bytes)?;
 
-  Ok(fname)
+  Ok(rel.to_string_lossy().to_string())
 }
 
-/// Update `summary` and `files_touched` given `commit`'s file entries.
+/// Update `summary` and `files_touched` given `commit`'s file entries; a grouped merge counts its group's commits
+/// instead, since its own numstat repeats what they changed.
 fn accumulate_summary_and_files(commit: &Commit, summary: &mut ChangeSet, files_touched: &mut HashSet<String>) {
-  let (add, del) = crate::commit::sum_additions_deletions(&commit.files);
+  if let Some(group) = &commit.merge_group {
+    for member in &group.commits {
+      accumulate_summary_and_files(member, summary, files_touched);
+    }
+    return;
+  }
+
+  let (add, del) = crate::commit::commit_additions_deletions(commit);
   summary.additions += add;
   summary.deletions += del;
 
   for f in &commit.files {
-    files_touched.insert(f.file.clone());
+    let lang = summary
+      .changes_by_language
+      .entry(
+        f.language
+          .clone()
+          .unwrap_or_else(|| crate::languages::OTHER.to_string()),
+      )
+      .or_default();
+    lang.additions += f.additions.unwrap_or(0);
+    lang.deletions += f.deletions.unwrap_or(0);
+
+    if files_touched.insert(f.file.clone()) {
+      lang.files_touched += 1;
+    }
   }
 }
 
@@ -90,6 +451,7 @@ pub struct ReportParams {
   pub include_merges: bool,
   pub include_patch: bool,
   pub max_patch_bytes: usize,
+  pub max_files_per_commit: Option<usize>,
   pub tz: String,
   pub split_apart: bool,
   pub split_out: Option<String>,
@@ -98,6 +460,50 @@ pub struct ReportParams {
   pub github_prs: bool,
   pub now_local: Option<DateTime<Local>>,
   pub estimate_effort: bool,
+  pub provenance: Option<Provenance>,
+  pub no_blob_stats: bool,
+  pub stale_days: i64,
+  pub layout: crate::layout::OutputLayout,
+  pub unmerged_base: Option<String>,
+  pub service_map: Option<ServiceMap>,
+  pub identity_map: Option<crate::identities::IdentityMap>,
+  pub include_pr_patch: bool,
+  pub pr_files: bool,
+  pub redactor: Option<PathRedactor>,
+  pub env_trailers: Vec<String>,
+  pub patch_budget_bytes: Option<usize>,
+  pub side_indexes: bool,
+  pub automation: Option<AutomationDetector>,
+  /// --issue-pattern/--issue-url-template, compiled
+  pub issue_keys: Option<IssueKeyMatcher>,
+  pub repo_policy: bool,
+  pub collaboration: bool,
+  pub snapshot_paths: Vec<String>,
+  pub snapshot_max_bytes: usize,
+  pub conflict_hotspots: bool,
+  pub dependency_changes: bool,
+  pub new_contributors: bool,
+  pub no_bodies: bool,
+  pub summary_only: bool,
+  /// --work-items gap in minutes; None leaves work_items out
+  pub work_items: Option<i64>,
+  /// --calendar: day and weekday × hour buckets
+  pub calendar: bool,
+  /// --releases: commit.release and summary.releases
+  pub releases: bool,
+  pub fields: Option<crate::fields::FieldSelection>,
+  pub all_refs: bool,
+  /// --first-parent: walk first parents only and nest each merge's commits under merge_group
+  pub first_parent: bool,
+  /// --committers: committers map, authored_by_committer, and summary.maintainer_activity
+  pub committers: bool,
+  /// --branch/--ref revisions to walk instead of HEAD
+  pub refs: Vec<String>,
+  pub paths: Vec<String>,
+  pub sample: Option<usize>,
+  pub jobs: usize,
+  /// --cache: processed commits reused across runs
+  pub commit_cache: Option<crate::commit_cache::CommitCache>,
 }
 
 /// Build `ReportParams` from an `EffectiveConfig` and an explicit `[since, until]` window.
@@ -115,6 +521,7 @@ pub fn build_report_params(cfg: &crate::cli::EffectiveConfig, since: String, unt
     include_merges: cfg.include_merges,
     include_patch: cfg.include_patch,
     max_patch_bytes: cfg.max_patch_bytes,
+    max_files_per_commit: cfg.max_files_per_commit,
     tz: cfg.tz.clone(),
     split_apart: cfg.split_apart,
     split_out: if cfg.out != "-" { Some(cfg.out.clone()) } else { None },
@@ -123,6 +530,44 @@ pub fn build_report_params(cfg: &crate::cli::EffectiveConfig, since: String, unt
     github_prs: cfg.github_prs,
     now_local: None,
     estimate_effort: cfg.estimate_effort,
+    provenance: Some(crate::provenance::from_config(cfg)),
+    no_blob_stats: cfg.no_blob_stats,
+    stale_days: cfg.stale_days,
+    layout: cfg.layout,
+    unmerged_base: cfg.unmerged_base.clone(),
+    service_map: cfg.service_map.clone(),
+    identity_map: cfg.identity_map.clone(),
+    include_pr_patch: cfg.include_pr_patch,
+    pr_files: cfg.pr_files,
+    redactor: PathRedactor::new(&cfg.redact_paths),
+    env_trailers: cfg.env_trailers.clone(),
+    patch_budget_bytes: cfg.patch_budget_bytes,
+    side_indexes: cfg.side_indexes,
+    automation: AutomationDetector::new(&cfg.bot_authors).ok(),
+    issue_keys: IssueKeyMatcher::new(&cfg.issue_patterns, &cfg.issue_url_templates)
+      .ok()
+      .flatten(),
+    repo_policy: cfg.repo_policy,
+    collaboration: cfg.collaboration,
+    snapshot_paths: cfg.snapshot_paths.clone(),
+    snapshot_max_bytes: cfg.snapshot_max_bytes,
+    conflict_hotspots: cfg.conflict_hotspots,
+    dependency_changes: cfg.dependency_changes,
+    new_contributors: cfg.new_contributors,
+    no_bodies: cfg.no_bodies,
+    summary_only: cfg.summary_only,
+    work_items: cfg.work_items,
+    calendar: cfg.calendar,
+    releases: cfg.releases,
+    fields: cfg.fields.clone(),
+    all_refs: cfg.all_refs,
+    first_parent: cfg.first_parent,
+    committers: cfg.committers,
+    refs: cfg.refs.clone(),
+    paths: cfg.paths.clone(),
+    sample: cfg.sample,
+    jobs: cfg.jobs,
+    commit_cache: crate::commit_cache::CommitCache::for_config(cfg),
   }
 }
 
@@ -137,34 +582,43 @@ use crate::commit::{ProcessContext, process_commit};
 
 /// Generates a `SimpleReport` containing all commit data in memory.
 pub fn run_simple(params: &ReportParams) -> Result<SimpleReport> {
-  let shas = gitio::rev_list(&params.repo, &params.since, &params.until, params.include_merges)?;
+  range_started(params);
+  let github_before = ghapi::request_stats();
+  let warn_mark = util::warning_count();
+  let (shas, sample_plan) = range_shas(params)?;
   let context = build_process_context(params);
 
-  let mut commits: Vec<Commit> = Vec::with_capacity(shas.len());
+  let progress = Progress::new(params.label.as_deref().unwrap_or("window"), shas.len());
+  let (processed, patch_budget) = process_with_patch_budget(&shas, &context, params, progress.as_ref())?;
+  drop(progress);
+
+  let mut commits: Vec<Commit> = Vec::with_capacity(processed.len());
   let mut authors: BTreeMap<String, i64> = BTreeMap::new();
+  let mut author_stats = AuthorStatsAccumulator::new(&params.since, &params.until);
   let mut changeset = ChangeSet {
     additions: 0,
     deletions: 0,
     files_touched: 0,
+    changes_by_language: BTreeMap::new(),
   };
   let mut files_touched: HashSet<String> = HashSet::new();
 
-  for sha in shas.iter() {
-    let mut commit = process_commit(sha, &context)?;
-
+  for mut commit in processed {
     if let Some(patches_dir_str) = &params.save_patches_dir {
-      crate::commit::save_patch_to_disk(&mut commit, &params.repo, Path::new(patches_dir_str))?;
+      crate::commit::save_patch_to_disk(
+        &mut commit,
+        &params.repo,
+        Path::new(patches_dir_str),
+        params.redactor.as_ref(),
+        &params.paths,
+      )?;
     }
 
     // Accumulate summary stats
-    let author_key = author_key_for(&commit.author);
+    let author_key = commit.author.key();
     *authors.entry(author_key).or_insert(0) += 1;
-
-    for f in &commit.files {
-      changeset.additions += f.additions.unwrap_or(0);
-      changeset.deletions += f.deletions.unwrap_or(0);
-      files_touched.insert(f.file.clone());
-    }
+    author_stats.add(&commit);
+    accumulate_summary_and_files(&commit, &mut changeset, &mut files_touched);
 
     commits.push(commit);
   }
@@ -180,6 +634,7 @@ pub fn run_simple(params: &ReportParams) -> Result<SimpleReport> {
     label: params.label.clone().unwrap_or_else(|| "window".into()),
     start: params.since.clone(),
     end: params.until.clone(),
+    refs: params.refs.clone(),
   };
   let report_options = build_report_options(params);
   let summary = ReportSummary {
@@ -188,24 +643,84 @@ pub fn run_simple(params: &ReportParams) -> Result<SimpleReport> {
     count: commits.len(),
     report_options,
     changes: changeset,
+    provenance: params.provenance.clone(),
+    services: params.service_map.as_ref().map(|_| summarize_services(&commits)),
+    github: github_repo_info(&params.repo),
+    environments: summarize_environments(&commits),
+    patch_budget,
+    automation: summarize_automation(&commits),
+    repo_policy: fetch_repo_policy(params),
+    collaboration: params
+      .collaboration
+      .then(|| crate::collaboration::build_collaboration(&commits)),
+    boundary_commits: boundary_commits(params, &commits),
+    conflict_hotspots: conflict_hotspots(params)?,
+    dependency_changes: params
+      .dependency_changes
+      .then(|| crate::dependencies::summarize_dependency_changes(&params.repo, &commits)),
+    sample: sample_plan.map(|plan| crate::sampling::estimate(&plan, &commits)),
+    review_pairs: params
+      .github_prs
+      .then(|| crate::review_pairs::build_review_pairs(&commits))
+      .flatten(),
+    new_contributors: new_contributors(params, &commits)?,
+    estimated_minutes: estimated_minutes(params, &commits),
+    rework_cycles: params
+      .github_prs
+      .then(|| crate::rework::build_rework_summary(&commits))
+      .flatten(),
+    maintainer_activity: maintainer_activity(params, &commits)?,
+    releases: release_summary(params, &release_counts(&commits))?,
+    warnings: enrichment_warnings(&github_before),
   };
 
+  range_finished(params, summary.count);
   let report = SimpleReport {
     summary,
     authors,
-    commits,
+    committers: params
+      .committers
+      .then(|| crate::maintainers::committer_counts(&commits)),
+    author_stats: author_stats.finish(),
+    work_items: params
+      .work_items
+      .map(|gap| crate::work_items::build_work_items(&commits, gap)),
+    calendar: params
+      .calendar
+      .then(|| crate::calendar::build_calendar(&commits, &params.tz)),
+    commits: if params.summary_only { Vec::new() } else { commits },
     items: None,
     unmerged_activity: None,
+    indexes: None,
+    warnings: util::warnings_since(warn_mark),
   };
 
   Ok(report)
 }
 
+/// Serialize `report`; with --summary-only the (already emptied) commits array is dropped entirely.
+fn report_json(report: &SimpleReport, params: &ReportParams) -> Result<serde_json::Value> {
+  let mut v = serde_json::to_value(report)?;
+
+  if params.summary_only {
+    if let Some(obj) = v.as_object_mut() {
+      obj.remove("commits");
+    }
+  }
+
+  if let (Some(fields), Some(commits)) = (&params.fields, v.get_mut("commits").and_then(|c| c.as_array_mut())) {
+    commits.iter_mut().for_each(|c| fields.apply(c));
+  }
+
+  Ok(v)
+}
+
 /// Unified entry: returns a report JSON; when split_apart, writes shards and returns a pointer {dir,file}.
 pub fn run_report(params: &ReportParams) -> Result<serde_json::Value> {
   if !params.split_apart {
     let r = run_simple(params)?;
-    return Ok(serde_json::to_value(&r)?);
+    crate::exit_summary::record_range(r.summary.count);
+    return report_json(&r, params);
   }
   let label = params.label.clone().unwrap_or_else(|| "window".to_string());
   let base_dir = if let Some(dir) = &params.split_out {
@@ -218,15 +733,21 @@ pub fn run_report(params: &ReportParams) -> Result<serde_json::Value> {
       .to_string_lossy()
       .to_string()
   };
-  let subdir = Path::new(&base_dir).join(&label);
-  std::fs::create_dir_all(&subdir)?;
+  let base_path = Path::new(&base_dir);
+  let subdir = base_path.join(params.layout.range_dir(&label));
+  std::fs::create_dir_all(base_path)?;
+  range_started(params);
+  let github_before = ghapi::request_stats();
+  let warn_mark = util::warning_count();
 
   // Process the primary commit range: write shards and collect items/summary/authors/commits
-  let (commits, items, summary, authors) = process_commit_range(params, &subdir, &label)?;
+  let (shas, sample_plan) = range_shas(params)?;
+  let (commits, items, summary, authors, author_stats, patch_budget) =
+    process_commit_range(params, &shas, base_path, &label)?;
 
   // Optionally process unmerged branches
-  let _unmerged_activity = if params.include_unmerged {
-    Some(process_unmerged_branches(params, &subdir, &label)?)
+  let unmerged_activity = if params.include_unmerged {
+    Some(process_unmerged_branches(params, base_path, &label)?)
   } else {
     None
   };
@@ -237,6 +758,7 @@ pub fn run_report(params: &ReportParams) -> Result<serde_json::Value> {
     label: label.clone(),
     start: params.since.clone(),
     end: params.until.clone(),
+    refs: params.refs.clone(),
   };
   let report_options = build_report_options(params);
   let summary = ReportSummary {
@@ -245,59 +767,285 @@ pub fn run_report(params: &ReportParams) -> Result<serde_json::Value> {
     count: commits.len(),
     report_options,
     changes: summary,
+    provenance: params.provenance.clone(),
+    services: params.service_map.as_ref().map(|_| summarize_services(&commits)),
+    github: github_repo_info(&params.repo),
+    environments: summarize_environments(&commits),
+    patch_budget,
+    automation: summarize_automation(&commits),
+    repo_policy: fetch_repo_policy(params),
+    collaboration: params
+      .collaboration
+      .then(|| crate::collaboration::build_collaboration(&commits)),
+    boundary_commits: boundary_commits(params, &commits),
+    conflict_hotspots: conflict_hotspots(params)?,
+    dependency_changes: params
+      .dependency_changes
+      .then(|| crate::dependencies::summarize_dependency_changes(&params.repo, &commits)),
+    sample: sample_plan.map(|plan| crate::sampling::estimate(&plan, &commits)),
+    review_pairs: params
+      .github_prs
+      .then(|| crate::review_pairs::build_review_pairs(&commits))
+      .flatten(),
+    new_contributors: new_contributors(params, &commits)?,
+    estimated_minutes: estimated_minutes(params, &commits),
+    rework_cycles: params
+      .github_prs
+      .then(|| crate::rework::build_rework_summary(&commits))
+      .flatten(),
+    maintainer_activity: maintainer_activity(params, &commits)?,
+    releases: release_summary(params, &release_counts(&commits))?,
+    warnings: enrichment_warnings(&github_before),
+  };
+  let indexes = if params.side_indexes && !params.summary_only {
+    Some(crate::side_indexes::write_side_indexes(
+      &subdir, &label, &commits, &items,
+    )?)
+  } else {
+    None
   };
+
+  range_finished(params, summary.count);
   let report = SimpleReport {
     summary,
     authors,
-    commits,
-    items: Some(items),
+    committers: params
+      .committers
+      .then(|| crate::maintainers::committer_counts(&commits)),
+    author_stats,
+    work_items: params
+      .work_items
+      .map(|gap| crate::work_items::build_work_items(&commits, gap)),
+    calendar: params
+      .calendar
+      .then(|| crate::calendar::build_calendar(&commits, &params.tz)),
+    commits: if params.summary_only { Vec::new() } else { commits },
+    items: (!params.summary_only).then_some(items),
+    unmerged_activity,
+    indexes,
+    warnings: util::warnings_since(warn_mark),
+  };
+
+  let report_file = params.layout.report_file(&label);
+  let json = report_json(&report, params)?;
+  let report_path = base_path.join(&report_file);
+  crate::validate_output::check(Artifact::Report, &report_path, &json)?;
+  util::write_output(&report_path, &serde_json::to_vec_pretty(&json)?)?;
+  crate::exit_summary::record_range(report.summary.count);
+
+  Ok(serde_json::json!({ "dir": base_dir, "file": report_file }))
+}
+
+/// Commits per `--format ndjson` processing batch: enough to keep --jobs busy, few enough to bound memory.
+const NDJSON_BATCH: usize = 256;
+
+/// `--format ndjson`: write one commit object per line, then one summary line (`summary`, `authors`, `author_stats`,
+/// and `calendar` with --calendar).
+///
+/// With --releases, commits carry `release` and the summary lists `releases`, counted as commits stream past.
+///
+/// Commits are processed in batches and dropped once written, so memory stays flat however large the window.
+/// Totals, authors, author_stats, and estimated minutes accumulate as commits stream past; summary sections that
+/// need the whole commit list at once (services, environments, automation, boundary commits, PR estimates) are left
+/// out. Returns the number of commits written.
+pub fn run_ndjson(params: &ReportParams, out: &mut dyn std::io::Write) -> Result<usize> {
+  range_started(params);
+  let github_before = ghapi::request_stats();
+  let warn_mark = util::warning_count();
+  let (shas, _) = range_shas(params)?;
+  let context = build_process_context(params);
+  let refs = if params.all_refs {
+    Some(gitio::refs_containing(
+      &params.repo,
+      &params.since,
+      &params.until,
+      params.include_merges,
+    )?)
+  } else {
+    None
+  };
+
+  let mut authors: BTreeMap<String, i64> = BTreeMap::new();
+  let mut author_stats = AuthorStatsAccumulator::new(&params.since, &params.until);
+  let mut changeset = ChangeSet {
+    additions: 0,
+    deletions: 0,
+    files_touched: 0,
+    changes_by_language: BTreeMap::new(),
+  };
+  let mut files_touched: HashSet<String> = HashSet::new();
+  let mut minutes = 0.0_f64;
+  let mut calendar = params.calendar.then(|| CalendarAccumulator::new(&params.tz));
+  let mut release_counts: BTreeMap<String, usize> = BTreeMap::new();
+
+  let progress = Progress::new(params.label.as_deref().unwrap_or("window"), shas.len());
+
+  for batch in shas.chunks(NDJSON_BATCH) {
+    let mut processed = process_commits(
+      batch,
+      &context,
+      params.jobs,
+      params.commit_cache.as_ref(),
+      progress.as_ref(),
+    )?;
+    attach_merge_groups(&mut processed, &context, params)?;
+    if params.releases {
+      crate::releases::tag_commits(&params.repo, &mut processed)?;
+      crate::releases::count_by_tag(&processed, &mut release_counts);
+    }
+
+    for mut commit in processed {
+      if let Some(refs) = &refs {
+        commit.refs = refs.get(&commit.sha).cloned();
+      }
+
+      if let Some(patches_dir_str) = &params.save_patches_dir {
+        crate::commit::save_patch_to_disk(
+          &mut commit,
+          &params.repo,
+          Path::new(patches_dir_str),
+          params.redactor.as_ref(),
+          &params.paths,
+        )?;
+      }
+
+      *authors.entry(commit.author.key()).or_insert(0) += 1;
+      author_stats.add(&commit);
+      if let Some(cal) = calendar.as_mut() {
+        cal.add(&commit);
+      }
+      accumulate_summary_and_files(&commit, &mut changeset, &mut files_touched);
+      minutes += commit.estimated_minutes.unwrap_or(0.0);
+
+      let mut line = serde_json::to_value(&commit)?;
+      if let Some(fields) = &params.fields {
+        fields.apply(&mut line);
+      }
+      serde_json::to_writer(&mut *out, &line)?;
+      out.write_all(b"\n")?;
+    }
+  }
+  drop(progress);
+
+  changeset.files_touched = files_touched.len();
+
+  let summary = ReportSummary {
+    repo: params.repo.clone(),
+    range: RangeInfo {
+      label: params.label.clone().unwrap_or_else(|| "window".into()),
+      start: params.since.clone(),
+      end: params.until.clone(),
+      refs: params.refs.clone(),
+    },
+    count: shas.len(),
+    report_options: build_report_options(params),
+    changes: changeset,
+    provenance: params.provenance.clone(),
+    services: None,
+    github: github_repo_info(&params.repo),
+    environments: None,
+    patch_budget: None,
+    automation: None,
+    repo_policy: fetch_repo_policy(params),
+    collaboration: None,
+    boundary_commits: None,
+    conflict_hotspots: conflict_hotspots(params)?,
+    dependency_changes: None,
+    sample: None,
+    review_pairs: None,
+    new_contributors: None,
+    estimated_minutes: params.estimate_effort.then(|| (minutes * 10.0).round() / 10.0),
+    rework_cycles: None,
+    maintainer_activity: None,
+    releases: release_summary(params, &release_counts)?,
+    warnings: enrichment_warnings(&github_before),
+  };
+  range_finished(params, summary.count);
+  let report = SimpleReport {
+    summary,
+    authors,
+    committers: None,
+    author_stats: author_stats.finish(),
+    work_items: None,
+    calendar: calendar.map(CalendarAccumulator::finish),
+    commits: Vec::new(),
+    items: None,
     unmerged_activity: None,
+    indexes: None,
+    warnings: util::warnings_since(warn_mark),
   };
 
-  let report_path = Path::new(&base_dir).join(format!("report-{}.json", label));
-  std::fs::write(&report_path, serde_json::to_vec_pretty(&report)?)?;
+  let mut last = serde_json::to_value(&report)?;
+  if let Some(obj) = last.as_object_mut() {
+    obj.remove("commits");
+  }
+  serde_json::to_writer(&mut *out, &last)?;
+  out.write_all(b"\n")?;
+  out.flush()?;
 
-  Ok(serde_json::json!({ "dir": base_dir, "file": format!("report-{}.json", label) }))
+  Ok(shas.len())
 }
 
 // --- `run_full` Sub-logic ---
 
 /// Helper for `run_full` to process the main list of commits.
-fn process_commit_range(params: &ReportParams, subdir: &Path, label: &str) -> Result<ProcessRangeOut> {
-  let shas = gitio::rev_list(&params.repo, &params.since, &params.until, params.include_merges)?;
+fn process_commit_range(
+  params: &ReportParams,
+  shas: &[String],
+  base_dir: &Path,
+  label: &str,
+) -> Result<ProcessRangeOut> {
   let context = build_process_context(params);
+  let range_dir = params.layout.range_dir(label);
+  let subdir = base_dir.join(&range_dir);
+
+  let progress = Progress::new(label, shas.len());
+  let (processed, patch_budget) = process_with_patch_budget(shas, &context, params, progress.as_ref())?;
+  drop(progress);
 
-  let mut commits: Vec<Commit> = Vec::with_capacity(shas.len());
-  let mut items = Vec::with_capacity(shas.len());
+  let mut commits: Vec<Commit> = Vec::with_capacity(processed.len());
+  let mut items = Vec::with_capacity(processed.len());
   let mut authors: BTreeMap<String, i64> = BTreeMap::new();
+  let mut author_stats = AuthorStatsAccumulator::new(&params.since, &params.until);
   let mut summary = ChangeSet {
     additions: 0,
     deletions: 0,
     files_touched: 0,
+    changes_by_language: BTreeMap::new(),
   };
   let mut files_touched: HashSet<String> = HashSet::new();
 
-  for sha in shas.iter() {
-    let mut commit = process_commit(sha, &context)?;
-
+  for mut commit in processed {
     if params.save_patches_dir.is_some() {
       let patch_dir = subdir.join("patches");
-      crate::commit::save_patch_to_disk(&mut commit, &params.repo, &patch_dir)?;
+      crate::commit::save_patch_to_disk(
+        &mut commit,
+        &params.repo,
+        &patch_dir,
+        params.redactor.as_ref(),
+        &params.paths,
+      )?;
     }
 
-    // Write commit shard to disk
-    let fname = write_commit_shard(subdir, &commit, &params.tz)?;
+    // --summary-only: no shards, so nothing for items or snapshot files to point at
+    if !params.summary_only {
+      crate::snapshots::write_snapshot_files(&mut commit, &params.repo, &subdir, &range_dir)?;
 
-    // Accumulate manifest data
-    let item = ManifestItem {
-      sha: commit.sha.clone(),
-      file: Path::new(label).join(&fname).to_string_lossy().to_string(),
-      subject: commit.subject.clone(),
-    };
+      // Write commit shard to disk
+      let file = write_commit_shard(base_dir, params, label, None, &commit)?;
+
+      // Accumulate manifest data
+      let item = ManifestItem {
+        sha: commit.sha.clone(),
+        file,
+        subject: commit.subject.clone(),
+      };
 
-    items.push(item);
-    let author_key = author_key_for(&commit.author);
+      items.push(item);
+    }
+    let author_key = commit.author.key();
     *authors.entry(author_key).or_insert(0) += 1;
+    author_stats.add(&commit);
     accumulate_summary_and_files(&commit, &mut summary, &mut files_touched);
 
     commits.push(commit);
@@ -310,7 +1058,115 @@ fn process_commit_range(params: &ReportParams, subdir: &Path, label: &str) -> Re
 
   summary.files_touched = files_touched.len();
 
-  Ok((commits, items, summary, authors))
+  Ok((commits, items, summary, authors, author_stats.finish(), patch_budget))
+}
+
+/// Process every commit in the range, annotate `refs` with --all-refs, then apply `--patch-budget-bytes` across
+/// them before anything is written.
+fn process_with_patch_budget(
+  shas: &[String],
+  context: &ProcessContext,
+  params: &ReportParams,
+  progress: Option<&Progress>,
+) -> Result<(Vec<Commit>, Option<PatchBudgetSummary>)> {
+  let mut commits = process_commits(shas, context, params.jobs, params.commit_cache.as_ref(), progress)?;
+  tracing::debug!(target: "gar", commits = commits.len(), "commits processed");
+  attach_merge_groups(&mut commits, context, params)?;
+
+  if params.releases {
+    crate::releases::tag_commits(&params.repo, &mut commits)?;
+  }
+
+  if params.committers {
+    crate::maintainers::mark_authored_by_committer(&mut commits);
+  }
+
+  if params.all_refs {
+    let refs = gitio::refs_containing(&params.repo, &params.since, &params.until, params.include_merges)?;
+
+    for c in commits.iter_mut() {
+      c.refs = refs.get(&c.sha).cloned();
+    }
+  }
+
+  let patch_budget = params
+    .patch_budget_bytes
+    .map(|budget| crate::patch_budget::apply_patch_budget(&mut commits, budget));
+
+  Ok((commits, patch_budget))
+}
+
+/// Run `process_commit` for every SHA on up to `jobs` threads (0 = one per CPU); results keep the order of `shas`.
+/// Each finished commit ticks `progress`, when there is one.
+fn process_commits(
+  shas: &[String],
+  context: &ProcessContext,
+  jobs: usize,
+  cache: Option<&CommitCache>,
+  progress: Option<&Progress>,
+) -> Result<Vec<Commit>> {
+  use rayon::prelude::*;
+
+  let process = |sha: &String| {
+    let commit = process_commit_cached(sha, context, cache);
+    crate::progress::tick(progress, sha);
+
+    commit
+  };
+
+  if jobs == 1 || shas.len() < 2 {
+    return shas.iter().map(process).collect();
+  }
+
+  let pool = rayon::ThreadPoolBuilder::new()
+    .num_threads(jobs)
+    .build()
+    .context("starting the --jobs thread pool")?;
+
+  let parent = crate::telemetry::current();
+
+  pool.install(|| {
+    shas
+      .par_iter()
+      .map(|sha| {
+        let _attached = parent.attach();
+        process(sha)
+      })
+      .collect()
+  })
+}
+
+/// With `--first-parent`, process the commits each merge brought in and nest them under its `merge_group`.
+fn attach_merge_groups(commits: &mut [Commit], context: &ProcessContext, params: &ReportParams) -> Result<()> {
+  if !params.first_parent {
+    return Ok(());
+  }
+
+  for commit in commits.iter_mut().filter(|c| crate::merge_groups::is_merge(c)) {
+    let shas = gitio::merge_group_shas(&params.repo, &commit.sha, params.include_merges, &params.paths)
+      .with_context(|| format!("listing the commits merged by {}", commit.short_sha))?;
+    let members = process_commits(&shas, context, params.jobs, params.commit_cache.as_ref(), None)?;
+
+    commit.merge_group = crate::merge_groups::build_merge_group(members);
+  }
+
+  Ok(())
+}
+
+/// `process_commit`, served from and stored into the `--cache` commit cache when one is active.
+fn process_commit_cached(sha: &str, context: &ProcessContext, cache: Option<&CommitCache>) -> Result<Commit> {
+  if let Some(commit) = cache.and_then(|c| c.get(sha)) {
+    return Ok(commit);
+  }
+
+  let commit = process_commit(sha, context)?;
+  tracing::trace!(target: "gar", sha = %commit.short_sha, files = commit.files.len(), "commit processed");
+
+  if let Some(c) = cache {
+    c.put(&commit);
+  }
+
+  Ok(commit)
 }
 
 /// Compute and attach PR-level effort estimates to each commit's PRs using the full range context.
@@ -333,13 +1189,10 @@ fn attach_pr_estimates(commits: &mut [Commit]) {
 }
 
 /// Helper for `run_full` to process unmerged branches.
-fn process_unmerged_branches(params: &ReportParams, subdir: &Path, label: &str) -> Result<UnmergedActivity> {
-  // Collect list of branches to scan (excluding current)
-  let current_branch = gitio::current_branch(&params.repo)?;
-  let branches: Vec<String> = gitio::list_local_branches(&params.repo)?
-    .into_iter()
-    .filter(|b| Some(b.as_str()) != current_branch.as_deref())
-    .collect();
+fn process_unmerged_branches(params: &ReportParams, base_dir: &Path, label: &str) -> Result<UnmergedActivity> {
+  // Collect list of branches to scan (excluding current, or the base itself with --unmerged-base)
+  let base = unmerged_base(params)?;
+  let branches = gitio::unmerged_candidates(&params.repo, params.unmerged_base.as_deref())?;
 
   let context = ProcessContext {
     repo: &params.repo,
@@ -347,36 +1200,72 @@ fn process_unmerged_branches(params: &ReportParams, subdir: &Path, label: &str)
     github_prs: params.github_prs,
     include_patch: params.include_patch,
     max_patch_bytes: params.max_patch_bytes,
+    max_files_per_commit: params.max_files_per_commit,
     estimate_effort: params.estimate_effort,
+    no_blob_stats: params.no_blob_stats,
+    service_map: params.service_map.as_ref(),
+    identity_map: params.identity_map.as_ref(),
+    include_pr_patch: params.include_pr_patch,
+    pr_files: params.pr_files,
+    redactor: params.redactor.as_ref(),
+    env_trailers: &params.env_trailers,
+    automation: params.automation.as_ref(),
+    issue_keys: params.issue_keys.as_ref(),
+    snapshot_paths: &params.snapshot_paths,
+    snapshot_max_bytes: params.snapshot_max_bytes,
+    no_bodies: params.no_bodies,
+    paths: &params.paths,
   };
 
+  let now = util::effective_now(params.now_local);
+
   let mut unmerged_activity = UnmergedActivity {
+    base: params.unmerged_base.clone(),
     branches_scanned: branches.len(),
     total_unmerged_commits: 0,
     branches: Vec::new(),
+    stale_after_days: params.stale_days,
+    stale_branches: Vec::new(),
   };
 
   for branch in branches {
-    let unmerged_shas = collect_unmerged_shas(params, &branch)?;
+    let merged_into_head = gitio::branch_merged_into(&params.repo, &branch, base)?;
+    let tip_epoch = gitio::branch_tip_epoch(&params.repo, &branch)?;
+    let last_commit_at = tip_epoch.map(|ts| util::iso_in_tz(ts, &params.tz));
+    let last_commit_age_days = tip_epoch.map(|ts| age_in_days(now.timestamp(), ts));
+    let stale = is_stale_branch(merged_into_head, last_commit_age_days, params.stale_days);
+
+    if stale {
+      let stale_entry = StaleBranch {
+        name: branch.clone(),
+        last_commit_at: last_commit_at.clone(),
+        last_commit_age_days,
+      };
+
+      unmerged_activity.stale_branches.push(stale_entry);
+    }
+
+    let unmerged_shas = collect_unmerged_shas(params, &branch, base)?;
 
     if unmerged_shas.is_empty() {
       continue;
     }
 
-    let branch_dir_name = branch.replace('/', "__");
-    let branch_dir = subdir.join("unmerged").join(&branch_dir_name);
-
-    let branch_items = write_branch_shards(&context, params, label, &branch_dir_name, &branch_dir, &unmerged_shas)?;
+    let (branch_items, authors) = write_branch_shards(&context, params, base_dir, label, &branch, &unmerged_shas)?;
 
-    let (behind, ahead) = gitio::branch_ahead_behind(&params.repo, &branch)?;
-    unmerged_activity.total_unmerged_commits += branch_items.len();
+    let (behind, ahead) = gitio::branch_ahead_behind(&params.repo, base, &branch)?;
+    unmerged_activity.total_unmerged_commits += unmerged_shas.len();
 
     let branch_entry = BranchItems {
       name: branch.clone(),
-      merged_into_head: gitio::branch_merged_into_head(&params.repo, &branch)?,
+      merged_into_head,
       ahead_of_head: ahead,
       behind_head: behind,
       items: branch_items,
+      authors,
+      last_commit_at,
+      last_commit_age_days,
+      stale,
     };
 
     unmerged_activity.branches.push(branch_entry);
@@ -387,58 +1276,92 @@ fn process_unmerged_branches(params: &ReportParams, subdir: &Path, label: &str)
 
 // --- Extracted Helpers (Unmerged Branches) ---
 
-/// Collect SHAs for commits on `branch` not yet merged into `HEAD` within the configured time range.
-fn collect_unmerged_shas(params: &ReportParams, branch: &str) -> anyhow::Result<Vec<String>> {
+/// The ref unmerged branches are compared against: `--unmerged-base` (verified to resolve) or HEAD.
+fn unmerged_base(params: &ReportParams) -> anyhow::Result<&str> {
+  let Some(base) = params.unmerged_base.as_deref() else {
+    return Ok("HEAD");
+  };
+
+  gitio::resolve_commit(&params.repo, base).context("--unmerged-base")?;
+
+  Ok(base)
+}
+
+/// Collect SHAs for commits on `branch` not yet merged into `base` within the configured time range.
+fn collect_unmerged_shas(params: &ReportParams, branch: &str, base: &str) -> anyhow::Result<Vec<String>> {
   let shas = gitio::unmerged_commits_in_range(
     &params.repo,
     branch,
+    base,
     &params.since,
     &params.until,
     params.include_merges,
+    &params.paths,
   )?;
 
   Ok(shas)
 }
 
-/// Process `unmerged_shas` for a branch: build commits, optionally save patches, write shards, and return manifest items.
+/// Whole days elapsed between `tip_epoch` and `now_epoch` (never negative).
+fn age_in_days(now_epoch: i64, tip_epoch: i64) -> i64 {
+  (now_epoch - tip_epoch).max(0) / 86_400
+}
+
+/// A branch is stale when it is not merged into the unmerged base and its tip is older than `stale_days`.
+fn is_stale_branch(merged_into_head: Option<bool>, age_days: Option<i64>, stale_days: i64) -> bool {
+  let unmerged = merged_into_head != Some(true);
+  let old = age_days.is_some_and(|d| d > stale_days);
+
+  unmerged && old
+}
+
+/// Process `unmerged_shas` for a branch: build commits, optionally save patches, write shards, and return manifest
+/// items plus per-author commit counts (items stay empty with --summary-only).
 fn write_branch_shards(
   context: &ProcessContext,
   params: &ReportParams,
+  base_dir: &Path,
   label: &str,
-  branch_dir_name: &str,
-  branch_dir: &Path,
+  branch: &str,
   unmerged_shas: &[String],
-) -> anyhow::Result<Vec<ManifestItem>> {
+) -> anyhow::Result<(Vec<ManifestItem>, BTreeMap<String, i64>)> {
+  let rel_dir = params.layout.branch_dir(label, branch);
+  let branch_dir = base_dir.join(&rel_dir);
   let mut branch_items = Vec::with_capacity(unmerged_shas.len());
+  let mut authors: BTreeMap<String, i64> = BTreeMap::new();
 
-  for sha in unmerged_shas.iter() {
-    let mut commit = process_commit(sha, context)?;
-
+  for mut commit in process_commits(unmerged_shas, context, params.jobs, params.commit_cache.as_ref(), None)? {
     if params.save_patches_dir.is_some() {
       let patch_dir = branch_dir.join("patches");
-      crate::commit::save_patch_to_disk(&mut commit, &params.repo, &patch_dir)?;
+      crate::commit::save_patch_to_disk(
+        &mut commit,
+        &params.repo,
+        &patch_dir,
+        params.redactor.as_ref(),
+        &params.paths,
+      )?;
     }
 
-    let fname = write_commit_shard(branch_dir, &commit, &params.tz)?;
-
-    let item = ManifestItem {
-      sha: commit.sha.clone(),
-      file: Path::new(label)
-        .join("unmerged")
-        .join(branch_dir_name)
-        .join(fname)
-        .to_string_lossy()
-        .to_string(),
-      subject: commit.subject.clone(),
-    };
+    if !params.summary_only {
+      crate::snapshots::write_snapshot_files(&mut commit, &params.repo, &branch_dir, &rel_dir)?;
+
+      let file = write_commit_shard(base_dir, params, label, Some(branch), &commit)?;
 
-    branch_items.push(item);
+      let item = ManifestItem {
+        sha: commit.sha.clone(),
+        file,
+        subject: commit.subject.clone(),
+      };
+
+      branch_items.push(item);
+    }
+    *authors.entry(commit.author.key()).or_insert(0) += 1;
   }
 
-  Ok(branch_items)
+  Ok((branch_items, authors))
 }
 
-// Shard filename helper lives in util; imported above.
+// Shard paths come from crate::layout::OutputLayout.
 
 // --- Tests ---
 
@@ -483,6 +1406,7 @@ mod tests {
       include_merges: true,
       include_patch: true,
       max_patch_bytes: 16,
+      max_files_per_commit: None,
       tz: "utc".into(),
       split_apart: false,
       split_out: None,
@@ -491,6 +1415,42 @@ mod tests {
       github_prs: true,
       now_local: None,
       estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
     };
     let report = run_simple(&params).unwrap();
     assert!(report.summary.count >= 1);
@@ -511,6 +1471,7 @@ mod tests {
       include_merges: false,
       include_patch: false,
       max_patch_bytes: 0,
+      max_files_per_commit: None,
       tz: "local".into(),
       split_apart: false,
       split_out: None,
@@ -519,6 +1480,42 @@ mod tests {
       github_prs: false,
       now_local: None,
       estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
     };
     let report = run_simple(&params).unwrap();
     assert!(report.summary.count >= 1);
@@ -537,6 +1534,7 @@ mod tests {
       include_merges: true,
       include_patch: false,
       max_patch_bytes: 0,
+      max_files_per_commit: None,
       tz: "local".into(),
       split_apart: true,
       split_out: Some(tmpdir.path().to_string_lossy().to_string()),
@@ -545,6 +1543,42 @@ mod tests {
       github_prs: false,
       now_local: None,
       estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
     };
     let out = run_report(&params).unwrap();
     let dir = out.get("dir").unwrap().as_str().unwrap();
@@ -553,6 +1587,76 @@ mod tests {
     assert!(path.exists());
   }
 
+  #[test]
+  fn summary_only_split_writes_no_shards_and_drops_commits() {
+    let repo = fixture_repo();
+    let tmpdir = tempfile::TempDir::new().unwrap();
+    let params = ReportParams {
+      repo,
+      label: Some("window".into()),
+      since: "2025-08-01".into(),
+      until: "2025-09-01".into(),
+      include_merges: true,
+      include_patch: false,
+      max_patch_bytes: 0,
+      max_files_per_commit: None,
+      tz: "utc".into(),
+      split_apart: true,
+      split_out: Some(tmpdir.path().to_string_lossy().to_string()),
+      include_unmerged: false,
+      save_patches_dir: None,
+      github_prs: false,
+      now_local: None,
+      estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec![],
+      patch_budget_bytes: None,
+      side_indexes: true,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: true,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
+    };
+    let out = run_report(&params).unwrap();
+    let v: serde_json::Value =
+      serde_json::from_slice(&std::fs::read(tmpdir.path().join("report-window.json")).unwrap()).unwrap();
+
+    assert_eq!(out["file"], "report-window.json");
+    assert!(v["summary"]["count"].as_u64().unwrap() > 0);
+    assert_eq!(v["summary"]["report_options"]["summary_only"], true);
+    assert!(v["authors"].as_object().is_some_and(|a| !a.is_empty()));
+    assert!(v.get("commits").is_none() && v.get("items").is_none() && v.get("indexes").is_none());
+    assert!(!tmpdir.path().join("window").exists());
+  }
+
   #[test]
   fn run_split_embeds_patches() {
     let repo = fixture_repo();
@@ -565,6 +1669,7 @@ mod tests {
       include_merges: true,
       include_patch: true,
       max_patch_bytes: 32,
+      max_files_per_commit: None,
       tz: "utc".into(),
       split_apart: true,
       split_out: Some(tmpdir.path().to_string_lossy().to_string()),
@@ -573,6 +1678,42 @@ mod tests {
       github_prs: true,
       now_local: None,
       estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
     };
     let out = run_report(&params).unwrap();
     let dir = out.get("dir").unwrap().as_str().unwrap();
@@ -593,6 +1734,7 @@ mod tests {
       include_merges: false,
       include_patch: false,
       max_patch_bytes: 0,
+      max_files_per_commit: None,
       tz: "utc".into(),
       split_apart: true,
       split_out: Some(tmpdir.path().to_string_lossy().to_string()),
@@ -601,6 +1743,42 @@ mod tests {
       github_prs: false,
       now_local: None,
       estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
     };
     let out = run_report(&params).unwrap();
     let dir = out.get("dir").unwrap().as_str().unwrap();
@@ -643,6 +1821,7 @@ mod tests {
       include_merges: true,
       include_patch: false,
       max_patch_bytes: 0,
+      max_files_per_commit: None,
       tz: "utc".into(),
       split_apart: true,
       split_out: Some(tmpdir.path().to_string_lossy().to_string()),
@@ -651,6 +1830,42 @@ mod tests {
       github_prs: false,
       now_local: None,
       estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
     };
     let out = run_report(&params).unwrap();
     let dir = out.get("dir").unwrap().as_str().unwrap();
@@ -664,6 +1879,97 @@ mod tests {
     assert!(v.get("commits").is_some());
   }
 
+  #[test]
+  fn no_bodies_empties_body_and_keeps_subject() {
+    let td = tempfile::TempDir::new().unwrap();
+    let repo = td.path();
+    let sh = |args: &[&str]| {
+      let st = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .status()
+        .unwrap();
+      assert!(st.success(), "git {:?} failed", args);
+    };
+    sh(&["init", "-q", "-b", "main"]);
+    sh(&["config", "user.name", "Fixture Bot"]);
+    sh(&["config", "user.email", "fixture@example.com"]);
+    sh(&["config", "commit.gpgsign", "false"]);
+    std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+    sh(&["add", "."]);
+    sh(&[
+      "commit",
+      "-q",
+      "-m",
+      "Ship it",
+      "-m",
+      "Internal ticket SECRET-1\n\nDeployed-To: prod",
+    ]);
+
+    let params = ReportParams {
+      repo: repo.to_string_lossy().to_string(),
+      label: None,
+      since: "1970-01-01".into(),
+      until: "2100-01-01".into(),
+      include_merges: true,
+      include_patch: false,
+      max_patch_bytes: 0,
+      max_files_per_commit: None,
+      tz: "utc".into(),
+      split_apart: false,
+      split_out: None,
+      include_unmerged: false,
+      save_patches_dir: None,
+      github_prs: false,
+      now_local: None,
+      estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: true,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
+    };
+    let report = run_simple(&params).unwrap();
+    let c = &report.commits[0];
+
+    assert_eq!(c.subject, "Ship it");
+    assert!(c.body.is_empty());
+    assert!(c.body_lines.is_none());
+    // trailer-derived fields are computed before the body is dropped
+    assert_eq!(c.environments, Some(vec!["prod".to_string()]));
+  }
+
   // clip_patch tests moved to util
 
   #[test]
@@ -680,5 +1986,205 @@ mod tests {
     assert_eq!(entries[0].file, "file.txt");
   }
 
+  #[test]
+  fn unmerged_activity_reports_authors_and_stale_branches() {
+    let td = tempfile::TempDir::new().unwrap();
+    let repo = td.path();
+    let sh = |args: &[&str], date: &str| {
+      let st = std::process::Command::new("git")
+        .args(args)
+        .current_dir(repo)
+        .env("GIT_AUTHOR_DATE", date)
+        .env("GIT_COMMITTER_DATE", date)
+        .status()
+        .unwrap();
+      assert!(st.success(), "git {:?} failed", args);
+    };
+    let d0 = "2025-06-01T12:00:00Z";
+    sh(&["init", "-q", "-b", "main"], d0);
+    sh(&["config", "user.name", "Fixture Bot"], d0);
+    sh(&["config", "user.email", "fixture@example.com"], d0);
+    sh(&["config", "commit.gpgsign", "false"], d0);
+    std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+    sh(&["add", "."], d0);
+    sh(&["commit", "-q", "-m", "A"], d0);
+    sh(&["checkout", "-q", "-b", "feature/old"], d0);
+    std::fs::write(repo.join("b.txt"), "b\n").unwrap();
+    sh(&["add", "."], d0);
+    sh(&["commit", "-q", "-m", "B"], "2025-06-02T12:00:00Z");
+    sh(&["checkout", "-q", "main"], d0);
+
+    let tmpdir = tempfile::TempDir::new().unwrap();
+    let now = chrono::DateTime::parse_from_rfc3339("2025-08-15T12:00:00Z")
+      .unwrap()
+      .with_timezone(&Local);
+    let params = ReportParams {
+      repo: repo.to_string_lossy().to_string(),
+      label: Some("window".into()),
+      since: "1970-01-01".into(),
+      until: "2100-01-01".into(),
+      include_merges: true,
+      include_patch: false,
+      max_patch_bytes: 0,
+      max_files_per_commit: None,
+      tz: "utc".into(),
+      split_apart: true,
+      split_out: Some(tmpdir.path().to_string_lossy().to_string()),
+      include_unmerged: true,
+      save_patches_dir: None,
+      github_prs: false,
+      now_local: Some(now),
+      estimate_effort: false,
+      provenance: None,
+      no_blob_stats: false,
+      stale_days: 30,
+      layout: Default::default(),
+      unmerged_base: None,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      patch_budget_bytes: None,
+      side_indexes: false,
+      automation: None,
+      issue_keys: None,
+      repo_policy: false,
+      collaboration: false,
+      snapshot_paths: vec![],
+      snapshot_max_bytes: 0,
+      conflict_hotspots: false,
+      dependency_changes: false,
+      new_contributors: false,
+      no_bodies: false,
+      summary_only: false,
+      work_items: None,
+      calendar: false,
+      releases: false,
+      fields: None,
+      all_refs: false,
+      first_parent: false,
+      committers: false,
+      refs: vec![],
+      paths: vec![],
+      sample: None,
+      jobs: 0,
+      commit_cache: None,
+    };
+    let out = run_report(&params).unwrap();
+    let dir = out.get("dir").unwrap().as_str().unwrap();
+    let file = out.get("file").unwrap().as_str().unwrap();
+    let data = std::fs::read(std::path::Path::new(dir).join(file)).unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&data).unwrap();
+    let ua = v.get("unmerged_activity").expect("unmerged_activity present");
+    let branch = &ua["branches"][0];
+    assert_eq!(branch["name"], "feature/old");
+    assert_eq!(branch["authors"]["Fixture Bot <fixture@example.com>"], 1);
+    assert_eq!(branch["last_commit_age_days"], 74);
+    assert_eq!(branch["stale"], true);
+    assert_eq!(ua["stale_after_days"], 30);
+    assert_eq!(ua["stale_branches"][0]["name"], "feature/old");
+  }
+
+  #[test]
+  fn environments_group_by_trailer_and_iso_week() {
+    let names = vec!["Deployed-To".to_string(), "Environment".to_string()];
+    let body = "Ship it\n\ndeployed-to: staging, production\nEnvironment: staging";
+    assert_eq!(
+      crate::commit::environments_from_trailers(body, &names),
+      Some(vec!["staging".to_string(), "production".to_string()])
+    );
+    assert_eq!(crate::commit::environments_from_trailers("Ship it", &names), None);
+
+    let commit = |local: &str, envs: &[&str]| {
+      let mut c = Commit::fixture("abc").at(0, local);
+      c.environments = (!envs.is_empty()).then(|| envs.iter().map(|e| e.to_string()).collect());
+      c
+    };
+    let commits = vec![
+      commit("2025-08-11T09:00:00+00:00", &["staging", "production"]),
+      commit("2025-08-13T09:00:00+00:00", &["staging"]),
+      commit("2025-08-20T09:00:00+00:00", &["production"]),
+      commit("2025-08-21T09:00:00+00:00", &[]),
+    ];
+
+    let envs = summarize_environments(&commits).unwrap();
+
+    assert_eq!(envs["staging"].commits, 2);
+    assert_eq!(envs["staging"].weeks["2025-W33"], 2);
+    assert_eq!(envs["production"].weeks["2025-W34"], 1);
+    assert!(summarize_environments(&commits[3..]).is_none());
+  }
+
+  #[test]
+  fn automation_split_separates_bot_activity() {
+    let commit = |name: &str, automated: bool| {
+      let mut c = Commit::fixture("abc")
+        .with_author(name, "x@x")
+        .with_files(&[("a.txt", 3, 1)]);
+      c.automated = automated.then_some(true);
+      c
+    };
+
+    assert!(summarize_automation(&[commit("Jane", false)]).is_none());
+
+    let split = summarize_automation(&[
+      commit("Jane", false),
+      commit("dependabot[bot]", true),
+      commit("Jane", false),
+    ])
+    .unwrap();
+
+    assert_eq!(split.human.commits, 2);
+    assert_eq!(split.human.additions, 6);
+    assert_eq!(split.automated.commits, 1);
+    assert_eq!(split.automated.authors["dependabot[bot] <x@x>"], 1);
+  }
+
+  #[test]
+  fn stale_branch_requires_unmerged_and_old_tip() {
+    assert_eq!(age_in_days(10 * 86_400 + 5, 0), 10);
+    assert_eq!(age_in_days(0, 100), 0);
+    assert!(is_stale_branch(Some(false), Some(31), 30));
+    assert!(is_stale_branch(None, Some(31), 30));
+    assert!(!is_stale_branch(Some(false), Some(30), 30));
+    assert!(!is_stale_branch(Some(true), Some(90), 30));
+    assert!(!is_stale_branch(Some(false), None, 30));
+  }
+
+  #[test]
+  fn parallel_processing_keeps_rev_list_order() {
+    let repo = fixture_repo();
+    let shas = gitio::rev_list(&repo, "2025-08-01", "2025-09-01", true, &[]).unwrap();
+    let context = ProcessContext {
+      repo: &repo,
+      tz: "utc",
+      github_prs: false,
+      include_patch: true,
+      max_patch_bytes: 0,
+      max_files_per_commit: None,
+      estimate_effort: false,
+      no_blob_stats: false,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: &[],
+      automation: None,
+      issue_keys: None,
+      snapshot_paths: &[],
+      snapshot_max_bytes: 0,
+      no_bodies: false,
+      paths: &[],
+    };
+    let shas_of = |commits: Vec<Commit>| commits.into_iter().map(|c| c.sha).collect::<Vec<_>>();
+
+    assert_eq!(shas_of(process_commits(&shas, &context, 1, None, None).unwrap()), shas);
+    assert_eq!(shas_of(process_commits(&shas, &context, 4, None, None).unwrap()), shas);
+    assert!(process_commits(&["not-a-sha".to_string(), shas[0].clone()], &context, 2, None, None).is_err());
+  }
+
   // proptests for clip_patch moved to util
 }
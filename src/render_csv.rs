@@ -0,0 +1,175 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --format csv: render a range report's commits as CSV, one row per commit, for spreadsheets
+// role: assembly/render_csv
+// inputs: SimpleReport (single run, or a split/multi range report read back from disk); --csv-columns selection
+// outputs: CSV text: a header row, then one row per commit in report order
+// side_effects: None
+// invariants:
+// - columns appear in --csv-columns order (DEFAULT_COLUMNS when none are given); unknown names are rejected up front
+// - fields follow RFC 4180: quoted when they contain a comma, quote, or line break; quotes doubled; CRLF line endings
+// - additions/deletions include the totals of files dropped by --max-files-per-commit; files counts them too
+// - prs lists linked PR numbers ascending, deduped, separated by ';'
+// errors: parse_columns rejects unknown or empty column names
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeSet;
+
+use anyhow::{Result, bail};
+
+use crate::model::{Commit, SimpleReport};
+
+/// Columns `--csv-columns` accepts.
+pub const COLUMNS: [&str; 11] = [
+  "sha",
+  "short_sha",
+  "author",
+  "author_email",
+  "date",
+  "subject",
+  "additions",
+  "deletions",
+  "files",
+  "prs",
+  "automated",
+];
+
+/// Columns written when `--csv-columns` is not given.
+pub const DEFAULT_COLUMNS: [&str; 8] = [
+  "sha",
+  "author",
+  "date",
+  "subject",
+  "additions",
+  "deletions",
+  "files",
+  "prs",
+];
+
+/// Validate `--csv-columns` (repeatable or comma-separated); an empty selection means `DEFAULT_COLUMNS`.
+pub fn parse_columns(selected: &[String]) -> Result<Vec<String>> {
+  if selected.is_empty() {
+    return Ok(DEFAULT_COLUMNS.iter().map(|c| c.to_string()).collect());
+  }
+
+  selected
+    .iter()
+    .map(|raw| {
+      let name = raw.trim().to_ascii_lowercase();
+
+      if !COLUMNS.contains(&name.as_str()) {
+        bail!(
+          "--csv-columns: unknown column {:?}; expected one of: {}",
+          raw,
+          COLUMNS.join(", ")
+        );
+      }
+
+      Ok(name)
+    })
+    .collect()
+}
+
+fn field(s: &str) -> String {
+  if s.contains([',', '"', '\n', '\r']) {
+    format!("\"{}\"", s.replace('"', "\"\""))
+  } else {
+    s.to_string()
+  }
+}
+
+fn value(c: &Commit, column: &str) -> String {
+  let (add, del) = crate::commit::commit_additions_deletions(c);
+  let omitted = c.files_truncated.as_ref().map_or(0, |t| t.omitted);
+
+  match column {
+    "sha" => c.sha.clone(),
+    "short_sha" => c.short_sha.clone(),
+    "author" => c.author.name.clone(),
+    "author_email" => c.author.email.clone(),
+    "date" => c.timestamps.commit_local.clone(),
+    "subject" => c.subject.clone(),
+    "additions" => add.to_string(),
+    "deletions" => del.to_string(),
+    "files" => (c.files.len() + omitted).to_string(),
+    "prs" => {
+      let numbers: BTreeSet<i64> = c
+        .github
+        .iter()
+        .flat_map(|g| &g.pull_requests)
+        .map(|pr| pr.number)
+        .collect();
+
+      numbers.iter().map(i64::to_string).collect::<Vec<_>>().join(";")
+    }
+    "automated" => c.automated.unwrap_or(false).to_string(),
+    _ => String::new(),
+  }
+}
+
+/// Render the commits of `report` as CSV with the given (already validated) columns.
+pub fn render_report(report: &SimpleReport, columns: &[String]) -> String {
+  let mut csv = columns.join(",");
+  csv.push_str("\r\n");
+
+  for c in &report.commits {
+    let row: Vec<String> = columns.iter().map(|col| field(&value(c, col))).collect();
+    csv.push_str(&row.join(","));
+    csv.push_str("\r\n");
+  }
+
+  csv
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn report() -> SimpleReport {
+    let pr = |n: i64| json!({ "number": n, "title": "t", "state": "closed", "html_url": format!("https://github.com/o/r/pull/{}", n) });
+    let mut commit = Commit::fixture("a1b2c3d4e5")
+      .with_author("Doe, Jane", "jane@x")
+      .at(0, "2025-08-12T14:03:00+00:00")
+      .with_files(&[("a.rs", 3, 1), ("b.rs", 2, 0)])
+      .with_pull_requests(json!([pr(7), pr(5), pr(7)]));
+    commit.short_sha = "a1b2c3d".into();
+    commit.subject = "Say \"hi\"".into();
+    commit.files[1].status = "A".into();
+
+    serde_json::from_value(json!({
+      "summary": {
+        "repo": "/src/widgets",
+        "range": { "label": "2025-08", "start": "2025-08-01T00:00:00", "end": "2025-09-01T00:00:00" },
+        "count": 1,
+        "report_options": { "include_merges": false, "include_patch": false, "include_unmerged": false, "tz": "utc" },
+        "changeset": { "additions": 5, "deletions": 1, "files_touched": 2 }
+      },
+      "authors": { "Doe, Jane <jane@x>": 1 },
+      "commits": [serde_json::to_value(commit).unwrap()]
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn rows_follow_columns_and_escape_fields() {
+    let columns = parse_columns(&[]).unwrap();
+    let csv = render_report(&report(), &columns);
+    let lines: Vec<&str> = csv.split("\r\n").collect();
+
+    assert_eq!(lines[0], "sha,author,date,subject,additions,deletions,files,prs");
+    assert_eq!(
+      lines[1],
+      "a1b2c3d4e5,\"Doe, Jane\",2025-08-12T14:03:00+00:00,\"Say \"\"hi\"\"\",5,1,2,5;7"
+    );
+    assert_eq!(lines[2], "");
+
+    let trimmed = parse_columns(&["short_sha".into(), " Subject ".into()]).unwrap();
+    assert_eq!(
+      render_report(&report(), &trimmed),
+      "short_sha,subject\r\na1b2c3d,\"Say \"\"hi\"\"\"\r\n"
+    );
+    assert!(parse_columns(&["sha".into(), "body".into()]).is_err());
+  }
+}
@@ -0,0 +1,452 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --format markdown: render a range report (SimpleReport) as Markdown for standups and wikis
+// role: assembly/render_markdown
+// inputs: SimpleReport (single run, or a split/multi range report read back from disk)
+// outputs: Markdown text: title, totals table, authors table, pull requests, per-commit sections, unmerged branches
+// side_effects: None
+// invariants:
+// - sections appear in that order; empty PR and unmerged sections are omitted
+// - authors are ordered by commit count desc, then name; PRs are deduped by number and ordered by number
+// - the authors table gains an Activity column (one glyph per window day) only when author_stats carry calendars
+// - commits keep report order; each lists author, local commit time, churn, linked PRs, body, and files
+// - table cells escape '|' so author names and paths cannot break the table
+// errors: parse rejects unknown format names
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::Result;
+
+use crate::model::{Commit, GithubPullRequest, SimpleReport};
+
+/// Rendering of report output (`--format`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ReportFormat {
+  #[default]
+  Json,
+  Markdown,
+  /// One row per commit (see crate::render_csv)
+  Csv,
+  /// One commit object per line, then a summary line, streamed (see crate::render::run_ndjson)
+  Ndjson,
+}
+
+/// Accepted `--format` values.
+pub const FORMATS: [&str; 4] = ["json", "markdown", "csv", "ndjson"];
+
+impl ReportFormat {
+  pub fn parse(s: &str) -> Result<Self> {
+    match s.to_ascii_lowercase().as_str() {
+      "json" => Ok(Self::Json),
+      "markdown" | "md" => Ok(Self::Markdown),
+      "csv" => Ok(Self::Csv),
+      "ndjson" | "jsonl" => Ok(Self::Ndjson),
+      _ => anyhow::bail!("--format expects one of {}, got {:?}", FORMATS.join(", "), s),
+    }
+  }
+}
+
+fn cell(s: &str) -> String {
+  s.replace('|', "\\|")
+}
+
+/// PRs linked from any commit of the report, one per number.
+fn pull_requests(commits: &[Commit]) -> BTreeMap<i64, &GithubPullRequest> {
+  let mut prs = BTreeMap::new();
+
+  for c in commits {
+    for pr in c.github.iter().flat_map(|g| &g.pull_requests) {
+      prs.entry(pr.number).or_insert(pr);
+    }
+  }
+
+  prs
+}
+
+fn pr_line(pr: &GithubPullRequest, commits_in_range: usize) -> String {
+  let mut notes = vec![pr.state.clone()];
+
+  if pr.merged_at.is_some() {
+    notes[0] = "merged".into();
+  }
+  if let Some(login) = pr.submitter.as_ref().and_then(|u| u.login.as_deref()) {
+    notes.push(format!("by @{}", login));
+  }
+  if let Some(n) = pr.review_count {
+    notes.push(format!("{} review(s)", n));
+  }
+  notes.push(format!("{} commit(s) in range", commits_in_range));
+
+  format!(
+    "- [#{}]({}) {} ({})",
+    pr.number,
+    pr.html_url,
+    pr.title,
+    notes.join("; ")
+  )
+}
+
+fn write_commit(md: &mut String, c: &Commit) {
+  let (add, del) = crate::commit::commit_additions_deletions(c);
+  let omitted = c.files_truncated.as_ref().map_or(0, |t| t.omitted);
+  let _ = writeln!(md, "### `{}` {}\n", c.short_sha, c.subject);
+  let _ = writeln!(md, "- Author: {} <{}>", c.author.name, c.author.email);
+  let _ = writeln!(md, "- Committed: {}", c.timestamps.commit_local);
+  let _ = writeln!(
+    md,
+    "- Changes: +{} / -{} across {} file(s)",
+    add,
+    del,
+    c.files.len() + omitted
+  );
+
+  let linked: Vec<String> = c
+    .github
+    .iter()
+    .flat_map(|g| &g.pull_requests)
+    .map(|pr| format!("[#{}]({})", pr.number, pr.html_url))
+    .collect();
+
+  if !linked.is_empty() {
+    let _ = writeln!(md, "- Pull requests: {}", linked.join(", "));
+  }
+
+  if let Some(r) = &c.release {
+    let _ = writeln!(md, "- Released in: `{}`", r.tag);
+  }
+
+  if let Some(g) = &c.merge_group {
+    let _ = writeln!(
+      md,
+      "- Merged {} commit(s): +{} / -{} across {} file(s)",
+      g.commits.len(),
+      g.additions,
+      g.deletions,
+      g.files_touched
+    );
+    for m in &g.commits {
+      let _ = writeln!(md, "  - `{}` {} ({})", m.short_sha, m.subject, m.author.name);
+    }
+  }
+
+  let body = c.body.trim();
+
+  if !body.is_empty() {
+    md.push('\n');
+    for line in body.lines() {
+      let _ = writeln!(md, "> {}", line);
+    }
+  }
+
+  if !c.files.is_empty() {
+    md.push('\n');
+    for f in &c.files {
+      let churn = match (f.additions, f.deletions) {
+        (Some(a), Some(d)) => format!(" +{} / -{}", a, d),
+        _ => String::new(),
+      };
+      let _ = writeln!(md, "- `{}` ({}){}", f.file, f.status, churn);
+    }
+    if let Some(t) = &c.files_truncated {
+      let _ = writeln!(
+        md,
+        "- …and {} more file(s) +{} / -{}",
+        t.omitted, t.total_additions, t.total_deletions
+      );
+    }
+  }
+
+  md.push('\n');
+}
+
+/// Day-per-glyph strip of an `activity_calendar`: █ active, · idle.
+fn activity_strip(calendar: &str) -> String {
+  calendar.chars().map(|b| if b == '1' { '█' } else { '·' }).collect()
+}
+
+/// Render `report` as Markdown.
+pub fn render_report(report: &SimpleReport) -> String {
+  let s = &report.summary;
+  let repo_name = Path::new(&s.repo)
+    .file_name()
+    .map(|n| n.to_string_lossy().to_string())
+    .unwrap_or_else(|| s.repo.clone());
+  let prs = pull_requests(&report.commits);
+
+  let mut md = format!("# Activity report: {} ({})\n\n", repo_name, s.range.label);
+  let _ = writeln!(md, "{} → {}\n", s.range.start, s.range.end);
+
+  md.push_str("## Totals\n\n| Metric | Value |\n| --- | ---: |\n");
+  let _ = writeln!(md, "| Commits | {} |", s.count);
+  let _ = writeln!(md, "| Authors | {} |", report.authors.len());
+  let _ = writeln!(md, "| Additions | +{} |", s.changes.additions);
+  let _ = writeln!(md, "| Deletions | -{} |", s.changes.deletions);
+  let _ = writeln!(md, "| Files touched | {} |", s.changes.files_touched);
+  if !prs.is_empty() {
+    let _ = writeln!(md, "| Pull requests | {} |", prs.len());
+  }
+
+  if !s.changes.changes_by_language.is_empty() {
+    let mut langs: Vec<_> = s.changes.changes_by_language.iter().collect();
+    langs.sort_by(|a, b| {
+      (b.1.additions + b.1.deletions)
+        .cmp(&(a.1.additions + a.1.deletions))
+        .then_with(|| a.0.cmp(b.0))
+    });
+
+    md.push_str("\n## Languages\n\n| Language | Files | Additions | Deletions |\n| --- | ---: | ---: | ---: |\n");
+    for (lang, c) in langs {
+      let _ = writeln!(
+        md,
+        "| {} | {} | +{} | -{} |",
+        cell(lang),
+        c.files_touched,
+        c.additions,
+        c.deletions
+      );
+    }
+  }
+
+  if !report.authors.is_empty() {
+    let mut authors: Vec<(&String, &i64)> = report.authors.iter().collect();
+    authors.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+    let calendars: BTreeMap<&str, &str> = report
+      .author_stats
+      .iter()
+      .filter(|a| !a.activity_calendar.is_empty())
+      .map(|a| (a.author.as_str(), a.activity_calendar.as_str()))
+      .collect();
+
+    if calendars.is_empty() {
+      md.push_str("\n## Authors\n\n| Author | Commits |\n| --- | ---: |\n");
+      for (name, n) in authors {
+        let _ = writeln!(md, "| {} | {} |", cell(name), n);
+      }
+    } else {
+      md.push_str("\n## Authors\n\n| Author | Commits | Activity |\n| --- | ---: | --- |\n");
+      for (name, n) in authors {
+        let strip = calendars
+          .get(name.as_str())
+          .map(|c| format!("`{}`", activity_strip(c)))
+          .unwrap_or_default();
+        let _ = writeln!(md, "| {} | {} | {} |", cell(name), n, strip);
+      }
+    }
+  }
+
+  if let Some(items) = report.work_items.as_ref().filter(|w| !w.is_empty()) {
+    md.push_str("\n## Work items\n\n");
+    for w in items {
+      let _ = writeln!(
+        md,
+        "- {} ({}; {} commit(s) over {} min; +{} / -{})",
+        w.subject,
+        w.author,
+        w.commits.len(),
+        w.duration_seconds / 60,
+        w.additions,
+        w.deletions
+      );
+    }
+  }
+
+  if let Some(releases) = s.releases.as_ref().filter(|r| !r.is_empty()) {
+    md.push_str("\n## Releases\n\n");
+    for r in releases {
+      let tag = match &r.url {
+        Some(url) => format!("[{}]({})", r.tag, url),
+        None => format!("`{}`", r.tag),
+      };
+      let _ = writeln!(
+        md,
+        "- {} at `{}` ({}; {} commit(s) in range)",
+        tag, r.commit, r.created_at, r.commits
+      );
+    }
+  }
+
+  if !prs.is_empty() {
+    md.push_str("\n## Pull requests\n\n");
+    for (number, pr) in &prs {
+      let in_range = report
+        .commits
+        .iter()
+        .filter(|c| {
+          c.github
+            .iter()
+            .flat_map(|g| &g.pull_requests)
+            .any(|p| p.number == *number)
+        })
+        .count();
+      let _ = writeln!(md, "{}", pr_line(pr, in_range));
+    }
+  }
+
+  md.push_str("\n## Commits\n\n");
+  if report.commits.is_empty() {
+    md.push_str("No commits.\n\n");
+  }
+  for c in &report.commits {
+    write_commit(&mut md, c);
+  }
+
+  if let Some(u) = report.unmerged_activity.as_ref().filter(|u| !u.branches.is_empty()) {
+    let _ = writeln!(
+      md,
+      "## Unmerged branches (vs {})\n",
+      u.base.as_deref().unwrap_or("HEAD")
+    );
+    for b in &u.branches {
+      let mut line = format!("- `{}`: {} commit(s)", b.name, b.items.len());
+      if let (Some(ahead), Some(behind)) = (b.ahead_of_head, b.behind_head) {
+        let _ = write!(line, ", {} ahead / {} behind", ahead, behind);
+      }
+      if b.stale {
+        line.push_str(", stale");
+      }
+      let _ = writeln!(md, "{}", line);
+    }
+    md.push('\n');
+  }
+
+  md.truncate(md.trim_end().len());
+  md.push('\n');
+
+  md
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn report() -> SimpleReport {
+    let pr = json!({
+      "number": 5, "title": "Widgets", "state": "closed", "merged_at": "2025-08-13T10:00:00Z",
+      "html_url": "https://github.com/o/r/pull/5", "submitter": { "login": "ann" }, "review_count": 2
+    });
+    let commit = |sha: &str, subject: &str, body: &str, name: &str| {
+      let mut c = Commit::fixture(sha)
+        .with_author(name, "a@x")
+        .at(0, "2025-08-12T09:00:00+00:00")
+        .with_files(&[("src/w.rs", 10, 2)])
+        .with_pull_requests(json!([pr.clone()]));
+      c.subject = subject.into();
+      c.body = body.into();
+
+      serde_json::to_value(c).unwrap()
+    };
+
+    serde_json::from_value(json!({
+      "summary": {
+        "repo": "/src/widgets",
+        "range": { "label": "2025-08", "start": "2025-08-01T00:00:00", "end": "2025-09-01T00:00:00" },
+        "count": 2,
+        "report_options": { "include_merges": false, "include_patch": false, "include_unmerged": false, "tz": "utc" },
+        "changeset": { "additions": 20, "deletions": 4, "files_touched": 1 }
+      },
+      "authors": { "Ann <a@x>": 1, "Bo|b <a@x>": 1 },
+      "commits": [
+        commit("aaa1111", "Add widget", "Why:\nbecause", "Ann"),
+        commit("bbb2222", "Tune widget", "", "Bo|b"),
+      ]
+    }))
+    .unwrap()
+  }
+
+  #[test]
+  fn report_renders_totals_prs_and_commit_sections() {
+    let md = render_report(&report());
+
+    assert!(md.starts_with("# Activity report: widgets (2025-08)\n"));
+    assert!(md.contains("| Commits | 2 |\n| Authors | 2 |\n| Additions | +20 |"));
+    assert!(md.contains("| Pull requests | 1 |"));
+    assert!(md.contains("| Bo\\|b <a@x> | 1 |"));
+    assert!(
+      md.contains("- [#5](https://github.com/o/r/pull/5) Widgets (merged; by @ann; 2 review(s); 2 commit(s) in range)")
+    );
+    assert!(md.contains("### `aaa1111` Add widget\n\n- Author: Ann <a@x>\n"));
+    assert!(md.contains("> Why:\n> because\n"));
+    assert!(md.contains("- `src/w.rs` (M) +10 / -2"));
+    assert!(!md.contains("## Unmerged branches"));
+    assert!(md.ends_with("+10 / -2\n"));
+
+    let mut with_calendar = report();
+    with_calendar.author_stats = vec![
+      serde_json::from_value(json!({
+        "author": "Ann <a@x>", "commits": 1, "additions": 10, "deletions": 2, "files_touched": 1,
+        "active_days": 1, "first_commit_at": "", "last_commit_at": "", "activity_calendar": "0100"
+      }))
+      .unwrap(),
+    ];
+    let md = render_report(&with_calendar);
+    assert!(md.contains("| Author | Commits | Activity |"));
+    assert!(md.contains("| Ann <a@x> | 1 | `·█··` |"));
+    assert!(md.contains("| Bo\\|b <a@x> | 1 |  |"));
+    assert!(!md.contains("## Work items"));
+    assert!(!md.contains("## Languages"));
+
+    let mut with_languages = report();
+    let churn = |additions, deletions, files_touched| crate::model::LanguageChanges {
+      additions,
+      deletions,
+      files_touched,
+    };
+    with_languages.summary.changes.changes_by_language =
+      BTreeMap::from([("Markdown".into(), churn(1, 0, 1)), ("Rust".into(), churn(19, 4, 2))]);
+    let md = render_report(&with_languages);
+    assert!(md.contains("| Language | Files | Additions | Deletions |\n| --- | ---: | ---: | ---: |\n| Rust | 2 | +19 | -4 |\n| Markdown | 1 | +1 | -0 |\n"));
+
+    let mut with_items = report();
+    with_items.work_items = Some(vec![crate::model::WorkItem {
+      author: "Ann <a@x>".into(),
+      commits: vec!["aaa1111".into(), "ccc3333".into()],
+      subject: "Add widget".into(),
+      duration_seconds: 2700,
+      additions: 12,
+      deletions: 3,
+      ..Default::default()
+    }]);
+    let md = render_report(&with_items);
+    assert!(md.contains("## Work items\n\n- Add widget (Ann <a@x>; 2 commit(s) over 45 min; +12 / -3)\n"));
+
+    let mut with_merge = report();
+    let member = with_merge.commits[1].clone();
+    with_merge.commits[0].merge_group = crate::merge_groups::build_merge_group(vec![member]);
+    let md = render_report(&with_merge);
+    assert!(md.contains("- Merged 1 commit(s): +10 / -2 across 1 file(s)\n  - `bbb2222` Tune widget (Bo|b)\n"));
+    assert!(!md.contains("## Releases"));
+
+    let mut with_releases = report();
+    with_releases.commits[0].release = Some(crate::model::ReleaseRef {
+      tag: "v1.1".into(),
+      url: None,
+    });
+    with_releases.summary.releases = Some(vec![crate::model::Release {
+      tag: "v1.1".into(),
+      commit: "aaa1111".into(),
+      created_at: "2025-08-14T10:00:00Z".into(),
+      commits: 1,
+      url: Some("https://github.com/o/r/releases/tag/v1.1".into()),
+    }]);
+    let md = render_report(&with_releases);
+    assert!(md.contains(
+      "## Releases\n\n- [v1.1](https://github.com/o/r/releases/tag/v1.1) at `aaa1111` (2025-08-14T10:00:00Z; 1 commit(s) in range)\n"
+    ));
+    assert!(md.contains("- Released in: `v1.1`\n"));
+  }
+
+  #[test]
+  fn formats_parse_and_unknown_formats_are_rejected() {
+    assert_eq!(ReportFormat::parse("markdown").unwrap(), ReportFormat::Markdown);
+    assert_eq!(ReportFormat::parse("JSON").unwrap(), ReportFormat::Json);
+    assert_eq!(ReportFormat::parse("csv").unwrap(), ReportFormat::Csv);
+    assert_eq!(ReportFormat::parse("jsonl").unwrap(), ReportFormat::Ndjson);
+    assert!(ReportFormat::parse("html").is_err());
+  }
+}
@@ -0,0 +1,129 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Build summary.review_pairs: median first-review latency per (PR author → reviewer) pairing over the window
+// role: summary/review_pairs
+// inputs: Processed commits of one range with github.pull_requests[].review_responses (--github-prs)
+// outputs: author login → reviewer login → ReviewLatency { pull_requests, median_response_seconds }; None when empty
+// side_effects: None
+// invariants:
+// - each PR counts once (deduped by number) even when several commits link to it
+// - the author is the PR submitter; self-reviews (author replies) are skipped
+// - the median of an even count is the mean of the two middle values, rounded down
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use crate::model::{Commit, GithubPullRequest, ReviewLatency};
+
+pub type ReviewPairs = BTreeMap<String, BTreeMap<String, ReviewLatency>>;
+
+/// Aggregate per-PR review responses into an author × reviewer latency matrix.
+pub fn build_review_pairs(commits: &[Commit]) -> Option<ReviewPairs> {
+  let mut prs: BTreeMap<i64, &GithubPullRequest> = BTreeMap::new();
+
+  for c in commits {
+    for pr in c.github.iter().flat_map(|g| &g.pull_requests) {
+      prs.entry(pr.number).or_insert(pr);
+    }
+  }
+
+  let mut samples: BTreeMap<(String, String), Vec<i64>> = BTreeMap::new();
+
+  for pr in prs.values() {
+    let Some(author) = pr.submitter.as_ref().and_then(|u| u.login.clone()) else {
+      continue;
+    };
+
+    for r in pr.review_responses.iter().flatten() {
+      if r.reviewer != author {
+        samples
+          .entry((author.clone(), r.reviewer.clone()))
+          .or_default()
+          .push(r.response_seconds);
+      }
+    }
+  }
+
+  if samples.is_empty() {
+    return None;
+  }
+
+  let mut matrix = ReviewPairs::new();
+
+  for ((author, reviewer), mut seconds) in samples {
+    seconds.sort_unstable();
+    matrix.entry(author).or_default().insert(
+      reviewer,
+      ReviewLatency {
+        pull_requests: seconds.len() as i64,
+        median_response_seconds: median(&seconds),
+      },
+    );
+  }
+
+  Some(matrix)
+}
+
+fn median(sorted: &[i64]) -> i64 {
+  let mid = sorted.len() / 2;
+
+  if sorted.len() % 2 == 1 {
+    sorted[mid]
+  } else {
+    (sorted[mid - 1] + sorted[mid]).div_euclid(2)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn pr(number: i64, author: &str, responses: serde_json::Value) -> serde_json::Value {
+    json!({
+      "number": number, "title": "t", "state": "closed",
+      "html_url": format!("https://github.com/o/r/pull/{}", number),
+      "submitter": { "login": author },
+      "review_responses": responses
+    })
+  }
+
+  fn commit(sha: &str, prs: serde_json::Value) -> Commit {
+    Commit::fixture(sha).with_pull_requests(prs)
+  }
+
+  #[test]
+  fn medians_per_pair_count_each_pr_once() {
+    let resp = |reviewer: &str, s: i64| json!({ "reviewer": reviewer, "response_seconds": s });
+    let pr1 = pr(1, "ann", json!([resp("bob", 600), resp("ann", 60)]));
+    let commits = vec![
+      commit("a1", json!([pr1.clone()])),
+      commit("a2", json!([pr1])),
+      commit("a3", json!([pr(2, "ann", json!([resp("bob", 3000), resp("cy", 90)]))])),
+      commit("a4", json!([pr(3, "ann", json!([resp("bob", 1200)]))])),
+    ];
+
+    let m = build_review_pairs(&commits).unwrap();
+
+    assert_eq!(m.len(), 1);
+    assert_eq!(
+      m["ann"]["bob"],
+      ReviewLatency {
+        pull_requests: 3,
+        median_response_seconds: 1200
+      }
+    );
+    assert_eq!(m["ann"]["cy"].pull_requests, 1);
+    // self-review replies are not a pairing
+    assert!(!m["ann"].contains_key("ann"));
+  }
+
+  #[test]
+  fn even_counts_average_the_middle_and_no_reviews_is_none() {
+    assert_eq!(median(&[100, 300]), 200);
+    assert_eq!(median(&[1, 2, 3, 10]), 2);
+    assert!(build_review_pairs(&[commit("a1", json!([pr(1, "ann", json!(null))]))]).is_none());
+  }
+}
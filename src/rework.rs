@@ -0,0 +1,124 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Build summary.rework_cycles: how many change-request → new commits → approval loops the window's PRs went through
+// role: summary/rework
+// inputs: Processed commits of one range with github.pull_requests[].rework_cycles / rework_cycle_seconds (--github-prs)
+// outputs: ReworkSummary { pull_requests, distribution ("0", "1", "2", "3+" → PRs), median_cycle_seconds }; None when no PR was reviewed
+// side_effects: None
+// invariants:
+// - each PR counts once (deduped by number) even when several commits link to it
+// - only PRs with a review timeline (rework_cycles present) are counted; every bucket key is always present
+// - the median of an even count is the mean of the two middle values, rounded down
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+
+use crate::model::{Commit, GithubPullRequest, ReworkSummary};
+
+/// Loop counts at or above this share the last bucket ("3+").
+const OPEN_BUCKET: i64 = 3;
+
+/// Aggregate per-PR rework loops into a distribution and the median loop length.
+pub fn build_rework_summary(commits: &[Commit]) -> Option<ReworkSummary> {
+  let mut prs: BTreeMap<i64, &GithubPullRequest> = BTreeMap::new();
+
+  for c in commits {
+    for pr in c.github.iter().flat_map(|g| &g.pull_requests) {
+      prs.entry(pr.number).or_insert(pr);
+    }
+  }
+
+  let mut distribution: BTreeMap<String, i64> = (0..=OPEN_BUCKET).map(|n| (bucket(n), 0)).collect();
+  let mut seconds: Vec<i64> = Vec::new();
+  let mut pull_requests = 0;
+
+  for pr in prs.values() {
+    let Some(cycles) = pr.rework_cycles else {
+      continue;
+    };
+
+    pull_requests += 1;
+    *distribution.entry(bucket(cycles)).or_insert(0) += 1;
+    seconds.extend(pr.rework_cycle_seconds.iter().flatten());
+  }
+
+  if pull_requests == 0 {
+    return None;
+  }
+
+  seconds.sort_unstable();
+
+  Some(ReworkSummary {
+    pull_requests,
+    distribution,
+    median_cycle_seconds: (!seconds.is_empty()).then(|| median(&seconds)),
+  })
+}
+
+fn bucket(cycles: i64) -> String {
+  if cycles >= OPEN_BUCKET {
+    format!("{}+", OPEN_BUCKET)
+  } else {
+    cycles.to_string()
+  }
+}
+
+fn median(sorted: &[i64]) -> i64 {
+  let mid = sorted.len() / 2;
+
+  if sorted.len() % 2 == 1 {
+    sorted[mid]
+  } else {
+    (sorted[mid - 1] + sorted[mid]).div_euclid(2)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn pr(number: i64, cycles: Option<i64>, seconds: serde_json::Value) -> serde_json::Value {
+    json!({
+      "number": number, "title": "t", "state": "closed",
+      "html_url": format!("https://github.com/o/r/pull/{}", number),
+      "rework_cycles": cycles,
+      "rework_cycle_seconds": seconds
+    })
+  }
+
+  fn commit(sha: &str, prs: serde_json::Value) -> Commit {
+    Commit::fixture(sha).with_pull_requests(prs)
+  }
+
+  #[test]
+  fn buckets_each_pr_once_and_takes_the_median_loop() {
+    let looped = pr(1, Some(1), json!([600]));
+    let commits = vec![
+      commit("a1", json!([looped.clone()])),
+      commit("a2", json!([looped])),
+      commit("a3", json!([pr(2, Some(0), json!(null))])),
+      commit("a4", json!([pr(3, Some(4), json!([100, 200, 900, 50]))])),
+      commit("a5", json!([pr(4, None, json!(null))])),
+    ];
+
+    let s = build_rework_summary(&commits).unwrap();
+
+    assert_eq!(s.pull_requests, 3);
+    assert_eq!(s.distribution["0"], 1);
+    assert_eq!(s.distribution["1"], 1);
+    assert_eq!(s.distribution["2"], 0);
+    assert_eq!(s.distribution["3+"], 1);
+    assert_eq!(s.median_cycle_seconds, Some(200));
+  }
+
+  #[test]
+  fn unreviewed_windows_have_no_summary() {
+    assert!(build_rework_summary(&[commit("a1", json!([pr(1, None, json!(null))]))]).is_none());
+
+    let clean = build_rework_summary(&[commit("a1", json!([pr(1, Some(0), json!(null))]))]).unwrap();
+    assert_eq!(clean.median_cycle_seconds, None);
+  }
+}
@@ -0,0 +1,258 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Aggregate all per-range reports of a multi-range run into rollup.json (totals, churn trend, author retention)
+// role: persistence/rollup
+// inputs: base_dir and the RangeEntry list written to manifest.json; the per-range report files on disk
+// outputs: rollup.json under base_dir
+// side_effects: Reads every per-range report; writes rollup.json
+// invariants:
+// - trend and retention follow manifest range order (chronological)
+// - authors are keyed "Name <email>" exactly as in the per-range reports' `authors` maps
+// - retention compares each range with the previous one only; the first range counts every author as new
+// errors: Missing or unparsable range reports surface with their path
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+
+use crate::manifest::RangeEntry;
+
+pub const ROLLUP_FILE: &str = "rollup.json";
+
+#[derive(Debug, Serialize, Default, PartialEq)]
+pub struct RollupTotals {
+  pub commits: i64,
+  pub additions: i64,
+  pub deletions: i64,
+  pub authors: usize,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RollupRange {
+  pub label: String,
+  pub start: String,
+  pub end: String,
+  pub commits: i64,
+  pub additions: i64,
+  pub deletions: i64,
+  pub churn: i64,
+  /// Churn change versus the previous range (absent for the first range)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub churn_delta: Option<i64>,
+  pub authors: usize,
+  /// summary.estimated_minutes of the range report (--estimate-effort)
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub estimated_minutes: Option<f64>,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RollupAuthor {
+  pub commits: i64,
+  pub active_ranges: usize,
+  pub first_range: String,
+  pub last_range: String,
+}
+
+#[derive(Debug, Serialize, PartialEq)]
+pub struct RetentionPoint {
+  pub label: String,
+  pub active: usize,
+  /// Active here and in the previous range
+  pub retained: usize,
+  /// Active here but not in the previous range
+  pub new: usize,
+  /// Active in the previous range but not here
+  pub departed: usize,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Rollup {
+  pub repo: String,
+  pub generated_at: String,
+  pub ranges: usize,
+  pub totals: RollupTotals,
+  pub trend: Vec<RollupRange>,
+  pub retention: Vec<RetentionPoint>,
+  pub authors: BTreeMap<String, RollupAuthor>,
+}
+
+/// Per-range figures pulled from one report's summary and authors map.
+struct RangeFigures {
+  commits: i64,
+  additions: i64,
+  deletions: i64,
+  estimated_minutes: Option<f64>,
+  authors: BTreeMap<String, i64>,
+}
+
+fn read_figures(path: &Path) -> Result<RangeFigures> {
+  let bytes = crate::util::read_output(path).with_context(|| format!("reading range report {}", path.display()))?;
+  let v: serde_json::Value =
+    serde_json::from_slice(&bytes).with_context(|| format!("parsing range report {}", path.display()))?;
+
+  let int = |ptr: &str| v.pointer(ptr).and_then(|x| x.as_i64()).unwrap_or(0);
+  let authors: BTreeMap<String, i64> = serde_json::from_value(v["authors"].clone()).unwrap_or_default();
+
+  Ok(RangeFigures {
+    commits: int("/summary/count"),
+    additions: int("/summary/changeset/additions"),
+    deletions: int("/summary/changeset/deletions"),
+    estimated_minutes: v.pointer("/summary/estimated_minutes").and_then(|x| x.as_f64()),
+    authors,
+  })
+}
+
+/// Build the rollup from the range reports listed in `entries` (paths relative to `base_dir`).
+pub fn build_rollup(
+  repo: &str,
+  generated_at: DateTime<Local>,
+  base_dir: &str,
+  entries: &[RangeEntry],
+) -> Result<Rollup> {
+  let mut rollup = Rollup {
+    repo: repo.to_string(),
+    generated_at: generated_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    ranges: entries.len(),
+    totals: RollupTotals::default(),
+    trend: Vec::with_capacity(entries.len()),
+    retention: Vec::with_capacity(entries.len()),
+    authors: BTreeMap::new(),
+  };
+  let mut previous: Option<(i64, BTreeSet<String>)> = None;
+
+  for e in entries {
+    let f = read_figures(&Path::new(base_dir).join(&e.file))?;
+    let churn = f.additions + f.deletions;
+    let active: BTreeSet<String> = f.authors.keys().cloned().collect();
+
+    rollup.totals.commits += f.commits;
+    rollup.totals.additions += f.additions;
+    rollup.totals.deletions += f.deletions;
+
+    for (author, commits) in &f.authors {
+      let entry = rollup.authors.entry(author.clone()).or_insert_with(|| RollupAuthor {
+        commits: 0,
+        active_ranges: 0,
+        first_range: e.label.clone(),
+        last_range: e.label.clone(),
+      });
+      entry.commits += commits;
+      entry.active_ranges += 1;
+      entry.last_range = e.label.clone();
+    }
+
+    let (churn_delta, retained, departed) = match &previous {
+      Some((prev_churn, prev_active)) => (
+        Some(churn - prev_churn),
+        active.intersection(prev_active).count(),
+        prev_active.difference(&active).count(),
+      ),
+      None => (None, 0, 0),
+    };
+
+    rollup.trend.push(RollupRange {
+      label: e.label.clone(),
+      start: e.start.clone(),
+      end: e.end.clone(),
+      commits: f.commits,
+      additions: f.additions,
+      deletions: f.deletions,
+      churn,
+      churn_delta,
+      authors: active.len(),
+      estimated_minutes: f.estimated_minutes,
+    });
+    rollup.retention.push(RetentionPoint {
+      label: e.label.clone(),
+      active: active.len(),
+      retained,
+      new: active.len() - retained,
+      departed,
+    });
+
+    previous = Some((churn, active));
+  }
+
+  rollup.totals.authors = rollup.authors.len();
+
+  Ok(rollup)
+}
+
+/// Write `rollup` as `rollup.json` under `base_dir`.
+pub fn write_rollup(base_dir: &str, rollup: &Rollup) -> Result<PathBuf> {
+  let path = Path::new(base_dir).join(ROLLUP_FILE);
+
+  crate::validate_output::check(crate::validate_output::Artifact::Rollup, &path, rollup)?;
+  crate::util::write_output(&path, &serde_json::to_vec_pretty(rollup)?)?;
+
+  Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn write_report(dir: &Path, label: &str, count: i64, add: i64, del: i64, authors: &[(&str, i64)]) -> RangeEntry {
+    let authors: BTreeMap<&str, i64> = authors.iter().cloned().collect();
+    let file = format!("report-{}.json", label);
+    let v = serde_json::json!({
+      "summary": { "count": count, "changeset": { "additions": add, "deletions": del, "files_touched": 0 } },
+      "authors": authors,
+    });
+    std::fs::write(dir.join(&file), serde_json::to_vec(&v).unwrap()).unwrap();
+
+    RangeEntry {
+      label: label.into(),
+      start: format!("{}-01", label),
+      end: String::new(),
+      file,
+    }
+  }
+
+  #[test]
+  fn rollup_totals_trend_and_retention() {
+    let td = tempfile::TempDir::new().unwrap();
+    let entries = vec![
+      write_report(td.path(), "2025-07", 3, 10, 2, &[("A <a@x>", 2), ("B <b@x>", 1)]),
+      write_report(td.path(), "2025-08", 2, 4, 4, &[("A <a@x>", 1), ("C <c@x>", 1)]),
+    ];
+
+    let r = build_rollup("<repo>", Local::now(), &td.path().to_string_lossy(), &entries).unwrap();
+
+    assert_eq!(r.totals.commits, 5);
+    assert_eq!(r.totals.authors, 3);
+    assert_eq!(r.trend[0].churn, 12);
+    assert_eq!(r.trend[1].churn_delta, Some(-4));
+    assert_eq!(
+      r.retention[1],
+      RetentionPoint {
+        label: "2025-08".into(),
+        active: 2,
+        retained: 1,
+        new: 1,
+        departed: 1,
+      }
+    );
+    assert_eq!(r.authors["A <a@x>"].active_ranges, 2);
+    assert_eq!(r.authors["A <a@x>"].last_range, "2025-08");
+    assert_eq!(r.authors["B <b@x>"].first_range, "2025-07");
+  }
+
+  #[test]
+  fn missing_range_report_is_an_error() {
+    let td = tempfile::TempDir::new().unwrap();
+    let entries = vec![RangeEntry {
+      label: "x".into(),
+      start: String::new(),
+      end: String::new(),
+      file: "nope.json".into(),
+    }];
+
+    assert!(build_rollup("<repo>", Local::now(), &td.path().to_string_lossy(), &entries).is_err());
+  }
+}
@@ -0,0 +1,283 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --sample N previews: pick a stratified commit sample (ISO week × author) and extrapolate range totals
+// role: processing/sampling
+// inputs: (sha, committer epoch, author email) for every commit in the window; sample size; processed sampled commits
+// outputs: SamplePlan (picked shas + strata sizes); SampleEstimate for summary.sample
+// side_effects: None
+// invariants:
+// - deterministic: strata ordered by key, members by (epoch, sha); picks are evenly spaced within a stratum
+// - when N >= strata, every stratum gets one pick and the rest are allotted by largest remainder of its size
+// - when N < strata, the largest strata get one pick each; unsampled strata are imputed with the overall sample mean
+// - intervals are 95% normal approximations of the stratified total; `low` never drops below the sampled sum
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{Datelike, TimeZone, Utc};
+
+use crate::model::{Commit, EstimatedTotal, SampleEstimate};
+
+const Z_95: f64 = 1.96;
+
+/// One stratum: its population size and the shas picked from it.
+#[derive(Debug, Clone)]
+pub struct Stratum {
+  pub population: usize,
+  pub picked: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SamplePlan {
+  pub population: usize,
+  pub strata: Vec<Stratum>,
+}
+
+impl SamplePlan {
+  pub fn picked(&self) -> impl Iterator<Item = &String> {
+    self.strata.iter().flat_map(|s| s.picked.iter())
+  }
+}
+
+fn stratum_key(epoch: i64, author_email: &str) -> String {
+  let week = Utc
+    .timestamp_opt(epoch, 0)
+    .single()
+    .map(|d| d.iso_week())
+    .map(|w| format!("{}-W{:02}", w.year(), w.week()))
+    .unwrap_or_default();
+
+  format!("{} {}", week, author_email.to_lowercase())
+}
+
+/// Choose `n` commits stratified by ISO week (UTC) and author email.
+pub fn stratified_sample(population: &[(String, i64, String)], n: usize) -> SamplePlan {
+  let mut groups: BTreeMap<String, Vec<(i64, &str)>> = BTreeMap::new();
+
+  for (sha, epoch, email) in population {
+    groups
+      .entry(stratum_key(*epoch, email))
+      .or_default()
+      .push((*epoch, sha.as_str()));
+  }
+
+  let groups: Vec<(String, Vec<(i64, &str)>)> = groups
+    .into_iter()
+    .map(|(k, mut members)| {
+      members.sort();
+      (k, members)
+    })
+    .collect();
+  let sizes: Vec<usize> = groups.iter().map(|(_, m)| m.len()).collect();
+  let alloc = allocate(&sizes, n);
+
+  SamplePlan {
+    population: population.len(),
+    strata: groups
+      .into_iter()
+      .zip(alloc)
+      .map(|((_, members), take)| Stratum {
+        population: members.len(),
+        picked: (0..take)
+          .map(|i| members[(2 * i + 1) * members.len() / (2 * take)].1.to_string())
+          .collect(),
+      })
+      .collect(),
+  }
+}
+
+/// Picks per stratum summing to min(n, total), each capped at the stratum size.
+fn allocate(sizes: &[usize], n: usize) -> Vec<usize> {
+  let mut alloc = vec![0usize; sizes.len()];
+
+  if n < sizes.len() {
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by(|a, b| sizes[*b].cmp(&sizes[*a]).then(a.cmp(b)));
+
+    for i in order.into_iter().take(n) {
+      alloc[i] = 1;
+    }
+
+    return alloc;
+  }
+
+  alloc.iter_mut().for_each(|a| *a = 1);
+
+  let spare: usize = sizes.iter().map(|s| s - 1).sum();
+  let extra = (n - sizes.len()).min(spare);
+
+  if extra == 0 {
+    return alloc;
+  }
+
+  // Largest remainder over each stratum's spare capacity
+  let quotas: Vec<f64> = sizes
+    .iter()
+    .map(|s| (s - 1) as f64 * extra as f64 / spare as f64)
+    .collect();
+  let mut given = 0;
+
+  for (a, q) in alloc.iter_mut().zip(&quotas) {
+    *a += q.floor() as usize;
+    given += q.floor() as usize;
+  }
+
+  let mut order: Vec<usize> = (0..sizes.len()).collect();
+  order.sort_by(|a, b| {
+    let ra = quotas[*a] - quotas[*a].floor();
+    let rb = quotas[*b] - quotas[*b].floor();
+    rb.total_cmp(&ra).then(a.cmp(b))
+  });
+
+  let open: Vec<usize> = order.into_iter().filter(|i| alloc[*i] < sizes[*i]).collect();
+
+  for i in open.into_iter().take(extra - given) {
+    alloc[i] += 1;
+  }
+
+  alloc
+}
+
+fn mean_var(values: &[f64]) -> (f64, f64) {
+  let n = values.len() as f64;
+  let mean = values.iter().sum::<f64>() / n.max(1.0);
+  let var = if values.len() > 1 {
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0)
+  } else {
+    0.0
+  };
+
+  (mean, var)
+}
+
+/// Stratified estimate of a per-commit metric's range total.
+fn estimate_total(plan: &SamplePlan, by_sha: &HashMap<&str, &Commit>, metric: fn(&Commit) -> i64) -> EstimatedTotal {
+  let value_of = |sha: &String| by_sha.get(sha.as_str()).map(|c| metric(c) as f64);
+  let all: Vec<f64> = plan.picked().filter_map(value_of).collect();
+  let (overall_mean, overall_var) = mean_var(&all);
+  let sampled_sum: f64 = all.iter().sum();
+
+  let mut total = 0.0;
+  let mut variance = 0.0;
+
+  for s in &plan.strata {
+    let big_n = s.population as f64;
+    let values: Vec<f64> = s.picked.iter().filter_map(value_of).collect();
+
+    if values.is_empty() {
+      // Unsampled stratum: impute the overall mean
+      total += big_n * overall_mean;
+      variance += big_n * big_n * overall_var / (all.len() as f64).max(1.0);
+      continue;
+    }
+
+    let n = values.len() as f64;
+    let (mean, var) = mean_var(&values);
+    let var = if values.len() > 1 { var } else { overall_var };
+
+    total += big_n * mean;
+    variance += big_n * big_n * (1.0 - n / big_n) * var / n;
+  }
+
+  let half = Z_95 * variance.sqrt();
+
+  EstimatedTotal {
+    estimate: total.round() as i64,
+    low: (total - half).max(sampled_sum).round() as i64,
+    high: (total + half).max(sampled_sum).round() as i64,
+  }
+}
+
+/// Extrapolate additions, deletions, and file changes from the processed sample to the whole range.
+pub fn estimate(plan: &SamplePlan, commits: &[Commit]) -> SampleEstimate {
+  let by_sha: HashMap<&str, &Commit> = commits.iter().map(|c| (c.sha.as_str(), c)).collect();
+
+  SampleEstimate {
+    method: "stratified by ISO week and author".into(),
+    sampled: commits.len(),
+    population: plan.population,
+    strata: plan.strata.len(),
+    confidence: 0.95,
+    additions: estimate_total(plan, &by_sha, |c| crate::commit::commit_additions_deletions(c).0),
+    deletions: estimate_total(plan, &by_sha, |c| crate::commit::commit_additions_deletions(c).1),
+    file_changes: estimate_total(plan, &by_sha, |c| {
+      (c.files.len() + c.files_truncated.as_ref().map_or(0, |t| t.omitted)) as i64
+    }),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const WEEK: i64 = 7 * 24 * 3600;
+  /// 2025-08-04T00:00:00Z (a Monday)
+  const BASE: i64 = 1_754_265_600;
+
+  fn population() -> Vec<(String, i64, String)> {
+    // week 1: ann ×6, bob ×2; week 2: ann ×2
+    let mut p = Vec::new();
+    for i in 0..6 {
+      p.push((format!("a1{}", i), BASE + i * 60, "ann@x".to_string()));
+    }
+    for i in 0..2 {
+      p.push((format!("b1{}", i), BASE + 3600 + i * 60, "Bob@X".to_string()));
+    }
+    for i in 0..2 {
+      p.push((format!("a2{}", i), BASE + WEEK + i * 60, "ann@x".to_string()));
+    }
+    p
+  }
+
+  fn commit(sha: &str, additions: i64) -> Commit {
+    Commit::fixture(sha).with_files(&[("f", additions, 0)])
+  }
+
+  #[test]
+  fn every_stratum_is_represented_and_sizes_are_proportional() {
+    let plan = stratified_sample(&population(), 5);
+
+    let picks: Vec<(usize, usize)> = plan.strata.iter().map(|s| (s.population, s.picked.len())).collect();
+    // strata in key order: 2025-W32 ann@x, 2025-W32 bob@x, 2025-W33 ann@x
+    assert_eq!(picks, vec![(6, 3), (2, 1), (2, 1)]);
+    assert_eq!(stratum_key(BASE + WEEK, "Bob@X"), "2025-W33 bob@x");
+    // evenly spaced within the stratum
+    assert_eq!(plan.strata[0].picked, vec!["a11", "a13", "a15"]);
+  }
+
+  #[test]
+  fn fewer_picks_than_strata_favors_the_largest() {
+    assert_eq!(allocate(&[2, 6, 2], 1), vec![0, 1, 0]);
+    assert_eq!(allocate(&[1, 1], 10), vec![1, 1]);
+  }
+
+  #[test]
+  fn uniform_strata_extrapolate_exactly() {
+    let plan = stratified_sample(&population(), 5);
+    let commits: Vec<Commit> = plan.picked().map(|sha| commit(sha, 10)).collect();
+
+    let e = estimate(&plan, &commits);
+
+    assert_eq!((e.sampled, e.population, e.strata), (5, 10, 3));
+    assert_eq!(e.additions.estimate, 100);
+    assert_eq!((e.additions.low, e.additions.high), (100, 100));
+    assert_eq!(e.file_changes.estimate, 10);
+  }
+
+  #[test]
+  fn interval_widens_with_spread_and_stays_above_sampled_sum() {
+    let plan = stratified_sample(&population(), 5);
+    let commits: Vec<Commit> = plan
+      .picked()
+      .enumerate()
+      .map(|(i, sha)| commit(sha, if i % 2 == 0 { 1 } else { 50 }))
+      .collect();
+
+    let e = estimate(&plan, &commits);
+
+    assert!(e.additions.low < e.additions.estimate && e.additions.estimate < e.additions.high);
+    assert!(e.additions.low >= commits.iter().map(|c| c.files[0].additions.unwrap()).sum::<i64>());
+  }
+}
@@ -0,0 +1,125 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --emit-schema: print the JSON Schema of an output shape, generated from the model types of this exact binary
+// role: output/schema
+// inputs: Schema kind: simple (range report), commit (commit shard), overall (manifest.json)
+// outputs: JSON Schema (draft 2020-12) text for stdout
+// side_effects: None
+// invariants:
+// - schemas are derived (schemars) from the same types the writers serialize, so they cannot drift from this build's output
+// - $comment records the producing binary version; CI can pin validation to the version it runs
+// - tests/schemas/*.json stay the hand-maintained contract (stricter: additionalProperties false); these generated schemas accept a superset
+// errors: Unknown kinds are rejected by clap before emit() runs
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use anyhow::{Result, bail};
+
+use crate::manifest::Manifest;
+use crate::model::{Commit, SimpleReport};
+
+/// Kinds `--emit-schema` accepts.
+pub const SCHEMA_KINDS: [&str; 3] = ["simple", "commit", "overall"];
+
+/// JSON Schema for one output `kind`, as a JSON value.
+pub fn schema_value(kind: &str) -> Result<serde_json::Value> {
+  let (schema, title) = match kind {
+    "simple" => (schemars::schema_for!(SimpleReport), "Git Activity Report - Report"),
+    "commit" => (schemars::schema_for!(Commit), "Git Activity Report - Commit"),
+    "overall" => (
+      schemars::schema_for!(Manifest),
+      "Git Activity Report - Overall Manifest",
+    ),
+    _ => bail!(
+      "--emit-schema: unknown kind {:?}; expected one of: {}",
+      kind,
+      SCHEMA_KINDS.join(", ")
+    ),
+  };
+
+  let mut v = serde_json::to_value(schema)?;
+  v["title"] = title.into();
+  v["$comment"] = format!("generated by git-activity-report {}", env!("CARGO_PKG_VERSION")).into();
+
+  Ok(v)
+}
+
+/// `--emit-schema KIND`: pretty-printed schema text.
+pub fn emit(kind: &str) -> Result<String> {
+  Ok(serde_json::to_string_pretty(&schema_value(kind)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use serde_json::json;
+
+  fn assert_valid(kind: &str, instance: &serde_json::Value) {
+    let validator = jsonschema::validator_for(&schema_value(kind).unwrap()).unwrap();
+    let errors: Vec<String> = validator.iter_errors(instance).map(|e| e.to_string()).collect();
+    assert!(errors.is_empty(), "{} schema rejected output: {:?}", kind, errors);
+  }
+
+  #[test]
+  fn generated_schemas_accept_real_output() {
+    let commit = Commit::fixture("a1b2c3d4e5").with_files(&[("a.rs", 1, 0)]);
+    let commit = serde_json::to_value(&commit).unwrap();
+    assert_valid("commit", &commit);
+
+    let report: SimpleReport = serde_json::from_value(json!({
+      "summary": {
+        "repo": "/src/widgets",
+        "range": { "label": "2025-08", "start": "2025-08-01T00:00:00", "end": "2025-09-01T00:00:00" },
+        "count": 1,
+        "report_options": { "include_merges": false, "include_patch": false, "include_unmerged": false, "tz": "utc" },
+        "changeset": { "additions": 1, "deletions": 0, "files_touched": 1 }
+      },
+      "authors": { "Ann <ann@x>": 1 },
+      "commits": [commit.clone()]
+    }))
+    .unwrap();
+    assert_valid("simple", &serde_json::to_value(&report).unwrap());
+
+    let metric = |rolling_avg: f64| crate::trends::MetricTrend {
+      rolling_avg,
+      direction: Some("up".into()),
+    };
+    let td = tempfile::TempDir::new().unwrap();
+    let base = td.path().to_string_lossy().to_string();
+    let entries = vec![crate::manifest::RangeEntry {
+      label: "2025-08".into(),
+      start: "2025-08-01T00:00:00".into(),
+      end: "2025-09-01T00:00:00".into(),
+      file: "report-2025-08.json".into(),
+    }];
+    let path = crate::manifest::write_overall_manifest(
+      "<repo>",
+      chrono::Local::now(),
+      true,
+      false,
+      false,
+      false,
+      &[],
+      &[crate::trends::RangeTrend {
+        commits: metric(4.0),
+        churn: metric(120.5),
+        authors: metric(2.0),
+        estimated_minutes: None,
+      }],
+      &[crate::model::Warning {
+        stage: "github".into(),
+        sha: None,
+        message: "3 GitHub request(s) skipped".into(),
+      }],
+      &base,
+      &entries,
+    )
+    .unwrap();
+    let manifest: serde_json::Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+    assert_valid("overall", &manifest);
+
+    // a commit is not a report
+    assert!(!jsonschema::is_valid(&schema_value("simple").unwrap(), &commit));
+    assert!(schema_value("rollup").is_err());
+  }
+}
@@ -0,0 +1,141 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Map file paths to logical services/components via a JSON config of path globs
+// role: services/mapping
+// inputs: --service-map JSON file ({"services":[{"glob","service"}]}); file paths from commit entries
+// outputs: ServiceMap (ordered rules); service name per FileEntry
+// side_effects: Reads the config file once during normalize
+// invariants:
+// - rules are evaluated in file order; the first matching glob wins
+// - `*` and `?` never cross `/`; `**` spans directories and `**/` also matches zero directories
+// - files matching no rule keep `service` unset
+// errors: Config read/parse failures bubble up with the config path as context
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use anyhow::{Context, Result};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::model::FileEntry;
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ServiceRule {
+  pub glob: String,
+  pub service: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ServiceMap {
+  pub services: Vec<ServiceRule>,
+}
+
+impl ServiceMap {
+  /// Load a service map from a JSON file.
+  pub fn load(path: &str) -> Result<Self> {
+    let text = std::fs::read_to_string(path).with_context(|| format!("reading service map {}", path))?;
+    let map: ServiceMap = serde_json::from_str(&text).with_context(|| format!("parsing service map {}", path))?;
+
+    Ok(map)
+  }
+
+  /// Service for `path` (first matching rule), if any.
+  pub fn service_for(&self, path: &str) -> Option<&str> {
+    self
+      .services
+      .iter()
+      .find(|r| glob_match(r.glob.as_bytes(), path.as_bytes()))
+      .map(|r| r.service.as_str())
+  }
+
+  /// Attach `service` to each file entry that matches a rule.
+  pub fn tag_files(&self, files: &mut [FileEntry]) {
+    for f in files.iter_mut() {
+      f.service = self.service_for(&f.file).map(|s| s.to_string());
+    }
+  }
+}
+
+/// Minimal path glob matcher supporting `*`, `?`, and `**`.
+pub(crate) fn glob_match(pattern: &[u8], path: &[u8]) -> bool {
+  match pattern.first() {
+    None => path.is_empty(),
+    Some(b'*') if pattern.get(1) == Some(&b'*') => {
+      let rest = &pattern[2..];
+
+      // `**/` may also match zero directories
+      if rest.first() == Some(&b'/') && glob_match(&rest[1..], path) {
+        return true;
+      }
+
+      (0..=path.len()).any(|i| glob_match(rest, &path[i..]))
+    }
+    Some(b'*') => {
+      let rest = &pattern[1..];
+
+      for i in 0..=path.len() {
+        if glob_match(rest, &path[i..]) {
+          return true;
+        }
+
+        if i < path.len() && path[i] == b'/' {
+          break;
+        }
+      }
+
+      false
+    }
+    Some(b'?') => !path.is_empty() && path[0] != b'/' && glob_match(&pattern[1..], &path[1..]),
+    Some(c) => path.first() == Some(c) && glob_match(&pattern[1..], &path[1..]),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rule(glob: &str, service: &str) -> ServiceRule {
+    ServiceRule {
+      glob: glob.into(),
+      service: service.into(),
+    }
+  }
+
+  #[test]
+  fn glob_semantics() {
+    assert!(glob_match(b"services/auth/**", b"services/auth/src/lib.rs"));
+    assert!(!glob_match(b"services/auth/**", b"services/billing/x.rs"));
+    assert!(glob_match(b"src/*.rs", b"src/main.rs"));
+    assert!(!glob_match(b"src/*.rs", b"src/enrichment/mod.rs"));
+    assert!(glob_match(b"**/*.md", b"README.md"));
+    assert!(glob_match(b"**/*.md", b"docs/guide/intro.md"));
+    assert!(glob_match(b"file?.txt", b"file1.txt"));
+    assert!(!glob_match(b"file?.txt", b"file/.txt"));
+  }
+
+  #[test]
+  fn first_matching_rule_wins() {
+    let map = ServiceMap {
+      services: vec![
+        rule("services/auth/legacy/**", "auth-legacy"),
+        rule("services/auth/**", "auth"),
+      ],
+    };
+
+    assert_eq!(map.service_for("services/auth/legacy/a.rs"), Some("auth-legacy"));
+    assert_eq!(map.service_for("services/auth/b.rs"), Some("auth"));
+    assert_eq!(map.service_for("README.md"), None);
+  }
+
+  #[test]
+  fn load_parses_json_config() {
+    let td = tempfile::TempDir::new().unwrap();
+    let p = td.path().join("services.json");
+    std::fs::write(&p, r#"{"services":[{"glob":"api/**","service":"api"}]}"#).unwrap();
+
+    let map = ServiceMap::load(&p.to_string_lossy()).unwrap();
+
+    assert_eq!(map.services.len(), 1);
+    assert_eq!(map.service_for("api/v1/users.rs"), Some("api"));
+  }
+}
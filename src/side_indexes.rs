@@ -0,0 +1,168 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Per-author and per-day side index files for split-apart ranges (--side-indexes)
+// role: persistence/side-indexes
+// inputs: Range subdir, range label, processed commits with their manifest items (same order)
+// outputs: <label>/authors/<slug>.json and <label>/days/<YYYY-MM-DD>.json; SideIndexes map for the range report
+// side_effects: Creates directories and writes JSON files under the range subdir
+// invariants:
+// - index `items` use the same base-dir-relative `file` paths as the range report's `items`
+// - days come from the commit's local timestamp in the report tz, matching shard file names
+// - author slugs are derived from "Name <email>"; colliding slugs get -2, -3, ... in sorted author order
+// errors: IO errors surfaced with file path context
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::model::{Commit, ManifestItem, SideIndexes};
+
+#[derive(Debug, Serialize)]
+struct IndexFile<'a> {
+  kind: &'static str,
+  key: &'a str,
+  range: &'a str,
+  count: usize,
+  items: Vec<&'a ManifestItem>,
+}
+
+/// Write author and day indexes for one range; returns key → file maps (paths relative to the base dir).
+pub fn write_side_indexes(
+  subdir: &Path,
+  label: &str,
+  commits: &[Commit],
+  items: &[ManifestItem],
+) -> Result<SideIndexes> {
+  let mut by_author: BTreeMap<String, Vec<&ManifestItem>> = BTreeMap::new();
+  let mut by_day: BTreeMap<String, Vec<&ManifestItem>> = BTreeMap::new();
+
+  for (commit, item) in commits.iter().zip(items) {
+    let author = commit.author.key();
+    let day: String = commit.timestamps.commit_local.chars().take(10).collect();
+
+    by_author.entry(author).or_default().push(item);
+    by_day.entry(day).or_default().push(item);
+  }
+
+  let mut indexes = SideIndexes::default();
+  let mut used_slugs: BTreeMap<String, usize> = BTreeMap::new();
+
+  for (author, entries) in &by_author {
+    let base = author_slug(author);
+    let n = used_slugs.entry(base.clone()).or_insert(0);
+    *n += 1;
+    let slug = if *n == 1 { base } else { format!("{}-{}", base, n) };
+
+    let rel = format!("authors/{}.json", slug);
+    write_index(subdir, &rel, "author", author, label, entries)?;
+    indexes.authors.insert(author.clone(), format!("{}/{}", label, rel));
+  }
+
+  for (day, entries) in &by_day {
+    let rel = format!("days/{}.json", day);
+    write_index(subdir, &rel, "day", day, label, entries)?;
+    indexes.days.insert(day.clone(), format!("{}/{}", label, rel));
+  }
+
+  Ok(indexes)
+}
+
+fn write_index(
+  subdir: &Path,
+  rel: &str,
+  kind: &'static str,
+  key: &str,
+  label: &str,
+  items: &[&ManifestItem],
+) -> Result<()> {
+  let path = subdir.join(rel);
+
+  if let Some(parent) = path.parent() {
+    std::fs::create_dir_all(parent)?;
+  }
+
+  let index = IndexFile {
+    kind,
+    key,
+    range: label,
+    count: items.len(),
+    items: items.to_vec(),
+  };
+  crate::util::write_output(&path, &serde_json::to_vec_pretty(&index)?)?;
+
+  Ok(())
+}
+
+/// Filesystem-safe slug for an author key: lowercase ASCII alphanumerics joined by single dashes.
+pub fn author_slug(author: &str) -> String {
+  let mut slug = String::new();
+
+  for c in author.chars() {
+    if c.is_ascii_alphanumeric() {
+      slug.push(c.to_ascii_lowercase());
+    } else if !slug.ends_with('-') && !slug.is_empty() {
+      slug.push('-');
+    }
+  }
+
+  let slug = slug.trim_end_matches('-').to_string();
+
+  if slug.is_empty() {
+    return "unknown".into();
+  }
+
+  slug
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn commit(name: &str, email: &str, local: &str) -> Commit {
+    Commit::fixture("abc").with_author(name, email).at(0, local)
+  }
+
+  fn item(file: &str) -> ManifestItem {
+    ManifestItem {
+      sha: "abc".into(),
+      file: file.into(),
+      subject: "s".into(),
+    }
+  }
+
+  #[test]
+  fn slugs_are_ascii_and_dash_separated() {
+    assert_eq!(author_slug("Jane Doe <jane@example.com>"), "jane-doe-jane-example-com");
+    assert_eq!(author_slug("Zoë <z@x>"), "zo-z-x");
+    assert_eq!(author_slug("<>"), "unknown");
+  }
+
+  #[test]
+  fn writes_author_and_day_indexes() {
+    let td = tempfile::TempDir::new().unwrap();
+    let commits = vec![
+      commit("Jane", "j@x", "2025-08-13T10:00:00+00:00"),
+      commit("Bob", "b@x", "2025-08-13T12:00:00+00:00"),
+      commit("Jane", "j@x", "2025-08-14T09:00:00+00:00"),
+    ];
+    let items = vec![item("w/1.json"), item("w/2.json"), item("w/3.json")];
+
+    let idx = write_side_indexes(td.path(), "w", &commits, &items).unwrap();
+
+    assert_eq!(idx.authors["Jane <j@x>"], "w/authors/jane-j-x.json");
+    assert_eq!(idx.days["2025-08-13"], "w/days/2025-08-13.json");
+
+    let day: serde_json::Value =
+      serde_json::from_slice(&std::fs::read(td.path().join("days/2025-08-13.json")).unwrap()).unwrap();
+    assert_eq!(day["count"], 2);
+    assert_eq!(day["items"][1]["file"], "w/2.json");
+
+    let jane: serde_json::Value =
+      serde_json::from_slice(&std::fs::read(td.path().join("authors/jane-j-x.json")).unwrap()).unwrap();
+    assert_eq!(jane["count"], 2);
+  }
+}
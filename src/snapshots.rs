@@ -0,0 +1,202 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Capture post-image content of changed files matching --snapshot-path globs (compliance archives of critical files)
+// role: enrichment/file-snapshots
+// inputs: Commit files (pre-redaction paths), snapshot globs, size cap; output dir for split-apart runs
+// outputs: commit.file_snapshots (embedded, capped content) or full-content files under <dir>/snapshots/<short_sha>/<path>
+// side_effects: git rev-parse/cat-file per matching file; writes snapshot files in split-apart runs
+// invariants:
+// - deleted files have no post-image and are skipped
+// - paths that --redact-paths masks are never snapshotted (content would reveal what redaction hides)
+// - non-UTF-8 (or NUL-containing) content is marked binary and only ever written to disk, never embedded
+// - written snapshots drop the embedded content; snapshot_file is relative to the output base dir
+// errors: git failures for a file skip that file (best-effort); IO errors while writing surface with the file path
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use crate::gitio;
+use crate::model::{Commit, FileSnapshot};
+use crate::redact::PathRedactor;
+use crate::services::glob_match;
+use crate::util::clip_patch;
+
+/// Default `--snapshot-max-bytes`.
+pub const DEFAULT_SNAPSHOT_MAX_BYTES: usize = 64 * 1024;
+
+fn matches(globs: &[String], path: &str) -> bool {
+  globs.iter().any(|g| glob_match(g.as_bytes(), path.as_bytes()))
+}
+
+fn is_binary(bytes: &[u8]) -> bool {
+  bytes.contains(&0) || std::str::from_utf8(bytes).is_err()
+}
+
+/// Snapshots for the commit's changed files that match `globs` (content capped at `max_bytes`; 0 = no cap).
+pub fn collect_snapshots(
+  commit: &Commit,
+  repo: &str,
+  globs: &[String],
+  max_bytes: usize,
+  redactor: Option<&PathRedactor>,
+) -> Option<Vec<FileSnapshot>> {
+  if globs.is_empty() {
+    return None;
+  }
+
+  let snapshots: Vec<FileSnapshot> = commit
+    .files
+    .iter()
+    .filter(|f| !f.status.starts_with('D') && matches(globs, &f.file))
+    .filter(|f| redactor.is_none_or(|r| r.redact_path(&f.file).is_none()))
+    .filter_map(|f| {
+      let (blob, bytes) = gitio::file_at(repo, &commit.sha, &f.file).ok()?;
+      let size = bytes.len();
+
+      if is_binary(&bytes) {
+        return Some(FileSnapshot {
+          file: f.file.clone(),
+          blob,
+          size,
+          content: None,
+          clipped: None,
+          binary: Some(true),
+          snapshot_file: None,
+        });
+      }
+
+      let (content, clipped) = clip_patch(String::from_utf8(bytes).ok()?, max_bytes);
+
+      Some(FileSnapshot {
+        file: f.file.clone(),
+        blob,
+        size,
+        content,
+        clipped,
+        binary: None,
+        snapshot_file: None,
+      })
+    })
+    .collect();
+
+  (!snapshots.is_empty()).then_some(snapshots)
+}
+
+/// Write full snapshot contents under `dir/snapshots/<short_sha>/` and point `snapshot_file` at them.
+///
+/// `rel_dir` is `dir` relative to the output base dir (e.g. the range label).
+pub fn write_snapshot_files(commit: &mut Commit, repo: &str, dir: &Path, rel_dir: &Path) -> Result<()> {
+  let Some(snapshots) = commit.file_snapshots.as_mut() else {
+    return Ok(());
+  };
+
+  for s in snapshots.iter_mut() {
+    let rel = Path::new("snapshots").join(&commit.short_sha).join(&s.file);
+    let path = dir.join(&rel);
+    let (_, bytes) = gitio::file_at(repo, &commit.sha, &s.file)?;
+
+    if let Some(parent) = path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, bytes).with_context(|| format!("writing {}", path.display()))?;
+
+    s.content = None;
+    s.clipped = None;
+    s.snapshot_file = Some(rel_dir.join(rel).to_string_lossy().to_string());
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::model::FileEntry;
+  use std::process::Command;
+
+  fn git(dir: &Path, args: &[&str]) -> String {
+    let out = Command::new("git")
+      .args(args)
+      .current_dir(dir)
+      .env("GIT_AUTHOR_NAME", "A")
+      .env("GIT_AUTHOR_EMAIL", "a@x")
+      .env("GIT_COMMITTER_NAME", "A")
+      .env("GIT_COMMITTER_EMAIL", "a@x")
+      .output()
+      .unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    String::from_utf8_lossy(&out.stdout).trim().to_string()
+  }
+
+  fn repo_with_policy() -> (tempfile::TempDir, Commit) {
+    let td = tempfile::TempDir::new().unwrap();
+    let dir = td.path();
+    git(dir, &["init", "-q"]);
+    std::fs::create_dir_all(dir.join("policy")).unwrap();
+    std::fs::write(dir.join("policy/SECURITY.md"), "report to security@example.com\n").unwrap();
+    std::fs::write(dir.join("policy/logo.bin"), [0u8, 159, 146, 150]).unwrap();
+    std::fs::write(dir.join("README.md"), "hi\n").unwrap();
+    git(dir, &["add", "."]);
+    git(dir, &["commit", "-q", "-m", "init"]);
+    let sha = git(dir, &["rev-parse", "HEAD"]);
+
+    let mut commit = Commit::fixture(&sha);
+    commit.files = ["README.md", "policy/SECURITY.md", "policy/logo.bin"]
+      .iter()
+      .map(|f| FileEntry::fixture(f, "A"))
+      .collect();
+
+    (td, commit)
+  }
+
+  #[test]
+  fn embeds_capped_text_and_marks_binary() {
+    let (td, commit) = repo_with_policy();
+    let repo = td.path().to_string_lossy().to_string();
+
+    let snaps = collect_snapshots(&commit, &repo, &["policy/**".into()], 6, None).unwrap();
+
+    assert_eq!(snaps.len(), 2);
+    assert_eq!(snaps[0].file, "policy/SECURITY.md");
+    assert_eq!(snaps[0].content.as_deref(), Some("report"));
+    assert_eq!(snaps[0].clipped, Some(true));
+    assert_eq!(snaps[0].size, 31);
+    assert_eq!(snaps[1].binary, Some(true));
+    assert!(snaps[1].content.is_none());
+    assert!(collect_snapshots(&commit, &repo, &["*.tf".into()], 0, None).is_none());
+  }
+
+  #[test]
+  fn redacted_paths_are_not_snapshotted() {
+    let (td, commit) = repo_with_policy();
+    let repo = td.path().to_string_lossy().to_string();
+    let redactor = PathRedactor::new(&["policy/SECURITY.md".into()]).unwrap();
+
+    let snaps = collect_snapshots(&commit, &repo, &["policy/*".into()], 0, Some(&redactor)).unwrap();
+
+    assert_eq!(snaps.len(), 1);
+    assert_eq!(snaps[0].file, "policy/logo.bin");
+  }
+
+  #[test]
+  fn writes_full_content_next_to_shard() {
+    let (td, mut commit) = repo_with_policy();
+    let repo = td.path().to_string_lossy().to_string();
+    let out = tempfile::TempDir::new().unwrap();
+    commit.file_snapshots = collect_snapshots(&commit, &repo, &["policy/SECURITY.md".into()], 6, None);
+
+    write_snapshot_files(&mut commit, &repo, out.path(), Path::new("2025-08")).unwrap();
+
+    let s = &commit.file_snapshots.as_ref().unwrap()[0];
+    let rel = format!("snapshots/{}/policy/SECURITY.md", commit.short_sha);
+    assert_eq!(s.snapshot_file.as_deref(), Some(format!("2025-08/{}", rel).as_str()));
+    assert!(s.content.is_none() && s.clipped.is_none());
+    assert_eq!(
+      std::fs::read_to_string(out.path().join(rel)).unwrap(),
+      "report to security@example.com\n"
+    );
+  }
+}
@@ -0,0 +1,148 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Library CommitStream: yield processed Commit structs for a window lazily, without building a report
+// role: library/public-api
+// inputs: repo path, since/until (Git approxidate), StreamOptions
+// outputs: Iterator<Item = Result<Commit>> in report order (earliest→latest)
+// side_effects: git reads only; never writes files (no shards, patches, or snapshots on disk)
+// invariants:
+// - commit selection is gitio::rev_list and each item is commit::process_commit, the same calls the CLI makes
+// - the SHA list is resolved up front; each commit is processed only when `next()` reaches it
+// - range-level passes (patch budget, PR estimates, summaries) are not applied; items match single-report commits otherwise
+// errors: construction fails when rev-list fails or --bot-authors patterns are invalid; per-commit failures are yielded as Err
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+//! Lazy access to processed commits.
+//!
+//! ```no_run
+//! use git_activity_report::stream::{CommitStream, StreamOptions};
+//!
+//! let commits = CommitStream::new(".", "2025-08-01", "2025-09-01", StreamOptions::default())?;
+//!
+//! for commit in commits {
+//!   let commit = commit?;
+//!   println!("{} {}", commit.short_sha, commit.subject);
+//! }
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use anyhow::{Result, bail};
+
+use crate::automation::{AutomationDetector, DEFAULT_BOT_PATTERNS};
+use crate::commit::{ProcessContext, process_commit};
+use crate::gitio;
+use crate::model::Commit;
+use crate::util;
+
+/// Per-commit options; `Default` matches the CLI defaults.
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+  pub include_merges: bool,
+  pub include_patch: bool,
+  /// Per-commit patch cap (0 = no limit)
+  pub max_patch_bytes: usize,
+  /// Keep only the N highest-churn file entries per commit (None = all)
+  pub max_files_per_commit: Option<usize>,
+  /// "local", "utc", or an IANA zone such as "Europe/London"
+  pub tz: String,
+  pub estimate_effort: bool,
+  pub no_blob_stats: bool,
+  pub no_bodies: bool,
+  /// Look up PRs for each commit (network unless the GAR_TEST_* fixtures are set)
+  pub github_prs: bool,
+  pub env_trailers: Vec<String>,
+  pub bot_authors: Vec<String>,
+  /// Pathspecs limiting commits and their files, as `--path` (empty = whole tree)
+  pub paths: Vec<String>,
+}
+
+impl Default for StreamOptions {
+  fn default() -> Self {
+    Self {
+      include_merges: false,
+      include_patch: false,
+      max_patch_bytes: 0,
+      max_files_per_commit: None,
+      tz: "local".into(),
+      estimate_effort: false,
+      no_blob_stats: false,
+      no_bodies: false,
+      github_prs: false,
+      env_trailers: vec!["Deployed-To".into(), "Environment".into()],
+      bot_authors: DEFAULT_BOT_PATTERNS.map(String::from).to_vec(),
+      paths: Vec::new(),
+    }
+  }
+}
+
+/// Processed commits of `[since, until)` on HEAD, produced one at a time.
+pub struct CommitStream {
+  repo: String,
+  options: StreamOptions,
+  automation: AutomationDetector,
+  shas: std::vec::IntoIter<String>,
+}
+
+impl CommitStream {
+  pub fn new(repo: &str, since: &str, until: &str, options: StreamOptions) -> Result<Self> {
+    if !util::is_known_tz(&options.tz) {
+      bail!(
+        "unknown timezone {:?}; expected local, utc, or an IANA zone name",
+        options.tz
+      );
+    }
+
+    let repo = util::canonicalize_lossy(std::path::Path::new(repo));
+    let shas = gitio::rev_list(&repo, since, until, options.include_merges, &options.paths)?;
+    let automation = AutomationDetector::new(&options.bot_authors)?;
+
+    Ok(Self {
+      repo,
+      options,
+      automation,
+      shas: shas.into_iter(),
+    })
+  }
+
+  fn context(&self) -> ProcessContext<'_> {
+    ProcessContext {
+      repo: &self.repo,
+      tz: &self.options.tz,
+      github_prs: self.options.github_prs,
+      include_patch: self.options.include_patch,
+      max_patch_bytes: self.options.max_patch_bytes,
+      max_files_per_commit: self.options.max_files_per_commit,
+      estimate_effort: self.options.estimate_effort,
+      no_blob_stats: self.options.no_blob_stats,
+      service_map: None,
+      identity_map: None,
+      include_pr_patch: false,
+      pr_files: false,
+      redactor: None,
+      env_trailers: &self.options.env_trailers,
+      automation: Some(&self.automation),
+      issue_keys: None,
+      snapshot_paths: &[],
+      snapshot_max_bytes: 0,
+      no_bodies: self.options.no_bodies,
+      paths: &self.options.paths,
+    }
+  }
+}
+
+impl Iterator for CommitStream {
+  type Item = Result<Commit>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let sha = self.shas.next()?;
+
+    Some(process_commit(&sha, &self.context()))
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    self.shas.size_hint()
+  }
+}
+
+impl ExactSizeIterator for CommitStream {}
@@ -0,0 +1,197 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: OpenTelemetry spans for run phases (range resolution, per-commit processing, enrichment calls, file writes)
+// role: observability/tracing
+// inputs: OTEL_EXPORTER_OTLP_ENDPOINT / OTEL_EXPORTER_OTLP_TRACES_ENDPOINT and the other standard OTEL_* exporter variables
+// outputs: OTLP/HTTP spans under service.name=git-activity-report (only in builds with the `otel` feature)
+// side_effects: With `otel` and an endpoint set, a background batch exporter sends spans; flushed when the Telemetry guard drops
+// invariants:
+// - without the `otel` feature every type here is zero-sized and every call is a no-op
+// - spans nest under the span current on their thread; rayon workers continue the trace via Parent::attach
+// - tracing never changes report output or exit status; exporter setup failures only warn
+// errors: None surfaced (setup failures are logged and tracing stays off)
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+#[cfg(feature = "otel")]
+use opentelemetry::trace::{Status, TraceContextExt, Tracer};
+
+#[cfg(feature = "otel")]
+const TRACER: &str = "git-activity-report";
+
+/// Flushes and shuts down the exporter when dropped; hold it for the whole run.
+pub struct Telemetry {
+  #[cfg(feature = "otel")]
+  provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+/// Start exporting spans when an OTLP endpoint is configured; otherwise spans are discarded.
+pub fn init() -> Telemetry {
+  #[cfg(feature = "otel")]
+  {
+    let configured = ["OTEL_EXPORTER_OTLP_ENDPOINT", "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT"]
+      .iter()
+      .any(|k| std::env::var_os(k).is_some_and(|v| !v.is_empty()));
+
+    if !configured {
+      return Telemetry { provider: None };
+    }
+
+    let exporter = match opentelemetry_otlp::SpanExporter::builder().with_http().build() {
+      Ok(e) => e,
+      Err(e) => {
+        crate::util::log(
+          crate::util::LogLevel::Warn,
+          "otel",
+          &format!("tracing disabled: building the OTLP exporter failed: {}", e),
+        );
+        return Telemetry { provider: None };
+      }
+    };
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+      .with_batch_exporter(exporter)
+      .with_resource(
+        opentelemetry_sdk::Resource::builder()
+          .with_service_name(TRACER)
+          .with_attribute(opentelemetry::KeyValue::new(
+            "service.version",
+            env!("CARGO_PKG_VERSION"),
+          ))
+          .build(),
+      )
+      .build();
+
+    opentelemetry::global::set_tracer_provider(provider.clone());
+
+    Telemetry {
+      provider: Some(provider),
+    }
+  }
+
+  #[cfg(not(feature = "otel"))]
+  Telemetry {}
+}
+
+impl Drop for Telemetry {
+  fn drop(&mut self) {
+    #[cfg(feature = "otel")]
+    if let Some(provider) = self.provider.take() {
+      if let Err(e) = provider.shutdown() {
+        crate::util::log(
+          crate::util::LogLevel::Warn,
+          "otel",
+          &format!("flushing spans failed: {}", e),
+        );
+      }
+    }
+  }
+}
+
+/// One traced phase; current on this thread until dropped, when it ends.
+pub struct Span {
+  #[cfg(feature = "otel")]
+  cx: opentelemetry::Context,
+  #[cfg(feature = "otel")]
+  _current: opentelemetry::ContextGuard,
+}
+
+/// Start `name` as a child of this thread's current span.
+pub fn span(name: &'static str) -> Span {
+  #[cfg(feature = "otel")]
+  {
+    let parent = opentelemetry::Context::current();
+    let cx = parent.with_span(opentelemetry::global::tracer(TRACER).start_with_context(name, &parent));
+    let _current = cx.clone().attach();
+
+    Span { cx, _current }
+  }
+
+  #[cfg(not(feature = "otel"))]
+  {
+    let _ = name;
+    Span {}
+  }
+}
+
+impl Span {
+  /// Add a string attribute (range label, commit SHA, output path, ...).
+  pub fn attr(self, key: &'static str, value: impl Into<String>) -> Self {
+    #[cfg(feature = "otel")]
+    self
+      .cx
+      .span()
+      .set_attribute(opentelemetry::KeyValue::new(key, value.into()));
+    #[cfg(not(feature = "otel"))]
+    let _: (&str, String) = (key, value.into());
+
+    self
+  }
+
+  /// Mark the span as failed with `message`.
+  pub fn fail(&self, message: &str) {
+    #[cfg(feature = "otel")]
+    self.cx.span().set_status(Status::error(message.to_string()));
+    #[cfg(not(feature = "otel"))]
+    let _ = message;
+  }
+}
+
+impl Drop for Span {
+  fn drop(&mut self) {
+    #[cfg(feature = "otel")]
+    self.cx.span().end();
+  }
+}
+
+/// The current span, carried to worker threads so their spans join the same trace.
+#[derive(Clone)]
+pub struct Parent {
+  #[cfg(feature = "otel")]
+  cx: opentelemetry::Context,
+}
+
+/// Keeps a `Parent` current on a worker thread until dropped.
+pub struct Attached {
+  #[cfg(feature = "otel")]
+  _current: opentelemetry::ContextGuard,
+}
+
+/// Capture this thread's current span for use on other threads.
+pub fn current() -> Parent {
+  Parent {
+    #[cfg(feature = "otel")]
+    cx: opentelemetry::Context::current(),
+  }
+}
+
+impl Parent {
+  /// Make this span current on the calling thread; spans started while the guard lives are its children.
+  pub fn attach(&self) -> Attached {
+    Attached {
+      #[cfg(feature = "otel")]
+      _current: self.cx.clone().attach(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn spans_nest_across_threads_without_an_exporter() {
+    let _telemetry = init();
+    let run = span("run").attr("repo", "/tmp/repo");
+    let parent = current();
+
+    std::thread::spawn(move || {
+      let _attached = parent.attach();
+      let commit = span("process_commit").attr("commit.sha", "abc123");
+      commit.fail("boom");
+    })
+    .join()
+    .unwrap();
+
+    drop(run);
+  }
+}
@@ -0,0 +1,173 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Annotate each range of a multi-range run with 3-period rolling averages and trend direction for manifest.json
+// role: summary/trends
+// inputs: Rollup (per-range trend figures, in manifest order)
+// outputs: RangeTrend[] aligned with manifest ranges[] (empty for single-range runs)
+// side_effects: None
+// invariants:
+// - rolling_avg is the mean of the range and up to 2 preceding ranges (fewer at the start of the run), rounded to 2 decimals
+// - direction compares the rolling average with the previous range's: up/down beyond ±5%, else flat; absent on the first range
+// - estimated_minutes is annotated only when every range report carries summary.estimated_minutes (--estimate-effort)
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::rollup::Rollup;
+
+const ROLLING_PERIODS: usize = 3;
+const FLAT_TOLERANCE: f64 = 0.05;
+
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+pub struct MetricTrend {
+  pub rolling_avg: f64,
+  /// "up", "down", or "flat" versus the previous range's rolling average
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub direction: Option<String>,
+}
+
+/// Trend annotations for one manifest range entry.
+#[derive(Debug, Serialize, JsonSchema, PartialEq)]
+pub struct RangeTrend {
+  pub commits: MetricTrend,
+  pub churn: MetricTrend,
+  pub authors: MetricTrend,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  pub estimated_minutes: Option<MetricTrend>,
+}
+
+fn round2(x: f64) -> f64 {
+  (x * 100.0).round() / 100.0
+}
+
+fn direction(previous: f64, current: f64) -> &'static str {
+  let change = if previous == 0.0 {
+    current.signum()
+  } else {
+    (current - previous) / previous.abs()
+  };
+
+  if change > FLAT_TOLERANCE {
+    "up"
+  } else if change < -FLAT_TOLERANCE {
+    "down"
+  } else {
+    "flat"
+  }
+}
+
+fn metric_trends(values: &[f64]) -> Vec<MetricTrend> {
+  let averages: Vec<f64> = (0..values.len())
+    .map(|i| {
+      let window = &values[(i + 1).saturating_sub(ROLLING_PERIODS)..=i];
+
+      window.iter().sum::<f64>() / window.len() as f64
+    })
+    .collect();
+
+  averages
+    .iter()
+    .enumerate()
+    .map(|(i, avg)| MetricTrend {
+      rolling_avg: round2(*avg),
+      direction: (i > 0).then(|| direction(averages[i - 1], *avg).to_string()),
+    })
+    .collect()
+}
+
+/// Per-range trend annotations for the run summarized by `rollup`; empty unless it has consecutive ranges.
+pub fn build_trends(rollup: &Rollup) -> Vec<RangeTrend> {
+  if rollup.trend.len() < 2 {
+    return Vec::new();
+  }
+
+  let series =
+    |f: fn(&crate::rollup::RollupRange) -> f64| metric_trends(&rollup.trend.iter().map(f).collect::<Vec<_>>());
+  let commits = series(|r| r.commits as f64);
+  let churn = series(|r| r.churn as f64);
+  let authors = series(|r| r.authors as f64);
+  let minutes: Option<Vec<f64>> = rollup.trend.iter().map(|r| r.estimated_minutes).collect();
+  let mut minutes = minutes.map(|m| metric_trends(&m).into_iter());
+
+  commits
+    .into_iter()
+    .zip(churn)
+    .zip(authors)
+    .map(|((commits, churn), authors)| RangeTrend {
+      commits,
+      churn,
+      authors,
+      estimated_minutes: minutes.as_mut().and_then(Iterator::next),
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::rollup::{RollupRange, RollupTotals};
+
+  fn rollup(ranges: &[(i64, i64, usize, Option<f64>)]) -> Rollup {
+    Rollup {
+      repo: "<repo>".into(),
+      generated_at: String::new(),
+      ranges: ranges.len(),
+      totals: RollupTotals::default(),
+      trend: ranges
+        .iter()
+        .enumerate()
+        .map(|(i, (commits, churn, authors, minutes))| RollupRange {
+          label: format!("r{}", i),
+          start: String::new(),
+          end: String::new(),
+          commits: *commits,
+          additions: *churn,
+          deletions: 0,
+          churn: *churn,
+          churn_delta: None,
+          authors: *authors,
+          estimated_minutes: *minutes,
+        })
+        .collect(),
+      retention: Vec::new(),
+      authors: Default::default(),
+    }
+  }
+
+  #[test]
+  fn rolling_averages_cover_up_to_three_ranges() {
+    let t = build_trends(&rollup(&[
+      (3, 30, 2, Some(60.0)),
+      (6, 30, 2, Some(30.0)),
+      (9, 30, 2, Some(30.0)),
+      (1, 30, 2, Some(0.0)),
+    ]));
+
+    assert_eq!(t.len(), 4);
+    assert_eq!(
+      t[0].commits,
+      MetricTrend {
+        rolling_avg: 3.0,
+        direction: None,
+      }
+    );
+    assert_eq!(t[1].commits.rolling_avg, 4.5);
+    assert_eq!(t[2].commits.rolling_avg, 6.0);
+    assert_eq!(t[3].commits.rolling_avg, 5.33);
+    let directions: Vec<Option<&str>> = t.iter().map(|r| r.commits.direction.as_deref()).collect();
+    assert_eq!(directions, vec![None, Some("up"), Some("up"), Some("down")]);
+    assert!(t.iter().skip(1).all(|r| r.churn.direction.as_deref() == Some("flat")));
+    assert_eq!(t[3].estimated_minutes.as_ref().unwrap().rolling_avg, 20.0);
+  }
+
+  #[test]
+  fn single_range_or_partial_effort_yield_no_annotations() {
+    assert!(build_trends(&rollup(&[(3, 30, 2, None)])).is_empty());
+
+    let t = build_trends(&rollup(&[(3, 30, 2, Some(5.0)), (3, 30, 2, None)]));
+    assert!(t.iter().all(|r| r.estimated_minutes.is_none()));
+  }
+}
@@ -9,12 +9,20 @@
 // - prepare_out_dir returns an existing directory (either provided or temp timestamped)
 // - clip_patch never splits UTF-8; indicates clipping accurately
 // - format_shard_name pattern is stable and locale-independent
+// - every repo git invocation goes through git_command: --git-arg values first, then absolute GIT_DIR/GIT_WORK_TREE when set
+// - git_version runs `git --version` at most once per process; version gates treat an unknown version as current
+// - run_git retries lock-related failures (index.lock, gc.pid, ...) after a bounded wait unless --no-wait
+// - write_output replaces files atomically (sibling temp file + rename) in the --output-encoding; read_output undoes it
+// - human diagnostics go through log() as tracing events (see crate::logging): stderr only (stdout stays data), hidden by --quiet except errors, mirrored to --json-logs-to
+// - every warning is also kept in memory ({stage: log target, sha?, message}) for reports' and the manifest's warnings[] (see warnings_since)
 // errors: run_git surfaces command + stderr; IO errors bubble with context
 // tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
 // === Module Header END ===
 
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Local, SecondsFormat, TimeZone, Utc};
@@ -33,26 +41,232 @@ pub fn canonicalize_lossy<P: AsRef<Path>>(p: P) -> String {
   pb.to_string_lossy().to_string()
 }
 
+/// Global git options (`--git-arg`) and repository location overrides applied to every repo git invocation.
+#[derive(Debug, Clone, Default)]
+pub struct GitInvocation {
+  pub args: Vec<String>,
+  pub git_dir: Option<String>,
+  pub work_tree: Option<String>,
+}
+
+impl GitInvocation {
+  /// Build from `--git-arg` values plus GIT_DIR/GIT_WORK_TREE from the environment, made absolute
+  /// against the process cwd (git would otherwise resolve relative values against the repo dir).
+  pub fn from_env(args: Vec<String>) -> Self {
+    let abs = |key: &str| std::env::var_os(key).filter(|v| !v.is_empty()).map(canonicalize_lossy);
+
+    Self {
+      args,
+      git_dir: abs("GIT_DIR"),
+      work_tree: abs("GIT_WORK_TREE"),
+    }
+  }
+
+  /// Add the global args and location overrides to `cmd` (before any subcommand args).
+  fn apply(&self, cmd: &mut Command) {
+    cmd.args(&self.args);
+
+    if let Some(dir) = &self.git_dir {
+      cmd.env("GIT_DIR", dir);
+    }
+
+    if let Some(tree) = &self.work_tree {
+      cmd.env("GIT_WORK_TREE", tree);
+    }
+  }
+}
+
+static GIT_INVOCATION: once_cell::sync::OnceCell<GitInvocation> = once_cell::sync::OnceCell::new();
+
+/// Install the process-wide git invocation settings (first call wins).
+pub fn set_git_invocation(inv: GitInvocation) {
+  let _ = GIT_INVOCATION.set(inv);
+}
+
+/// The absolute GIT_DIR override, if one was installed; in-process readers (libgit2) must honor it like `git` does.
+pub fn git_dir_override() -> Option<String> {
+  GIT_INVOCATION.get().and_then(|inv| inv.git_dir.clone())
+}
+
+/// A `git` command for `repo` with the configured global args and GIT_DIR/GIT_WORK_TREE applied.
+pub fn git_command(repo: &str) -> Command {
+  let mut cmd = Command::new("git");
+  cmd.current_dir(repo);
+
+  if let Some(inv) = GIT_INVOCATION.get() {
+    inv.apply(&mut cmd);
+  }
+
+  cmd
+}
+
+/// Lock files left by concurrent git processes (gc, fetch, an editor running `git status`, ...).
+const GIT_LOCK_FILES: [&str; 4] = ["index.lock", "gc.pid", "packed-refs.lock", "shallow.lock"];
+/// Total time one command may spend waiting for those locks to clear (shared by its retries), and the retry cap.
+const LOCK_WAIT_MAX: Duration = Duration::from_secs(30);
+const LOCK_POLL: Duration = Duration::from_millis(250);
+const LOCK_RETRIES: usize = 3;
+
+static NO_WAIT: once_cell::sync::OnceCell<bool> = once_cell::sync::OnceCell::new();
+
+/// `--no-wait`: fail immediately instead of waiting out another git process's locks (first call wins).
+pub fn set_no_wait(no_wait: bool) {
+  let _ = NO_WAIT.set(no_wait);
+}
+
+/// The git dir for `repo`: GIT_DIR override, `.git` directory, or the target of a `.git` file (worktrees).
+fn git_dir_for(repo: &str) -> PathBuf {
+  if let Some(dir) = GIT_INVOCATION.get().and_then(|inv| inv.git_dir.clone()) {
+    return PathBuf::from(dir);
+  }
+
+  let dot_git = Path::new(repo).join(".git");
+
+  std::fs::read_to_string(&dot_git)
+    .ok()
+    .and_then(|s| s.trim().strip_prefix("gitdir:").map(|d| Path::new(repo).join(d.trim())))
+    .unwrap_or(dot_git)
+}
+
+/// Names of concurrent-git lock files currently present for `repo` (checks the common dir of worktrees too).
+pub fn held_git_locks(repo: &str) -> Vec<String> {
+  let git_dir = git_dir_for(repo);
+  let common = std::fs::read_to_string(git_dir.join("commondir"))
+    .ok()
+    .map(|c| git_dir.join(c.trim()));
+
+  GIT_LOCK_FILES
+    .iter()
+    .filter(|f| git_dir.join(f).exists() || common.as_ref().is_some_and(|c| c.join(f).exists()))
+    .map(|f| f.to_string())
+    .collect()
+}
+
+/// Whether git's stderr points at a lock held by another process.
+fn mentions_lock(stderr: &str) -> bool {
+  let s = stderr.to_ascii_lowercase();
+
+  s.contains(".lock") || s.contains("unable to lock") || s.contains("gc is already running")
+}
+
+/// Poll until no lock files remain or `deadline` passes.
+fn wait_for_locks(repo: &str, deadline: std::time::Instant) {
+  while !held_git_locks(repo).is_empty() && std::time::Instant::now() < deadline {
+    std::thread::sleep(LOCK_POLL);
+  }
+}
+
+/// Run a repo git command. Failures caused by another git process's locks (index.lock, gc.pid, ...)
+/// are retried after a bounded wait unless `--no-wait` is set.
 pub fn run_git(repo: &str, args: &[String]) -> Result<String> {
-  let out = Command::new("git")
-    .args(args)
-    .current_dir(repo)
-    .output()
-    .with_context(|| format!("spawning git {:?}", args))?;
-
-  if out.status.success() {
-    Ok(String::from_utf8_lossy(&out.stdout).to_string())
-  } else {
+  if !Path::new(repo).is_dir() {
+    anyhow::bail!("repository directory {} no longer exists (git {:?})", repo, args);
+  }
+
+  let mut attempt = 0;
+  let mut deadline: Option<std::time::Instant> = None;
+  tracing::trace!(target: "gar", args = ?args, "git");
+
+  loop {
+    let out = git_command(repo)
+      .args(args)
+      .output()
+      .with_context(|| format!("spawning git {:?}", args))?;
+
+    if out.status.success() {
+      return Ok(String::from_utf8_lossy(&out.stdout).to_string());
+    }
+
     let stderr = String::from_utf8_lossy(&out.stderr);
+    let locks = held_git_locks(repo);
+    // A running gc can make object reads fail transiently; other lock files only matter when git says so.
+    let lock_related = mentions_lock(&stderr) || (locks.iter().any(|l| l == "gc.pid") && !stderr.trim().is_empty());
+    let wait_until = *deadline.get_or_insert_with(|| std::time::Instant::now() + LOCK_WAIT_MAX);
+
+    if lock_related && attempt < LOCK_RETRIES && std::time::Instant::now() < wait_until {
+      if NO_WAIT.get().copied().unwrap_or(false) {
+        anyhow::bail!(
+          "git {:?} failed while another git process holds the repository ({}); rerun without --no-wait to wait: {}",
+          args,
+          if locks.is_empty() {
+            "lock".to_string()
+          } else {
+            locks.join(", ")
+          },
+          stderr
+        );
+      }
+
+      wait_for_locks(repo, wait_until);
+      attempt += 1;
+      continue;
+    }
+
     anyhow::bail!("git {:?} failed: {}", args, stderr)
   }
 }
 
+/// Installed git release, parsed from `git --version` ("git version 2.39.2.windows.1" → 2.39.2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GitVersion {
+  pub major: u32,
+  pub minor: u32,
+  pub patch: u32,
+}
+
+impl GitVersion {
+  pub fn parse(version_output: &str) -> Option<Self> {
+    let token = version_output
+      .split_whitespace()
+      .find(|t| t.starts_with(|c: char| c.is_ascii_digit()))?;
+    let mut parts = token.split('.').map(|p| p.parse::<u32>().ok());
+
+    Some(Self {
+      major: parts.next()??,
+      minor: parts.next().flatten().unwrap_or(0),
+      patch: parts.next().flatten().unwrap_or(0),
+    })
+  }
+}
+
+impl std::fmt::Display for GitVersion {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+  }
+}
+
+static GIT_VERSION: once_cell::sync::OnceCell<Option<GitVersion>> = once_cell::sync::OnceCell::new();
+
+/// The installed git version, detected once per run; None when `git --version` fails or is unparsable.
+pub fn git_version() -> Option<GitVersion> {
+  *GIT_VERSION.get_or_init(|| {
+    let out = Command::new("git").arg("--version").output().ok()?;
+
+    out
+      .status
+      .success()
+      .then(|| GitVersion::parse(&String::from_utf8_lossy(&out.stdout)))
+      .flatten()
+  })
+}
+
+/// Whether the installed git is at least `major.minor`; an undetected version is assumed current.
+pub fn git_at_least(major: u32, minor: u32) -> bool {
+  git_version().is_none_or(|v| (v.major, v.minor) >= (major, minor))
+}
+
 /// Generates a short 12-character SHA from a full one.
 pub fn short_sha(full: &str) -> String {
   full.chars().take(12).collect()
 }
 
+/// Whether `tz` is a --tz value the formatters understand: "local", "utc" (any case), or an IANA zone name.
+///
+/// Anything else would make `iso_in_tz` and `format_shard_name` fall back to UTC silently.
+pub fn is_known_tz(tz: &str) -> bool {
+  tz.eq_ignore_ascii_case("local") || tz.eq_ignore_ascii_case("utc") || tz.parse::<Tz>().is_ok()
+}
+
 /// Formats a Unix epoch timestamp into an RFC3339 string in the specified timezone.
 pub fn iso_in_tz(epoch: i64, tz: &str) -> String {
   if tz.eq_ignore_ascii_case("local") {
@@ -96,6 +310,223 @@ pub fn clip_patch(patch_text: String, max_bytes: usize) -> (Option<String>, Opti
   (Some(String::from_utf8_lossy(&bytes[..end]).to_string()), Some(true))
 }
 
+/// Text encoding for written report artifacts (`--output-encoding`).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputEncoding {
+  #[default]
+  Utf8,
+  Utf8Bom,
+  Utf16Le,
+}
+
+/// Accepted `--output-encoding` values.
+pub const OUTPUT_ENCODINGS: [&str; 3] = ["utf-8", "utf-8-bom", "utf-16le"];
+
+impl OutputEncoding {
+  pub fn parse(s: &str) -> Result<Self> {
+    match s.to_ascii_lowercase().as_str() {
+      "utf-8" | "utf8" => Ok(Self::Utf8),
+      "utf-8-bom" | "utf8-bom" => Ok(Self::Utf8Bom),
+      "utf-16le" | "utf-16" | "utf16le" => Ok(Self::Utf16Le),
+      _ => anyhow::bail!(
+        "--output-encoding expects one of {}, got {:?}",
+        OUTPUT_ENCODINGS.join(", "),
+        s
+      ),
+    }
+  }
+
+  fn encode(self, utf8: &[u8]) -> Vec<u8> {
+    match self {
+      Self::Utf8 => utf8.to_vec(),
+      Self::Utf8Bom => [&[0xEF, 0xBB, 0xBF][..], utf8].concat(),
+      Self::Utf16Le => {
+        let text = String::from_utf8_lossy(utf8);
+        let mut out = vec![0xFF, 0xFE];
+        out.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+        out
+      }
+    }
+  }
+}
+
+static OUTPUT_ENCODING: once_cell::sync::OnceCell<OutputEncoding> = once_cell::sync::OnceCell::new();
+
+/// Install the process-wide `--output-encoding` (first call wins).
+pub fn set_output_encoding(encoding: OutputEncoding) {
+  let _ = OUTPUT_ENCODING.set(encoding);
+}
+
+/// Atomically write a report artifact (reports, shards, manifest, rollup, indexes) in the configured encoding.
+///
+/// Content goes to a sibling temp file first and is renamed into place, so readers never see a partial file.
+/// The temp name carries the pid, so concurrent runs writing the same artifact never share a temp file.
+pub fn write_output(path: &Path, utf8: &[u8]) -> Result<()> {
+  let _span = crate::telemetry::span("write_output").attr("file.path", path.display().to_string());
+  let encoded = OUTPUT_ENCODING.get().copied().unwrap_or_default().encode(utf8);
+  let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+  let tmp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+  std::fs::write(&tmp, encoded).with_context(|| format!("writing {}", tmp.display()))?;
+  std::fs::rename(&tmp, path).with_context(|| format!("writing {}", path.display()))?;
+
+  Ok(())
+}
+
+/// UTF-8 bytes of an artifact written by `write_output`, whatever `--output-encoding` it used.
+pub fn decode_output(bytes: Vec<u8>) -> Vec<u8> {
+  if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+    return rest.to_vec();
+  }
+
+  if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+    let units: Vec<u16> = rest.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+
+    return String::from_utf16_lossy(&units).into_bytes();
+  }
+
+  bytes
+}
+
+/// Read an artifact written by `write_output` back as UTF-8.
+pub fn read_output(path: &Path) -> Result<Vec<u8>> {
+  let bytes = std::fs::read(path).with_context(|| format!("reading {}", path.display()))?;
+
+  Ok(decode_output(bytes))
+}
+
+/// Severity of a diagnostic message; `--quiet` hides everything below `Error` on stderr, `-v`/`-vv` show `Debug`/`Trace`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+  Trace,
+  Debug,
+  Info,
+  Warn,
+  Error,
+}
+
+impl LogLevel {
+  fn as_str(self) -> &'static str {
+    match self {
+      Self::Trace => "trace",
+      Self::Debug => "debug",
+      Self::Info => "info",
+      Self::Warn => "warn",
+      Self::Error => "error",
+    }
+  }
+}
+
+struct LogSink {
+  json: Option<std::sync::Mutex<std::fs::File>>,
+}
+
+static LOG_SINK: once_cell::sync::OnceCell<LogSink> = once_cell::sync::OnceCell::new();
+static WARNINGS: std::sync::Mutex<Vec<crate::model::Warning>> = std::sync::Mutex::new(Vec::new());
+
+/// Install `--quiet`, `--verbose`, and `--json-logs-to` (first call wins); the JSON log file is created or appended to.
+pub fn set_logging(quiet: bool, verbose: u8, json_logs_to: Option<&str>) -> Result<()> {
+  let json = match json_logs_to {
+    Some(path) => Some(std::sync::Mutex::new(
+      std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening --json-logs-to {}", path))?,
+    )),
+    None => None,
+  };
+  let _ = LOG_SINK.set(LogSink { json });
+  crate::logging::install(quiet, verbose);
+
+  Ok(())
+}
+
+/// Emit a diagnostic as a tracing event: `[target] message` on stderr at the enabled levels, plus a JSON line when configured.
+pub fn log(level: LogLevel, target: &str, message: &str) {
+  if level == LogLevel::Warn {
+    record_warning(target, None, message);
+  }
+
+  emit(level, target, message);
+}
+
+/// Log a warning about one commit; the report's `warnings[]` entry carries its sha.
+pub fn warn_commit(target: &str, sha: &str, message: &str) {
+  record_warning(target, Some(sha), message);
+  emit(LogLevel::Warn, target, &format!("{}: {}", short_sha(sha), message));
+}
+
+fn record_warning(stage: &str, sha: Option<&str>, message: &str) {
+  if let Ok(mut w) = WARNINGS.lock() {
+    w.push(crate::model::Warning {
+      stage: stage.to_string(),
+      sha: sha.map(String::from),
+      message: message.to_string(),
+    });
+  }
+}
+
+/// Hand a message to the subscriber crate::logging installs; before set_logging, info and up go straight to stderr.
+fn emit(level: LogLevel, stage: &str, message: &str) {
+  if !tracing::dispatcher::has_been_set() {
+    if level >= LogLevel::Info {
+      crate::progress::clear_line();
+      eprintln!("[{}] {}", stage, message);
+    }
+
+    return;
+  }
+
+  match level {
+    LogLevel::Trace => tracing::trace!(target: "gar", stage, "{}", message),
+    LogLevel::Debug => tracing::debug!(target: "gar", stage, "{}", message),
+    LogLevel::Info => tracing::info!(target: "gar", stage, "{}", message),
+    LogLevel::Warn => tracing::warn!(target: "gar", stage, "{}", message),
+    LogLevel::Error => tracing::error!(target: "gar", stage, "{}", message),
+  }
+}
+
+/// Warnings logged so far in this process (shown or not).
+pub fn warning_count() -> usize {
+  WARNINGS.lock().map(|w| w.len()).unwrap_or(0)
+}
+
+/// Warnings logged after the first `mark` (a previous `warning_count()`), oldest first.
+pub fn warnings_since(mark: usize) -> Vec<crate::model::Warning> {
+  WARNINGS
+    .lock()
+    .map(|w| w.get(mark..).unwrap_or_default().to_vec())
+    .unwrap_or_default()
+}
+
+/// Append one `{ts, level, target, message}` line to the `--json-logs-to` file, if any (never stderr).
+pub fn log_json(level: LogLevel, target: &str, message: &str) {
+  log_json_fields(level, target, message, &BTreeMap::new());
+}
+
+/// `log_json` plus the event's structured `fields` (omitted when empty).
+pub fn log_json_fields(level: LogLevel, target: &str, message: &str, fields: &BTreeMap<String, String>) {
+  let Some(file) = LOG_SINK.get().and_then(|s| s.json.as_ref()) else {
+    return;
+  };
+  let mut line = serde_json::json!({
+    "ts": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+    "level": level.as_str(),
+    "target": target,
+    "message": message,
+  });
+
+  if !fields.is_empty() {
+    line["fields"] = serde_json::json!(fields);
+  }
+
+  if let Ok(mut f) = file.lock() {
+    use std::io::Write as _;
+    let _ = writeln!(f, "{}", line);
+  }
+}
+
 /// Returns the effective "now" given an optional override.
 ///
 /// When `override_now` is `Some`, that instant is returned; otherwise
@@ -125,6 +556,21 @@ pub fn prepare_out_dir(out: &str, now_opt: Option<DateTime<Local>>) -> anyhow::R
   Ok(dir)
 }
 
+/// Append every file below `dir` (recursively) to `out`, in directory-listing order.
+pub fn collect_files(dir: &Path, out: &mut Vec<PathBuf>) -> Result<()> {
+  for entry in std::fs::read_dir(dir).with_context(|| format!("listing {}", dir.display()))? {
+    let path = entry?.path();
+
+    if path.is_dir() {
+      collect_files(&path, out)?;
+    } else {
+      out.push(path);
+    }
+  }
+
+  Ok(())
+}
+
 /// Render a section-1 man page for a clap `CommandFactory` implementor.
 /// Returns the troff content as a UTF-8 string.
 pub fn render_man_page<T: CommandFactory>() -> anyhow::Result<String> {
@@ -147,12 +593,162 @@ pub fn diff_seconds(start_iso: &str, end_iso: &str) -> Option<i64> {
   Some((pe - ps).num_seconds())
 }
 
+/// Parse `Key: value` trailers from the last paragraph of a commit message body.
+/// The paragraph only counts as a trailer block when every non-empty line is a trailer.
+pub fn parse_trailers(body: &str) -> Vec<(String, String)> {
+  let Some(last) = body.trim_end().rsplit("\n\n").next() else {
+    return Vec::new();
+  };
+
+  let mut out = Vec::new();
+
+  for line in last.lines().map(str::trim).filter(|l| !l.is_empty()) {
+    let Some((key, value)) = line.split_once(':') else {
+      return Vec::new();
+    };
+    let key_ok = !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-');
+
+    if !key_ok || value.trim().is_empty() {
+      return Vec::new();
+    }
+
+    out.push((key.to_string(), value.trim().to_string()));
+  }
+
+  out
+}
+
+/// ISO week label (`YYYY-Www`) for an RFC3339 timestamp, in that timestamp's own offset.
+pub fn iso_week_label(iso: &str) -> Option<String> {
+  let dt = chrono::DateTime::parse_from_rfc3339(iso).ok()?;
+
+  Some(dt.format("%G-W%V").to_string())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
   use chrono::{Local, TimeZone};
   use clap::Parser;
 
+  #[test]
+  fn warnings_are_kept_with_stage_and_sha() {
+    let mark = warning_count();
+    log(LogLevel::Info, "gar", "not a warning");
+    log(LogLevel::Warn, "github", "2 GitHub request(s) skipped");
+    warn_commit("gar", "0123456789abcdef0123", "numstat unavailable");
+
+    let logged = warnings_since(mark);
+    assert!(logged.contains(&crate::model::Warning {
+      stage: "github".into(),
+      sha: None,
+      message: "2 GitHub request(s) skipped".into(),
+    }));
+    assert!(logged.contains(&crate::model::Warning {
+      stage: "gar".into(),
+      sha: Some("0123456789abcdef0123".into()),
+      message: "numstat unavailable".into(),
+    }));
+    assert!(logged.iter().all(|w| w.message != "not a warning"));
+    assert!(warning_count() >= mark + 2);
+  }
+
+  #[test]
+  fn output_encodings_round_trip_through_decode() {
+    let text = "{\"name\": \"Zoë\"}";
+
+    for enc in OUTPUT_ENCODINGS {
+      let encoded = OutputEncoding::parse(enc).unwrap().encode(text.as_bytes());
+      assert_eq!(decode_output(encoded), text.as_bytes(), "{}", enc);
+    }
+
+    assert_eq!(OutputEncoding::Utf8Bom.encode(b"{}"), b"\xEF\xBB\xBF{}");
+    assert_eq!(
+      OutputEncoding::Utf16Le.encode(b"{}"),
+      vec![0xFF, 0xFE, b'{', 0, b'}', 0]
+    );
+    assert!(OutputEncoding::parse("latin1").is_err());
+  }
+
+  #[test]
+  fn write_output_replaces_without_leaving_temp_files() {
+    let td = tempfile::TempDir::new().unwrap();
+    let path = td.path().join("report.json");
+    std::fs::write(&path, "old").unwrap();
+
+    write_output(&path, b"{}").unwrap();
+
+    assert_eq!(read_output(&path).unwrap(), b"{}");
+    assert_eq!(std::fs::read_dir(td.path()).unwrap().count(), 1);
+  }
+
+  #[test]
+  fn run_git_waits_for_index_lock_then_retries() {
+    let td = tempfile::TempDir::new().unwrap();
+    let repo = td.path().to_string_lossy().to_string();
+    run_git(&repo, &["init".into(), "-q".into()]).unwrap();
+    std::fs::write(td.path().join("a.txt"), "a\n").unwrap();
+
+    let lock = td.path().join(".git/index.lock");
+    std::fs::write(&lock, "").unwrap();
+    assert_eq!(held_git_locks(&repo), vec!["index.lock"]);
+    let releaser = std::thread::spawn(move || {
+      std::thread::sleep(Duration::from_millis(400));
+      std::fs::remove_file(lock).unwrap();
+    });
+
+    run_git(&repo, &["add".into(), "a.txt".into()]).unwrap();
+    releaser.join().unwrap();
+    assert!(held_git_locks(&repo).is_empty());
+  }
+
+  #[test]
+  fn lock_messages_and_missing_repo_dir() {
+    assert!(mentions_lock(
+      "fatal: Unable to create '/r/.git/index.lock': File exists."
+    ));
+    assert!(!mentions_lock("fatal: bad revision 'nope'"));
+
+    let err = run_git("/nonexistent/gar-repo", &["status".into()]).unwrap_err();
+    assert!(err.to_string().contains("no longer exists"));
+  }
+
+  #[test]
+  fn git_invocation_prefixes_args_and_sets_locations() {
+    let inv = GitInvocation {
+      args: vec!["-c".into(), "core.abbrev=12".into()],
+      git_dir: Some("/srv/repo.git".into()),
+      work_tree: None,
+    };
+    let mut cmd = Command::new("git");
+
+    inv.apply(&mut cmd);
+    cmd.arg("log");
+
+    let args: Vec<_> = cmd.get_args().map(|a| a.to_string_lossy().to_string()).collect();
+    assert_eq!(args, vec!["-c", "core.abbrev=12", "log"]);
+    let envs: Vec<_> = cmd.get_envs().map(|(k, _)| k.to_string_lossy().to_string()).collect();
+    assert_eq!(envs, vec!["GIT_DIR"]);
+  }
+
+  #[test]
+  fn parse_trailers_reads_last_paragraph_only() {
+    let body = "Explain the change.\n\nDeployed-To: staging, production\nSigned-off-by: A <a@b.c>\n";
+    let t = parse_trailers(body);
+    assert_eq!(t.len(), 2);
+    assert_eq!(t[0], ("Deployed-To".to_string(), "staging, production".to_string()));
+
+    assert!(parse_trailers("Just prose here.\n\nNot: a trailer block\nbecause this line is prose").is_empty());
+    assert!(parse_trailers("").is_empty());
+  }
+
+  #[test]
+  fn iso_week_label_uses_iso_year() {
+    assert_eq!(iso_week_label("2025-08-13T10:00:00+00:00").as_deref(), Some("2025-W33"));
+    assert_eq!(iso_week_label("2024-12-30T10:00:00+00:00").as_deref(), Some("2025-W01"));
+    assert_eq!(iso_week_label("nope"), None);
+  }
+
   #[test]
   fn short_sha_truncates() {
     assert_eq!(short_sha("abcdef1234567890"), "abcdef123456");
@@ -225,6 +821,18 @@ mod tests {
     assert!(name.ends_with("-abcdef123456.json"));
     assert_eq!(name.len(), "YYYY.MM.DD-HH.MM-abcdef123456.json".len());
   }
+
+  #[test]
+  fn git_versions_parse_vendor_suffixes() {
+    let v = |s: &str| GitVersion::parse(s).map(|v| v.to_string());
+
+    assert_eq!(v("git version 2.45.0\n").as_deref(), Some("2.45.0"));
+    assert_eq!(v("git version 2.39.2.windows.1").as_deref(), Some("2.39.2"));
+    assert_eq!(v("git version 2.39.3 (Apple Git-146)").as_deref(), Some("2.39.3"));
+    assert_eq!(v("git version 1.8").as_deref(), Some("1.8.0"));
+    assert!(v("not git").is_none());
+    assert!(GitVersion::parse("git version 2.1.4") < GitVersion::parse("git version 2.9.0"));
+  }
 }
 
 /// Formats a file name for a commit shard based on its timestamp and SHA.
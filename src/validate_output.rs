@@ -0,0 +1,126 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: --validate-output self-check: validate reports, shards, manifests, and rollups against the embedded JSON schemas before writing
+// role: output/validation
+// inputs: artifact kind, destination path, serializable artifact; the tests/schemas/*.schema.json files embedded at build time
+// outputs: Ok(()) or an error naming the file, schema, JSON pointer, and violation
+// side_effects: None (schemas compile once on first use)
+// invariants:
+// - off by default; when off, check() returns before serializing anything
+// - validation runs before the artifact is written or printed, so a failing run leaves no invalid file behind
+// - the first violation fails the run; its JSON pointer is relative to the artifact root
+// errors: Schema violations bail with the offending path and pointer
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::path::Path;
+
+use anyhow::Result;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+
+static ENABLED: OnceCell<bool> = OnceCell::new();
+
+/// `--validate-output`: check every artifact against its schema before writing it (first call wins).
+pub fn set_validate_output(enabled: bool) {
+  let _ = ENABLED.set(enabled);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Artifact {
+  Report,
+  Commit,
+  Manifest,
+  Rollup,
+}
+
+impl Artifact {
+  fn schema_name(self) -> &'static str {
+    match self {
+      Artifact::Report => "report",
+      Artifact::Commit => "commit",
+      Artifact::Manifest => "overall",
+      Artifact::Rollup => "rollup",
+    }
+  }
+
+  fn validator(self) -> &'static jsonschema::Validator {
+    static REPORT: Lazy<jsonschema::Validator> =
+      Lazy::new(|| compile(include_str!("../tests/schemas/git-activity-report.report.schema.json")));
+    static COMMIT: Lazy<jsonschema::Validator> =
+      Lazy::new(|| compile(include_str!("../tests/schemas/git-activity-report.commit.schema.json")));
+    static MANIFEST: Lazy<jsonschema::Validator> =
+      Lazy::new(|| compile(include_str!("../tests/schemas/git-activity-report.overall.schema.json")));
+    static ROLLUP: Lazy<jsonschema::Validator> =
+      Lazy::new(|| compile(include_str!("../tests/schemas/git-activity-report.rollup.schema.json")));
+
+    match self {
+      Artifact::Report => &REPORT,
+      Artifact::Commit => &COMMIT,
+      Artifact::Manifest => &MANIFEST,
+      Artifact::Rollup => &ROLLUP,
+    }
+  }
+}
+
+fn compile(schema: &str) -> jsonschema::Validator {
+  let schema: serde_json::Value = serde_json::from_str(schema).expect("embedded schema is JSON");
+
+  jsonschema::validator_for(&schema).expect("embedded schema compiles")
+}
+
+/// With `--validate-output`, fail unless `value` (about to be written to `path`) matches the schema for `artifact`.
+pub fn check<T: Serialize + ?Sized>(artifact: Artifact, path: &Path, value: &T) -> Result<()> {
+  if !ENABLED.get().copied().unwrap_or(false) {
+    return Ok(());
+  }
+
+  validate(artifact, path, &serde_json::to_value(value)?)
+}
+
+fn validate(artifact: Artifact, path: &Path, value: &serde_json::Value) -> Result<()> {
+  if let Err(e) = artifact.validator().validate(value) {
+    anyhow::bail!(
+      "--validate-output: {} does not match the {} schema at JSON pointer \"{}\": {}",
+      path.display(),
+      artifact.schema_name(),
+      e.instance_path,
+      e
+    );
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn embedded_schemas_compile() {
+    for artifact in [Artifact::Report, Artifact::Commit, Artifact::Manifest, Artifact::Rollup] {
+      let _ = artifact.validator();
+    }
+  }
+
+  #[test]
+  fn violations_name_the_path_and_pointer() {
+    let report = serde_json::json!({
+      "summary": {
+        "repo": "/r", "count": "two",
+        "range": { "label": "2025-08", "start": "2025-08-01T00:00:00", "end": "2025-09-01T00:00:00" },
+        "report_options": { "include_merges": false, "include_patch": false, "include_unmerged": false, "tz": "utc" },
+        "changeset": { "additions": 0, "deletions": 0, "files_touched": 0 }
+      },
+      "authors": {}
+    });
+
+    let err = validate(Artifact::Report, Path::new("out/report-2025-08.json"), &report)
+      .unwrap_err()
+      .to_string();
+
+    assert!(err.contains("out/report-2025-08.json"), "{}", err);
+    assert!(err.contains("report schema"), "{}", err);
+    assert!(err.contains("\"/summary/count\""), "{}", err);
+  }
+}
@@ -0,0 +1,155 @@
+// === Module Header (agents-tooling) START ===
+// header: Parsed by scripts/check_module_headers.sh for purpose/role presence; keep keys on single-line entries.
+// purpose: Group consecutive commits by one author that touch overlapping files within a short gap into work items
+// role: summary/work-items
+// inputs: Processed commits of one range; --work-item-gap (minutes)
+// outputs: Vec<WorkItem> for SimpleReport.work_items (--work-items), each with its commits, combined churn, and duration
+// side_effects: None
+// invariants:
+// - authors are keyed exactly like `authors` (Person::key); each author's commits are walked in author-time order
+// - a commit joins its author's open item when it comes at most the gap after the item's last commit and shares a path with the item; otherwise it closes that item and opens a new one
+// - commits without listed files never join an item and start their own
+// - additions/deletions include entries dropped by --max-files-per-commit; files lists only listed paths
+// - items are ordered by start time, then author
+// errors: None
+// tie_breakers: contracts > orchestration > correctness > performance > minimal_diffs
+// === Module Header END ===
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::model::{Commit, WorkItem};
+
+/// Default `--work-item-gap`: the longest pause between two commits of one work item, in minutes.
+pub const DEFAULT_GAP_MINUTES: i64 = 120;
+
+/// An item still accepting commits, with its raw bounds.
+struct OpenItem {
+  item: WorkItem,
+  start: i64,
+  last: i64,
+  files: BTreeSet<String>,
+}
+
+impl OpenItem {
+  fn accepts(&self, commit: &Commit, gap_seconds: i64) -> bool {
+    commit.timestamps.author - self.last <= gap_seconds && commit.files.iter().any(|f| self.files.contains(&f.file))
+  }
+
+  fn add(&mut self, commit: &Commit) {
+    let (additions, deletions) = crate::commit::commit_additions_deletions(commit);
+
+    self.item.commits.push(commit.short_sha.clone());
+    self.item.additions += additions;
+    self.item.deletions += deletions;
+    self.files.extend(commit.files.iter().map(|f| f.file.clone()));
+    self.last = commit.timestamps.author;
+    self.item.ended_at = commit.timestamps.author_local.clone();
+  }
+
+  fn finish(self) -> (i64, WorkItem) {
+    let mut item = self.item;
+    item.duration_seconds = self.last - self.start;
+    item.files = self.files.into_iter().collect();
+
+    (self.start, item)
+  }
+}
+
+/// Cluster `commits` into work items; `gap_minutes` bounds the pause between two commits of one item.
+pub fn build_work_items(commits: &[Commit], gap_minutes: i64) -> Vec<WorkItem> {
+  let gap_seconds = gap_minutes * 60;
+  let mut ordered: Vec<&Commit> = commits.iter().collect();
+  ordered.sort_by_key(|c| c.timestamps.author);
+
+  let mut open: BTreeMap<String, OpenItem> = BTreeMap::new();
+  let mut closed: Vec<(i64, WorkItem)> = Vec::new();
+
+  for commit in ordered {
+    let author = commit.author.key();
+
+    if !open.get(&author).is_some_and(|o| o.accepts(commit, gap_seconds)) {
+      let fresh = OpenItem {
+        item: WorkItem {
+          author: author.clone(),
+          subject: commit.subject.clone(),
+          started_at: commit.timestamps.author_local.clone(),
+          ..Default::default()
+        },
+        start: commit.timestamps.author,
+        last: commit.timestamps.author,
+        files: BTreeSet::new(),
+      };
+
+      if let Some(prev) = open.insert(author.clone(), fresh) {
+        closed.push(prev.finish());
+      }
+    }
+
+    if let Some(o) = open.get_mut(&author) {
+      o.add(commit);
+    }
+  }
+
+  closed.extend(open.into_values().map(OpenItem::finish));
+  closed.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.author.cmp(&b.1.author)));
+
+  closed.into_iter().map(|(_, item)| item).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const T0: i64 = 1_755_000_000;
+
+  fn commit(sha: &str, name: &str, minutes: i64, files: &[(&str, i64)]) -> Commit {
+    let files: Vec<(&str, i64, i64)> = files.iter().map(|&(f, additions)| (f, additions, 1)).collect();
+    let mut c = Commit::fixture(sha)
+      .with_author(name, &format!("{}@x", name.to_lowercase()))
+      .at(T0 + minutes * 60, &format!("t+{}m", minutes))
+      .with_files(&files);
+    c.subject = format!("subject {}", sha);
+    c
+  }
+
+  #[test]
+  fn overlapping_commits_within_the_gap_form_one_item() {
+    let commits = vec![
+      commit("a3", "Ann", 60, &[("c.rs", 4)]),
+      commit("a1", "Ann", 0, &[("a.rs", 10)]),
+      commit("b1", "Bob", 10, &[("a.rs", 1)]),
+      commit("a2", "Ann", 30, &[("a.rs", 2), ("b.rs", 3)]),
+      commit("a4", "Ann", 400, &[("c.rs", 1)]),
+      commit("a5", "Ann", 410, &[]),
+    ];
+
+    let items = build_work_items(&commits, DEFAULT_GAP_MINUTES);
+    let shape: Vec<(&str, Vec<&str>)> = items
+      .iter()
+      .map(|i| (i.author.as_str(), i.commits.iter().map(String::as_str).collect()))
+      .collect();
+
+    assert_eq!(
+      shape,
+      vec![
+        ("Ann <ann@x>", vec!["a1", "a2"]),
+        ("Bob <bob@x>", vec!["b1"]),
+        ("Ann <ann@x>", vec!["a3"]),
+        ("Ann <ann@x>", vec!["a4"]),
+        ("Ann <ann@x>", vec!["a5"]),
+      ]
+    );
+
+    let first = &items[0];
+    assert_eq!(first.subject, "subject a1");
+    assert_eq!((first.started_at.as_str(), first.ended_at.as_str()), ("t+0m", "t+30m"));
+    assert_eq!(first.duration_seconds, 30 * 60);
+    assert_eq!((first.additions, first.deletions), (15, 3));
+    assert_eq!(first.files, vec!["a.rs", "b.rs"]);
+    assert_eq!(items[1].duration_seconds, 0);
+
+    // a wider gap lets the later c.rs commit join a3
+    let wide = build_work_items(&commits, 6 * 60);
+    assert_eq!(wide[2].commits, vec!["a3", "a4"]);
+  }
+}
@@ -1,14 +1,23 @@
 // Driver for integration + snapshot tests under tests/integration/
 // Keeps tests organized in a subdirectory while remaining visible to Cargo.
 //
+#[path = "integration/cli_archive.rs"]
+mod cli_archive;
+#[path = "integration/cli_doctor.rs"]
+mod cli_doctor;
 #[path = "integration/cli_gen_man.rs"]
 mod cli_gen_man;
 #[path = "integration/cli_windows.rs"]
 mod cli_windows;
+#[path = "integration/commit_stream.rs"]
+mod commit_stream;
 #[path = "integration/for_phrases.rs"]
 mod for_phrases;
 #[path = "integration/full_unmerged.rs"]
 mod full_unmerged;
+#[cfg(feature = "mock")]
+#[path = "integration/github_mock_backend.rs"]
+mod github_mock_backend;
 #[path = "integration/overall_manifest.rs"]
 mod overall_manifest;
 #[path = "integration/patch_behaviors.rs"]
@@ -17,6 +26,8 @@ mod patch_behaviors;
 mod report_end_to_end;
 #[path = "integration/schema_validation.rs"]
 mod schema_validation;
+#[path = "integration/window_api.rs"]
+mod window_api;
 
 // snapshots
 #[path = "integration/cli_full_snapshot.rs"]
@@ -0,0 +1,96 @@
+use assert_cmd::Command;
+use std::io::Read;
+use test_support;
+
+fn run_with_archive(archive: &std::path::Path, out_dir: &std::path::Path) -> serde_json::Value {
+  let repo = test_support::fixture_repo();
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--split-apart",
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo.to_str().unwrap(),
+      "--out",
+      out_dir.to_str().unwrap(),
+      "--archive",
+      archive.to_str().unwrap(),
+      "--now-override",
+      "2025-09-01T00:00:00Z",
+    ])
+    .output()
+    .unwrap();
+
+  assert!(
+    out.status.success(),
+    "cli run failed: {}",
+    String::from_utf8_lossy(&out.stderr)
+  );
+
+  serde_json::from_slice(&out.stdout).unwrap()
+}
+
+#[test]
+fn archive_pointer_references_inner_report_path() {
+  let td = tempfile::TempDir::new().unwrap();
+  let archive = td.path().join("activity.tar.gz");
+
+  let ptr = run_with_archive(&archive, &td.path().join("out"));
+
+  assert_eq!(ptr["archive"]["format"], "tar.gz");
+  assert!(ptr["archive"]["entries"].as_u64().unwrap() >= 2);
+  assert!(archive.exists());
+
+  let mut tar = Vec::new();
+  flate2::read::GzDecoder::new(std::fs::File::open(&archive).unwrap())
+    .read_to_end(&mut tar)
+    .unwrap();
+  let file = ptr["file"].as_str().unwrap();
+  let needle = format!("{}\0", file);
+  assert!(
+    tar.windows(needle.len()).any(|w| w == needle.as_bytes()),
+    "report {} missing from archive",
+    file
+  );
+}
+
+#[test]
+fn archive_requires_output_directory_mode() {
+  let repo = test_support::fixture_repo();
+  let td = tempfile::TempDir::new().unwrap();
+
+  Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo.to_str().unwrap(),
+      "--archive",
+      td.path().join("x.zip").to_str().unwrap(),
+    ])
+    .assert()
+    .failure();
+
+  Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--split-apart",
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo.to_str().unwrap(),
+      "--archive",
+      td.path().join("x.rar").to_str().unwrap(),
+    ])
+    .assert()
+    .failure();
+}
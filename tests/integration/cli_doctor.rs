@@ -0,0 +1,46 @@
+use assert_cmd::Command;
+
+#[test]
+fn cli_doctor_reports_invalid_repo_with_fix() {
+  let td = tempfile::TempDir::new().unwrap();
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  let out = cmd
+    .args(["--doctor", "--tz", "utc", "--repo"])
+    .arg(td.path())
+    .output()
+    .unwrap();
+  assert!(!out.status.success());
+  let s = String::from_utf8_lossy(&out.stdout);
+  assert!(s.contains("[ok] git:"));
+  assert!(s.contains("[FAIL] repo:"));
+  assert!(s.contains("fix: pass --repo"));
+  assert!(s.contains("[ok] timezone: utc"));
+}
+
+#[test]
+fn cli_doctor_honors_github_repo_on_non_github_origin() {
+  let td = tempfile::TempDir::new().unwrap();
+  let git = |args: &[&str]| {
+    let st = std::process::Command::new("git")
+      .args(args)
+      .current_dir(td.path())
+      .status()
+      .unwrap();
+    assert!(st.success(), "git {:?} failed", args);
+  };
+  git(&["init", "-q"]);
+  git(&["remote", "add", "origin", "https://git.example.com/acme/widgets.git"]);
+
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  let out = cmd
+    .args(["--doctor", "--tz", "utc", "--github-repo", "acme/widgets", "--repo"])
+    .arg(td.path())
+    .env_remove("GITHUB_TOKEN")
+    .env_remove("GH_TOKEN")
+    .env_remove("GITHUB_APP_ID")
+    .output()
+    .unwrap();
+  let s = String::from_utf8_lossy(&out.stdout);
+  assert!(s.contains("[ok] github origin: acme/widgets"), "{}", s);
+  assert!(!s.contains("--github-prs will be skipped"), "{}", s);
+}
@@ -72,5 +72,10 @@ fn cli_simple_snapshot() {
     }
   }
 
-  insta::assert_json_snapshot!(v, { ".authors" => insta::sorted_redaction() });
+  insta::assert_json_snapshot!(v, {
+    ".authors" => insta::sorted_redaction(),
+    ".author_stats[].first_commit_at" => "[local]",
+    ".author_stats[].last_commit_at" => "[local]",
+    ".summary.provenance.git_version" => "[git-version]",
+  });
 }
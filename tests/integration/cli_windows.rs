@@ -8,7 +8,7 @@ fn errors_when_no_time_selection() {
   let out = cmd.output().unwrap();
   assert!(!out.status.success());
   let err = String::from_utf8_lossy(&out.stderr);
-  assert!(err.contains("Provide one of --month, --for, or (--since AND --until)"));
+  assert!(err.contains("Provide one of --month, --for, --ranges-json, --since, or --until"));
 }
 
 #[test]
@@ -32,6 +32,36 @@ fn month_simple_smoke() {
   assert!(String::from_utf8_lossy(&out.stdout).contains("\"range\""));
 }
 
+#[test]
+fn repeated_month_runs_multi_range_with_manifest() {
+  let repo = test_support::init_fixture_repo();
+  let td = tempfile::TempDir::new().unwrap();
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  cmd.args([
+    "--month",
+    "aug 2025",
+    "--month",
+    "2025-7",
+    "--repo",
+    repo.path().to_str().unwrap(),
+    "--out",
+    td.path().to_str().unwrap(),
+  ]);
+  let out = cmd.output().unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let pointer: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  assert_eq!(pointer["manifest"], "manifest.json");
+  let manifest: serde_json::Value =
+    serde_json::from_slice(&std::fs::read(td.path().join("manifest.json")).unwrap()).unwrap();
+  let labels: Vec<&str> = manifest["ranges"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|r| r["label"].as_str().unwrap())
+    .collect();
+  assert_eq!(labels, ["2025-07", "2025-08"]);
+}
+
 #[test]
 fn full_mode_accepts_out_dir() {
   let repo = test_support::init_fixture_repo();
@@ -52,3 +82,196 @@ fn full_mode_accepts_out_dir() {
   let err = String::from_utf8_lossy(&out.stderr);
   assert!(!err.contains("ignored"));
 }
+
+#[test]
+fn since_merge_base_reports_only_commits_after_the_fork() {
+  let repo = test_support::init_fixture_repo();
+  test_support::run(repo.path(), &["checkout", "-q", "-b", "topic"]);
+  std::fs::write(repo.path().join("NOTES.md"), "wip\n").unwrap();
+  test_support::run(repo.path(), &["add", "."]);
+  let status = std::process::Command::new("git")
+    .args(["commit", "-q", "-m", "docs: topic notes"])
+    .current_dir(repo.path())
+    .env("GIT_AUTHOR_DATE", "2025-08-20T10:00:00")
+    .env("GIT_COMMITTER_DATE", "2025-08-20T10:00:00")
+    .status()
+    .unwrap();
+  assert!(status.success());
+
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  cmd.args([
+    "--since-merge-base",
+    "main",
+    "--repo",
+    repo.path().to_str().unwrap(),
+    "--tz",
+    "utc",
+    "--now-override",
+    "2025-09-01T00:00:00Z",
+  ]);
+  let out = cmd.output().unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  let subjects: Vec<&str> = v["commits"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|c| c["subject"].as_str().unwrap())
+    .collect();
+  assert_eq!(subjects, vec!["docs: topic notes"]);
+}
+
+#[test]
+fn since_merge_base_conflicts_with_other_windows() {
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  cmd.args(["--since-merge-base", "main", "--month", "2025-08", "--repo", "."]);
+  let out = cmd.output().unwrap();
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("choose only one"));
+}
+
+#[test]
+fn daily_digest_covers_the_previous_day_and_unmerged_branches() {
+  let repo = test_support::init_fixture_repo();
+  let repo_path = repo.path().to_str().unwrap();
+  test_support::run(repo.path(), &["checkout", "-q", "-b", "wip"]);
+  std::fs::write(repo.path().join("notes.txt"), "wip\n").unwrap();
+  test_support::run(repo.path(), &["add", "."]);
+  let status = std::process::Command::new("git")
+    .args(["commit", "-q", "-m", "wip: notes"])
+    .current_dir(repo.path())
+    .env("GIT_AUTHOR_DATE", "2025-08-13T11:00:00")
+    .env("GIT_COMMITTER_DATE", "2025-08-13T11:00:00")
+    .status()
+    .unwrap();
+  assert!(status.success());
+  test_support::run(repo.path(), &["checkout", "-q", "main"]);
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--digest",
+      "daily",
+      "--repo",
+      repo_path,
+      "--now-override",
+      "2025-08-14T08:00:00",
+    ])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+  let digest: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  assert_eq!(digest["date"], "2025-08-13");
+  let subjects: Vec<&str> = digest["commits"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|c| c["subject"].as_str().unwrap())
+    .collect();
+  assert_eq!(subjects, vec!["refactor: extract payment service"]);
+  assert_eq!(
+    digest["unmerged_branches"],
+    serde_json::json!([{ "name": "wip", "commits": 1 }])
+  );
+  assert!(digest["markdown"].as_str().unwrap().contains("- `wip` (1 commit(s))"));
+}
+
+#[test]
+fn digest_conflicts_with_window_flags() {
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(["--digest", "daily", "--month", "2025-08", "--repo", "."])
+    .output()
+    .unwrap();
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("--digest picks its own window"));
+}
+
+#[test]
+fn quiet_keeps_stderr_clean_and_json_logs_capture_diagnostics() {
+  let repo = test_support::fixture_repo();
+  let td = tempfile::TempDir::new().unwrap();
+  let log = td.path().join("gar.jsonl");
+  let log_path = log.to_str().unwrap();
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(["--month", "2025-08", "--repo", repo.to_str().unwrap(), "--quiet"])
+    .args(["--json-logs-to", log_path])
+    .output()
+    .unwrap();
+  assert!(out.status.success());
+  assert!(out.stderr.is_empty(), "{}", String::from_utf8_lossy(&out.stderr));
+  assert!(serde_json::from_slice::<serde_json::Value>(&out.stdout).is_ok());
+
+  // errors still reach stderr under --quiet, and are appended to the same log
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(["--quiet", "--json-logs-to", log_path])
+    .output()
+    .unwrap();
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("Provide one of --month"));
+
+  let lines: Vec<serde_json::Value> = std::fs::read_to_string(&log)
+    .unwrap()
+    .lines()
+    .map(|l| serde_json::from_str(l).unwrap())
+    .collect();
+  assert!(
+    lines
+      .iter()
+      .any(|l| l["level"] == "info" && l["message"] == "resolving ranges...")
+  );
+  let last = lines.last().unwrap();
+  assert_eq!(
+    (last["level"].as_str(), last["target"].as_str()),
+    (Some("error"), Some("gar"))
+  );
+  assert!(last["ts"].is_string());
+}
+
+#[test]
+fn exit_summary_is_the_last_stderr_line() {
+  let repo = test_support::fixture_repo();
+  let td = tempfile::TempDir::new().unwrap();
+  let out_file = td.path().join("report.json");
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--month",
+      "2025-08",
+      "--repo",
+      repo.to_str().unwrap(),
+      "--quiet",
+      "--exit-summary",
+    ])
+    .args(["--out", out_file.to_str().unwrap()])
+    .output()
+    .unwrap();
+  assert!(out.status.success());
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  let summary: serde_json::Value = serde_json::from_str(stderr.lines().last().unwrap()).unwrap();
+  assert_eq!(summary["kind"], "exit_summary");
+  assert_eq!(summary["status"], "ok");
+  assert_eq!(
+    (summary["ranges"].as_u64(), summary["commits"].as_u64()),
+    (Some(1), Some(2))
+  );
+  assert_eq!(summary["outputs"], serde_json::json!([out_file.to_str().unwrap()]));
+  assert!(summary["duration_ms"].is_u64());
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(["--repo", repo.to_str().unwrap(), "--exit-summary"])
+    .output()
+    .unwrap();
+  assert_eq!(out.status.code(), Some(1));
+  let stderr = String::from_utf8_lossy(&out.stderr);
+  let summary: serde_json::Value = serde_json::from_str(stderr.lines().last().unwrap()).unwrap();
+  assert_eq!(summary["status"], "error");
+  assert!(summary["error"].as_str().unwrap().contains("Provide one of --month"));
+  assert_eq!(summary["ranges"], 0);
+}
@@ -0,0 +1,27 @@
+use assert_cmd::Command;
+use git_activity_report::stream::{CommitStream, StreamOptions};
+
+#[test]
+fn stream_yields_the_report_commits_lazily() {
+  let repo = test_support::init_fixture_repo();
+  let repo_path = repo.path().to_str().unwrap();
+  let (since, until) = ("2025-08-01", "2025-09-01");
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(["--since", since, "--until", until, "--repo", repo_path, "--tz", "utc"])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let report: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+
+  let options = StreamOptions {
+    tz: "utc".into(),
+    ..StreamOptions::default()
+  };
+  let stream = CommitStream::new(repo_path, since, until, options).unwrap();
+  assert_eq!(stream.len(), report["commits"].as_array().unwrap().len());
+
+  let commits: Vec<serde_json::Value> = stream.map(|c| serde_json::to_value(c.unwrap()).unwrap()).collect();
+  assert_eq!(&serde_json::Value::from(commits), &report["commits"]);
+}
@@ -80,3 +80,56 @@ fn snapshot_first_shard_commit() {
 
   insta::assert_json_snapshot!(v);
 }
+
+#[test]
+fn layouts_place_shards_where_report_items_point() {
+  let _env = test_support::with_env(&[("TZ", "UTC")]);
+  let repo = test_support::fixture_repo();
+  let repo_path = repo.to_str().unwrap();
+
+  for layout in ["flat", "dated", "hashed"] {
+    let outdir = tempfile::TempDir::new().unwrap();
+    let out = test_support::cmd_bin("git-activity-report")
+      .args([
+        "--split-apart",
+        "--since",
+        "2025-08-01",
+        "--until",
+        "2025-09-01",
+        "--repo",
+        repo_path,
+        "--out",
+        outdir.path().to_str().unwrap(),
+        "--tz",
+        "utc",
+        "--layout",
+        layout,
+      ])
+      .output()
+      .unwrap();
+
+    assert!(
+      out.status.success(),
+      "{}: {}",
+      layout,
+      String::from_utf8_lossy(&out.stderr)
+    );
+    let top: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    assert_eq!(top["file"], "report-window.json");
+    let report: serde_json::Value =
+      serde_json::from_slice(&std::fs::read(outdir.path().join("report-window.json")).unwrap()).unwrap();
+    let items = report["items"].as_array().expect("items array");
+    assert!(!items.is_empty());
+
+    for item in items {
+      let file = item["file"].as_str().unwrap();
+      let short = &item["sha"].as_str().unwrap()[..2];
+      assert!(outdir.path().join(file).is_file(), "{}: missing {}", layout, file);
+      match layout {
+        "flat" => assert!(file.starts_with("window--") && !file.contains('/'), "{}", file),
+        "dated" => assert!(file.starts_with("window/2025/08/"), "{}", file),
+        _ => assert!(file.starts_with(&format!("window/{}/", short)), "{}", file),
+      }
+    }
+  }
+}
@@ -109,3 +109,76 @@ fn full_mode_month_label_and_manifest_filename() {
   let top: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
   assert_eq!(top["file"].as_str().unwrap(), "report-2025-08.json");
 }
+
+#[test]
+fn unmerged_base_compares_branches_against_the_given_ref() {
+  let repo = test_support::init_fixture_repo();
+  let repo_path = repo.path().to_str().unwrap();
+  // check out a feature branch with one commit that main does not have
+  test_support::run(repo.path(), &["checkout", "-q", "-b", "wip"]);
+  std::fs::write(repo.path().join("notes.txt"), "wip\n").unwrap();
+  test_support::run(repo.path(), &["add", "."]);
+  let status = std::process::Command::new("git")
+    .args(["commit", "-q", "-m", "wip: notes"])
+    .current_dir(repo.path())
+    .env("GIT_AUTHOR_DATE", "2025-08-14T10:00:00")
+    .env("GIT_COMMITTER_DATE", "2025-08-14T10:00:00")
+    .status()
+    .unwrap();
+  assert!(status.success());
+
+  let unmerged = |extra: &[&str]| -> serde_json::Value {
+    let outdir = tempfile::TempDir::new().unwrap();
+    let out = Command::cargo_bin("git-activity-report")
+      .unwrap()
+      .args([
+        "--split-apart",
+        "--month",
+        "2025-08",
+        "--include-unmerged",
+        "--repo",
+        repo_path,
+      ])
+      .args(["--out", outdir.path().to_str().unwrap()])
+      .args(extra)
+      .output()
+      .unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    let top: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+    let path = std::path::Path::new(top["dir"].as_str().unwrap()).join(top["file"].as_str().unwrap());
+    let report: serde_json::Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+    report["unmerged_activity"].clone()
+  };
+
+  // against HEAD (wip) nothing is unmerged
+  let head = unmerged(&[]);
+  assert!(head.get("base").is_none());
+  assert_eq!(head["total_unmerged_commits"], 0);
+
+  // against main, the checked-out branch's commit shows up
+  let main = unmerged(&["--unmerged-base", "main"]);
+  assert_eq!(main["base"], "main");
+  let branches = main["branches"].as_array().unwrap();
+  let names: Vec<&str> = branches.iter().map(|b| b["name"].as_str().unwrap()).collect();
+  assert!(names.contains(&"wip") && !names.contains(&"main"));
+  let wip = branches.iter().find(|b| b["name"] == "wip").unwrap();
+  assert_eq!(wip["items"].as_array().unwrap().len(), 1);
+  assert_eq!(wip["merged_into_head"], false);
+  assert_eq!(wip["ahead_of_head"], 1);
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--split-apart",
+      "--month",
+      "2025-08",
+      "--include-unmerged",
+      "--repo",
+      repo_path,
+    ])
+    .args(["--unmerged-base", "origin/nope"])
+    .output()
+    .unwrap();
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("--unmerged-base"));
+}
@@ -0,0 +1,130 @@
+use assert_cmd::Command;
+
+// Requires `--features mock`: GAR_MOCK_GITHUB points the binary at a scripted GitHub backend.
+#[test]
+fn scripted_backend_serves_paged_reviews() {
+  let td = tempfile::TempDir::new().unwrap();
+  let repo = td.path();
+  let sh = |args: &[&str]| {
+    let st = std::process::Command::new("git")
+      .args(args)
+      .current_dir(repo)
+      .env("GIT_AUTHOR_DATE", "2025-08-10T12:00:00Z")
+      .env("GIT_COMMITTER_DATE", "2025-08-10T12:00:00Z")
+      .status()
+      .unwrap();
+    assert!(st.success(), "git {:?} failed", args);
+  };
+  sh(&["init", "-q", "-b", "main"]);
+  sh(&["config", "user.name", "Fixture Bot"]);
+  sh(&["config", "user.email", "fixture@example.com"]);
+  sh(&["config", "commit.gpgsign", "false"]);
+  sh(&["remote", "add", "origin", "https://github.com/acme/widgets.git"]);
+  std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+  sh(&["add", "."]);
+  sh(&["commit", "-q", "-m", "A"]);
+
+  let script = serde_json::json!({
+    "seed": 1,
+    "pulls_for_commit": { "*": [{
+      "number": 5, "title": "Add a", "state": "closed",
+      "html_url": "https://github.com/acme/widgets/pull/5",
+      "user": { "login": "octo" }
+    }] },
+    "reviews": { "5": [
+      [{ "state": "COMMENTED", "user": { "login": "r1" }, "submitted_at": "2025-08-10T13:00:00Z" }],
+      [{ "state": "APPROVED", "user": { "login": "r2" }, "submitted_at": "2025-08-10T14:00:00Z" }]
+    ] }
+  });
+  let script_path = td.path().join("mock.json");
+  std::fs::write(&script_path, script.to_string()).unwrap();
+
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  let out = cmd
+    .env("GAR_MOCK_GITHUB", &script_path)
+    .env_remove("GITHUB_TOKEN")
+    .env_remove("GH_TOKEN")
+    .args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--tz",
+      "utc",
+      "--github-prs",
+      "--repo",
+    ])
+    .arg(repo)
+    .output()
+    .unwrap();
+  assert!(out.status.success());
+
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  let pr = &v["commits"][0]["github"]["pull_requests"][0];
+  assert_eq!(pr["number"], 5);
+  assert_eq!(pr["review_count"], 2);
+  assert_eq!(pr["approval_count"], 1);
+}
+
+#[test]
+fn repo_policy_reports_protection_and_head_status() {
+  let td = tempfile::TempDir::new().unwrap();
+  let repo = td.path();
+  let sh = |args: &[&str]| {
+    let st = std::process::Command::new("git")
+      .args(args)
+      .current_dir(repo)
+      .env("GIT_AUTHOR_DATE", "2025-08-10T12:00:00Z")
+      .env("GIT_COMMITTER_DATE", "2025-08-10T12:00:00Z")
+      .status()
+      .unwrap();
+    assert!(st.success(), "git {:?} failed", args);
+  };
+  sh(&["init", "-q", "-b", "main"]);
+  sh(&["config", "user.name", "Fixture Bot"]);
+  sh(&["config", "user.email", "fixture@example.com"]);
+  sh(&["config", "commit.gpgsign", "false"]);
+  sh(&["remote", "add", "origin", "https://github.com/acme/widgets.git"]);
+  std::fs::write(repo.join("a.txt"), "a\n").unwrap();
+  sh(&["add", "."]);
+  sh(&["commit", "-q", "-m", "A"]);
+
+  let script = serde_json::json!({
+    "repo": { "default_branch": "main" },
+    "branch_protection": { "main": {
+      "required_pull_request_reviews": { "required_approving_review_count": 1 },
+      "enforce_admins": { "enabled": true }
+    } },
+    "commit_status": { "*": { "state": "failure", "total_count": 1, "statuses": [{ "context": "ci", "state": "failure" }] } }
+  });
+  let script_path = td.path().join("mock.json");
+  std::fs::write(&script_path, script.to_string()).unwrap();
+
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  let out = cmd
+    .env("GAR_MOCK_GITHUB", &script_path)
+    .env_remove("GITHUB_TOKEN")
+    .env_remove("GH_TOKEN")
+    .args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--tz",
+      "utc",
+      "--repo-policy",
+      "--repo",
+    ])
+    .arg(repo)
+    .output()
+    .unwrap();
+  assert!(out.status.success());
+
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  let policy = &v["summary"]["repo_policy"];
+  assert_eq!(policy["default_branch"], "main");
+  assert_eq!(policy["branch_protection"]["required_approving_review_count"], 1);
+  assert_eq!(policy["branch_protection"]["enforce_admins"], true);
+  assert_eq!(policy["head_status"]["state"], "failure");
+  assert_eq!(policy["head_status"]["sha"], v["commits"][0]["sha"]);
+}
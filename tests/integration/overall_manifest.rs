@@ -2,17 +2,18 @@ use assert_cmd::Command;
 use jsonschema::validator_for;
 use test_support;
 
-fn compile_overall_schema() -> jsonschema::Validator {
+fn compile_schema(name: &str) -> jsonschema::Validator {
   let manifest_dir = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-  let path = manifest_dir
-    .join("tests")
-    .join("schemas")
-    .join("git-activity-report.overall.schema.json");
+  let path = manifest_dir.join("tests").join("schemas").join(name);
   let data = std::fs::read(&path).expect("schema file");
   let v: serde_json::Value = serde_json::from_slice(&data).expect("schema json");
   validator_for(&v).expect("compile schema")
 }
 
+fn compile_overall_schema() -> jsonschema::Validator {
+  compile_schema("git-activity-report.overall.schema.json")
+}
+
 #[test]
 fn overall_manifest_schema_validates_and_files_exist() {
   let repo = test_support::fixture_repo();
@@ -58,6 +59,15 @@ fn overall_manifest_schema_validates_and_files_exist() {
     let p = std::path::Path::new(dir).join(file);
     assert!(p.exists(), "range file path should exist");
   }
+
+  let rollup_file = top_ptr["rollup"].as_str().expect("rollup string");
+  let rollup: serde_json::Value =
+    serde_json::from_slice(&std::fs::read(std::path::Path::new(dir).join(rollup_file)).unwrap()).unwrap();
+  compile_schema("git-activity-report.rollup.schema.json")
+    .validate(&rollup)
+    .expect("rollup schema validation failed");
+  assert_eq!(rollup["ranges"].as_u64().unwrap() as usize, ranges.len());
+  assert_eq!(rollup["trend"].as_array().unwrap().len(), ranges.len());
 }
 
 #[test]
@@ -49,3 +49,35 @@ fn simple_save_patches_sets_local_path() {
   let path = commits[0]["patch_references"]["local_patch_file"].as_str().unwrap();
   assert!(std::path::Path::new(path).exists());
 }
+
+#[test]
+fn patch_budget_caps_total_embedded_bytes() {
+  let repo = test_support::fixture_repo();
+  let repo_path = repo.to_str().unwrap();
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  cmd.args([
+    "--since",
+    "2025-08-01",
+    "--until",
+    "2025-09-01",
+    "--repo",
+    repo_path,
+    "--patch-budget-bytes",
+    "200",
+  ]);
+  let out = cmd.output().unwrap();
+  assert!(out.status.success());
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  let budget = &v["summary"]["patch_budget"];
+  assert_eq!(budget["budget_bytes"], 200);
+  assert!(budget["embedded_bytes"].as_u64().unwrap() <= 200);
+
+  let commits = v["commits"].as_array().unwrap();
+  let embedded: usize = commits
+    .iter()
+    .filter_map(|c| c["patch_lines"].as_array())
+    .map(|lines| lines.iter().map(|l| l.as_str().unwrap().len() + 1).sum::<usize>())
+    .sum();
+  assert!(embedded <= 200 + commits.len());
+  assert!(commits.iter().all(|c| c["patch_allocation"].is_string()));
+}
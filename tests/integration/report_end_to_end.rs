@@ -114,3 +114,431 @@ fn simple_mode_writes_to_file_and_validates_shape() {
   assert!(c0.get("patch").is_none());
   assert!(c0.get("patch_clipped").is_none());
 }
+
+#[test]
+fn split_mode_side_indexes_point_at_existing_shards() {
+  let repo = test_support::fixture_repo();
+  let repo_path = repo.to_str().unwrap();
+  let tmpdir = tempfile::TempDir::new().unwrap();
+
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  cmd.args([
+    "--split-apart",
+    "--side-indexes",
+    "--since",
+    "2025-08-01",
+    "--until",
+    "2025-09-01",
+    "--repo",
+    repo_path,
+    "--out",
+    tmpdir.path().to_str().unwrap(),
+    "--tz",
+    "utc",
+  ]);
+
+  let out = cmd.output().unwrap();
+  assert!(out.status.success());
+
+  let ptr: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  let dir = std::path::Path::new(ptr["dir"].as_str().unwrap());
+  let report: serde_json::Value =
+    serde_json::from_slice(&std::fs::read(dir.join(ptr["file"].as_str().unwrap())).unwrap()).unwrap();
+
+  let days = report["indexes"]["days"].as_object().expect("days index map");
+  assert!(!days.is_empty());
+
+  let mut indexed = 0;
+
+  for file in days.values() {
+    let idx: serde_json::Value =
+      serde_json::from_slice(&std::fs::read(dir.join(file.as_str().unwrap())).unwrap()).unwrap();
+    assert_eq!(idx["kind"], "day");
+
+    for item in idx["items"].as_array().unwrap() {
+      assert!(dir.join(item["file"].as_str().unwrap()).exists());
+      indexed += 1;
+    }
+  }
+
+  assert_eq!(indexed, report["items"].as_array().unwrap().len());
+  assert!(!report["indexes"]["authors"].as_object().unwrap().is_empty());
+}
+
+#[test]
+fn output_encoding_utf16_writes_bom_and_query_reads_it_back() {
+  let repo = test_support::fixture_repo();
+  let repo_path = repo.to_str().unwrap();
+  let tmpdir = tempfile::TempDir::new().unwrap();
+  let outfile = tmpdir.path().join("out.json");
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(["--since", "2025-08-01", "--until", "2025-09-01", "--repo", repo_path])
+    .args(["--output-encoding", "utf-16le", "--out", outfile.to_str().unwrap()])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+  let bytes = std::fs::read(&outfile).unwrap();
+  assert_eq!(&bytes[..4], &[0xFF, 0xFE, b'{', 0]);
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(["--query", outfile.to_str().unwrap(), "/summary/range/start"])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  assert!(String::from_utf8_lossy(&out.stdout).starts_with("2025-08-01"));
+}
+
+#[test]
+fn markdown_format_prints_report_and_split_runs_add_md_files() {
+  let repo = test_support::fixture_repo();
+  let repo_path = repo.to_str().unwrap();
+  let window = [
+    "--since",
+    "2025-08-01",
+    "--until",
+    "2025-09-01",
+    "--repo",
+    repo_path,
+    "--tz",
+    "utc",
+  ];
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(window)
+    .args(["--format", "markdown"])
+    .output()
+    .unwrap();
+
+  assert!(out.status.success());
+  let md = String::from_utf8(out.stdout).unwrap();
+  assert!(md.starts_with("# Activity report: "), "{}", md);
+  assert!(md.contains("## Totals\n\n| Metric | Value |"));
+  assert!(md.contains("\n## Commits\n\n### `"));
+
+  let outdir = tempfile::TempDir::new().unwrap();
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args(window)
+    .args([
+      "--format",
+      "markdown",
+      "--split-apart",
+      "--out",
+      outdir.path().to_str().unwrap(),
+    ])
+    .output()
+    .unwrap();
+
+  assert!(out.status.success());
+  let pointer: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  assert_eq!(pointer["file"], "report-window.json");
+  let md = std::fs::read_to_string(outdir.path().join("report-window.md")).unwrap();
+  assert!(md.starts_with("# Activity report: ") && md.contains("(window)"));
+}
+
+#[test]
+fn all_refs_annotates_commits_with_containing_refs() {
+  let repo = test_support::fixture_repo();
+  let repo_path = repo.to_str().unwrap();
+  let run = |extra: &[&str]| -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+    cmd.args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo_path,
+      "--include-merges",
+    ]);
+    cmd.args(extra);
+    let out = cmd.output().unwrap();
+    assert!(out.status.success());
+    serde_json::from_slice(&out.stdout).unwrap()
+  };
+
+  let head_only = run(&[]);
+  let all = run(&["--all-refs"]);
+
+  assert!(all["summary"]["count"].as_u64() >= head_only["summary"]["count"].as_u64());
+  assert_eq!(all["summary"]["report_options"]["all_refs"], true);
+  assert!(head_only["commits"][0].get("refs").is_none());
+
+  for c in all["commits"].as_array().unwrap() {
+    let refs: Vec<&str> = c["refs"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .filter_map(|r| r.as_str())
+      .collect();
+    assert!(refs.contains(&"main"), "{:?}", refs);
+  }
+}
+
+#[test]
+fn path_filters_scope_commits_and_files() {
+  let repo = test_support::fixture_repo();
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  cmd.args([
+    "--since",
+    "2025-08-01",
+    "--until",
+    "2025-09-01",
+    "--repo",
+    repo.to_str().unwrap(),
+    "--path",
+    "spec/",
+  ]);
+  let out = cmd.output().unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+
+  assert_eq!(v["summary"]["report_options"]["paths"], serde_json::json!(["spec/"]));
+  let commits = v["commits"].as_array().unwrap();
+  assert_eq!(commits.len(), 1);
+  for f in commits[0]["files"].as_array().unwrap() {
+    assert!(f["file"].as_str().unwrap().starts_with("spec/"), "{}", f);
+  }
+}
+
+#[test]
+fn max_files_per_commit_caps_entries_and_keeps_totals() {
+  let repo = test_support::fixture_repo();
+  let run = |extra: &[&str]| -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+    cmd.args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo.to_str().unwrap(),
+    ]);
+    cmd.args(extra);
+    let out = cmd.output().unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    serde_json::from_slice(&out.stdout).unwrap()
+  };
+
+  let full = run(&[]);
+  let capped = run(&["--max-files-per-commit", "1"]);
+
+  assert_eq!(
+    capped["summary"]["changeset"]["additions"],
+    full["summary"]["changeset"]["additions"]
+  );
+  let two_files = capped["commits"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|c| !c["files_truncated"].is_null())
+    .expect("the two-file commit is truncated");
+  assert_eq!(two_files["files"].as_array().unwrap().len(), 1);
+  assert_eq!(two_files["files_truncated"]["omitted"], 1);
+}
+
+#[test]
+fn identity_map_merges_aliases_in_author_stats() {
+  let repo = test_support::fixture_repo();
+  let td = tempfile::TempDir::new().unwrap();
+  let map = td.path().join("identities.toml");
+  std::fs::write(
+    &map,
+    "[[identities]]\nname = \"Release Team\"\nemail = \"team@example.com\"\naliases = [\"FIXTURE@example.com\"]\n",
+  )
+  .unwrap();
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  cmd.args([
+    "--since",
+    "2025-08-01",
+    "--until",
+    "2025-09-01",
+    "--repo",
+    repo.to_str().unwrap(),
+    "--identity-map",
+    map.to_str().unwrap(),
+  ]);
+  let out = cmd.output().unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+
+  assert_eq!(
+    v["authors"],
+    serde_json::json!({ "Release Team <team@example.com>": 2 })
+  );
+  let author = &v["commits"][0]["author"];
+  assert_eq!(author["email"], "fixture@example.com");
+  assert_eq!(author["identity"]["email"], "team@example.com");
+}
+
+#[test]
+fn fields_projects_commits_and_echoes_selection() {
+  let repo = test_support::fixture_repo();
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  let out = cmd
+    .args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo.to_str().unwrap(),
+      "--fields",
+      "sha,subject,author.name,files.stats",
+    ])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+
+  assert_eq!(
+    v["summary"]["report_options"]["fields"],
+    serde_json::json!(["sha", "subject", "author.name", "files.stats"])
+  );
+  // Summaries are computed before projection
+  assert_eq!(v["summary"]["changeset"]["additions"], 3);
+  for c in v["commits"].as_array().unwrap() {
+    let mut keys: Vec<&str> = c.as_object().unwrap().keys().map(|k| k.as_str()).collect();
+    keys.sort();
+    assert_eq!(keys, ["author", "files", "sha", "subject"]);
+    assert_eq!(c["author"], serde_json::json!({ "name": "Fixture Bot" }));
+    for f in c["files"].as_array().unwrap() {
+      assert!(f.get("status").is_none());
+      assert!(f.get("additions").is_some());
+    }
+  }
+
+  let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+  let out = cmd
+    .args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo.to_str().unwrap(),
+      "--fields",
+      "sha,subjet",
+    ])
+    .output()
+    .unwrap();
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("unknown commit field \"subjet\""));
+}
+
+#[test]
+fn cache_reuses_processed_commits_until_settings_change() {
+  let repo = test_support::fixture_repo();
+  let cache_dir = tempfile::TempDir::new().unwrap();
+  let run = |extra: &[&str]| -> serde_json::Value {
+    let mut cmd = Command::cargo_bin("git-activity-report").unwrap();
+    cmd.env("GAR_CACHE_DIR", cache_dir.path()).args([
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo.to_str().unwrap(),
+    ]);
+    cmd.args(extra);
+    let out = cmd.output().unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    serde_json::from_slice(&out.stdout).unwrap()
+  };
+  let subjects = |v: &serde_json::Value| -> Vec<String> {
+    v["commits"]
+      .as_array()
+      .unwrap()
+      .iter()
+      .map(|c| c["subject"].as_str().unwrap().to_string())
+      .collect()
+  };
+
+  let first = run(&["--cache"]);
+  let entries: Vec<std::path::PathBuf> = std::fs::read_dir(cache_dir.path())
+    .unwrap()
+    .flat_map(|repo_dir| std::fs::read_dir(repo_dir.unwrap().path().join("commits")).unwrap())
+    .map(|e| e.unwrap().path())
+    .collect();
+  assert_eq!(entries.len(), 2);
+
+  // Tamper with the cached entries so hits are observable
+  for path in &entries {
+    let mut entry: serde_json::Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+    entry["commit"]["subject"] = "from cache".into();
+    std::fs::write(path, serde_json::to_vec(&entry).unwrap()).unwrap();
+  }
+
+  assert_eq!(subjects(&run(&["--cache"])), ["from cache", "from cache"]);
+  assert_eq!(subjects(&run(&["--cache", "--no-cache"])), subjects(&first));
+  // Different processing settings invalidate the entries
+  assert_eq!(subjects(&run(&["--cache", "--no-bodies"])), subjects(&first));
+}
+
+#[test]
+fn first_parent_totals_match_a_plain_run() {
+  let td = tempfile::TempDir::new().unwrap();
+  let repo = td.path();
+  let git = |date: &str, args: &[&str]| {
+    let st = std::process::Command::new("git")
+      .args(args)
+      .current_dir(repo)
+      .env("GIT_AUTHOR_DATE", date)
+      .env("GIT_COMMITTER_DATE", date)
+      .status()
+      .unwrap();
+    assert!(st.success(), "git {:?} failed", args);
+  };
+  let commit = |date: &str, file: &str, body: &str| {
+    std::fs::write(repo.join(file), body).unwrap();
+    git(date, &["add", "."]);
+    git(date, &["commit", "-q", "-m", file]);
+  };
+  git("2025-08-01T10:00:00", &["init", "-q", "-b", "main"]);
+  git("2025-08-01T10:00:00", &["config", "user.name", "Fixture Bot"]);
+  git("2025-08-01T10:00:00", &["config", "user.email", "fixture@example.com"]);
+  git("2025-08-01T10:00:00", &["config", "commit.gpgsign", "false"]);
+  commit("2025-08-01T10:00:00", "a.rs", "fn a() {}\n");
+  git("2025-08-01T10:00:00", &["checkout", "-q", "-b", "feat"]);
+  commit("2025-08-02T10:00:00", "b.rs", "fn b() {}\nfn c() {}\n");
+  commit("2025-08-03T10:00:00", "c.txt", "c\n");
+  git("2025-08-03T10:00:00", &["checkout", "-q", "main"]);
+  commit("2025-08-04T10:00:00", "d.rs", "fn d() {}\n");
+  git(
+    "2025-08-05T10:00:00",
+    &["merge", "-q", "--no-ff", "-m", "merge feat", "feat"],
+  );
+
+  let run = |extra: &[&str]| -> serde_json::Value {
+    let out = Command::cargo_bin("git-activity-report")
+      .unwrap()
+      .args(["--since", "2025-07-01", "--until", "2025-09-01"])
+      .args(["--tz", "utc", "--repo"])
+      .arg(repo)
+      .args(extra)
+      .output()
+      .unwrap();
+    assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+    serde_json::from_slice(&out.stdout).unwrap()
+  };
+
+  let plain = run(&[]);
+  let grouped = run(&["--first-parent"]);
+
+  let merge = grouped["commits"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .find(|c| c["subject"] == "merge feat")
+    .expect("merge commit on the first-parent line");
+  assert_eq!(merge["merge_group"]["commits"].as_array().unwrap().len(), 2);
+
+  let changeset = &plain["summary"]["changeset"];
+  assert_eq!(changeset["additions"], 5);
+  assert_eq!(grouped["summary"]["changeset"], *changeset);
+}
@@ -98,3 +98,53 @@ fn multi_range_overall_and_reports_conform_to_schemas() {
     }
   }
 }
+
+#[test]
+fn validate_output_passes_split_multi_range_and_single_runs() {
+  let repo = test_support::fixture_repo();
+  let repo_path = repo.to_str().unwrap();
+  let outdir = tempfile::TempDir::new().unwrap();
+  let out_path = outdir.path().to_str().unwrap();
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--validate-output",
+      "--split-apart",
+      "--for",
+      "every month for the last 2 months",
+      "--repo",
+      repo_path,
+      "--out",
+      out_path,
+      "--include-unmerged",
+      "--now-override",
+      "2025-09-01T12:00:00",
+    ])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let top_ptr: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  assert!(
+    std::path::Path::new(top_ptr["dir"].as_str().unwrap())
+      .join("manifest.json")
+      .exists()
+  );
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--validate-output",
+      "--since",
+      "2025-08-01",
+      "--until",
+      "2025-09-01",
+      "--repo",
+      repo_path,
+    ])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let v: serde_json::Value = serde_json::from_slice(&out.stdout).unwrap();
+  assert_eq!(v["summary"]["count"], 2);
+}
@@ -70,5 +70,10 @@ fn simple_report_snapshot() {
     }
   }
 
-  insta::assert_json_snapshot!(v, { ".authors" => insta::sorted_redaction() });
+  insta::assert_json_snapshot!(v, {
+    ".authors" => insta::sorted_redaction(),
+    ".author_stats[].first_commit_at" => "[local]",
+    ".author_stats[].last_commit_at" => "[local]",
+    ".summary.provenance.git_version" => "[git-version]",
+  });
 }
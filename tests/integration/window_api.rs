@@ -0,0 +1,68 @@
+use assert_cmd::Command;
+use git_activity_report::range_windows::{WindowSpec, compute_window_strings, month_bounds, parse_now, resolve_ranges};
+
+#[test]
+fn library_buckets_match_cli_manifest() {
+  let repo = test_support::init_fixture_repo();
+  let td = tempfile::TempDir::new().unwrap();
+  let phrase = "every month for the last 3 months";
+  let now = "2025-09-10T12:00:00";
+
+  let out = Command::cargo_bin("git-activity-report")
+    .unwrap()
+    .args([
+      "--for",
+      phrase,
+      "--repo",
+      repo.path().to_str().unwrap(),
+      "--out",
+      td.path().to_str().unwrap(),
+      "--now-override",
+      now,
+    ])
+    .output()
+    .unwrap();
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+
+  let manifest: serde_json::Value =
+    serde_json::from_slice(&std::fs::read(td.path().join("manifest.json")).unwrap()).unwrap();
+  let ranges = resolve_ranges(&WindowSpec::ForPhrase { phrase: phrase.into() }, parse_now(Some(now))).unwrap();
+
+  let from_cli: Vec<(&str, &str, &str)> = manifest["ranges"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|r| {
+      (
+        r["label"].as_str().unwrap(),
+        r["range"]["start"].as_str().unwrap(),
+        r["range"]["end"].as_str().unwrap(),
+      )
+    })
+    .collect();
+  let from_lib: Vec<(&str, &str, &str)> = ranges
+    .iter()
+    .map(|r| (r.label.as_str(), r.since.as_str(), r.until.as_str()))
+    .collect();
+  assert_eq!(from_cli, from_lib);
+}
+
+#[test]
+fn library_month_helpers() {
+  assert_eq!(
+    month_bounds("aug 2025").unwrap(),
+    ("2025-08-01T00:00:00".to_string(), "2025-09-01T00:00:00".to_string())
+  );
+
+  let (since, until) = compute_window_strings(
+    &WindowSpec::Months {
+      yms: vec!["2025-07".into(), "2025-08".into()],
+    },
+    None,
+  )
+  .unwrap();
+  assert_eq!(
+    (since.as_str(), until.as_str()),
+    ("2025-07-01T00:00:00", "2025-09-01T00:00:00")
+  );
+}